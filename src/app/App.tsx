@@ -1,12 +1,78 @@
 import { basename, resolve } from "node:path";
 import { useCallback, useEffect, useMemo, useState } from "react";
-import { Box, Text, useApp, useInput, useStdout } from "ink";
+import { Box, Text, useApp, useInput, useStdin, type Key } from "ink";
 
 import type { ProjectRef } from "../domain/project";
+import { findSimilarTasks, type SimilarTaskMatch } from "../domain/task-similarity";
+import { computeRunHistoryHint, formatRunHistoryHint } from "../domain/run-history";
+import { formatSubtaskSummary, orderTasksByHierarchy, summarizeSubtasks } from "../domain/subtasks";
+import { STATUS_GLYPHS, statusColor, type Palette } from "../domain/palette";
+import type { CardField } from "../domain/task-card";
+import { groupFailedTasks, listRetryChain } from "../domain/task-failure";
+import { formatWatchdogLimit } from "../domain/task-watchdog";
+import { estimateTaskProgress, formatProgressBar } from "../domain/task-progress";
+import { averageRunDurationMs, estimateQueueStartTimes } from "../domain/task-queue";
+import {
+  buildActivityHeatmap,
+  getActivityHeatmapStart,
+  isMetricsRollup,
+  type ActivityHeatmap,
+  type MetricsRollup,
+  type MetricsRollupRow,
+} from "../domain/metrics";
+import type { BlobStore } from "../runtime/blob-store";
+import { buildProjectChangelog } from "../runtime/changelog";
+import { installTaskGitHooks, uninstallTaskGitHooks } from "../runtime/git-hooks";
+import { exportTaskLogArchive } from "../runtime/log-archive";
+import { exportTaskTranscriptHtml } from "../runtime/transcript-export";
+import {
+  attributeFileToTasks,
+  type TaskAttributionEntry,
+} from "../runtime/task-attribution";
 import type { ConversationSdkSessionMessage } from "../domain/conversation";
-import type { TaskRuntime } from "../domain/task";
+import { formatDeepLink } from "../domain/deep-link";
+import { compareDiffFiles, formatDiffTotals, sumDiffFiles, type DiffComparison, type DiffFile } from "../domain/diff";
+import { describePermissionRequest, type PermissionReply, type PermissionRequest } from "../domain/permission";
+import {
+  listNextTaskStates,
+  listOpenBlockers,
+  nextTaskPriority,
+  normalizePathScope,
+  type MergeStrategy,
+  type TaskRuntime,
+} from "../domain/task";
+import { resolveBoardColumns } from "../domain/board-column";
+import { formatPoints, sumEstimates } from "../domain/capacity";
+import { compareBoardOrder } from "../domain/task-rank";
+import {
+  computeMilestoneProgress,
+  isMilestoneOverdue,
+  type Milestone,
+} from "../domain/milestone";
+import { findLabelByName, isLabelColor, LABEL_COLORS, type Label } from "../domain/label";
+import {
+  formatWorkingHours,
+  isDeferredByWorkingHours,
+  nextWorkingHoursStart,
+  parseWorkingHoursSpec,
+  type WorkingHours,
+} from "../domain/working-hours";
 import type { RuntimeEventMap, RuntimeLogEntry } from "../runtime/event-bus";
 import { ProjectRegistry } from "../runtime/project-registry";
+import type { LabelRegistry } from "../runtime/label-registry";
+import type { MilestoneRegistry } from "../runtime/milestone-registry";
+import type { PageRegistry } from "../runtime/page-registry";
+import type { DraftStore, TextDraft } from "../runtime/draft-store";
+import type { MetricsStore } from "../runtime/metrics-store";
+import type { TaskConflictRisk } from "../domain/conflict-risk";
+import type { ProjectPage } from "../domain/page";
+import {
+  toValidationIssue,
+  validateName,
+  validatePrompt,
+  validateTitle,
+  type ValidationIssue,
+} from "../domain/validation";
 import { RuntimeEventBus } from "../runtime/event-bus";
 import { OpenCodeRuntime } from "../runtime/opencode-runtime";
 import {
@@ -14,11 +80,50 @@ import {
   type RunTaskInput,
   type TaskOrchestratorEvent,
 } from "../runtime/task-orchestrator";
-import { WorktreeManager } from "../runtime/worktree-manager";
+import { TaskPlanner } from "../runtime/task-planner";
+import { WorktreeManager, type TaskCommit } from "../runtime/worktree-manager";
+import { CommitHistoryView } from "./views/commit-history-view";
+import { buildCompareRows, CompareView } from "./views/compare-view";
+import { buildDiffRows, DiffView } from "./views/diff-view";
+import { FailureTriageView } from "./views/failure-triage-view";
+import { QueueView } from "./views/queue-view";
+import { ActivityHeatmapView, MetricsView } from "./views/metrics-view";
+import { PagesView } from "./views/pages-view";
+import { editTextInExternalEditor } from "./external-editor";
+import {
+  findAgentInstructions,
+  readAgentInstructions,
+  writeAgentInstructions,
+} from "../runtime/agent-instructions";
+import { LogReaderView } from "./views/log-reader-view";
 import { LogView, type LogViewLevel } from "./views/log-view";
 import { ProjectSelectorView } from "./views/project-selector-view";
-import { TaskBoardView } from "./views/task-board-view";
+import { TerminalTooSmallView } from "./views/terminal-too-small-view";
+import {
+  findTaskColumn,
+  formatTaskCardLabel,
+  listColumnTasksForTask,
+  TaskBoardView,
+} from "./views/task-board-view";
+import { copyToClipboard, type ClipboardResult } from "./clipboard";
+import { openTerminalIn } from "./terminal-launcher";
+import { describeGotoTarget, resolveGotoTarget } from "./goto";
+import { advanceTutorial, TUTORIAL_STEPS, type TutorialSnapshot } from "./tutorial";
+import {
+  isMacroRegister,
+  MACRO_PLAY_KEY,
+  MACRO_RECORD_KEY,
+  toMacroStep,
+  type MacroRecording,
+  type MacroReplay,
+  type MacroStep,
+} from "./keyboard-macro";
+import { clampRows, computePanelRows, isTerminalTooSmall, useTerminalSize } from "./terminal-size";
 import { nextRoute, ROUTE_DESCRIPTORS, type AppRoute } from "./routes";
+import {
+  emitTerminalNotification,
+  type TerminalNotificationMode,
+} from "../runtime/terminal-notification";
 
 type BannerTone = "info" | "success" | "warn" | "error";
 
@@ -34,44 +139,136 @@ type TaskSessionMessage = {
 
 type PromptModel = NonNullable<RunTaskInput["model"]>;
 
+type QuickAddState = {
+  field: "project" | "title";
+  projectQuery: string;
+  selectedIndex: number;
+  title: string;
+};
+
 type ModelOption = {
   label: string;
   model?: PromptModel;
 };
 
+type InlineRenameState = {
+  target: "project" | "task";
+  id: string;
+  value: string;
+};
+
+type SimilarTaskWarningState = {
+  prompt: string;
+  matches: SimilarTaskMatch[];
+};
+
+type PlanReviewState = {
+  parentTaskId: string;
+  items: string[];
+  selectedIndex: number;
+  editingValue?: string;
+};
+
+type MilestonePanelState = {
+  selectedIndex: number;
+  createInput?: string;
+};
+
+type DiffViewState = {
+  taskId: string;
+  loadedAt: number;
+  files: DiffFile[];
+  fileIndex: number;
+  /** Hunk indexes folded in the current file. */
+  foldedHunks: ReadonlySet<number>;
+  scrollOffset: number;
+  /** Set when showing one commit from the commit history instead of the whole branch. */
+  commit?: string;
+};
+
+type CompareViewState = {
+  leftTaskId: string;
+  rightTaskId: string;
+  comparison: DiffComparison;
+  scrollOffset: number;
+};
+
+type CommitHistoryState = {
+  taskId: string;
+  commits: TaskCommit[];
+  selectedIndex: number;
+};
+
 type ReviewDiffState = {
   taskId: string;
+  loadedAt: number;
   branch: string;
   defaultBranch: string;
   summary: string;
   diff: string;
+  files: DiffFile[];
 };
 
 export type AppServices = {
   runtime: OpenCodeRuntime;
   projectRegistry: ProjectRegistry;
+  milestoneRegistry: MilestoneRegistry;
+  labelRegistry: LabelRegistry;
+  pageRegistry: PageRegistry;
+  draftStore: DraftStore;
+  metricsStore: MetricsStore;
   orchestrator: TaskOrchestrator;
+  taskPlanner: TaskPlanner;
   worktreeManager: WorktreeManager;
   eventBus: RuntimeEventBus;
 };
 
+type StartupNavigation = {
+  project?: string;
+  task?: string;
+  /** Sent to `task` as a follow-up; the log panel opens to follow it. */
+  followUpPrompt?: string;
+};
+
 type AppProps = {
   services: AppServices;
   defaultProjectDirectory?: string;
   initialRoute?: AppRoute;
+  /** Navigation from command-line flags, applied once the runtime is ready. */
+  startup?: StartupNavigation;
+  notificationMode?: TerminalNotificationMode;
+  /** Shell command template for `t`; see `openTerminalIn`. */
+  terminalCommand?: string;
+  cardFields?: readonly CardField[];
+  palette?: Palette;
+  exportDirectory?: string;
+  /** Off-host store that exports are also copied to, under `exports/`. */
+  artifactStore?: BlobStore;
 };
 
 const MAX_LOG_ENTRIES = 200;
 const LOG_SCROLL_STEP = 1;
 const LOG_SCROLL_PAGE = 8;
+const MAX_BULK_PREVIEW_ROWS = 10;
+const DIFF_VIEW_REFRESH_MS = 3_000;
+const COMMENT_PREVIEW_COUNT = 3;
+const WORKING_HOURS_CHECK_MS = 60_000;
 
 export function App({
   services,
   defaultProjectDirectory,
   initialRoute = "project-selector",
+  startup,
+  notificationMode = "off",
+  terminalCommand,
+  cardFields,
+  palette = "default",
+  exportDirectory,
+  artifactStore,
 }: AppProps) {
   const { exit } = useApp();
-  const { stdout } = useStdout();
+  const terminalSize = useTerminalSize();
+  const { setRawMode, isRawModeSupported } = useStdin();
   const [loading, setLoading] = useState(true);
   const [busyMessage, setBusyMessage] = useState<string>();
   const [errorMessage, setErrorMessage] = useState<string>();
@@ -81,17 +278,27 @@ export function App({
   const [activeProjectId, setActiveProjectId] = useState<string>();
   const [selectedProjectIndex, setSelectedProjectIndex] = useState(0);
   const [tasks, setTasks] = useState<TaskRuntime[]>([]);
+  const [permissionRequests, setPermissionRequests] = useState<PermissionRequest[]>([]);
+  const [dismissedPermissionIds, setDismissedPermissionIds] = useState<string[]>([]);
   const [selectedTaskIndex, setSelectedTaskIndex] = useState(0);
   const [logs, setLogs] = useState<RuntimeLogEntry[]>([]);
+  const [watchedTaskLogs, setWatchedTaskLogs] = useState<RuntimeLogEntry[]>([]);
   const [sessionMessagesByTaskID, setSessionMessagesByTaskID] = useState<
     Record<string, TaskSessionMessage[]>
   >({});
   const [newProjectPathInput, setNewProjectPathInput] = useState<string>();
   const [newTaskPromptInput, setNewTaskPromptInput] = useState<string>();
   const [taskModel, setTaskModel] = useState<PromptModel | undefined>();
+  const [taskPathScope, setTaskPathScope] = useState<string>();
+  const [newTaskBlockerId, setNewTaskBlockerId] = useState<string>();
+  /** Parent of the task being typed, when it was started with `a`. */
+  const [subtaskParentId, setSubtaskParentId] = useState<string>();
+  const [collapsedTaskIds, setCollapsedTaskIds] = useState<ReadonlySet<string>>(new Set());
   const [modelOptions, setModelOptions] = useState<ModelOption[]>([]);
   const [modelFilterInput, setModelFilterInput] = useState("");
   const [modelPickerOpen, setModelPickerOpen] = useState(false);
+  // Set when the picker changes an existing task's model rather than the one for new tasks.
+  const [modelPickerTaskId, setModelPickerTaskId] = useState<string>();
   const [selectedModelOptionIndex, setSelectedModelOptionIndex] = useState(0);
   const [promptByTaskID, setPromptByTaskID] = useState<Record<string, string>>(
     {},
@@ -102,11 +309,69 @@ export function App({
   const [followUpPromptInput, setFollowUpPromptInput] = useState<string>();
   const [logViewLevel, setLogViewLevel] = useState<LogViewLevel>("info");
   const [isLogViewOpen, setIsLogViewOpen] = useState(false);
+  const [workingHoursClock, setWorkingHoursClock] = useState(() => Date.now());
+  const [heldNotifications, setHeldNotifications] = useState<{ projectId: string; message: string }[]>([]);
+  const [isLogReadingMode, setIsLogReadingMode] = useState(false);
+  const [isLogWrapEnabled, setIsLogWrapEnabled] = useState(true);
   const [logScrollOffset, setLogScrollOffset] = useState(0);
   const [reviewDiff, setReviewDiff] = useState<ReviewDiffState>();
+  const [inlineRename, setInlineRename] = useState<InlineRenameState>();
+  const [similarTaskWarning, setSimilarTaskWarning] =
+    useState<SimilarTaskWarningState>();
+  const [bulkTaskPrompts, setBulkTaskPrompts] = useState<string[]>();
+  const [planReview, setPlanReview] = useState<PlanReviewState>();
+  const [focusMode, setFocusMode] = useState(false);
+  const [collapsedColumns, setCollapsedColumns] = useState<ReadonlySet<string>>(new Set());
+  const [showCancelled, setShowCancelled] = useState(false);
+  const [checkpointPanel, setCheckpointPanel] = useState<{ selectedIndex: number }>();
+  const [cancelReasonInput, setCancelReasonInput] = useState<string>();
+  const [isFailureViewOpen, setIsFailureViewOpen] = useState(false);
+  const [diffView, setDiffView] = useState<DiffViewState>();
+  const [commitHistory, setCommitHistory] = useState<CommitHistoryState>();
+  const [compareView, setCompareView] = useState<CompareViewState>();
+  const [isQueueViewOpen, setIsQueueViewOpen] = useState(false);
+  const [selectedQueueIndex, setSelectedQueueIndex] = useState(0);
+  const [metricsReport, setMetricsReport] = useState<{
+    rollup: MetricsRollup;
+    from?: string;
+    to?: string;
+    rows: MetricsRollupRow[];
+    heatmap?: ActivityHeatmap;
+  }>();
+  const [attributionPathInput, setAttributionPathInput] = useState<string>();
+  const [changelogSinceInput, setChangelogSinceInput] = useState<string>();
+  const [estimateInput, setEstimateInput] = useState<string>();
+  const [milestones, setMilestones] = useState<Milestone[]>([]);
+  const [commandInput, setCommandInput] = useState<string>();
+  const [quickAdd, setQuickAdd] = useState<QuickAddState>();
+  const [pendingTaskFocusId, setPendingTaskFocusId] = useState<string>();
+  const [startupApplied, setStartupApplied] = useState(false);
+  /** Current tutorial step; steps only count tasks created after `startedAt`. */
+  const [tutorial, setTutorial] = useState<{ stepIndex: number; startedAt: number }>();
+  const [tutorialOffered, setTutorialOffered] = useState(false);
+  const [pages, setPages] = useState<ProjectPage[]>([]);
+  const [selectedPageIndex, setSelectedPageIndex] = useState(0);
+  const [newPageTitleInput, setNewPageTitleInput] = useState<string>();
+  const [milestoneFilterId, setMilestoneFilterId] = useState<string>();
+  const [labels, setLabels] = useState<Label[]>([]);
+  const [labelFilterId, setLabelFilterId] = useState<string>();
+  const [milestonePanel, setMilestonePanel] = useState<MilestonePanelState>();
+  const [attribution, setAttribution] = useState<{
+    filePath: string;
+    entries: TaskAttributionEntry[];
+  }>();
+  const [selectedFailureIndex, setSelectedFailureIndex] = useState(0);
+  const [fieldError, setFieldError] = useState<ValidationIssue>();
+  const [draftOffer, setDraftOffer] = useState<TextDraft>();
+  const [instructionFiles, setInstructionFiles] = useState<Record<string, string | undefined>>({});
   // Track last key press for double-key detection (e.g., "dd" to delete)
   const [lastKeyPress, setLastKeyPress] = useState<{ key: string; time: number } | null>(null);
   const DOUBLE_KEY_TIMEOUT_MS = 500; // Reset after 500ms
+  const [macroRegisters, setMacroRegisters] = useState<Record<string, MacroStep[]>>({});
+  const [macroRecording, setMacroRecording] = useState<MacroRecording>();
+  const [macroReplay, setMacroReplay] = useState<MacroReplay>();
+  const [pendingMacroKey, setPendingMacroKey] = useState<string>();
+  const [lastMacroRegister, setLastMacroRegister] = useState<string>();
 
   const pushBanner = useCallback((tone: BannerTone, message: string) => {
     setStatusBanner({
@@ -140,15 +405,284 @@ export function App({
     [projects, activeProjectId],
   );
 
+  const boardColumns = useMemo(() => resolveBoardColumns(activeProject?.columns), [activeProject]);
+
   const tasksForActiveProject = useMemo(() => {
     if (!activeProject) {
       return [];
     }
 
-    return tasks.filter((task) => task.projectId === activeProject.id);
-  }, [activeProject, tasks]);
+    // Highest priority first within each column, then the manual order; subtasks still follow their parent.
+    return orderTasksByHierarchy(
+      tasks
+        .filter(
+          (task) =>
+            task.projectId === activeProject.id &&
+            (showCancelled || task.state !== "cancelled") &&
+            (milestoneFilterId === undefined || task.milestoneId === milestoneFilterId) &&
+            (labelFilterId === undefined || task.labelIds?.includes(labelFilterId) === true),
+        )
+        .sort(compareBoardOrder),
+      collapsedTaskIds,
+    );
+  }, [activeProject, tasks, milestoneFilterId, labelFilterId, showCancelled, collapsedTaskIds]);
+
+  const hiddenCancelledCount = useMemo(
+    () =>
+      showCancelled || !activeProject
+        ? 0
+        : tasks.filter((task) => task.projectId === activeProject.id && task.state === "cancelled").length,
+    [activeProject, tasks, showCancelled],
+  );
+
+  const milestoneFilter = milestones.find((milestone) => milestone.id === milestoneFilterId);
+  const labelFilter = labels.find((label) => label.id === labelFilterId);
+  const labelsById = useMemo(() => new Map(labels.map((label) => [label.id, label])), [labels]);
+
+  const refreshMilestones = useCallback(async () => {
+    if (!activeProjectId) {
+      setMilestones([]);
+      return;
+    }
+
+    setMilestones(await services.milestoneRegistry.listMilestones(activeProjectId));
+  }, [activeProjectId, services.milestoneRegistry]);
+
+  const refreshLabels = useCallback(async () => {
+    setLabels(activeProjectId ? await services.labelRegistry.listLabels(activeProjectId) : []);
+  }, [activeProjectId, services.labelRegistry]);
+
+  const refreshPages = useCallback(async () => {
+    if (!activeProjectId) {
+      setPages([]);
+      return;
+    }
+
+    const nextPages = await services.pageRegistry.listPages(activeProjectId);
+    setPages(nextPages);
+    setSelectedPageIndex((current) => Math.min(current, Math.max(nextPages.length - 1, 0)));
+  }, [activeProjectId, services.pageRegistry]);
+
+  useEffect(() => {
+    void refreshPages().catch((error) => {
+      pushBanner("error", toErrorMessage(error));
+    });
+  }, [refreshPages, pushBanner]);
+
+  const refreshInstructionFiles = useCallback(async () => {
+    const entries = await Promise.all(
+      projects.map(async (project) => [project.id, await findAgentInstructions(project.rootDirectory)] as const),
+    );
+    setInstructionFiles(Object.fromEntries(entries));
+  }, [projects]);
+
+  useEffect(() => {
+    void refreshInstructionFiles();
+  }, [refreshInstructionFiles]);
+
+  const editSelectedProjectInstructions = useCallback(async () => {
+    const project = projects[selectedProjectIndex];
+    if (!project) {
+      pushBanner("warn", "No project selected.");
+      return;
+    }
+
+    if (isRawModeSupported) {
+      setRawMode(false);
+    }
+    try {
+      const instructions = await readAgentInstructions(project.rootDirectory);
+      const content = await editTextInExternalEditor(instructions.content, basename(instructions.filePath));
+      if (content === undefined) {
+        pushBanner("warn", "Editor exited without saving.");
+        return;
+      }
+
+      if (!instructions.exists && !content.trim()) {
+        pushBanner("info", "No agent instructions written.");
+        return;
+      }
+
+      await writeAgentInstructions(instructions.filePath, content);
+      await refreshInstructionFiles();
+      pushBanner("success", `Saved ${instructions.filePath}. New tasks will include it.`);
+    } catch (error) {
+      pushBanner("error", toErrorMessage(error));
+    } finally {
+      if (isRawModeSupported) {
+        setRawMode(true);
+      }
+    }
+  }, [projects, selectedProjectIndex, isRawModeSupported, setRawMode, refreshInstructionFiles, pushBanner]);
+
+  const editSelectedPage = useCallback(async () => {
+    const page = pages[selectedPageIndex];
+    if (!page) {
+      pushBanner("warn", "No page selected.");
+      return;
+    }
+
+    // Hand the terminal to the editor; Ink re-renders once raw mode is restored.
+    if (isRawModeSupported) {
+      setRawMode(false);
+    }
+    try {
+      const body = await editTextInExternalEditor(page.body, `${page.title.replace(/[^A-Za-z0-9_-]+/g, "-")}.md`);
+      if (body === undefined) {
+        pushBanner("warn", "Editor exited without saving.");
+        return;
+      }
+
+      await services.pageRegistry.updatePage(page.id, { body });
+      await refreshPages();
+      pushBanner("success", `Saved page ${page.title}.`);
+    } catch (error) {
+      pushBanner("error", toErrorMessage(error));
+    } finally {
+      if (isRawModeSupported) {
+        setRawMode(true);
+      }
+    }
+  }, [pages, selectedPageIndex, isRawModeSupported, setRawMode, services.pageRegistry, refreshPages, pushBanner]);
+
+  useEffect(() => {
+    setMilestoneFilterId(undefined);
+    setMilestonePanel(undefined);
+    void refreshMilestones().catch((error) => {
+      pushBanner("error", toErrorMessage(error));
+    });
+  }, [refreshMilestones, pushBanner]);
+
+  useEffect(() => {
+    setLabelFilterId(undefined);
+    void refreshLabels().catch((error) => {
+      pushBanner("error", toErrorMessage(error));
+    });
+  }, [refreshLabels, pushBanner]);
 
   const selectedTask = tasksForActiveProject[selectedTaskIndex];
+  const activePermissionRequest = permissionRequests.find(
+    (request) => !dismissedPermissionIds.includes(request.id),
+  );
+  const selectedTaskRetryChain = useMemo(
+    () => (selectedTask ? listRetryChain(selectedTask, tasks) : []),
+    [selectedTask, tasks],
+  );
+
+  // Only one long-text field is open at a time; its draft key names the field and its owner.
+  const activeDraftKey =
+    newTaskPromptInput !== undefined && activeProject
+      ? `task-prompt:${activeProject.id}`
+      : followUpPromptInput !== undefined && selectedTask
+        ? `follow-up:${selectedTask.taskId}`
+        : undefined;
+  const activeDraftValue = newTaskPromptInput ?? followUpPromptInput;
+  const newTaskRunHistory = useMemo(
+    () =>
+      newTaskPromptInput !== undefined && activeProject
+        ? computeRunHistoryHint(tasks, activeProject.id, newTaskPromptInput)
+        : undefined,
+    [newTaskPromptInput, activeProject, tasks],
+  );
+
+  useEffect(() => {
+    setDraftOffer(undefined);
+    if (!activeDraftKey) {
+      return;
+    }
+
+    let cancelled = false;
+    void services.draftStore.get(activeDraftKey).then((draft) => {
+      if (!cancelled && draft && draft.value.trim()) {
+        setDraftOffer(draft);
+      }
+    });
+
+    return () => {
+      cancelled = true;
+    };
+  }, [activeDraftKey, services.draftStore]);
+
+  useEffect(() => {
+    // Empty input is not saved so opening a field cannot clobber the draft it offers to restore.
+    if (activeDraftKey && activeDraftValue) {
+      services.draftStore.save(activeDraftKey, activeDraftValue);
+    }
+  }, [activeDraftKey, activeDraftValue, services.draftStore]);
+
+  const discardActiveDraft = useCallback(() => {
+    setDraftOffer(undefined);
+    if (activeDraftKey) {
+      void services.draftStore.discard(activeDraftKey);
+    }
+  }, [activeDraftKey, services.draftStore]);
+
+  const failureGroups = useMemo(() => groupFailedTasks(tasks), [tasks]);
+  const failedTasksInTriageOrder = useMemo(
+    () => failureGroups.flatMap((group) => group.tasks),
+    [failureGroups],
+  );
+  // `tasks` changes on every orchestrator event, which is also when the queue moves.
+  const queueSnapshot = useMemo(() => services.orchestrator.getQueueSnapshot(), [tasks, services.orchestrator]);
+  const averageRunMs = useMemo(() => averageRunDurationMs(tasks), [tasks]);
+  const findTaskById = useCallback(
+    (taskId: string) => tasks.find((task) => task.taskId === taskId),
+    [tasks],
+  );
+  const queuedTaskBlockers = useMemo(
+    () =>
+      new Map(
+        tasks.flatMap((task) => {
+          const blockers = task.state === "queued" ? listOpenBlockers(task, tasks) : [];
+          return blockers.length > 0 ? [[task.taskId, blockers] as const] : [];
+        }),
+      ),
+    [tasks],
+  );
+  const blockedTaskIds = useMemo(() => new Set(queuedTaskBlockers.keys()), [queuedTaskBlockers]);
+  const offHoursTaskIds = useMemo(() => {
+    const now = new Date();
+    const workingHoursByProjectId = new Map(projects.map((project) => [project.id, project.workingHours]));
+    return new Set(
+      tasks
+        .filter(
+          (task) =>
+            task.state === "queued" && isDeferredByWorkingHours(task, workingHoursByProjectId.get(task.projectId), now),
+        )
+        .map((task) => task.taskId),
+    );
+  }, [tasks, projects, workingHoursClock]);
+  const subtaskSummaries = useMemo(
+    () =>
+      new Map(
+        tasksForActiveProject.flatMap((task) => {
+          const summary = summarizeSubtasks(tasks, task.taskId);
+          return summary ? [[task.taskId, summary] as const] : [];
+        }),
+      ),
+    [tasks, tasksForActiveProject],
+  );
+  const tutorialStep = tutorial ? TUTORIAL_STEPS[tutorial.stepIndex] : undefined;
+  const selectedTaskBlockers = selectedTask ? listOpenBlockers(selectedTask, tasks) : [];
+  const newTaskBlocker = newTaskBlockerId ? findTaskById(newTaskBlockerId) : undefined;
+  const selectedTaskProgress = selectedTask ? estimateTaskProgress(selectedTask, averageRunMs) : undefined;
+  const queueEstimates = useMemo(
+    () => estimateQueueStartTimes(queueSnapshot, averageRunMs),
+    [queueSnapshot, averageRunMs],
+  );
+
+  const projectNames = useMemo(
+    () => Object.fromEntries(projects.map((project) => [project.id, project.name])),
+    [projects],
+  );
+
+  const openTasksForActiveProject = useMemo(
+    () =>
+      tasksForActiveProject.filter(
+        (task) => task.state !== "completed" && task.state !== "cleaning" && task.state !== "cancelled",
+      ),
+    [tasksForActiveProject],
+  );
 
   const taskLogs = useMemo(() => {
     if (!selectedTask) {
@@ -158,8 +692,14 @@ export function App({
     const scoped = logs.filter(
       (entry) => !entry.taskId || entry.taskId === selectedTask.taskId,
     );
-    return scoped;
-  }, [logs, selectedTask]);
+    const sequences = new Set(scoped.map((entry) => entry.sequence));
+    const evicted = watchedTaskLogs.filter(
+      (entry) => entry.taskId === selectedTask.taskId && !sequences.has(entry.sequence),
+    );
+    return evicted.length > 0
+      ? [...evicted, ...scoped].sort((left, right) => left.sequence - right.sequence)
+      : scoped;
+  }, [logs, watchedTaskLogs, selectedTask]);
 
   const taskMessages = useMemo(() => {
     if (!selectedTask) {
@@ -169,6 +709,12 @@ export function App({
     return sessionMessagesByTaskID[selectedTask.taskId] ?? [];
   }, [selectedTask, sessionMessagesByTaskID]);
 
+  const quickAddProjects = useMemo(
+    () => (quickAdd ? filterQuickAddProjects(projects, quickAdd.projectQuery) : []),
+    [projects, quickAdd],
+  );
+  const quickAddProject = quickAdd ? quickAddProjects[quickAdd.selectedIndex] : undefined;
+
   const filteredModelOptions = useMemo(() => {
     return filterModelOptions(modelOptions, modelFilterInput);
   }, [modelOptions, modelFilterInput]);
@@ -266,11 +812,88 @@ export function App({
         return services.orchestrator.getTask(taskID)?.projectId ?? "pending";
       });
       setTasks(services.orchestrator.listTasks());
+      setPermissionRequests(services.orchestrator.listPendingPermissions());
     });
 
     return unsubscribe;
   }, [services.orchestrator, services.eventBus]);
 
+  useEffect(() => {
+    if (notificationMode === "off") {
+      return;
+    }
+
+    return services.orchestrator.subscribe((event) => {
+      if (event.type !== "task.review" && event.type !== "task.failed") {
+        return;
+      }
+
+      const isWatchingTask =
+        route === "task-board" &&
+        !isLogViewOpen &&
+        event.task.projectId === activeProjectId;
+      if (isWatchingTask) {
+        return;
+      }
+
+      const label = event.task.shortCode ?? event.taskId;
+      const message =
+        event.type === "task.review"
+          ? `${label} is ready for review`
+          : `${label} failed: ${event.error}`;
+      const project = projects.find((candidate) => candidate.id === event.task.projectId);
+      if (isDeferredByWorkingHours(event.task, project?.workingHours)) {
+        setHeldNotifications((current) => [...current, { projectId: event.task.projectId, message }]);
+        return;
+      }
+
+      emitTerminalNotification(notificationMode, "iKanban", message);
+    });
+  }, [
+    services.orchestrator,
+    notificationMode,
+    route,
+    isLogViewOpen,
+    activeProjectId,
+    projects,
+  ]);
+
+  // Working hours open and close without any task event, so the board and held notifications are rechecked each minute.
+  const hasWorkingHours = projects.some((project) => project.workingHours !== undefined);
+  useEffect(() => {
+    if (!hasWorkingHours) {
+      return;
+    }
+
+    const timer = setInterval(() => {
+      setWorkingHoursClock(Date.now());
+    }, WORKING_HOURS_CHECK_MS);
+    return () => {
+      clearInterval(timer);
+    };
+  }, [hasWorkingHours]);
+
+  useEffect(() => {
+    const due = heldNotifications.filter(
+      (notification) =>
+        !isDeferredByWorkingHours({}, projects.find((project) => project.id === notification.projectId)?.workingHours),
+    );
+    if (due.length === 0) {
+      return;
+    }
+
+    setHeldNotifications((current) => current.filter((notification) => !due.includes(notification)));
+    if (notificationMode !== "off") {
+      emitTerminalNotification(
+        notificationMode,
+        "iKanban",
+        due.length === 1
+          ? due[0]!.message
+          : `${due.length} updates outside working hours: ${due.map((notification) => notification.message).join("; ")}`,
+      );
+    }
+  }, [heldNotifications, projects, notificationMode, workingHoursClock]);
+
   useEffect(() => {
     const unsubscribeLogs = services.eventBus.subscribeToLogs((entry) => {
       setLogs((current) => {
@@ -349,6 +972,34 @@ export function App({
     };
   }, [services.eventBus, pushBanner]);
 
+  const watchedLogTaskId = isLogViewOpen ? selectedTask?.taskId : undefined;
+  useEffect(() => {
+    setWatchedTaskLogs([]);
+    if (!watchedLogTaskId) {
+      return;
+    }
+
+    // A buffer of its own, so other busy tasks cannot push this task's entries out of the shared one.
+    return services.eventBus.subscribeToLogs(
+      (entry) => {
+        setWatchedTaskLogs((current) => [...current, entry].slice(-MAX_LOG_ENTRIES));
+      },
+      { taskId: watchedLogTaskId },
+    );
+  }, [services.eventBus, watchedLogTaskId]);
+
+  const answerPermission = useCallback(
+    async (request: PermissionRequest, reply: PermissionReply) => {
+      try {
+        await services.orchestrator.replyToPermission(request.id, reply);
+        setPermissionRequests(services.orchestrator.listPendingPermissions());
+      } catch (error) {
+        pushBanner("error", toErrorMessage(error));
+      }
+    },
+    [services.orchestrator, pushBanner],
+  );
+
   const selectProject = useCallback(
     async (projectID: string) => {
       setBusyMessage("Selecting project...");
@@ -366,300 +1017,1540 @@ export function App({
     [services.projectRegistry, pushBanner],
   );
 
-  const createProject = useCallback(
-    async (inputPath: string) => {
-      const normalizedInputPath = inputPath.trim();
-      if (!normalizedInputPath) {
-        pushBanner("warn", "Project path is required.");
+  const showMetrics = useCallback(
+    async (rollup: MetricsRollup, from?: string, to?: string) => {
+      if (!activeProjectId) {
+        pushBanner("warn", "No active project selected.");
         return;
       }
 
-      const rootDirectory = resolve(normalizedInputPath);
-      const existingProject = projects.find(
-        (project) => project.rootDirectory === rootDirectory,
-      );
-
-      setBusyMessage("Creating project...");
       try {
-        if (existingProject) {
-          pushBanner(
-            "warn",
-            `Project already exists for ${rootDirectory}. New project was not created.`,
-          );
-          return;
-        }
-
-        const projectName = basename(rootDirectory) || "project";
-        const projectID = buildUniqueProjectID(
-          projectName,
-          projects.map((project) => project.id),
-        );
-        const project = await services.projectRegistry.addProject({
-          id: projectID,
-          name: projectName,
-          rootDirectory,
-        });
-
-        await services.projectRegistry.selectProject(project.id);
-        await refreshProjects();
-        setRoute("task-board");
-        pushBanner("success", `Created project: ${project.name}`);
+        const rows = await services.metricsStore.query({ projectId: activeProjectId, from, to, rollup });
+        setMetricsReport({ rollup, from, to, rows });
       } catch (error) {
         pushBanner("error", toErrorMessage(error));
-      } finally {
-        setBusyMessage(undefined);
       }
     },
-    [projects, services.projectRegistry, refreshProjects, pushBanner],
+    [activeProjectId, services.metricsStore, pushBanner],
   );
 
-  const startProjectCreationInput = useCallback(() => {
-    const defaultPath = resolve(defaultProjectDirectory ?? process.cwd());
-    setNewProjectPathInput(defaultPath);
-    pushBanner("info", "Enter project path and press Enter to create project.");
-  }, [defaultProjectDirectory, pushBanner]);
-
-  const deleteSelectedProject = useCallback(async () => {
-    const project = projects[selectedProjectIndex];
-    if (!project) {
-      pushBanner("warn", "No project selected.");
+  const showActivityHeatmap = useCallback(async () => {
+    if (!activeProjectId) {
+      pushBanner("warn", "No active project selected.");
       return;
     }
 
-    setBusyMessage(`Deleting project ${project.id}...`);
     try {
-      const removed = await services.projectRegistry.removeProject(project.id);
-      if (!removed) {
-        pushBanner("warn", `Project ${project.id} was already removed.`);
-        await refreshProjects();
-        return;
-      }
-
-      await refreshProjects();
-      pushBanner("success", `Deleted project: ${project.name}`);
+      const now = Date.now();
+      const from = getActivityHeatmapStart(now);
+      const rows = await services.metricsStore.query({ projectId: activeProjectId, from, rollup: "day" });
+      setMetricsReport({ rollup: "day", from, rows, heatmap: buildActivityHeatmap(rows, now) });
     } catch (error) {
       pushBanner("error", toErrorMessage(error));
-    } finally {
-      setBusyMessage(undefined);
     }
-  }, [
-    projects,
-    selectedProjectIndex,
-    pushBanner,
-    services.projectRegistry,
-    refreshProjects,
-  ]);
+  }, [activeProjectId, services.metricsStore, pushBanner]);
 
-  const runTask = useCallback(
-    async (
-      initialPrompt?: string,
-      model: PromptModel | undefined = taskModel,
-    ) => {
-      if (!activeProject) {
-        pushBanner("warn", "No active project selected.");
+  /** Compares the selected task's changes with another task's, by default the previous attempt of its retry chain. */
+  const compareSelectedTask = useCallback(
+    async (query: string) => {
+      const task = selectedTask;
+      if (!task) {
+        pushBanner("warn", "No task selected.");
         return;
       }
 
-      const prompt = initialPrompt?.trim() || "";
-      const taskID = createTaskID(activeProject.id, prompt);
-      const resolvedPrompt =
-        prompt || buildDefaultPrompt(activeProject.name, taskID);
-      setPromptByTaskID((current) => ({
-        ...current,
-        [taskID]: resolvedPrompt,
-      }));
-      setModelByTaskID((current) => ({
-        ...current,
-        [taskID]: model,
-      }));
-
-      setBusyMessage(`Running ${taskID}...`);
-      try {
-        await services.orchestrator.runTask({
-          taskId: taskID,
-          projectId: activeProject.id,
-          initialPrompt: resolvedPrompt,
-          title: `Task ${taskID}`,
-          model,
-        });
-        pushBanner("success", `Task ${taskID} finished.`);
-      } catch (error) {
-        pushBanner("error", toErrorMessage(error));
-      } finally {
-        setBusyMessage(undefined);
-        setTasks(services.orchestrator.listTasks());
+      const allTasks = services.orchestrator.listTasks();
+      let other: TaskRuntime | undefined;
+      if (query) {
+        const resolution = resolveGotoTarget(query, { projects, tasks: allTasks });
+        other = resolution.status === "found" && resolution.target.kind === "task" ? resolution.target.task : undefined;
+      } else {
+        const runs = listRetryChain(task, allTasks);
+        const index = runs.findIndex((run) => run.taskId === task.taskId);
+        other = runs[index > 0 ? index - 1 : index + 1];
       }
-    },
-    [activeProject, pushBanner, services.orchestrator, taskModel],
-  );
 
-  const sendFollowUpPrompt = useCallback(
-    async (prompt: string) => {
-      const task = selectedTask;
-      if (!task) {
-        pushBanner("warn", "No task selected.");
+      if (!other || other.taskId === task.taskId) {
+        pushBanner("warn", query ? `No other task matches ${query}.` : "The selected task has no other attempt; use :compare <code|id>.");
         return;
       }
 
-      if (task.state !== "review") {
-        pushBanner(
-          "warn",
-          "Task must be in review state to send a follow-up prompt.",
-        );
+      const sides = [other, task].map((side) => ({
+        task: side,
+        project: projects.find((project) => project.id === side.projectId),
+      }));
+      const missing = sides.find((side) => !side.project || !side.task.worktreeDirectory || side.task.worktreeRemovedAt !== undefined);
+      if (missing) {
+        pushBanner("warn", `Task ${missing.task.taskId} has no worktree to compare.`);
         return;
       }
 
-      setBusyMessage(`Sending follow-up to ${task.taskId}...`);
+      setBusyMessage(`Comparing ${other.taskId} with ${task.taskId}...`);
       try {
-        await services.orchestrator.sendFollowUpPrompt(task.taskId, prompt);
-        pushBanner(
-          "success",
-          `Follow-up completed for ${task.taskId}. Now in review.`,
+        const [left, right] = await Promise.all(
+          sides.map((side) =>
+            services.worktreeManager.getTaskWorktreeDiff({
+              projectDirectory: side.project!.rootDirectory,
+              taskId: side.task.taskId,
+              worktreeDirectory: side.task.worktreeDirectory!,
+              pathScope: side.task.pathScope,
+            }),
+          ),
         );
+        setCompareView({
+          leftTaskId: other.taskId,
+          rightTaskId: task.taskId,
+          comparison: compareDiffFiles(left?.files ?? [], right?.files ?? []),
+          scrollOffset: 0,
+        });
       } catch (error) {
         pushBanner("error", toErrorMessage(error));
       } finally {
         setBusyMessage(undefined);
-        setTasks(services.orchestrator.listTasks());
       }
     },
-    [selectedTask, pushBanner, services.orchestrator],
+    [selectedTask, projects, pushBanner, services.orchestrator, services.worktreeManager],
   );
 
-  const mergeSelectedTask = useCallback(async () => {
+  const archiveSelectedTaskLogs = useCallback(async () => {
     const task = selectedTask;
     if (!task) {
       pushBanner("warn", "No task selected.");
       return;
     }
 
-    if (task.state !== "review") {
-      pushBanner("warn", "Task must be in review state to merge.");
+    if (!exportDirectory) {
+      pushBanner("warn", "Log archive export is not configured.");
       return;
     }
 
-    setBusyMessage(`Merging ${task.taskId}...`);
+    const runs = listRetryChain(task, services.orchestrator.listTasks());
+    const runIds = new Set(runs.map((run) => run.taskId));
+    const sequences = new Set<number>();
+    const runLogs = [...watchedTaskLogs, ...logs]
+      .filter((entry) => entry.taskId !== undefined && runIds.has(entry.taskId))
+      .filter((entry) => !sequences.has(entry.sequence) && sequences.add(entry.sequence))
+      .sort((left, right) => left.sequence - right.sequence);
+
+    setBusyMessage(`Archiving logs for ${task.taskId}...`);
     try {
-      const result = await services.orchestrator.mergeTask(task.taskId);
+      const result = await exportTaskLogArchive(exportDirectory, {
+        runs,
+        projectName: projects.find((project) => project.id === task.projectId)?.name,
+        logs: runLogs,
+        loadMessages: (run) => services.orchestrator.listTaskMessages(run.taskId),
+      });
       pushBanner(
         "success",
-        `Merged branch ${result.branch} for task ${task.taskId}.`,
+        `Archived ${runs.length} run(s) to ${result.filePath}${await copyToArtifactStore(artifactStore, result.filePath)}.`,
       );
-      setReviewDiff(undefined);
     } catch (error) {
       pushBanner("error", toErrorMessage(error));
     } finally {
       setBusyMessage(undefined);
-      setTasks(services.orchestrator.listTasks());
     }
-  }, [selectedTask, pushBanner, services.orchestrator]);
+  }, [selectedTask, exportDirectory, artifactStore, logs, watchedTaskLogs, projects, pushBanner, services.orchestrator]);
+
+  const runCommand = useCallback(
+    async (command: string) => {
+      const [name, ...args] = command.trim().split(/\s+/);
+      if (name === "metrics") {
+        const [rollup = "day", from, to] = args;
+        if (rollup === "heatmap") {
+          await showActivityHeatmap();
+          return;
+        }
 
-  const closeReviewDiff = useCallback(() => {
-    setReviewDiff(undefined);
-  }, []);
+        if (!isMetricsRollup(rollup)) {
+          pushBanner("warn", "Usage: :metrics [day|week|month|heatmap] [from YYYY-MM-DD] [to YYYY-MM-DD]");
+          return;
+        }
 
-  const startReviewDiff = useCallback(async () => {
-    const task = selectedTask;
-    if (!task) {
-      pushBanner("warn", "No task selected.");
-      return;
-    }
+        await showMetrics(rollup, from, to);
+        return;
+      }
 
-    if (task.state !== "review") {
-      pushBanner("warn", "Task must be in review state to inspect diff.");
-      return;
-    }
+      if (name === "scope") {
+        try {
+          const pathScope = normalizePathScope(args.join(" "));
+          setTaskPathScope(pathScope);
+          pushBanner("info", pathScope ? `New tasks are scoped to ${pathScope}/.` : "Cleared the new task path scope.");
+        } catch (error) {
+          pushBanner("warn", toErrorMessage(error));
+        }
+        return;
+      }
 
-    if (!activeProject) {
-      pushBanner("warn", "No active project selected.");
-      return;
-    }
+      if (name === "after" || name === "block" || name === "unblock") {
+        const query = args.join(" ");
+        const resolution = query ? resolveGotoTarget(query, { projects, tasks: services.orchestrator.listTasks() }) : undefined;
+        const blocker =
+          resolution?.status === "found" && resolution.target.kind === "task" ? resolution.target.task : undefined;
+        if (query && !blocker) {
+          pushBanner("warn", `No single task matches ${query}.`);
+          return;
+        }
 
-    if (!task.worktreeDirectory) {
-      pushBanner("warn", `Task ${task.taskId} has no worktree directory.`);
-      return;
-    }
+        if (name === "after") {
+          setNewTaskBlockerId(blocker?.taskId);
+          pushBanner(
+            "info",
+            blocker
+              ? `New tasks wait for ${blocker.shortCode ?? blocker.taskId} to complete before starting.`
+              : "New tasks no longer wait on another task.",
+          );
+          return;
+        }
 
-    setBusyMessage(`Loading review diff for ${task.taskId}...`);
-    try {
-      const result = await services.worktreeManager.getTaskWorktreeDiff({
-        projectDirectory: activeProject.rootDirectory,
-        taskId: task.taskId,
-        worktreeDirectory: task.worktreeDirectory,
+        if (!selectedTask || !blocker) {
+          pushBanner("warn", `Usage: :${name} <code|id> on the selected task.`);
+          return;
+        }
+
+        try {
+          const updatedTask =
+            name === "block"
+              ? await services.orchestrator.addTaskDependency(selectedTask.taskId, blocker.taskId)
+              : await services.orchestrator.removeTaskDependency(selectedTask.taskId, blocker.taskId);
+          pushBanner(
+            "info",
+            name === "block"
+              ? `${updatedTask.shortCode ?? updatedTask.taskId} waits for ${blocker.shortCode ?? blocker.taskId}.`
+              : `${updatedTask.shortCode ?? updatedTask.taskId} no longer waits for ${blocker.shortCode ?? blocker.taskId}.`,
+          );
+        } catch (error) {
+          pushBanner("warn", toErrorMessage(error));
+        }
+        return;
+      }
+
+      if (name === "label") {
+        const [action = "", labelName = "", color] = args;
+        if (color !== undefined && !isLabelColor(color)) {
+          pushBanner("warn", `Label colors: ${LABEL_COLORS.join(", ")}.`);
+          return;
+        }
+
+        const label = labelName ? findLabelByName(labels, labelName) : undefined;
+        try {
+          if (action === "") {
+            pushBanner(
+              "info",
+              labels.length > 0
+                ? `Labels: ${labels.map((entry) => `${entry.name} (${entry.color})`).join(", ")}`
+                : "No labels yet; :label add <name> [color] creates one.",
+            );
+          } else if (action === "filter") {
+            if (labelName && !label) {
+              pushBanner("warn", `No label named ${labelName}.`);
+              return;
+            }
+
+            setLabelFilterId(label?.id);
+            pushBanner("info", label ? `Showing tasks labelled ${label.name}.` : "Cleared the label filter.");
+          } else if (action === "add" && labelName) {
+            if (!selectedTask || !activeProjectId) {
+              pushBanner("warn", "No task selected.");
+              return;
+            }
+
+            const attached = label
+              ? color
+                ? await services.labelRegistry.setLabelColor(label.id, color)
+                : label
+              : await services.labelRegistry.createLabel({ projectId: activeProjectId, name: labelName, color });
+            await services.orchestrator.setTaskLabels(selectedTask.taskId, [...(selectedTask.labelIds ?? []), attached.id]);
+            pushBanner("success", `Labelled ${selectedTask.shortCode ?? selectedTask.taskId} ${attached.name}.`);
+          } else if (action === "rm" && label) {
+            if (!selectedTask) {
+              pushBanner("warn", "No task selected.");
+              return;
+            }
+
+            await services.orchestrator.setTaskLabels(
+              selectedTask.taskId,
+              (selectedTask.labelIds ?? []).filter((labelId) => labelId !== label.id),
+            );
+            pushBanner("info", `Removed ${label.name} from ${selectedTask.shortCode ?? selectedTask.taskId}.`);
+          } else if (action === "color" && label && color) {
+            await services.labelRegistry.setLabelColor(label.id, color);
+            pushBanner("info", `${label.name} is now ${color}.`);
+          } else if (action === "delete" && label) {
+            for (const task of services.orchestrator.listTasks()) {
+              if (task.labelIds?.includes(label.id)) {
+                await services.orchestrator.setTaskLabels(
+                  task.taskId,
+                  task.labelIds.filter((labelId) => labelId !== label.id),
+                );
+              }
+            }
+            await services.labelRegistry.removeLabel(label.id);
+            if (labelFilterId === label.id) {
+              setLabelFilterId(undefined);
+            }
+            pushBanner("info", `Deleted label ${label.name}.`);
+          } else if (labelName && !label && action !== "add") {
+            pushBanner("warn", `No label named ${labelName}.`);
+          } else {
+            pushBanner("warn", "Usage: :label add|rm <name> [color], :label color <name> <color>, :label delete <name> or :label filter [name]");
+          }
+        } catch (error) {
+          pushBanner("warn", toErrorMessage(error));
+        } finally {
+          await refreshLabels();
+          setTasks(services.orchestrator.listTasks());
+        }
+        return;
+      }
+
+      if (name === "comment") {
+        if (!selectedTask) {
+          pushBanner("warn", "No task selected.");
+          return;
+        }
+
+        const [action = "", idText = "", ...rest] = args;
+        const commentId = Number(idText);
+        const taskLabel = selectedTask.shortCode ?? selectedTask.taskId;
+        try {
+          if (action === "rm" && Number.isInteger(commentId) && rest.length === 0) {
+            await services.orchestrator.removeTaskComment(selectedTask.taskId, commentId);
+            pushBanner("info", `Deleted comment #${commentId} on ${taskLabel}.`);
+          } else if (action === "edit" && Number.isInteger(commentId) && rest.length > 0) {
+            await services.orchestrator.editTaskComment(selectedTask.taskId, commentId, rest.join(" "));
+            pushBanner("info", `Edited comment #${commentId} on ${taskLabel}.`);
+          } else if (args.length > 0) {
+            const comment = await services.orchestrator.addTaskComment(selectedTask.taskId, { body: args.join(" ") });
+            pushBanner("success", `Commented #${comment.id} on ${taskLabel}.`);
+          } else {
+            pushBanner("warn", "Usage: :comment <text>, :comment edit <n> <text> or :comment rm <n>");
+          }
+        } catch (error) {
+          pushBanner("warn", toErrorMessage(error));
+        } finally {
+          setTasks(services.orchestrator.listTasks());
+        }
+        return;
+      }
+
+      if (name === "hours") {
+        const [range = "", days] = args;
+        if (!activeProject) {
+          pushBanner("warn", "No active project.");
+          return;
+        }
+
+        try {
+          if (range === "") {
+            pushBanner(
+              "info",
+              activeProject.workingHours
+                ? `${activeProject.name} works ${formatWorkingHours(activeProject.workingHours)}.`
+                : `${activeProject.name} has no working hours; :hours 09:00-18:00 mon-fri sets them.`,
+            );
+            return;
+          }
+
+          const project = await services.orchestrator.setProjectWorkingHours(
+            activeProject.id,
+            range === "off" ? undefined : parseWorkingHoursSpec(range, days),
+          );
+          pushBanner(
+            "info",
+            project.workingHours
+              ? `${project.name} starts queued tasks and sends notifications only ${formatWorkingHours(project.workingHours)}; urgent tasks are exempt.`
+              : `Cleared the working hours of ${project.name}.`,
+          );
+          await refreshProjects();
+        } catch (error) {
+          pushBanner("warn", toErrorMessage(error));
+        }
+        return;
+      }
+
+      if (name === "limit") {
+        const [value = ""] = args;
+        if (!activeProject) {
+          pushBanner("warn", "No active project.");
+          return;
+        }
+
+        if (value === "") {
+          pushBanner(
+            "info",
+            activeProject.maxConcurrentTasks !== undefined
+              ? `${activeProject.name} runs at most ${activeProject.maxConcurrentTasks} tasks at once.`
+              : `${activeProject.name} uses the global per-project limit; :limit <n> sets its own.`,
+          );
+          return;
+        }
+
+        const limit = Number(value);
+        if (value !== "off" && (!Number.isInteger(limit) || limit <= 0)) {
+          pushBanner("warn", "Usage: :limit <tasks>|off for the active project.");
+          return;
+        }
+
+        try {
+          const project = await services.orchestrator.setProjectMaxConcurrentTasks(
+            activeProject.id,
+            value === "off" ? undefined : limit,
+          );
+          pushBanner(
+            "info",
+            project.maxConcurrentTasks !== undefined
+              ? `${project.name} runs at most ${project.maxConcurrentTasks} tasks at once.`
+              : `${project.name} uses the global per-project limit again.`,
+          );
+          await refreshProjects();
+        } catch (error) {
+          pushBanner("warn", toErrorMessage(error));
+        }
+        return;
+      }
+
+      if (name === "tutorial") {
+        if (args[0] === "off") {
+          setTutorial(undefined);
+          pushBanner("info", "Tutorial closed; :tutorial starts it again.");
+        } else {
+          setTutorial({ stepIndex: 0, startedAt: Date.now() });
+          pushBanner("info", "Tutorial started; each step completes when you do it in the app.");
+        }
+        return;
+      }
+
+      if (name === "timeout") {
+        const [value = ""] = args;
+        const minutes = Number(value);
+        if (!selectedTask || (value !== "off" && (!Number.isFinite(minutes) || minutes <= 0))) {
+          pushBanner("warn", "Usage: :timeout <minutes>|off on the selected task.");
+          return;
+        }
+
+        try {
+          const updatedTask = await services.orchestrator.setTaskTimeout(
+            selectedTask.taskId,
+            value === "off" ? undefined : Math.round(minutes * 60_000),
+          );
+          pushBanner(
+            "info",
+            updatedTask.timeoutMs
+              ? `${updatedTask.shortCode ?? updatedTask.taskId} runs are limited to ${formatWatchdogLimit(updatedTask.timeoutMs)}.`
+              : `Cleared the run timeout of ${updatedTask.shortCode ?? updatedTask.taskId}.`,
+          );
+        } catch (error) {
+          pushBanner("warn", toErrorMessage(error));
+        }
+        return;
+      }
+
+      if (name === "archive") {
+        await archiveSelectedTaskLogs();
+        return;
+      }
+
+      if (name === "compare") {
+        await compareSelectedTask(args.join(" "));
+        return;
+      }
+
+      if (name === "worktree") {
+        const [action = ""] = args;
+        try {
+          if (action === "rm") {
+            if (!selectedTask) {
+              pushBanner("warn", "No task selected.");
+              return;
+            }
+
+            const updatedTask = await services.orchestrator.removeTaskWorktree(selectedTask.taskId);
+            pushBanner("success", `Removed the worktree of ${updatedTask.shortCode ?? updatedTask.taskId}.`);
+          } else if (action === "orphans") {
+            const orphans = await services.orchestrator.listOrphanedWorktrees(activeProjectId);
+            pushBanner(
+              "info",
+              orphans.length > 0
+                ? `${orphans.length} orphaned worktree(s): ${orphans.join(", ")}`
+                : "No orphaned worktrees.",
+            );
+          } else if (action === "prune") {
+            const { removed, kept } = await services.orchestrator.pruneOrphanedWorktrees(activeProjectId);
+            pushBanner(
+              kept.length > 0 ? "warn" : "success",
+              `Removed ${removed.length} orphaned worktree(s)${kept.length > 0 ? `; kept ${kept.length} that could not be removed (see logs): ${kept.join(", ")}` : "."}`,
+            );
+          } else {
+            pushBanner("warn", "Usage: :worktree rm|orphans|prune");
+          }
+        } catch (error) {
+          pushBanner("error", toErrorMessage(error));
+        } finally {
+          setTasks(services.orchestrator.listTasks());
+        }
+        return;
+      }
+
+      if (name !== "goto" && name !== "g") {
+        pushBanner("warn", `Unknown command: ${name ?? ""}. Try :goto <code|id>, :scope <path>, :after <code|id>, :block <code|id>, :unblock <code|id>, :label, :comment <text>, :hours <HH:MM-HH:MM> [days], :timeout <minutes>, :archive, :tutorial, :compare, :worktree or :metrics.`);
+        return;
+      }
+
+      const query = args.join(" ");
+      const resolution = resolveGotoTarget(query, {
+        projects,
+        tasks: services.orchestrator.listTasks(),
       });
+      if (resolution.status === "not_found") {
+        pushBanner("warn", `Nothing matches ${query || "(empty)"}.`);
+        return;
+      }
 
-      setReviewDiff({
-        taskId: task.taskId,
-        branch: result.branch,
-        defaultBranch: result.defaultBranch,
-        summary: result.summary,
-        diff: result.diff,
+      if (resolution.status === "ambiguous") {
+        pushBanner("warn", `${query} is ambiguous: ${resolution.candidates.slice(0, 5).join(", ")}`);
+        return;
+      }
+
+      const { target } = resolution;
+      const projectId = target.kind === "project" ? target.project.id : target.task.projectId;
+      setIsFailureViewOpen(false);
+      setMilestoneFilterId(undefined);
+      setLabelFilterId(undefined);
+      setSelectedProjectIndex((current) => {
+        const index = projects.findIndex((project) => project.id === projectId);
+        return index >= 0 ? index : current;
       });
+      if (projectId !== activeProjectId) {
+        await selectProject(projectId);
+      }
 
-      if (!result.hasChanges) {
-        pushBanner(
-          "info",
-          `No diff detected for ${task.taskId}. You can still merge if needed.`,
-        );
+      setRoute("task-board");
+      if (target.kind === "task") {
+        setPendingTaskFocusId(target.task.taskId);
       }
-    } catch (error) {
-      pushBanner("error", toErrorMessage(error));
-    } finally {
-      setBusyMessage(undefined);
-    }
-  }, [selectedTask, pushBanner, activeProject, services.worktreeManager]);
+      pushBanner("info", `Jumped to ${describeGotoTarget(target)}.`);
+    },
+    [
+      projects,
+      activeProjectId,
+      activeProject,
+      refreshProjects,
+      selectedTask,
+      labels,
+      labelFilterId,
+      refreshLabels,
+      services.orchestrator,
+      services.labelRegistry,
+      selectProject,
+      showMetrics,
+      showActivityHeatmap,
+      archiveSelectedTaskLogs,
+      compareSelectedTask,
+      pushBanner,
+    ],
+  );
 
-  const startFollowUpPromptInput = useCallback(() => {
-    const task = selectedTask;
-    if (!task) {
-      pushBanner("warn", "No task selected.");
+  // First run: nothing has been set up yet, so walk through the basics.
+  useEffect(() => {
+    if (loading || tutorialOffered) {
       return;
     }
 
-    if (task.state !== "review") {
-      pushBanner(
-        "warn",
-        "Task must be in review state to send a follow-up prompt.",
-      );
+    setTutorialOffered(true);
+    if (projects.length === 0 && services.orchestrator.listTasks().length === 0) {
+      setTutorial({ stepIndex: 0, startedAt: Date.now() });
+    }
+  }, [loading, tutorialOffered, projects, services.orchestrator]);
+
+  useEffect(() => {
+    if (!tutorial) {
       return;
     }
 
-    setFollowUpPromptInput("");
-    pushBanner("info", "Enter follow-up prompt and press Enter to send.");
-  }, [selectedTask, pushBanner]);
+    const snapshot: TutorialSnapshot = {
+      route,
+      projectCount: projects.length,
+      activeProjectId,
+      tasks,
+      selectedTaskId: selectedTask?.taskId,
+      reviewTaskId: reviewDiff?.taskId,
+    };
+    const stepIndex = advanceTutorial(tutorial.stepIndex, snapshot, tutorial.startedAt);
+    if (stepIndex >= TUTORIAL_STEPS.length) {
+      setTutorial(undefined);
+      pushBanner("success", "Tutorial complete. The footer always lists the keys for the current view.");
+    } else if (stepIndex !== tutorial.stepIndex) {
+      setTutorial({ ...tutorial, stepIndex });
+    }
+  }, [tutorial, route, projects, activeProjectId, tasks, selectedTask, reviewDiff, pushBanner]);
 
-  const startTaskPromptInput = useCallback(() => {
-    if (!activeProject) {
-      pushBanner("warn", "No active project selected.");
+  useEffect(() => {
+    if (loading || startupApplied) {
       return;
     }
 
-    setNewTaskPromptInput("");
-    pushBanner(
-      "info",
-      `Enter the first task prompt and press Enter to run (model: ${formatModel(taskModel, defaultModelLabel)}).`,
-    );
-  }, [activeProject, defaultModelLabel, pushBanner, taskModel]);
-
-  const openTaskModelPicker = useCallback(async () => {
-    if (!activeProject) {
-      pushBanner("warn", "No active project selected.");
+    setStartupApplied(true);
+    if (!startup) {
       return;
     }
 
-    setBusyMessage("Loading available models...");
-    try {
-      const options = await loadModelOptions(
-        services,
-        activeProject.rootDirectory,
-      );
-      setModelOptions(options);
-      setModelFilterInput("");
-      const activeIndex = options.findIndex((candidate) =>
-        isSameModel(candidate.model, taskModel),
-      );
-      setSelectedModelOptionIndex(activeIndex >= 0 ? activeIndex : 0);
-      setModelPickerOpen(true);
+    const applyStartup = async () => {
+      if (startup.task) {
+        await runCommand(`goto ${startup.task}`);
+      } else if (startup.project) {
+        // Projects only: task ids start with the project id and would make the name ambiguous.
+        const resolution = resolveGotoTarget(startup.project, { projects, tasks: [] });
+        if (resolution.status !== "found" || resolution.target.kind !== "project") {
+          pushBanner("warn", `No single project matches --project ${startup.project}.`);
+          return;
+        }
+
+        const projectId = resolution.target.project.id;
+        setSelectedProjectIndex(projects.findIndex((project) => project.id === projectId));
+        await selectProject(projectId);
+      }
+
+      if (!startup.task || startup.followUpPrompt === undefined) {
+        return;
+      }
+
+      const resolution = resolveGotoTarget(startup.task, {
+        projects: [],
+        tasks: services.orchestrator.listTasks(),
+      });
+      if (resolution.status !== "found" || resolution.target.kind !== "task") {
+        return;
+      }
+
+      const task = resolution.target.task;
+      setIsLogViewOpen(true);
+      pushBanner("info", `Sending the --prompt-file follow-up to ${task.shortCode ?? task.taskId}.`);
+      try {
+        await services.orchestrator.sendFollowUpPrompt(task.taskId, startup.followUpPrompt);
+        pushBanner("success", `Follow-up for ${task.shortCode ?? task.taskId} finished.`);
+      } catch (error) {
+        pushBanner("error", toErrorMessage(error));
+      } finally {
+        setTasks(services.orchestrator.listTasks());
+      }
+    };
+
+    void applyStartup();
+  }, [loading, startupApplied, startup, projects, runCommand, selectProject, services.orchestrator, pushBanner]);
+
+  useEffect(() => {
+    if (!pendingTaskFocusId) {
+      return;
+    }
+
+    const index = tasksForActiveProject.findIndex((task) => task.taskId === pendingTaskFocusId);
+    if (index >= 0) {
+      setSelectedTaskIndex(index);
+      setPendingTaskFocusId(undefined);
+    }
+  }, [pendingTaskFocusId, tasksForActiveProject]);
+
+  const createProject = useCallback(
+    async (inputPath: string) => {
+      const normalizedInputPath = inputPath.trim();
+      if (!normalizedInputPath) {
+        pushBanner("warn", "Project path is required.");
+        return;
+      }
+
+      const rootDirectory = resolve(normalizedInputPath);
+      const existingProject = projects.find(
+        (project) => project.rootDirectory === rootDirectory,
+      );
+
+      setBusyMessage("Creating project...");
+      try {
+        if (existingProject) {
+          pushBanner(
+            "warn",
+            `Project already exists for ${rootDirectory}. New project was not created.`,
+          );
+          return;
+        }
+
+        const projectName = basename(rootDirectory) || "project";
+        const projectID = buildUniqueProjectID(
+          projectName,
+          projects.map((project) => project.id),
+        );
+        const project = await services.projectRegistry.addProject({
+          id: projectID,
+          name: projectName,
+          rootDirectory,
+        });
+
+        await services.projectRegistry.selectProject(project.id);
+        await refreshProjects();
+        setRoute("task-board");
+        pushBanner("success", `Created project: ${project.name}`);
+      } catch (error) {
+        const issue = toValidationIssue(error);
+        if (issue?.field === "rootDirectory") {
+          // Reopen the path input so the problem shows next to what was typed.
+          setNewProjectPathInput(inputPath);
+          setFieldError(issue);
+          return;
+        }
+
+        pushBanner("error", toErrorMessage(error));
+      } finally {
+        setBusyMessage(undefined);
+      }
+    },
+    [projects, services.projectRegistry, refreshProjects, pushBanner],
+  );
+
+  const startProjectCreationInput = useCallback(() => {
+    const defaultPath = resolve(defaultProjectDirectory ?? process.cwd());
+    setNewProjectPathInput(defaultPath);
+    pushBanner("info", "Enter project path and press Enter to create project.");
+  }, [defaultProjectDirectory, pushBanner]);
+
+  const toggleSelectedProjectGitHooks = useCallback(
+    async (install: boolean) => {
+      const project = projects[selectedProjectIndex];
+      if (!project) {
+        pushBanner("warn", "No project selected.");
+        return;
+      }
+
+      try {
+        if (install) {
+          const result = await installTaskGitHooks(project.rootDirectory);
+          pushBanner(
+            "success",
+            `Installed ${result.hooks.join(", ")} hooks in ${result.hooksDirectory}${
+              result.backedUp.length > 0 ? ` (existing ${result.backedUp.join(", ")} kept as backup)` : ""
+            }.`,
+          );
+          return;
+        }
+
+        const result = await uninstallTaskGitHooks(project.rootDirectory);
+        pushBanner(
+          result.hooks.length > 0 ? "success" : "info",
+          result.hooks.length > 0
+            ? `Removed ${result.hooks.join(", ")} hooks${
+              result.backedUp.length > 0 ? `, restored ${result.backedUp.join(", ")}` : ""
+            }.`
+            : "No ikanban hooks installed for this project.",
+        );
+      } catch (error) {
+        pushBanner("error", toErrorMessage(error));
+      }
+    },
+    [projects, selectedProjectIndex, pushBanner],
+  );
+
+  const copySelectedProjectChangelog = useCallback(
+    async (since: string) => {
+      const project = projects[selectedProjectIndex];
+      if (!project) {
+        pushBanner("warn", "No project selected.");
+        return;
+      }
+
+      setBusyMessage(`Building changelog for ${project.name}...`);
+      try {
+        const changelog = await buildProjectChangelog({
+          projectDirectory: project.rootDirectory,
+          projectName: project.name,
+          tasks: tasks.filter((task) => task.projectId === project.id),
+          since,
+        });
+        const copied = await copyToClipboard(changelog.markdown);
+        if (copied !== "copied") {
+          pushBanner("warn", describeClipboardFailure(copied));
+          return;
+        }
+
+        pushBanner(
+          "success",
+          `Copied changelog with ${changelog.entries.length} merged tasks${
+            changelog.since ? ` since ${changelog.since}` : ""
+          }.`,
+        );
+      } catch (error) {
+        pushBanner("error", toErrorMessage(error));
+      } finally {
+        setBusyMessage(undefined);
+      }
+    },
+    [projects, selectedProjectIndex, tasks, pushBanner],
+  );
+
+  const deleteSelectedProject = useCallback(async () => {
+    const project = projects[selectedProjectIndex];
+    if (!project) {
+      pushBanner("warn", "No project selected.");
+      return;
+    }
+
+    setBusyMessage(`Deleting project ${project.id}...`);
+    try {
+      const removed = await services.projectRegistry.removeProject(project.id);
+      if (!removed) {
+        pushBanner("warn", `Project ${project.id} was already removed.`);
+        await refreshProjects();
+        return;
+      }
+
+      await refreshProjects();
+      pushBanner("success", `Deleted project: ${project.name}`);
+    } catch (error) {
+      pushBanner("error", toErrorMessage(error));
+    } finally {
+      setBusyMessage(undefined);
+    }
+  }, [
+    projects,
+    selectedProjectIndex,
+    pushBanner,
+    services.projectRegistry,
+    refreshProjects,
+  ]);
+
+  const runTask = useCallback(
+    async (
+      initialPrompt?: string,
+      model: PromptModel | undefined = taskModel,
+      project: ProjectRef | undefined = activeProject,
+    ) => {
+      if (!project) {
+        pushBanner("warn", "No active project selected.");
+        return;
+      }
+
+      const prompt = initialPrompt?.trim() || "";
+      const taskID = createTaskID(project.id, prompt);
+      const resolvedPrompt =
+        prompt || buildDefaultPrompt(project.name, taskID);
+      setPromptByTaskID((current) => ({
+        ...current,
+        [taskID]: resolvedPrompt,
+      }));
+      setModelByTaskID((current) => ({
+        ...current,
+        [taskID]: model,
+      }));
+
+      setBusyMessage(`Running ${taskID}...`);
+      try {
+        await services.orchestrator.runTask({
+          taskId: taskID,
+          projectId: project.id,
+          initialPrompt: resolvedPrompt,
+          // The scope is a path in the active project's repository.
+          pathScope: project.id === activeProject?.id ? taskPathScope : undefined,
+          blockedBy: project.id === activeProject?.id && newTaskBlockerId ? [newTaskBlockerId] : undefined,
+          parentTaskId: project.id === activeProject?.id ? subtaskParentId : undefined,
+          model,
+        });
+        pushBanner("success", `Task ${taskID} finished.`);
+      } catch (error) {
+        pushBanner("error", toErrorMessage(error));
+      } finally {
+        setBusyMessage(undefined);
+        setTasks(services.orchestrator.listTasks());
+      }
+    },
+    [activeProject, pushBanner, services.orchestrator, taskModel, taskPathScope, newTaskBlockerId, subtaskParentId],
+  );
+
+  const retryFailedTask = useCallback(
+    async (task: TaskRuntime) => {
+      setBusyMessage(`Retrying ${task.shortCode ?? task.taskId}...`);
+      try {
+        const result = await services.orchestrator.retryTask(task.taskId);
+        pushBanner("success", `Retry ${result.task.shortCode ?? result.task.taskId} finished.`);
+      } catch (error) {
+        pushBanner("error", toErrorMessage(error));
+      } finally {
+        setBusyMessage(undefined);
+        setTasks(services.orchestrator.listTasks());
+      }
+    },
+    [pushBanner, services.orchestrator],
+  );
+
+  const attributeFile = useCallback(
+    async (filePath: string) => {
+      if (!activeProject) {
+        pushBanner("warn", "No active project selected.");
+        return;
+      }
+
+      setBusyMessage(`Tracing ${filePath}...`);
+      try {
+        const entries = await attributeFileToTasks({
+          projectDirectory: activeProject.rootDirectory,
+          filePath,
+          tasks: tasksForActiveProject,
+        });
+        setAttribution({ filePath, entries });
+        if (entries.length === 0) {
+          pushBanner("info", `No ikanban tasks touched ${filePath}.`);
+        }
+      } catch (error) {
+        pushBanner("error", toErrorMessage(error));
+      } finally {
+        setBusyMessage(undefined);
+      }
+    },
+    [activeProject, tasksForActiveProject, pushBanner],
+  );
+
+  const runTaskBatch = useCallback(
+    async (
+      prompts: string[],
+      model: PromptModel | undefined = taskModel,
+      parentTaskId?: string,
+    ) => {
+      if (!activeProject) {
+        pushBanner("warn", "No active project selected.");
+        return;
+      }
+
+      // The whole batch is built in one millisecond, so the line index keeps repeated prompts apart.
+      const inputs: RunTaskInput[] = prompts.map((prompt, index) => {
+        const taskID = createTaskID(`${activeProject.id}:${index}`, prompt);
+        return {
+          taskId: taskID,
+          projectId: activeProject.id,
+          parentTaskId,
+          initialPrompt: prompt,
+          pathScope: taskPathScope,
+          blockedBy: newTaskBlockerId ? [newTaskBlockerId] : undefined,
+          model,
+        };
+      });
+      setPromptByTaskID((current) => ({
+        ...current,
+        ...Object.fromEntries(inputs.map((input) => [input.taskId, input.initialPrompt])),
+      }));
+      setModelByTaskID((current) => ({
+        ...current,
+        ...Object.fromEntries(inputs.map((input) => [input.taskId, model])),
+      }));
+
+      setBusyMessage(`Running ${inputs.length} tasks...`);
+      try {
+        const results = await services.orchestrator.runTasks(inputs);
+        const failedCount = results.filter((result) => result.status === "rejected").length;
+        pushBanner(
+          failedCount > 0 ? "warn" : "success",
+          `${inputs.length - failedCount}/${inputs.length} tasks finished.`,
+        );
+      } catch (error) {
+        pushBanner("error", toErrorMessage(error));
+      } finally {
+        setBusyMessage(undefined);
+        setTasks(services.orchestrator.listTasks());
+      }
+    },
+    [activeProject, pushBanner, services.orchestrator, taskModel, taskPathScope, newTaskBlockerId],
+  );
+
+  const planSelectedTask = useCallback(async () => {
+    const task = selectedTask;
+    if (!task) {
+      pushBanner("warn", "No task selected.");
+      return;
+    }
+
+    if (!activeProject) {
+      pushBanner("warn", "No active project selected.");
+      return;
+    }
+
+    const objective = task.prompt ?? promptByTaskID[task.taskId] ?? task.title ?? "";
+    setBusyMessage(`Planning ${task.taskId}...`);
+    try {
+      const plan = await services.taskPlanner.planTask({
+        project: activeProject,
+        task,
+        objective,
+        model: task.model ?? taskModel,
+      });
+      setPlanReview({
+        parentTaskId: plan.parentTaskId,
+        items: plan.items,
+        selectedIndex: 0,
+      });
+      pushBanner(
+        "info",
+        `Review ${plan.items.length} planned subtasks before creating them.`,
+      );
+    } catch (error) {
+      pushBanner("error", toErrorMessage(error));
+    } finally {
+      setBusyMessage(undefined);
+    }
+  }, [
+    selectedTask,
+    activeProject,
+    promptByTaskID,
+    services.taskPlanner,
+    taskModel,
+    pushBanner,
+  ]);
+
+  const sendFollowUpPrompt = useCallback(
+    async (prompt: string) => {
+      const task = selectedTask;
+      if (!task) {
+        pushBanner("warn", "No task selected.");
+        return;
+      }
+
+      if (task.state !== "review") {
+        pushBanner(
+          "warn",
+          "Task must be in review state to send a follow-up prompt.",
+        );
+        return;
+      }
+
+      setBusyMessage(`Sending follow-up to ${task.taskId}...`);
+      try {
+        await services.orchestrator.sendFollowUpPrompt(task.taskId, prompt);
+        pushBanner(
+          "success",
+          `Follow-up completed for ${task.taskId}. Now in review.`,
+        );
+      } catch (error) {
+        pushBanner("error", toErrorMessage(error));
+      } finally {
+        setBusyMessage(undefined);
+        setTasks(services.orchestrator.listTasks());
+      }
+    },
+    [selectedTask, pushBanner, services.orchestrator],
+  );
+
+  const mergeSelectedTask = useCallback(async (strategy: MergeStrategy = "squash") => {
+    const task = selectedTask;
+    if (!task) {
+      pushBanner("warn", "No task selected.");
+      return;
+    }
+
+    if (task.state !== "review") {
+      pushBanner("warn", "Task must be in review state to merge.");
+      return;
+    }
+
+    if (!listNextTaskStates(task.state, activeProject?.taskTransitions).includes("completed")) {
+      pushBanner("warn", `Project rules do not allow merging from ${task.state}.`);
+      return;
+    }
+
+    setBusyMessage(`Merging ${task.taskId}...`);
+    try {
+      const result = await services.orchestrator.mergeTask(task.taskId, { strategy });
+      pushBanner(
+        "success",
+        result.merged
+          ? `${strategy === "fast-forward" ? "Fast-forwarded" : "Squash-merged"} ${result.branch} into ${result.targetBranch} at ${result.commit?.slice(0, 7)}.`
+          : `Nothing to merge from ${result.branch}; ${task.taskId} is completed.`,
+      );
+      setReviewDiff(undefined);
+    } catch (error) {
+      pushBanner("error", toErrorMessage(error));
+    } finally {
+      setBusyMessage(undefined);
+      setTasks(services.orchestrator.listTasks());
+    }
+  }, [selectedTask, activeProject, pushBanner, services.orchestrator]);
+
+  const pushSelectedTaskBranch = useCallback(
+    async (forceWithLease: boolean) => {
+      const task = selectedTask;
+      if (!task) {
+        pushBanner("warn", "No task selected.");
+        return;
+      }
+
+      if (task.state !== "review") {
+        pushBanner("warn", "Task must be in review state to push its branch.");
+        return;
+      }
+
+      setBusyMessage(`Pushing ${task.taskId}...`);
+      try {
+        const result = await services.orchestrator.pushTaskBranch(task.taskId, {
+          forceWithLease,
+        });
+        pushBanner(
+          "success",
+          `Pushed ${result.branch} to ${result.remote}${result.forced ? " (force-with-lease)" : ""}.`,
+        );
+      } catch (error) {
+        pushBanner("error", toErrorMessage(error));
+      } finally {
+        setBusyMessage(undefined);
+        setTasks(services.orchestrator.listTasks());
+      }
+    },
+    [selectedTask, pushBanner, services.orchestrator],
+  );
+
+  const openSelectedTaskPullRequest = useCallback(async () => {
+    const task = selectedTask;
+    if (!task) {
+      pushBanner("warn", "No task selected.");
+      return;
+    }
+
+    if (task.state !== "review") {
+      pushBanner("warn", "Task must be in review state to open a pull request.");
+      return;
+    }
+
+    setBusyMessage(`Opening a pull request for ${task.taskId}...`);
+    try {
+      const pullRequest = await services.orchestrator.openPullRequest(task.taskId);
+      pushBanner("success", `Opened pull request #${pullRequest.number}: ${pullRequest.url}`);
+    } catch (error) {
+      pushBanner("error", toErrorMessage(error));
+    } finally {
+      setBusyMessage(undefined);
+      setTasks(services.orchestrator.listTasks());
+    }
+  }, [selectedTask, pushBanner, services.orchestrator]);
+
+  const exportSelectedTaskTranscript = useCallback(async () => {
+    const task = selectedTask;
+    if (!task) {
+      pushBanner("warn", "No task selected.");
+      return;
+    }
+
+    if (!exportDirectory) {
+      pushBanner("warn", "Transcript export is not configured.");
+      return;
+    }
+
+    setBusyMessage(`Exporting transcript for ${task.taskId}...`);
+    try {
+      const messages = await services.orchestrator.listTaskMessages(task.taskId);
+      const result = await exportTaskTranscriptHtml(exportDirectory, {
+        task,
+        projectName: projects.find((project) => project.id === task.projectId)?.name,
+        messages,
+      });
+      pushBanner(
+        "success",
+        `Exported ${result.messageCount} messages to ${result.filePath}${await copyToArtifactStore(artifactStore, result.filePath)}.`,
+      );
+    } catch (error) {
+      pushBanner("error", toErrorMessage(error));
+    } finally {
+      setBusyMessage(undefined);
+    }
+  }, [selectedTask, exportDirectory, artifactStore, projects, pushBanner, services.orchestrator]);
+
+  const decideSelectedTaskReview = useCallback(
+    async (decision: "approved" | "changes_requested") => {
+      const task = selectedTask;
+      if (!task) {
+        pushBanner("warn", "No task selected.");
+        return;
+      }
+
+      try {
+        await services.orchestrator.decideTaskReview(task.taskId, decision);
+        setTasks(services.orchestrator.listTasks());
+
+        if (decision === "approved") {
+          pushBanner("success", `Approved ${task.taskId}. Press m to merge.`);
+          return;
+        }
+
+        setReviewDiff(undefined);
+        setFollowUpPromptInput("");
+        pushBanner(
+          "info",
+          `Requested changes on ${task.taskId}. Describe them and press Enter to send.`,
+        );
+      } catch (error) {
+        pushBanner("error", toErrorMessage(error));
+      }
+    },
+    [selectedTask, pushBanner, services.orchestrator],
+  );
+
+  const closeReviewDiff = useCallback(() => {
+    setReviewDiff(undefined);
+  }, []);
+
+  const loadDiffView = useCallback(
+    async (task: TaskRuntime, options: { quiet?: boolean } = {}) => {
+      const project = projects.find((candidate) => candidate.id === task.projectId);
+      if (!project || !task.worktreeDirectory) {
+        pushBanner("warn", `Task ${task.taskId} has no worktree directory.`);
+        return;
+      }
+
+      if (!options.quiet) {
+        setBusyMessage(`Loading diff for ${task.taskId}...`);
+      }
+      try {
+        const loadedAt = Date.now();
+        const result = await services.worktreeManager.getTaskWorktreeDiff({
+          projectDirectory: project.rootDirectory,
+          taskId: task.taskId,
+          worktreeDirectory: task.worktreeDirectory,
+          pathScope: task.pathScope,
+        });
+        setDiffView((current) => {
+          // A refresh stays on the file and scroll position being read.
+          const keep = current?.taskId === task.taskId ? current : undefined;
+          const currentPath = keep?.files[keep.fileIndex]?.path;
+          const fileIndex = Math.max(0, result.files.findIndex((file) => file.path === currentPath));
+          return {
+            taskId: task.taskId,
+            loadedAt,
+            files: result.files,
+            fileIndex,
+            foldedHunks: keep && fileIndex === keep.fileIndex ? keep.foldedHunks : new Set(),
+            scrollOffset: keep && fileIndex === keep.fileIndex ? keep.scrollOffset : 0,
+          };
+        });
+      } catch (error) {
+        pushBanner("error", toErrorMessage(error));
+      } finally {
+        if (!options.quiet) {
+          setBusyMessage(undefined);
+        }
+      }
+    },
+    [projects, pushBanner, services.worktreeManager],
+  );
+
+  const diffViewTask = diffView ? tasks.find((task) => task.taskId === diffView.taskId) : undefined;
+  const isDiffViewLive = diffViewTask?.state === "running" && !diffView?.commit;
+  const commitHistoryTask = commitHistory
+    ? tasks.find((task) => task.taskId === commitHistory.taskId)
+    : undefined;
+  // The worktree watcher only follows review worktrees, so a running agent's edits are polled for.
+  // Keyed on the task id: the task object is replaced on every update while it runs.
+  useEffect(() => {
+    if (!diffViewTask || !isDiffViewLive) {
+      return;
+    }
+
+    const timer = setInterval(() => {
+      void loadDiffView(diffViewTask, { quiet: true });
+    }, DIFF_VIEW_REFRESH_MS);
+    return () => {
+      clearInterval(timer);
+    };
+  }, [diffViewTask?.taskId, isDiffViewLive, loadDiffView]);
+
+  const openCommitHistory = useCallback(async () => {
+    const task = selectedTask;
+    const project = task ? projects.find((candidate) => candidate.id === task.projectId) : undefined;
+    if (!task || !project || !task.worktreeDirectory || task.worktreeRemovedAt !== undefined) {
+      pushBanner("warn", task ? `Task ${task.taskId} has no worktree to read commits from.` : "No task selected.");
+      return;
+    }
+
+    setBusyMessage(`Loading commits for ${task.taskId}...`);
+    try {
+      const commits = await services.worktreeManager.listTaskCommits({
+        projectDirectory: project.rootDirectory,
+        worktreeDirectory: task.worktreeDirectory,
+      });
+      setCommitHistory({ taskId: task.taskId, commits, selectedIndex: 0 });
+    } catch (error) {
+      pushBanner("error", toErrorMessage(error));
+    } finally {
+      setBusyMessage(undefined);
+    }
+  }, [selectedTask, projects, pushBanner, services.worktreeManager]);
+
+  const openCommitPatch = useCallback(
+    async (taskId: string, commit: TaskCommit) => {
+      const task = tasks.find((candidate) => candidate.taskId === taskId);
+      if (!task?.worktreeDirectory) {
+        return;
+      }
+
+      setBusyMessage(`Loading commit ${commit.hash.slice(0, 7)}...`);
+      try {
+        const files = await services.worktreeManager.getTaskCommitFiles(task.worktreeDirectory, commit.hash);
+        setDiffView({
+          taskId,
+          loadedAt: Date.now(),
+          files,
+          fileIndex: 0,
+          foldedHunks: new Set(),
+          scrollOffset: 0,
+          commit: commit.hash,
+        });
+      } catch (error) {
+        pushBanner("error", toErrorMessage(error));
+      } finally {
+        setBusyMessage(undefined);
+      }
+    },
+    [tasks, pushBanner, services.worktreeManager],
+  );
+
+  const startReviewDiff = useCallback(async () => {
+    const task = selectedTask;
+    if (!task) {
+      pushBanner("warn", "No task selected.");
+      return;
+    }
+
+    if (task.state !== "review") {
+      pushBanner("warn", "Task must be in review state to inspect diff.");
+      return;
+    }
+
+    if (!activeProject) {
+      pushBanner("warn", "No active project selected.");
+      return;
+    }
+
+    if (!task.worktreeDirectory) {
+      pushBanner("warn", `Task ${task.taskId} has no worktree directory.`);
+      return;
+    }
+
+    setBusyMessage(`Loading review diff for ${task.taskId}...`);
+    try {
+      const result = await services.worktreeManager.getTaskWorktreeDiff({
+        projectDirectory: activeProject.rootDirectory,
+        taskId: task.taskId,
+        worktreeDirectory: task.worktreeDirectory,
+        pathScope: task.pathScope,
+      });
+
+      setReviewDiff({
+        taskId: task.taskId,
+        loadedAt: Date.now(),
+        branch: result.branch,
+        defaultBranch: result.defaultBranch,
+        summary: result.summary,
+        diff: result.diff,
+        files: result.files,
+      });
+
+      if (!result.hasChanges) {
+        pushBanner(
+          "info",
+          `No diff detected for ${task.taskId}. You can still merge if needed.`,
+        );
+      }
+    } catch (error) {
+      pushBanner("error", toErrorMessage(error));
+    } finally {
+      setBusyMessage(undefined);
+    }
+  }, [selectedTask, pushBanner, activeProject, services.worktreeManager]);
+
+  const rebaseSelectedTask = useCallback(async () => {
+    const task = selectedTask;
+    if (!task) {
+      pushBanner("warn", "No task selected.");
+      return;
+    }
+
+    setBusyMessage(`Rebasing ${task.taskId}...`);
+    try {
+      const rebase = await services.orchestrator.rebaseTask(task.taskId);
+      if (rebase.status === "conflict") {
+        pushBanner(
+          "warn",
+          `Rebasing ${rebase.branch} onto ${rebase.onto} conflicts in ${rebase.conflictedFiles.join(", ") || "some files"}; the branch was left as it was.`,
+        );
+      } else if (rebase.status === "up_to_date") {
+        pushBanner("info", `${rebase.branch} is already up to date with ${rebase.onto}.`);
+      } else {
+        pushBanner("success", `Rebased ${rebase.branch} onto ${rebase.onto}.`);
+        if (reviewDiff?.taskId === task.taskId) {
+          await startReviewDiff();
+        }
+      }
+    } catch (error) {
+      pushBanner("error", toErrorMessage(error));
+    } finally {
+      setBusyMessage(undefined);
+    }
+  }, [selectedTask, reviewDiff?.taskId, startReviewDiff, pushBanner, services.orchestrator]);
+
+  const startInlineRename = useCallback(
+    (target: InlineRenameState["target"]) => {
+      if (target === "project") {
+        const project = projects[selectedProjectIndex];
+        if (!project) {
+          pushBanner("warn", "No project selected.");
+          return;
+        }
+
+        setInlineRename({ target, id: project.id, value: project.name });
+        return;
+      }
+
+      const task = selectedTask;
+      if (!task) {
+        pushBanner("warn", "No task selected.");
+        return;
+      }
+
+      setInlineRename({
+        target,
+        id: task.taskId,
+        value: task.title ?? task.taskId,
+      });
+    },
+    [projects, selectedProjectIndex, selectedTask, pushBanner],
+  );
+
+  const submitInlineRename = useCallback(
+    async (rename: InlineRenameState) => {
+      const name = rename.value.trim();
+      if (!name) {
+        pushBanner("warn", "Name is required.");
+        return;
+      }
+
+      try {
+        if (rename.target === "project") {
+          const project = await services.projectRegistry.renameProject(
+            rename.id,
+            name,
+          );
+          await refreshProjects();
+          pushBanner("success", `Renamed project to ${project.name}.`);
+          return;
+        }
+
+        await services.orchestrator.renameTask(rename.id, name);
+        setTasks(services.orchestrator.listTasks());
+        pushBanner("success", `Renamed task ${rename.id} to ${name}.`);
+      } catch (error) {
+        pushBanner("error", toErrorMessage(error));
+      }
+    },
+    [services.projectRegistry, services.orchestrator, refreshProjects, pushBanner],
+  );
+
+  const startFollowUpPromptInput = useCallback(() => {
+    const task = selectedTask;
+    if (!task) {
+      pushBanner("warn", "No task selected.");
+      return;
+    }
+
+    if (task.state !== "review") {
+      pushBanner(
+        "warn",
+        "Task must be in review state to send a follow-up prompt.",
+      );
+      return;
+    }
+
+    if (!listNextTaskStates(task.state, activeProject?.taskTransitions).includes("running")) {
+      pushBanner("warn", `Project rules do not allow follow-ups from ${task.state}.`);
+      return;
+    }
+
+    setFollowUpPromptInput("");
+    pushBanner("info", "Enter follow-up prompt and press Enter to send.");
+  }, [selectedTask, activeProject, pushBanner]);
+
+  const startTaskPromptInput = useCallback(() => {
+    if (!activeProject) {
+      pushBanner("warn", "No active project selected.");
+      return;
+    }
+
+    setSubtaskParentId(undefined);
+    setNewTaskPromptInput("");
+    pushBanner(
+      "info",
+      `Enter the first task prompt and press Enter to run (model: ${formatModel(taskModel, defaultModelLabel)}).`,
+    );
+  }, [activeProject, defaultModelLabel, pushBanner, taskModel]);
+
+  const startSubtaskPromptInput = useCallback(() => {
+    if (!selectedTask) {
+      pushBanner("warn", "No task selected.");
+      return;
+    }
+
+    setSubtaskParentId(selectedTask.taskId);
+    setNewTaskPromptInput("");
+    pushBanner("info", `Enter a subtask prompt for ${selectedTask.shortCode ?? selectedTask.taskId} and press Enter to run.`);
+  }, [selectedTask, pushBanner]);
+
+  const toggleSelectedTaskSubtasks = useCallback(() => {
+    if (!selectedTask || !subtaskSummaries.has(selectedTask.taskId)) {
+      pushBanner("warn", "The selected task has no subtasks.");
+      return;
+    }
+
+    const { taskId } = selectedTask;
+    setCollapsedTaskIds((current) => {
+      const next = new Set(current);
+      if (!next.delete(taskId)) {
+        next.add(taskId);
+      }
+      return next;
+    });
+  }, [selectedTask, subtaskSummaries, pushBanner]);
+
+  const setSelectedColumnCollapsed = useCallback(
+    (collapsed: boolean) => {
+      const column = selectedTask ? findTaskColumn(selectedTask, boardColumns) : undefined;
+      if (!column) {
+        pushBanner("warn", "No task selected.");
+        return;
+      }
+
+      setCollapsedColumns((current) => {
+        const next = new Set(current);
+        if (collapsed) {
+          next.add(column.id);
+        } else {
+          next.delete(column.id);
+        }
+        return next;
+      });
+      pushBanner("info", `${column.name} column ${collapsed ? "collapsed" : "opened"}.`);
+    },
+    [pushBanner, selectedTask, boardColumns],
+  );
+
+  const openTaskModelPicker = useCallback(async (targetTask?: TaskRuntime) => {
+    if (!activeProject) {
+      pushBanner("warn", "No active project selected.");
+      return;
+    }
+
+    setBusyMessage("Loading available models...");
+    try {
+      const options = await loadModelOptions(
+        services,
+        activeProject.rootDirectory,
+      );
+      setModelOptions(options);
+      setModelFilterInput("");
+      const activeIndex = options.findIndex((candidate) =>
+        isSameModel(candidate.model, targetTask ? targetTask.model : taskModel),
+      );
+      setSelectedModelOptionIndex(activeIndex >= 0 ? activeIndex : 0);
+      setModelPickerTaskId(targetTask?.taskId);
+      setModelPickerOpen(true);
       pushBanner(
         "info",
         "Type to filter models, use arrows, Enter select, Esc cancel.",
@@ -671,126 +2562,508 @@ export function App({
     }
   }, [activeProject, pushBanner, services, taskModel]);
 
-  const toggleLogViewLevel = useCallback(() => {
-    setLogViewLevel((current) => {
-      const next = current === "info" ? "debug" : "info";
-      pushBanner("info", `Log view level set to ${next}.`);
-      return next;
-    });
-  }, [pushBanner]);
+  const toggleLogViewLevel = useCallback(() => {
+    setLogViewLevel((current) => {
+      const next = current === "info" ? "debug" : "info";
+      pushBanner("info", `Log view level set to ${next}.`);
+      return next;
+    });
+  }, [pushBanner]);
+
+  const toggleLogView = useCallback(() => {
+    setIsLogReadingMode(false);
+    setIsLogViewOpen((current) => {
+      const next = !current;
+      pushBanner("info", next ? "Log panel opened." : "Log panel closed.");
+      return next;
+    });
+  }, [pushBanner]);
+
+  const scrollLogsUp = useCallback(
+    (step = LOG_SCROLL_STEP) => {
+      setLogScrollOffset((current) =>
+        Math.min(current + Math.max(1, step), Math.max(taskLogs.length - 1, 0)),
+      );
+    },
+    [taskLogs.length],
+  );
+
+  const scrollLogsDown = useCallback((step = LOG_SCROLL_STEP) => {
+    setLogScrollOffset((current) => Math.max(0, current - Math.max(1, step)));
+  }, []);
+
+  const scrollLogsToOldest = useCallback(() => {
+    setLogScrollOffset(Math.max(taskLogs.length - 1, 0));
+  }, [taskLogs.length]);
+
+  const scrollLogsToLatest = useCallback(() => {
+    setLogScrollOffset(0);
+  }, []);
+
+  const deleteSelectedTask = useCallback(async () => {
+    const task = selectedTask;
+    if (!task) {
+      pushBanner("warn", "No task selected.");
+      return;
+    }
+
+    setBusyMessage(`Deleting ${task.taskId}...`);
+    try {
+      const deleted = await services.orchestrator.deleteTask(task.taskId);
+      if (!deleted) {
+        pushBanner("warn", `Task ${task.taskId} was already removed.`);
+        return;
+      }
+
+      setSessionMessagesByTaskID((current) => {
+        if (!current[task.taskId]) {
+          return current;
+        }
+
+        const next = { ...current };
+        delete next[task.taskId];
+        return next;
+      });
+      setPromptByTaskID((current) => {
+        if (!current[task.taskId]) {
+          return current;
+        }
+
+        const next = { ...current };
+        delete next[task.taskId];
+        return next;
+      });
+      setModelByTaskID((current) => {
+        if (!(task.taskId in current)) {
+          return current;
+        }
+
+        const next = { ...current };
+        delete next[task.taskId];
+        return next;
+      });
+      setTasks(services.orchestrator.listTasks());
+      pushBanner(
+        "success",
+        `Deleted task ${task.taskId} and cleaned its worktree.`,
+      );
+    } catch (error) {
+      pushBanner("error", toErrorMessage(error));
+    } finally {
+      setBusyMessage(undefined);
+    }
+  }, [selectedTask, pushBanner, services.orchestrator]);
+
+  useEffect(() => {
+    setLogScrollOffset(0);
+  }, [selectedTask?.taskId]);
+
+  const isInTextInputMode =
+    newProjectPathInput !== undefined ||
+    newTaskPromptInput !== undefined ||
+    modelPickerOpen ||
+    followUpPromptInput !== undefined ||
+    inlineRename !== undefined ||
+    attributionPathInput !== undefined ||
+    changelogSinceInput !== undefined ||
+    estimateInput !== undefined ||
+    cancelReasonInput !== undefined ||
+    newPageTitleInput !== undefined ||
+    commandInput !== undefined ||
+    quickAdd !== undefined ||
+    milestonePanel?.createInput !== undefined ||
+    planReview?.editingValue !== undefined;
+
+  // Every key goes through here, whether typed or replayed from a macro.
+  const handleInput = async (input: string, key: Key) => {
+    if (fieldError) {
+      setFieldError(undefined);
+    }
+
+    const wantsMoveUp = input === "k" && !key.ctrl && !key.meta;
+    const wantsMoveDown = input === "j" && !key.ctrl && !key.meta;
+
+    if (key.ctrl && input === "c") {
+      exit();
+      return;
+    }
+
+    if (!isInTextInputMode && input === "q") {
+      exit();
+      return;
+    }
+
+    if (loading) {
+      return;
+    }
+
+    if (!isInTextInputMode && activePermissionRequest) {
+      if (key.escape) {
+        setDismissedPermissionIds((current) => [...current, activePermissionRequest.id]);
+        return;
+      }
+
+      const reply = ({ y: "once", a: "always", n: "reject" } as const)[input as "y" | "a" | "n"];
+      if (reply) {
+        void answerPermission(activePermissionRequest, reply);
+      }
+      return;
+    }
+
+    if (!isInTextInputMode && !isFailureViewOpen && !diffView && (input === "l" || input === "L")) {
+      toggleLogView();
+      return;
+    }
+
+    if (commandInput !== undefined) {
+      if (key.escape) {
+        setCommandInput(undefined);
+        return;
+      }
+
+      if (key.return) {
+        const command = commandInput.trim();
+        setCommandInput(undefined);
+        if (command) {
+          void runCommand(command);
+        }
+        return;
+      }
+
+      if (key.backspace || key.delete) {
+        if (commandInput.length === 0) {
+          setCommandInput(undefined);
+          return;
+        }
+
+        setCommandInput(commandInput.slice(0, -1));
+        return;
+      }
+
+      if (
+        input &&
+        !key.ctrl &&
+        !key.meta &&
+        !key.upArrow &&
+        !key.downArrow &&
+        !key.leftArrow &&
+        !key.rightArrow
+      ) {
+        setCommandInput(`${commandInput}${input}`);
+      }
+
+      return;
+    }
+
+    if (quickAdd) {
+      if (key.escape) {
+        setQuickAdd(undefined);
+        return;
+      }
+
+      if (quickAdd.field === "project") {
+        if (key.upArrow || key.downArrow) {
+          const offset = key.upArrow ? -1 : 1;
+          setQuickAdd({
+            ...quickAdd,
+            selectedIndex: Math.min(Math.max(quickAdd.selectedIndex + offset, 0), Math.max(quickAddProjects.length - 1, 0)),
+          });
+          return;
+        }
+
+        if (key.return) {
+          if (!quickAddProject) {
+            pushBanner("warn", `No project matches ${quickAdd.projectQuery}.`);
+            return;
+          }
+
+          setQuickAdd({ ...quickAdd, field: "title" });
+          return;
+        }
+
+        if (key.backspace || key.delete) {
+          setQuickAdd({ ...quickAdd, projectQuery: quickAdd.projectQuery.slice(0, -1), selectedIndex: 0 });
+          return;
+        }
+
+        if (input && !key.ctrl && !key.meta && !key.leftArrow && !key.rightArrow) {
+          setQuickAdd({ ...quickAdd, projectQuery: `${quickAdd.projectQuery}${input}`, selectedIndex: 0 });
+        }
+        return;
+      }
+
+      if (key.return) {
+        const title = quickAdd.title.trim();
+        const titleIssue = validatePrompt(title, "Task title");
+        if (titleIssue || !quickAddProject) {
+          setFieldError(titleIssue);
+          return;
+        }
+
+        setQuickAdd(undefined);
+        pushBanner("info", `Added a task to ${quickAddProject.name}.`);
+        void runTask(title, taskModel, quickAddProject);
+        return;
+      }
+
+      if (key.backspace || key.delete) {
+        if (quickAdd.title.length === 0) {
+          setQuickAdd({ ...quickAdd, field: "project" });
+          return;
+        }
+
+        setQuickAdd({ ...quickAdd, title: quickAdd.title.slice(0, -1) });
+        return;
+      }
+
+      if (input && !key.ctrl && !key.meta && !key.upArrow && !key.downArrow && !key.leftArrow && !key.rightArrow) {
+        setQuickAdd({ ...quickAdd, title: `${quickAdd.title}${input}` });
+      }
+      return;
+    }
+
+    // The diff, compare and commit viewers own every key, F included (fold all hunks).
+    const allowsGlobalShortcuts = !isInTextInputMode && !isLogViewOpen && !diffView && !compareView && !commitHistory;
+
+    if (allowsGlobalShortcuts && input === ":") {
+      setCommandInput("");
+      return;
+    }
+
+    if (allowsGlobalShortcuts && input === "+") {
+      if (projects.length === 0) {
+        pushBanner("warn", "Add a project first.");
+        return;
+      }
+
+      // Start on the active project so Enter alone keeps it.
+      const activeIndex = projects.findIndex((project) => project.id === activeProjectId);
+      setQuickAdd({ field: "project", projectQuery: "", selectedIndex: Math.max(activeIndex, 0), title: "" });
+      return;
+    }
+
+    if (allowsGlobalShortcuts && input === "F") {
+      setIsFailureViewOpen((current) => !current);
+      setSelectedFailureIndex(0);
+      return;
+    }
+
+    if (allowsGlobalShortcuts && input === "Q") {
+      setIsQueueViewOpen((current) => !current);
+      setSelectedQueueIndex(0);
+      setIsFailureViewOpen(false);
+      return;
+    }
+
+    if (metricsReport) {
+      if (key.escape) {
+        setMetricsReport(undefined);
+        return;
+      }
 
-  const toggleLogView = useCallback(() => {
-    setIsLogViewOpen((current) => {
-      const next = !current;
-      pushBanner("info", next ? "Log panel opened." : "Log panel closed.");
-      return next;
-    });
-  }, [pushBanner]);
+      if (input === "h") {
+        void showActivityHeatmap();
+        return;
+      }
 
-  const scrollLogsUp = useCallback(
-    (step = LOG_SCROLL_STEP) => {
-      setLogScrollOffset((current) =>
-        Math.min(current + Math.max(1, step), Math.max(taskLogs.length - 1, 0)),
-      );
-    },
-    [taskLogs.length],
-  );
+      const rollup = ({ d: "day", w: "week", m: "month" } as const)[input as "d" | "w" | "m"];
+      if (rollup) {
+        void showMetrics(rollup, metricsReport.heatmap ? undefined : metricsReport.from, metricsReport.to);
+      }
+      return;
+    }
 
-  const scrollLogsDown = useCallback((step = LOG_SCROLL_STEP) => {
-    setLogScrollOffset((current) => Math.max(0, current - Math.max(1, step)));
-  }, []);
+    if (isQueueViewOpen) {
+      if (key.escape) {
+        setIsQueueViewOpen(false);
+        return;
+      }
 
-  const scrollLogsToOldest = useCallback(() => {
-    setLogScrollOffset(Math.max(taskLogs.length - 1, 0));
-  }, [taskLogs.length]);
+      if (wantsMoveUp) {
+        setSelectedQueueIndex((current) => Math.max(0, current - 1));
+        return;
+      }
 
-  const scrollLogsToLatest = useCallback(() => {
-    setLogScrollOffset(0);
-  }, []);
+      if (wantsMoveDown) {
+        setSelectedQueueIndex((current) =>
+          Math.min(Math.max(queueEstimates.length - 1, 0), current + 1),
+        );
+        return;
+      }
+
+      const queuedTask = queueEstimates[selectedQueueIndex]?.task;
+      if ((input === "K" || input === "J") && queuedTask) {
+        try {
+          setSelectedQueueIndex(services.orchestrator.moveQueuedTask(queuedTask.taskId, input === "K" ? -1 : 1));
+          setTasks(services.orchestrator.listTasks());
+        } catch (error) {
+          pushBanner("error", toErrorMessage(error));
+        }
+        return;
+      }
+
+      if (input === "c" && queuedTask) {
+        void services.orchestrator
+          .cancelTask(queuedTask.taskId)
+          .then(() => {
+            setTasks(services.orchestrator.listTasks());
+            setSelectedQueueIndex((current) => Math.max(0, Math.min(current, queueEstimates.length - 2)));
+            pushBanner("success", `Cancelled ${queuedTask.shortCode ?? queuedTask.taskId}.`);
+          })
+          .catch((error) => {
+            pushBanner("error", toErrorMessage(error));
+          });
+        return;
+      }
 
-  const deleteSelectedTask = useCallback(async () => {
-    const task = selectedTask;
-    if (!task) {
-      pushBanner("warn", "No task selected.");
       return;
     }
 
-    setBusyMessage(`Deleting ${task.taskId}...`);
-    try {
-      const deleted = await services.orchestrator.deleteTask(task.taskId);
-      if (!deleted) {
-        pushBanner("warn", `Task ${task.taskId} was already removed.`);
+    if (diffView) {
+      const file = diffView.files[diffView.fileIndex];
+      const rows = file ? buildDiffRows(file, diffView.foldedHunks) : [];
+      const maxOffset = Math.max(rows.length - diffVisibleRows, 0);
+      const scrollTo = (offset: number) => {
+        setDiffView({ ...diffView, scrollOffset: Math.min(Math.max(offset, 0), maxOffset) });
+      };
+
+      if (key.escape || input === "v") {
+        setDiffView(undefined);
         return;
       }
 
-      setSessionMessagesByTaskID((current) => {
-        if (!current[task.taskId]) {
-          return current;
-        }
+      if (wantsMoveUp || wantsMoveDown) {
+        scrollTo(diffView.scrollOffset + (wantsMoveUp ? -1 : 1));
+        return;
+      }
 
-        const next = { ...current };
-        delete next[task.taskId];
-        return next;
-      });
-      setPromptByTaskID((current) => {
-        if (!current[task.taskId]) {
-          return current;
+      if (input === "u" || input === "d" || key.pageUp || key.pageDown) {
+        scrollTo(diffView.scrollOffset + (input === "u" || key.pageUp ? -diffVisibleRows : diffVisibleRows));
+        return;
+      }
+
+      if (input === "g" || input === "G") {
+        scrollTo(input === "g" ? 0 : maxOffset);
+        return;
+      }
+
+      if (input === "n" || input === "p") {
+        const fileIndex = diffView.fileIndex + (input === "n" ? 1 : -1);
+        if (fileIndex >= 0 && fileIndex < diffView.files.length) {
+          setDiffView({ ...diffView, fileIndex, foldedHunks: new Set(), scrollOffset: 0 });
         }
+        return;
+      }
 
-        const next = { ...current };
-        delete next[task.taskId];
-        return next;
-      });
-      setModelByTaskID((current) => {
-        if (!(task.taskId in current)) {
-          return current;
+      if (input === "f" || input === "F") {
+        const hunkCount = file?.hunks.length ?? 0;
+        const topHunk = rows[diffView.scrollOffset]?.hunkIndex;
+        let foldedHunks: Set<number>;
+        if (input === "F") {
+          // Fold everything unless everything is already folded.
+          foldedHunks = diffView.foldedHunks.size === hunkCount ? new Set() : new Set(file?.hunks.map((_, index) => index));
+        } else if (topHunk === undefined) {
+          return;
+        } else {
+          foldedHunks = new Set(diffView.foldedHunks);
+          if (!foldedHunks.delete(topHunk)) {
+            foldedHunks.add(topHunk);
+          }
         }
 
-        const next = { ...current };
-        delete next[task.taskId];
-        return next;
-      });
-      setTasks(services.orchestrator.listTasks());
-      pushBanner(
-        "success",
-        `Deleted task ${task.taskId} and cleaned its worktree.`,
-      );
-    } catch (error) {
-      pushBanner("error", toErrorMessage(error));
-    } finally {
-      setBusyMessage(undefined);
+        const nextRows = file ? buildDiffRows(file, foldedHunks) : [];
+        // Keep the toggled hunk's header at the top.
+        const headerRow = nextRows.findIndex((row) => row.kind === "hunk" && row.hunkIndex === topHunk);
+        setDiffView({
+          ...diffView,
+          foldedHunks,
+          scrollOffset: input === "f" && headerRow >= 0 ? headerRow : 0,
+        });
+        return;
+      }
+
+      return;
     }
-  }, [selectedTask, pushBanner, services.orchestrator]);
 
-  useEffect(() => {
-    setLogScrollOffset(0);
-  }, [selectedTask?.taskId]);
+    if (compareView) {
+      const maxOffset = Math.max(buildCompareRows(compareView.comparison).length - diffVisibleRows, 0);
+      const scrollTo = (offset: number) => {
+        setCompareView({ ...compareView, scrollOffset: Math.min(Math.max(offset, 0), maxOffset) });
+      };
 
-  useInput(async (input, key) => {
-    const isInTextInputMode =
-      newProjectPathInput !== undefined ||
-      newTaskPromptInput !== undefined ||
-      modelPickerOpen ||
-      followUpPromptInput !== undefined;
-    const wantsMoveUp = input === "k" && !key.ctrl && !key.meta;
-    const wantsMoveDown = input === "j" && !key.ctrl && !key.meta;
+      if (key.escape) {
+        setCompareView(undefined);
+        return;
+      }
 
-    if (key.ctrl && input === "c") {
-      exit();
-      return;
-    }
+      if (wantsMoveUp || wantsMoveDown) {
+        scrollTo(compareView.scrollOffset + (wantsMoveUp ? -1 : 1));
+        return;
+      }
+
+      if (input === "u" || input === "d" || key.pageUp || key.pageDown) {
+        scrollTo(compareView.scrollOffset + (input === "u" || key.pageUp ? -diffVisibleRows : diffVisibleRows));
+        return;
+      }
 
-    if (!isInTextInputMode && input === "q") {
-      exit();
       return;
     }
 
-    if (loading) {
+    if (commitHistory) {
+      if (key.escape || input === "h") {
+        setCommitHistory(undefined);
+        return;
+      }
+
+      if (wantsMoveUp || wantsMoveDown) {
+        setCommitHistory({
+          ...commitHistory,
+          selectedIndex: Math.min(
+            Math.max(commitHistory.selectedIndex + (wantsMoveUp ? -1 : 1), 0),
+            Math.max(commitHistory.commits.length - 1, 0),
+          ),
+        });
+        return;
+      }
+
+      const commit = commitHistory.commits[commitHistory.selectedIndex];
+      if (key.return && commit) {
+        void openCommitPatch(commitHistory.taskId, commit);
+        return;
+      }
+
       return;
     }
 
-    if (!isInTextInputMode && (input === "l" || input === "L")) {
-      toggleLogView();
+    if (isFailureViewOpen) {
+      if (key.escape) {
+        setIsFailureViewOpen(false);
+        return;
+      }
+
+      if (wantsMoveUp) {
+        setSelectedFailureIndex((current) => Math.max(0, current - 1));
+        return;
+      }
+
+      if (wantsMoveDown) {
+        setSelectedFailureIndex((current) =>
+          Math.min(Math.max(failedTasksInTriageOrder.length - 1, 0), current + 1),
+        );
+        return;
+      }
+
+      if (input === "r") {
+        const task = failedTasksInTriageOrder[selectedFailureIndex];
+        if (!task) {
+          pushBanner("warn", "No failed task selected.");
+          return;
+        }
+
+        void retryFailedTask(task);
+        return;
+      }
+
       return;
     }
 
@@ -846,37 +3119,240 @@ export function App({
         return;
       }
 
-      if (input === "G") {
-        scrollLogsToLatest();
+      if (input === "G") {
+        scrollLogsToLatest();
+        return;
+      }
+
+      if (input === "v" || input === "V") {
+        toggleLogViewLevel();
+        return;
+      }
+
+      if (input === "z" || (isLogReadingMode && key.escape)) {
+        setIsLogReadingMode(!isLogReadingMode);
+        return;
+      }
+
+      if (input === "w" && isLogReadingMode) {
+        setIsLogWrapEnabled((current) => !current);
+        return;
+      }
+
+      return;
+    }
+
+    if (reviewDiff) {
+      if (key.escape) {
+        closeReviewDiff();
+        pushBanner("info", `Closed review panel for ${reviewDiff.taskId}.`);
+        return;
+      }
+
+      if (input === "k") {
+        closeReviewDiff();
+        pushBanner("info", `Keeping ${reviewDiff.taskId} in review.`);
+        return;
+      }
+
+      if (input === "a") {
+        void decideSelectedTaskReview("approved");
+        return;
+      }
+
+      if (input === "c") {
+        void decideSelectedTaskReview("changes_requested");
+        return;
+      }
+
+      if (input === "m" || input === "M") {
+        void mergeSelectedTask(input === "M" ? "fast-forward" : "squash");
+        return;
+      }
+
+      if (input === "u" || input === "U") {
+        void pushSelectedTaskBranch(input === "U");
+        return;
+      }
+
+      if (input === "o") {
+        void openSelectedTaskPullRequest();
+        return;
+      }
+
+      if (input === "b") {
+        void rebaseSelectedTask();
+        return;
+      }
+
+      return;
+    }
+
+    if (planReview) {
+      if (planReview.editingValue !== undefined) {
+        if (key.escape) {
+          setPlanReview((current) =>
+            current ? { ...current, editingValue: undefined } : current,
+          );
+          return;
+        }
+
+        if (key.return) {
+          const editedItem = planReview.editingValue.trim();
+          setPlanReview((current) =>
+            current
+              ? {
+                ...current,
+                editingValue: undefined,
+                items: editedItem
+                  ? current.items.map((item, index) =>
+                    index === current.selectedIndex ? editedItem : item,
+                  )
+                  : current.items,
+              }
+              : current,
+          );
+          return;
+        }
+
+        if (key.backspace || key.delete) {
+          setPlanReview((current) =>
+            current
+              ? { ...current, editingValue: (current.editingValue ?? "").slice(0, -1) }
+              : current,
+          );
+          return;
+        }
+
+        if (
+          input &&
+          !key.ctrl &&
+          !key.meta &&
+          !key.upArrow &&
+          !key.downArrow &&
+          !key.leftArrow &&
+          !key.rightArrow
+        ) {
+          setPlanReview((current) =>
+            current
+              ? { ...current, editingValue: `${current.editingValue ?? ""}${input}` }
+              : current,
+          );
+        }
+
+        return;
+      }
+
+      if (key.escape) {
+        setPlanReview(undefined);
+        pushBanner("info", "Planned subtasks discarded.");
+        return;
+      }
+
+      if (key.return) {
+        const plan = planReview;
+        setPlanReview(undefined);
+        if (plan.items.length === 0) {
+          pushBanner("warn", "No planned subtasks left to create.");
+          return;
+        }
+
+        void runTaskBatch(plan.items, taskModel, plan.parentTaskId);
+        return;
+      }
+
+      if (wantsMoveUp || key.upArrow) {
+        setPlanReview((current) =>
+          current
+            ? { ...current, selectedIndex: Math.max(0, current.selectedIndex - 1) }
+            : current,
+        );
+        return;
+      }
+
+      if (wantsMoveDown || key.downArrow) {
+        setPlanReview((current) =>
+          current
+            ? {
+              ...current,
+              selectedIndex: Math.min(current.items.length - 1, current.selectedIndex + 1),
+            }
+            : current,
+        );
+        return;
+      }
+
+      if (input === "x") {
+        setPlanReview((current) => {
+          if (!current) {
+            return current;
+          }
+
+          const items = current.items.filter((_, index) => index !== current.selectedIndex);
+          return {
+            ...current,
+            items,
+            selectedIndex: Math.max(0, Math.min(current.selectedIndex, items.length - 1)),
+          };
+        });
         return;
       }
 
-      if (input === "v" || input === "V") {
-        toggleLogViewLevel();
+      if (input === "e") {
+        setPlanReview((current) =>
+          current
+            ? { ...current, editingValue: current.items[current.selectedIndex] ?? "" }
+            : current,
+        );
         return;
       }
 
       return;
     }
 
-    if (reviewDiff) {
+    if (inlineRename !== undefined) {
       if (key.escape) {
-        closeReviewDiff();
-        pushBanner("info", `Closed review panel for ${reviewDiff.taskId}.`);
+        setInlineRename(undefined);
+        pushBanner("info", "Rename cancelled.");
         return;
       }
 
-      if (input === "k") {
-        closeReviewDiff();
-        pushBanner("info", `Keeping ${reviewDiff.taskId} in review.`);
+      if (key.return) {
+        const issue =
+          inlineRename.target === "project"
+            ? validateName(inlineRename.value, "Project name")
+            : validateTitle(inlineRename.value, "Task title");
+        if (issue) {
+          setFieldError(issue);
+          return;
+        }
+
+        setInlineRename(undefined);
+        void submitInlineRename(inlineRename);
         return;
       }
 
-      if (input === "m") {
-        void mergeSelectedTask();
+      if (key.backspace || key.delete) {
+        setInlineRename((current) =>
+          current ? { ...current, value: current.value.slice(0, -1) } : current,
+        );
         return;
       }
 
+      if (
+        input &&
+        !key.ctrl &&
+        !key.meta &&
+        !key.upArrow &&
+        !key.downArrow &&
+        !key.leftArrow &&
+        !key.rightArrow
+      ) {
+        setInlineRename((current) =>
+          current ? { ...current, value: `${current.value}${input}` } : current,
+        );
+      }
+
       return;
     }
 
@@ -916,21 +3392,100 @@ export function App({
       return;
     }
 
+    if (bulkTaskPrompts) {
+      if (key.escape || input === "n") {
+        setBulkTaskPrompts(undefined);
+        pushBanner("info", "Pasted task list discarded.");
+        return;
+      }
+
+      if (key.return || input === "y") {
+        const promptsToRun = bulkTaskPrompts;
+        setBulkTaskPrompts(undefined);
+        setNewTaskPromptInput(undefined);
+        setSubtaskParentId(undefined);
+        void runTaskBatch(promptsToRun, taskModel, subtaskParentId);
+        return;
+      }
+
+      return;
+    }
+
+    if (similarTaskWarning) {
+      if (key.escape) {
+        setSimilarTaskWarning(undefined);
+        pushBanner("info", "Continue editing the task prompt.");
+        return;
+      }
+
+      if (key.return) {
+        const promptToSubmit = similarTaskWarning.prompt;
+        setSimilarTaskWarning(undefined);
+        discardActiveDraft();
+        setNewTaskPromptInput(undefined);
+        setSubtaskParentId(undefined);
+        void runTask(promptToSubmit, taskModel);
+        return;
+      }
+
+      if (input === "g") {
+        const [firstMatch] = similarTaskWarning.matches;
+        const matchIndex = firstMatch
+          ? tasksForActiveProject.findIndex(
+            (task) => task.taskId === firstMatch.task.taskId,
+          )
+          : -1;
+        setSimilarTaskWarning(undefined);
+        setNewTaskPromptInput(undefined);
+        if (matchIndex >= 0) {
+          setSelectedTaskIndex(matchIndex);
+          pushBanner("info", `Jumped to similar task ${firstMatch?.task.taskId}.`);
+        }
+        return;
+      }
+
+      return;
+    }
+
     if (newTaskPromptInput !== undefined) {
       if (key.escape) {
+        discardActiveDraft();
         setNewTaskPromptInput(undefined);
+        setSubtaskParentId(undefined);
         pushBanner("info", "Task creation cancelled.");
         return;
       }
 
+      if (key.ctrl && input === "r" && draftOffer) {
+        setNewTaskPromptInput(draftOffer.value);
+        setDraftOffer(undefined);
+        return;
+      }
+
       if (key.return) {
         const promptToSubmit = newTaskPromptInput.trim();
-        if (!promptToSubmit) {
-          pushBanner("warn", "Task prompt is required.");
+        const promptIssue = validatePrompt(promptToSubmit, "Task prompt");
+        if (promptIssue) {
+          setFieldError(promptIssue);
+          return;
+        }
+
+        const similarTasks = findSimilarTasks(
+          openTasksForActiveProject,
+          promptToSubmit,
+        );
+        if (similarTasks.length > 0) {
+          setSimilarTaskWarning({
+            prompt: promptToSubmit,
+            matches: similarTasks.slice(0, 3),
+          });
+          pushBanner("warn", "Similar tasks exist for this prompt.");
           return;
         }
 
+        discardActiveDraft();
         setNewTaskPromptInput(undefined);
+        setSubtaskParentId(undefined);
         void runTask(promptToSubmit, taskModel);
         return;
       }
@@ -951,6 +3506,18 @@ export function App({
         !key.leftArrow &&
         !key.rightArrow
       ) {
+        if (/[\r\n]/.test(input)) {
+          const pastedPrompts = splitPastedPrompts(`${newTaskPromptInput}${input}`);
+          if (pastedPrompts.length > 1) {
+            setBulkTaskPrompts(pastedPrompts);
+            pushBanner("info", `Create ${pastedPrompts.length} tasks from pasted lines?`);
+            return;
+          }
+
+          setNewTaskPromptInput(pastedPrompts[0] ?? "");
+          return;
+        }
+
         setNewTaskPromptInput((current) => `${current ?? ""}${input}`);
       }
 
@@ -960,6 +3527,7 @@ export function App({
     if (modelPickerOpen) {
       if (key.escape) {
         setModelPickerOpen(false);
+        setModelPickerTaskId(undefined);
         setModelFilterInput("");
         pushBanner("info", "Model selection cancelled.");
         return;
@@ -971,10 +3539,24 @@ export function App({
           pushBanner("warn", "No matching model option is selected.");
           return;
         }
-        setTaskModel(selectedOption.model);
         setModelPickerOpen(false);
+        setModelPickerTaskId(undefined);
         setModelFilterInput("");
-        pushBanner("success", `Task model set to ${selectedOption.label}.`);
+        if (!modelPickerTaskId) {
+          setTaskModel(selectedOption.model);
+          pushBanner("success", `Task model set to ${selectedOption.label}.`);
+          return;
+        }
+
+        try {
+          const updatedTask = await services.orchestrator.setTaskModel(modelPickerTaskId, selectedOption.model);
+          pushBanner(
+            "success",
+            `${updatedTask.shortCode ?? updatedTask.taskId} follow-ups now use ${selectedOption.label}.`,
+          );
+        } catch (error) {
+          pushBanner("error", toErrorMessage(error));
+        }
         return;
       }
 
@@ -1003,24 +3585,379 @@ export function App({
         !key.upArrow &&
         !key.downArrow &&
         !key.leftArrow &&
-        !key.rightArrow &&
-        input.length === 1
+        !key.rightArrow &&
+        input.length === 1
+      ) {
+        setModelFilterInput((current) => `${current}${input}`);
+        setSelectedModelOptionIndex(0);
+        return;
+      }
+
+      return;
+    }
+
+    if (milestonePanel) {
+      if (milestonePanel.createInput !== undefined) {
+        if (key.escape) {
+          setMilestonePanel({ ...milestonePanel, createInput: undefined });
+          return;
+        }
+
+        if (key.return) {
+          const match = /^(.*?)(?:\s+(\d{4}-\d{2}-\d{2}))?$/.exec(milestonePanel.createInput.trim());
+          const name = match?.[1]?.trim() ?? "";
+          const nameIssue = validateName(name, "Milestone name");
+          if (nameIssue) {
+            setFieldError(nameIssue);
+            return;
+          }
+
+          if (!activeProjectId) {
+            pushBanner("warn", "No active project selected.");
+            return;
+          }
+
+          setMilestonePanel({ ...milestonePanel, createInput: undefined });
+          void services.milestoneRegistry
+            .createMilestone({ projectId: activeProjectId, name, dueDate: match?.[2] })
+            .then(async (milestone) => {
+              await refreshMilestones();
+              pushBanner("success", `Created milestone ${milestone.name}.`);
+            })
+            .catch((error) => {
+              pushBanner("error", toErrorMessage(error));
+            });
+          return;
+        }
+
+        if (key.backspace || key.delete) {
+          setMilestonePanel({
+            ...milestonePanel,
+            createInput: milestonePanel.createInput.slice(0, -1),
+          });
+          return;
+        }
+
+        if (
+          input &&
+          !key.ctrl &&
+          !key.meta &&
+          !key.upArrow &&
+          !key.downArrow &&
+          !key.leftArrow &&
+          !key.rightArrow
+        ) {
+          setMilestonePanel({
+            ...milestonePanel,
+            createInput: `${milestonePanel.createInput}${input}`,
+          });
+        }
+
+        return;
+      }
+
+      // Row 0 is "All tasks"; milestone rows follow.
+      const highlighted = milestones[milestonePanel.selectedIndex - 1];
+
+      if (key.escape || input === "M") {
+        setMilestonePanel(undefined);
+        return;
+      }
+
+      if (wantsMoveUp || key.upArrow) {
+        setMilestonePanel({
+          ...milestonePanel,
+          selectedIndex: Math.max(0, milestonePanel.selectedIndex - 1),
+        });
+        return;
+      }
+
+      if (wantsMoveDown || key.downArrow) {
+        setMilestonePanel({
+          ...milestonePanel,
+          selectedIndex: Math.min(milestones.length, milestonePanel.selectedIndex + 1),
+        });
+        return;
+      }
+
+      if (key.return) {
+        setMilestoneFilterId(highlighted?.id);
+        setSelectedTaskIndex(0);
+        setMilestonePanel(undefined);
+        pushBanner("info", highlighted ? `Showing milestone ${highlighted.name}.` : "Showing all tasks.");
+        return;
+      }
+
+      if (input === "n") {
+        setMilestonePanel({ ...milestonePanel, createInput: "" });
+        return;
+      }
+
+      if (input === "a") {
+        const task = selectedTask;
+        if (!task) {
+          pushBanner("warn", "No task selected.");
+          return;
+        }
+
+        void services.orchestrator
+          .assignTaskMilestone(task.taskId, highlighted?.id)
+          .then(() => {
+            setTasks(services.orchestrator.listTasks());
+            pushBanner(
+              "success",
+              highlighted
+                ? `Assigned ${task.shortCode ?? task.taskId} to ${highlighted.name}.`
+                : `Removed ${task.shortCode ?? task.taskId} from its milestone.`,
+            );
+          })
+          .catch((error) => {
+            pushBanner("error", toErrorMessage(error));
+          });
+        return;
+      }
+
+      if (input === "x" && highlighted) {
+        void services.milestoneRegistry
+          .setMilestoneStatus(highlighted.id, highlighted.status === "open" ? "closed" : "open")
+          .then(() => refreshMilestones())
+          .catch((error) => {
+            pushBanner("error", toErrorMessage(error));
+          });
+        return;
+      }
+
+      return;
+    }
+
+    if (checkpointPanel) {
+      // Newest first, matching the panel.
+      const checkpoints = [...(selectedTask?.checkpoints ?? [])].reverse();
+      if (key.escape || input === "H") {
+        setCheckpointPanel(undefined);
+        return;
+      }
+
+      if (wantsMoveUp || wantsMoveDown) {
+        setCheckpointPanel({
+          selectedIndex: Math.min(
+            Math.max(checkpointPanel.selectedIndex + (wantsMoveUp ? -1 : 1), 0),
+            Math.max(checkpoints.length - 1, 0),
+          ),
+        });
+        return;
+      }
+
+      const checkpoint = checkpoints[checkpointPanel.selectedIndex];
+      if (key.return && selectedTask && checkpoint) {
+        setCheckpointPanel(undefined);
+        setBusyMessage(`Rolling back ${selectedTask.shortCode ?? selectedTask.taskId}...`);
+        void services.orchestrator
+          .rollbackToCheckpoint(selectedTask.taskId, checkpoint.commit)
+          .then(() => {
+            setTasks(services.orchestrator.listTasks());
+            pushBanner("success", `Worktree restored to checkpoint ${checkpoint.commit.slice(0, 8)} (${checkpoint.label}).`);
+          })
+          .catch((error) => {
+            pushBanner("error", toErrorMessage(error));
+          })
+          .finally(() => {
+            setBusyMessage(undefined);
+          });
+      }
+
+      return;
+    }
+
+    if (cancelReasonInput !== undefined) {
+      if (key.escape) {
+        setCancelReasonInput(undefined);
+        pushBanner("info", "Cancel aborted.");
+        return;
+      }
+
+      if (key.return) {
+        const task = selectedTask;
+        const reason = cancelReasonInput.trim();
+        setCancelReasonInput(undefined);
+        if (!task) {
+          return;
+        }
+
+        void services.orchestrator
+          .cancelTask(task.taskId, reason || undefined)
+          .then(() => {
+            setTasks(services.orchestrator.listTasks());
+            pushBanner(
+              "success",
+              `Cancelled ${task.shortCode ?? task.taskId}${reason ? `: ${reason}` : "."}`,
+            );
+          })
+          .catch((error) => {
+            pushBanner("error", toErrorMessage(error));
+          });
+        return;
+      }
+
+      if (key.backspace || key.delete) {
+        setCancelReasonInput((current) =>
+          current && current.length > 0 ? current.slice(0, -1) : "",
+        );
+        return;
+      }
+
+      if (input && !key.ctrl && !key.meta) {
+        setCancelReasonInput((current) => `${current ?? ""}${input}`);
+      }
+
+      return;
+    }
+
+    if (estimateInput !== undefined) {
+      if (key.escape) {
+        setEstimateInput(undefined);
+        return;
+      }
+
+      if (key.return) {
+        const task = selectedTask;
+        const value = estimateInput.trim();
+        const estimate = value ? Number(value) : undefined;
+        if (estimate !== undefined && (!Number.isFinite(estimate) || estimate < 0)) {
+          pushBanner("warn", "Estimate must be a non-negative number (empty clears it).");
+          return;
+        }
+
+        setEstimateInput(undefined);
+        if (!task) {
+          return;
+        }
+
+        void services.orchestrator
+          .setTaskEstimate(task.taskId, estimate)
+          .then(() => {
+            setTasks(services.orchestrator.listTasks());
+            pushBanner(
+              "success",
+              estimate === undefined
+                ? `Cleared estimate for ${task.shortCode ?? task.taskId}.`
+                : `Estimated ${task.shortCode ?? task.taskId} at ${formatPoints(estimate)}.`,
+            );
+          })
+          .catch((error) => {
+            pushBanner("error", toErrorMessage(error));
+          });
+        return;
+      }
+
+      if (key.backspace || key.delete) {
+        setEstimateInput((current) =>
+          current && current.length > 0 ? current.slice(0, -1) : "",
+        );
+        return;
+      }
+
+      if (input && /^[0-9.]+$/.test(input)) {
+        setEstimateInput((current) => `${current ?? ""}${input}`);
+      }
+
+      return;
+    }
+
+    if (changelogSinceInput !== undefined) {
+      if (key.escape) {
+        setChangelogSinceInput(undefined);
+        return;
+      }
+
+      if (key.return) {
+        const since = changelogSinceInput.trim();
+        setChangelogSinceInput(undefined);
+        void copySelectedProjectChangelog(since);
+        return;
+      }
+
+      if (key.backspace || key.delete) {
+        setChangelogSinceInput((current) =>
+          current && current.length > 0 ? current.slice(0, -1) : "",
+        );
+        return;
+      }
+
+      if (
+        input &&
+        !key.ctrl &&
+        !key.meta &&
+        !key.upArrow &&
+        !key.downArrow &&
+        !key.leftArrow &&
+        !key.rightArrow
+      ) {
+        setChangelogSinceInput((current) => `${current ?? ""}${input}`);
+      }
+
+      return;
+    }
+
+    if (attributionPathInput !== undefined) {
+      if (key.escape) {
+        setAttributionPathInput(undefined);
+        return;
+      }
+
+      if (key.return) {
+        const filePath = attributionPathInput.trim();
+        if (!filePath) {
+          pushBanner("warn", "File path is required.");
+          return;
+        }
+
+        setAttributionPathInput(undefined);
+        void attributeFile(filePath);
+        return;
+      }
+
+      if (key.backspace || key.delete) {
+        setAttributionPathInput((current) =>
+          current && current.length > 0 ? current.slice(0, -1) : "",
+        );
+        return;
+      }
+
+      if (
+        input &&
+        !key.ctrl &&
+        !key.meta &&
+        !key.upArrow &&
+        !key.downArrow &&
+        !key.leftArrow &&
+        !key.rightArrow
       ) {
-        setModelFilterInput((current) => `${current}${input}`);
-        setSelectedModelOptionIndex(0);
-        return;
+        setAttributionPathInput((current) => `${current ?? ""}${input}`);
       }
 
       return;
     }
 
+    if (attribution && key.escape) {
+      setAttribution(undefined);
+      return;
+    }
+
     if (followUpPromptInput !== undefined) {
       if (key.escape) {
+        discardActiveDraft();
         setFollowUpPromptInput(undefined);
         pushBanner("info", "Follow-up prompt cancelled.");
         return;
       }
 
+      if (key.ctrl && input === "r" && draftOffer) {
+        setFollowUpPromptInput(draftOffer.value);
+        setDraftOffer(undefined);
+        return;
+      }
+
       if (key.return) {
         const promptToSubmit = followUpPromptInput.trim();
         if (!promptToSubmit) {
@@ -1028,6 +3965,7 @@ export function App({
           return;
         }
 
+        discardActiveDraft();
         setFollowUpPromptInput(undefined);
         void sendFollowUpPrompt(promptToSubmit);
         return;
@@ -1055,6 +3993,138 @@ export function App({
       return;
     }
 
+    if (route === "pages") {
+      const page = pages[selectedPageIndex];
+
+      if (newPageTitleInput !== undefined) {
+        if (key.escape) {
+          setNewPageTitleInput(undefined);
+          return;
+        }
+
+        if (key.return) {
+          const title = newPageTitleInput.trim();
+          const titleIssue = validateTitle(title, "Page title");
+          if (titleIssue) {
+            setFieldError(titleIssue);
+            return;
+          }
+
+          if (!activeProjectId) {
+            pushBanner("warn", "No active project selected.");
+            return;
+          }
+
+          setNewPageTitleInput(undefined);
+          void services.pageRegistry
+            .createPage(activeProjectId, title)
+            .then(async () => {
+              await refreshPages();
+              setSelectedPageIndex(pages.length);
+              pushBanner("success", `Created page ${title}. Press e to edit it.`);
+            })
+            .catch((error) => {
+              pushBanner("error", toErrorMessage(error));
+            });
+          return;
+        }
+
+        if (key.backspace || key.delete) {
+          setNewPageTitleInput((current) =>
+            current && current.length > 0 ? current.slice(0, -1) : "",
+          );
+          return;
+        }
+
+        if (
+          input &&
+          !key.ctrl &&
+          !key.meta &&
+          !key.upArrow &&
+          !key.downArrow &&
+          !key.leftArrow &&
+          !key.rightArrow
+        ) {
+          setNewPageTitleInput((current) => `${current ?? ""}${input}`);
+        }
+
+        return;
+      }
+
+      if (wantsMoveUp) {
+        setSelectedPageIndex((current) => Math.max(0, current - 1));
+        return;
+      }
+
+      if (wantsMoveDown) {
+        setSelectedPageIndex((current) => Math.min(Math.max(pages.length - 1, 0), current + 1));
+        return;
+      }
+
+      if (input === "n") {
+        setNewPageTitleInput("");
+        return;
+      }
+
+      if (!page) {
+        return;
+      }
+
+      if (input === "e") {
+        void editSelectedPage();
+        return;
+      }
+
+      if (input === "i") {
+        void services.pageRegistry
+          .updatePage(page.id, { includeInPrompts: !page.includeInPrompts })
+          .then(() => refreshPages())
+          .catch((error) => {
+            pushBanner("error", toErrorMessage(error));
+          });
+        return;
+      }
+
+      if (input === "J" || input === "K") {
+        void services.pageRegistry
+          .movePage(page.id, input === "J" ? 1 : -1)
+          .then(async () => {
+            await refreshPages();
+            setSelectedPageIndex((current) =>
+              Math.min(Math.max(0, current + (input === "J" ? 1 : -1)), Math.max(pages.length - 1, 0)),
+            );
+          })
+          .catch((error) => {
+            pushBanner("error", toErrorMessage(error));
+          });
+        return;
+      }
+
+      if (input === "d") {
+        const now = Date.now();
+        const isDoublePress = lastKeyPress && lastKeyPress.key === "d" && now - lastKeyPress.time < DOUBLE_KEY_TIMEOUT_MS;
+
+        if (isDoublePress) {
+          setLastKeyPress(null);
+          void services.pageRegistry
+            .removePage(page.id)
+            .then(async () => {
+              await refreshPages();
+              pushBanner("success", `Deleted page ${page.title}.`);
+            })
+            .catch((error) => {
+              pushBanner("error", toErrorMessage(error));
+            });
+        } else {
+          setLastKeyPress({ key: "d", time: now });
+          pushBanner("warn", "Press 'd' again within 500ms to confirm delete.");
+        }
+        return;
+      }
+
+      return;
+    }
+
     if (route === "project-selector") {
       if (wantsMoveUp) {
         setSelectedProjectIndex((current) => Math.max(0, current - 1));
@@ -1086,6 +4156,49 @@ export function App({
         return;
       }
 
+      if (input === "r") {
+        startInlineRename("project");
+        return;
+      }
+
+      if (input === "c") {
+        setChangelogSinceInput("");
+        return;
+      }
+
+      if (input === "h" || input === "H") {
+        void toggleSelectedProjectGitHooks(input === "h");
+        return;
+      }
+
+      if (input === "a") {
+        void editSelectedProjectInstructions();
+        return;
+      }
+
+      return;
+    }
+
+    if (focusMode && key.escape) {
+      setFocusMode(false);
+      return;
+    }
+
+    if (focusMode && (wantsMoveUp || wantsMoveDown)) {
+      const columnTasks = listColumnTasksForTask(
+        tasksForActiveProject,
+        selectedTask?.taskId,
+        boardColumns,
+      );
+      const position = columnTasks.findIndex(
+        (task) => task.taskId === selectedTask?.taskId,
+      );
+      const nextTask = columnTasks[position + (wantsMoveUp ? -1 : 1)];
+      if (nextTask) {
+        setSelectedTaskIndex(
+          tasksForActiveProject.findIndex((task) => task.taskId === nextTask.taskId),
+        );
+      }
       return;
     }
 
@@ -1101,16 +4214,55 @@ export function App({
       return;
     }
 
+    if (lastKeyPress?.key === "z" && Date.now() - lastKeyPress.time < DOUBLE_KEY_TIMEOUT_MS) {
+      setLastKeyPress(null);
+      if (input === "z") {
+        setFocusMode(!focusMode);
+        pushBanner("info", focusMode ? "Focus mode off." : "Focus mode on.");
+        return;
+      }
+
+      if (input === "c" || input === "o") {
+        setSelectedColumnCollapsed(input === "c");
+        return;
+      }
+    }
+
+    if (input === "z") {
+      setLastKeyPress({ key: "z", time: Date.now() });
+      return;
+    }
+
     if (input === "n") {
       startTaskPromptInput();
       return;
     }
 
+    if (input === "a") {
+      startSubtaskPromptInput();
+      return;
+    }
+
+    if (input === "x") {
+      toggleSelectedTaskSubtasks();
+      return;
+    }
+
     if (input === "o") {
       void openTaskModelPicker();
       return;
     }
 
+    if (input === "O") {
+      if (!selectedTask) {
+        pushBanner("warn", "No task selected.");
+        return;
+      }
+
+      void openTaskModelPicker(selectedTask);
+      return;
+    }
+
     if (input === "d") {
       const now = Date.now();
       const isDoublePress = lastKeyPress && lastKeyPress.key === "d" && now - lastKeyPress.time < DOUBLE_KEY_TIMEOUT_MS;
@@ -1135,21 +4287,37 @@ export function App({
       return;
     }
 
+    if (input === "v") {
+      if (!selectedTask) {
+        pushBanner("warn", "No task selected.");
+        return;
+      }
+
+      void loadDiffView(selectedTask);
+      return;
+    }
+
+    if (input === "h") {
+      void openCommitHistory();
+      return;
+    }
+
+    if (input === "R") {
+      startInlineRename("task");
+      return;
+    }
+
+    if (input === "b") {
+      void planSelectedTask();
+      return;
+    }
+
     if (input === "y") {
       const task = tasksForActiveProject[selectedTaskIndex];
       if (task?.worktreeDirectory) {
-        const platform = process.platform;
-        if (platform === "darwin") {
-          await Bun.spawn(["pbcopy"], { stdin: new TextEncoder().encode(task.worktreeDirectory) });
-        } else if (platform === "linux") {
-          const escapedPath = task.worktreeDirectory.replace(/'/g, "'\\''");
-          const success = await Bun.spawn(["sh", "-c", `echo -n '$escapedPath' | xclip -selection clipboard 2>/dev/null || xsel --clipboard 2>/dev/null || wl-paste --no-newline 2>/dev/null`]).exited;
-          if (success !== 0) {
-            pushBanner("warn", "No clipboard tool found (install xclip, xsel, or wl-paste).");
-            return;
-          }
-        } else {
-          pushBanner("warn", "Clipboard not supported on this platform.");
+        const copied = await copyToClipboard(task.worktreeDirectory);
+        if (copied !== "copied") {
+          pushBanner("warn", describeClipboardFailure(copied));
           return;
         }
         pushBanner("success", `Copied worktree path: ${task.worktreeDirectory}`);
@@ -1159,19 +4327,265 @@ export function App({
       return;
     }
 
-    if (input === "m") {
-      void mergeSelectedTask();
+    if (input === "t") {
+      const task = tasksForActiveProject[selectedTaskIndex];
+      if (!task?.worktreeDirectory) {
+        pushBanner("warn", "No worktree path available for this task.");
+        return;
+      }
+
+      const launched = await openTerminalIn(task.worktreeDirectory, terminalCommand);
+      if (launched !== "opened") {
+        pushBanner(
+          "warn",
+          terminalCommand
+            ? `Terminal command failed: ${terminalCommand}`
+            : "Could not open a terminal; set IKANBAN_TERMINAL_COMMAND.",
+        );
+        return;
+      }
+      pushBanner("success", `Opened a terminal in ${task.worktreeDirectory}`);
+      return;
+    }
+
+    if (input === "A") {
+      if (permissionRequests.length === 0) {
+        pushBanner("info", "No permission requests are waiting.");
+        return;
+      }
+
+      setDismissedPermissionIds([]);
+      return;
+    }
+
+    if (input === "Y") {
+      const task = tasksForActiveProject[selectedTaskIndex];
+      if (!task) {
+        pushBanner("warn", "No task selected.");
+        return;
+      }
+
+      const link = formatDeepLink({ kind: "task", id: task.taskId });
+      const copied = await copyToClipboard(link);
+      pushBanner(
+        copied === "copied" ? "success" : "info",
+        copied === "copied" ? `Copied link: ${link}` : `Link: ${link} (${describeClipboardFailure(copied)})`,
+      );
+      return;
+    }
+
+    if (input === "m") {
+      void mergeSelectedTask();
+      return;
+    }
+
+    if (input === "u" || input === "U") {
+      void pushSelectedTaskBranch(input === "U");
+      return;
+    }
+
+    if (input === "X") {
+      void exportSelectedTaskTranscript();
+      return;
+    }
+
+    if (input === "W") {
+      setAttribution(undefined);
+      setAttributionPathInput("");
+      return;
+    }
+
+    if (input === "e") {
+      if (!selectedTask) {
+        pushBanner("warn", "No task selected.");
+        return;
+      }
+
+      setEstimateInput(selectedTask.estimate?.toString() ?? "");
+      return;
+    }
+
+    if (input === "J" || input === "K") {
+      if (!selectedTask) {
+        pushBanner("warn", "No task selected.");
+        return;
+      }
+
+      // Cards only trade places with siblings of the same priority; P moves them across priorities.
+      const group = listColumnTasksForTask(tasksForActiveProject, selectedTask.taskId, boardColumns).filter(
+        (task) =>
+          task.parentTaskId === selectedTask.parentTaskId &&
+          (task.priority ?? "medium") === (selectedTask.priority ?? "medium"),
+      );
+      const index = group.findIndex((task) => task.taskId === selectedTask.taskId);
+      const target = index + (input === "J" ? 1 : -1);
+      if (target < 0 || target >= group.length) {
+        pushBanner(
+          "info",
+          `${selectedTask.shortCode ?? selectedTask.taskId} is already ${input === "J" ? "last" : "first"} among its ${selectedTask.priority ?? "medium"}-priority cards here.`,
+        );
+        return;
+      }
+
+      const order = group.map((task) => task.taskId);
+      [order[index], order[target]] = [order[target]!, order[index]!];
+      void services.orchestrator
+        .reorderTask(selectedTask.taskId, order)
+        .then(() => {
+          setPendingTaskFocusId(selectedTask.taskId);
+        })
+        .catch((error) => pushBanner("error", toErrorMessage(error)));
+      return;
+    }
+
+    if (input === "P") {
+      if (!selectedTask) {
+        pushBanner("warn", "No task selected.");
+        return;
+      }
+
+      const priority = nextTaskPriority(selectedTask.priority);
+      void services.orchestrator
+        .setTaskPriority(selectedTask.taskId, priority)
+        .then(() => {
+          // The card moves within its column; keep it selected.
+          setPendingTaskFocusId(selectedTask.taskId);
+          pushBanner("info", `${selectedTask.shortCode ?? selectedTask.taskId} priority: ${priority}.`);
+        })
+        .catch((error) => pushBanner("error", toErrorMessage(error)));
+      return;
+    }
+
+    if (input === "c") {
+      if (!selectedTask) {
+        pushBanner("warn", "No task selected.");
+        return;
+      }
+
+      if (!listNextTaskStates(selectedTask.state, activeProject?.taskTransitions).includes("cancelled")) {
+        pushBanner("warn", `Task ${selectedTask.shortCode ?? selectedTask.taskId} cannot be cancelled from ${selectedTask.state}.`);
+        return;
+      }
+
+      setCancelReasonInput("");
+      return;
+    }
+
+    if (input === "H") {
+      if (!selectedTask?.checkpoints?.length) {
+        pushBanner("warn", "No checkpoints for this task yet.");
+        return;
+      }
+
+      setCheckpointPanel({ selectedIndex: 0 });
+      return;
+    }
+
+    if (input === "C") {
+      setShowCancelled(!showCancelled);
+      pushBanner("info", showCancelled ? "Cancelled tasks hidden." : "Showing cancelled tasks.");
+      return;
+    }
+
+    if (input === "M") {
+      const filterIndex = milestones.findIndex((milestone) => milestone.id === milestoneFilterId);
+      setMilestonePanel({ selectedIndex: filterIndex + 1 });
+      return;
+    }
+  };
+
+  const playMacro = (register: string | undefined) => {
+    const steps = register ? macroRegisters[register] : undefined;
+    if (!register || !steps || steps.length === 0) {
+      pushBanner("warn", register ? `Macro "${register} is empty.` : "No macro played yet.");
+      return;
+    }
+
+    // A macro played while recording becomes part of the recording.
+    setMacroRecording((current) => current && { ...current, steps: [...current.steps, ...steps] });
+    setLastMacroRegister(register);
+    setMacroReplay({ register, steps, index: 0 });
+  };
+
+  useInput((input, key) => {
+    if (macroReplay) {
+      if (key.escape) {
+        setMacroReplay(undefined);
+        pushBanner("warn", `Stopped macro "${macroReplay.register}.`);
+      } else if (key.ctrl && input === "c") {
+        exit();
+      }
+      return;
+    }
+
+    if (pendingMacroKey) {
+      setPendingMacroKey(undefined);
+      if (pendingMacroKey === MACRO_PLAY_KEY && input === MACRO_PLAY_KEY) {
+        playMacro(lastMacroRegister);
+        return;
+      }
+
+      if (!isMacroRegister(input)) {
+        pushBanner("warn", "Macro registers are a-z.");
+        return;
+      }
+
+      if (pendingMacroKey === MACRO_PLAY_KEY) {
+        playMacro(input);
+        return;
+      }
+
+      setMacroRecording({ register: input, steps: [] });
+      pushBanner("info", `Recording macro "${input}; press ${MACRO_RECORD_KEY} to stop.`);
+      return;
+    }
+
+    if (!isInTextInputMode && input === MACRO_RECORD_KEY && macroRecording) {
+      setMacroRegisters((current) => ({ ...current, [macroRecording.register]: macroRecording.steps }));
+      setMacroRecording(undefined);
+      pushBanner(
+        "success",
+        `Recorded ${macroRecording.steps.length} keys into "${macroRecording.register}; play with ${MACRO_PLAY_KEY}${macroRecording.register}.`,
+      );
+      return;
+    }
+
+    if (!isInTextInputMode && (input === MACRO_RECORD_KEY || input === MACRO_PLAY_KEY)) {
+      setPendingMacroKey(input);
       return;
     }
+
+    if (macroRecording) {
+      setMacroRecording((current) => current && { ...current, steps: [...current.steps, toMacroStep(input, key)] });
+    }
+
+    void handleInput(input, key);
   });
 
-  const frameWidth = Math.max(stdout.columns ?? 40, 40);
-  const frameHeight = Math.max(stdout.rows ?? 16, 16);
-  const logVisibleRows = Math.max(
-    isLogViewOpen ? frameHeight - 8 : Math.floor(frameHeight / 3),
-    6,
-  );
-  const reviewDiffVisibleRows = Math.max(frameHeight - 20, 8);
+  // One key per render, so each replayed key sees the state the previous one left behind.
+  useEffect(() => {
+    if (!macroReplay) {
+      return;
+    }
+
+    const step = macroReplay.steps[macroReplay.index];
+    if (!step) {
+      setMacroReplay(undefined);
+      return;
+    }
+
+    void handleInput(step.input, step.key).finally(() => {
+      setMacroReplay((current) => (current === macroReplay ? { ...current, index: current.index + 1 } : current));
+    });
+  }, [macroReplay]);
+
+  const frameWidth = terminalSize.columns;
+  const frameHeight = terminalSize.rows;
+  const {
+    log: logVisibleRows,
+    reviewDiff: reviewDiffVisibleRows,
+    diff: diffVisibleRows,
+  } = computePanelRows(frameHeight, isLogViewOpen);
   const visibleReviewDiff = useMemo(
     () =>
       reviewDiff
@@ -1180,6 +4594,26 @@ export function App({
     [reviewDiff, reviewDiffVisibleRows],
   );
 
+  if (isTerminalTooSmall(terminalSize)) {
+    return <TerminalTooSmallView size={terminalSize} />;
+  }
+
+  if (isLogViewOpen && isLogReadingMode) {
+    return (
+      <Box flexDirection="column" width={frameWidth} height={frameHeight} paddingX={1}>
+        <LogReaderView
+          title={selectedTask ? formatTaskCardLabel(selectedTask) : "all tasks"}
+          entries={taskLogs}
+          level={logViewLevel}
+          scrollOffset={logScrollOffset}
+          width={frameWidth - 2}
+          height={frameHeight}
+          wrap={isLogWrapEnabled}
+        />
+      </Box>
+    );
+  }
+
   return (
     <Box
       flexDirection="column"
@@ -1211,9 +4645,98 @@ export function App({
         </Box>
       ) : null}
 
+      {activePermissionRequest ? (
+        <Box marginBottom={1} borderStyle="round" borderColor="yellow" paddingX={1} flexDirection="column">
+          <Text color="yellow">
+            {formatPermissionRequester(activePermissionRequest, tasks)} asks to use{" "}
+            {describePermissionRequest(activePermissionRequest)}
+          </Text>
+          <Text color="gray">
+            y allow once | a always allow | n reject | Esc later (A on the board reopens)
+            {permissionRequests.length > 1 ? ` | ${permissionRequests.length - 1} more waiting` : ""}
+          </Text>
+        </Box>
+      ) : null}
+
       <Box flexDirection="column" flexGrow={1}>
         {loading ? (
           <Text color="yellow">Loading runtime and project state...</Text>
+        ) : metricsReport ? (
+          <Box flexDirection="column" flexGrow={1}>
+            <Text color="magentaBright">Metrics ({activeProject?.name ?? "-"})</Text>
+            <Box marginTop={1} flexDirection="column">
+              {metricsReport.heatmap ? (
+                <ActivityHeatmapView heatmap={metricsReport.heatmap} />
+              ) : (
+                <MetricsView
+                  rollup={metricsReport.rollup}
+                  rows={metricsReport.rows}
+                  from={metricsReport.from}
+                  to={metricsReport.to}
+                />
+              )}
+            </Box>
+          </Box>
+        ) : isQueueViewOpen ? (
+          <Box flexDirection="column" flexGrow={1}>
+            <Text color="magentaBright">Execution queue (all projects)</Text>
+            <Box marginTop={1} flexDirection="column">
+              <QueueView
+                running={queueSnapshot.running}
+                queued={queueEstimates}
+                maxConcurrent={queueSnapshot.maxConcurrent}
+                maxConcurrentPerProject={queueSnapshot.maxConcurrentPerProject}
+                maxConcurrentByProjectId={queueSnapshot.maxConcurrentByProjectId}
+                selectedIndex={selectedQueueIndex}
+                projectNames={projectNames}
+                offHoursTaskIds={offHoursTaskIds}
+                blockersByTaskId={queuedTaskBlockers}
+              />
+            </Box>
+          </Box>
+        ) : isFailureViewOpen ? (
+          <Box flexDirection="column" flexGrow={1}>
+            <Text color="magentaBright">
+              Failures ({failedTasksInTriageOrder.length})
+            </Text>
+            <Box marginTop={1} flexDirection="column">
+              <FailureTriageView
+                groups={failureGroups}
+                selectedIndex={selectedFailureIndex}
+                projectNames={projectNames}
+              />
+            </Box>
+          </Box>
+        ) : diffView ? (
+          <Box flexDirection="column" flexGrow={1}>
+            <DiffView
+              taskLabel={`${diffViewTask ? formatTaskCardLabel(diffViewTask) : diffView.taskId}${diffView.commit ? ` @ ${diffView.commit.slice(0, 7)}` : ""}`}
+              files={diffView.files}
+              fileIndex={diffView.fileIndex}
+              foldedHunks={diffView.foldedHunks}
+              scrollOffset={diffView.scrollOffset}
+              visibleRows={diffVisibleRows}
+              live={isDiffViewLive}
+            />
+          </Box>
+        ) : compareView ? (
+          <Box flexDirection="column" flexGrow={1}>
+            <CompareView
+              leftLabel={formatTaskReference(compareView.leftTaskId, tasks)}
+              rightLabel={formatTaskReference(compareView.rightTaskId, tasks)}
+              comparison={compareView.comparison}
+              scrollOffset={compareView.scrollOffset}
+              visibleRows={diffVisibleRows}
+            />
+          </Box>
+        ) : commitHistory ? (
+          <Box flexDirection="column" flexGrow={1}>
+            <CommitHistoryView
+              taskLabel={commitHistoryTask ? formatTaskCardLabel(commitHistoryTask) : commitHistory.taskId}
+              commits={commitHistory.commits}
+              selectedIndex={commitHistory.selectedIndex}
+            />
+          </Box>
         ) : isLogViewOpen ? (
           <Box flexDirection="column" flexGrow={1}>
             <LogView
@@ -1223,6 +4746,25 @@ export function App({
               visibleRows={logVisibleRows}
             />
           </Box>
+        ) : route === "pages" ? (
+          <Box flexDirection="column" flexGrow={1}>
+            <Text color="magentaBright">
+              Pages ({activeProject?.name ?? "none"}) - * included in task prompts
+            </Text>
+            <Box marginTop={1} flexDirection="column">
+              <PagesView
+                pages={pages}
+                selectedPageIndex={selectedPageIndex}
+                maxBodyLines={clampRows(frameHeight - 10, 6, frameHeight - 6)}
+              />
+            </Box>
+            {newPageTitleInput !== undefined ? (
+              <Box marginTop={1} flexDirection="column">
+                <Text color="cyan">New page title: {newPageTitleInput || " "}</Text>
+                {fieldError ? <Text color="red">{fieldError.message}</Text> : null}
+              </Box>
+            ) : null}
+          </Box>
         ) : route === "project-selector" ? (
           <Box flexDirection="column" flexGrow={1}>
             <Text color="magentaBright">Projects</Text>
@@ -1230,6 +4772,10 @@ export function App({
               <ProjectSelectorView
                 projects={projects}
                 selectedProjectIndex={selectedProjectIndex}
+                instructionFiles={instructionFiles}
+                renameValue={
+                  inlineRename?.target === "project" ? inlineRename.value : undefined
+                }
               />
             </Box>
           </Box>
@@ -1239,15 +4785,42 @@ export function App({
               <Text color="magentaBright">
                 Tasks ({activeProject?.name ?? "none"})
               </Text>
+              {milestoneFilter ? (
+                <Text color={isMilestoneOverdue(milestoneFilter) ? "red" : "gray"}>
+                  {formatMilestoneSummary(milestoneFilter, tasks)}
+                </Text>
+              ) : null}
+              {labelFilter ? (
+                <Text color={labelFilter.color}>Label: {labelFilter.name} (:label filter to clear)</Text>
+              ) : null}
               <Box marginTop={1} flexDirection="column">
                 <TaskBoardView
                   tasks={tasksForActiveProject}
                   selectedTaskIndex={selectedTaskIndex}
                   pendingTaskModelLabel={formatModel(taskModel, defaultModelLabel)}
+                  pendingTaskPathScope={taskPathScope}
+                  pendingTaskBlockerLabel={newTaskBlocker ? (newTaskBlocker.shortCode ?? newTaskBlocker.taskId) : undefined}
+                  blockedTaskIds={blockedTaskIds}
+                  offHoursTaskIds={offHoursTaskIds}
+                  subtaskSummaries={subtaskSummaries}
+                  collapsedTaskIds={collapsedTaskIds}
+                  labelsById={labelsById}
+                  renameValue={
+                    inlineRename?.target === "task" ? inlineRename.value : undefined
+                  }
+                  focusMode={focusMode}
+                  hiddenCancelledCount={hiddenCancelledCount}
+                  collapsedColumns={collapsedColumns}
+                  columns={boardColumns}
+                  averageRunMs={averageRunMs}
+                  width={frameWidth - 4}
+                  cardFields={cardFields}
+                  palette={palette}
                 />
               </Box>
             </Box>
 
+            {focusMode ? null : (
             <Box marginTop={1} flexDirection="column" flexGrow={1}>
               <Text color="magentaBright">Details</Text>
               <Box marginTop={1} flexDirection="column">
@@ -1256,12 +4829,24 @@ export function App({
                     <Text color="cyan">Review Panel ({reviewDiff.taskId})</Text>
                     <Text>
                       Branch: {reviewDiff.branch} -&gt; {reviewDiff.defaultBranch}
+                      {selectedTask?.pathScope ? ` | scope ${selectedTask.pathScope}/` : ""}
                     </Text>
+                    {reviewDiff.files.length > 0 ? (
+                      <Text>{formatDiffTotals(sumDiffFiles(reviewDiff.files))}</Text>
+                    ) : null}
                     <Text color="gray">
                       {reviewDiff.summary || "No file summary changes."}
                     </Text>
+                    {selectedTask?.worktreeChangedAt && selectedTask.worktreeChangedAt > reviewDiff.loadedAt ? (
+                      <Text color="red">
+                        Stale: worktree changed at {formatTime(selectedTask.worktreeChangedAt)} after this diff was loaded. Esc then r to reload.
+                      </Text>
+                    ) : null}
+                    <Text color={selectedTask?.review?.decision === "approved" ? "green" : "yellow"}>
+                      Reviewer: {selectedTask?.review?.reviewer ?? "-"} | {formatReviewDecision(selectedTask?.review?.decision)}
+                    </Text>
                     <Text color="gray">
-                      Decide: m merge | k keep in review | Esc close panel
+                      Decide: a approve | c request changes | m squash-merge | M fast-forward | b rebase | u push | o open PR | k keep in review | Esc close panel
                     </Text>
                     <Box marginTop={1} flexDirection="column">
                       <Text color="cyan">Diff</Text>
@@ -1281,18 +4866,169 @@ export function App({
                   </>
                 ) : selectedTask ? (
                   <>
+                    {selectedTask.title ? <Text>{selectedTask.title}</Text> : null}
+                    {selectedTask.estimate !== undefined ? (
+                      <Text color="gray">Estimate: {formatPoints(selectedTask.estimate)}</Text>
+                    ) : null}
                     <Text>
-                      {selectedTask.taskId} | {selectedTask.state} | {formatModel(modelByTaskID[selectedTask.taskId], defaultModelLabel)}
+                      {selectedTask.shortCode ? `${selectedTask.shortCode} | ` : ""}
+                      {selectedTask.taskId} | {selectedTask.state} | {formatModel(selectedTask.model ?? modelByTaskID[selectedTask.taskId], defaultModelLabel)}
+                    </Text>
+                    <Text color="gray">
+                      {selectedTask.projectId} | {formatSessionLabel(selectedTask)}
                     </Text>
+                    {selectedTask.checkpoints?.length ? (
+                      <Text color="gray">
+                        Checkpoints: {selectedTask.checkpoints.length} (latest {formatTime(selectedTask.checkpoints.at(-1)!.createdAt)}, H to roll back)
+                      </Text>
+                    ) : null}
+                    {selectedTask.pathScope ? (
+                      <Text color="gray">Scope: {selectedTask.pathScope}/</Text>
+                    ) : null}
+                    {selectedTask.priority ? <Text color="gray">Priority: {selectedTask.priority}</Text> : null}
+                    {selectedTask.labelIds?.some((labelId) => labelsById.has(labelId)) ? (
+                      <Text color="gray">
+                        Labels:
+                        {selectedTask.labelIds.map((labelId) => {
+                          const label = labelsById.get(labelId);
+                          return label ? (
+                            <Text key={labelId} color={label.color}>
+                              {" "}
+                              #{label.name}
+                            </Text>
+                          ) : null;
+                        })}
+                      </Text>
+                    ) : null}
+                    {subtaskSummaries.has(selectedTask.taskId) ? (
+                      <Text color="gray">
+                        Subtasks: {formatSubtaskSummary(subtaskSummaries.get(selectedTask.taskId)!)} done
+                        {collapsedTaskIds.has(selectedTask.taskId) ? " (folded, x to expand)" : ""}
+                      </Text>
+                    ) : null}
+                    {selectedTaskBlockers.length > 0 ? (
+                      <Text color="yellow">
+                        Blocked by: {selectedTaskBlockers.map((blocker) => `${blocker.shortCode ?? blocker.taskId} (${blocker.state})`).join(", ")}
+                      </Text>
+                    ) : null}
+                    {offHoursTaskIds.has(selectedTask.taskId) && activeProject?.workingHours ? (
+                      <Text color="yellow">
+                        Waiting for working hours ({formatWorkingHours(activeProject.workingHours)}
+                        {formatWorkingHoursOpening(activeProject.workingHours)}); urgent priority (P) starts it now
+                      </Text>
+                    ) : null}
+                    {selectedTask.timeoutMs ? (
+                      <Text color="gray">Timeout: {formatWatchdogLimit(selectedTask.timeoutMs)} per run</Text>
+                    ) : null}
+                    {selectedTaskProgress ? (
+                      <Text color="gray">
+                        Progress: {formatProgressBar(selectedTaskProgress.fraction)}
+                        {selectedTaskProgress.source === "plan" && selectedTask.planProgress
+                          ? ` (${selectedTask.planProgress.completed}/${selectedTask.planProgress.total} plan steps)`
+                          : " (estimated from the average run time)"}
+                      </Text>
+                    ) : null}
+                    {selectedTaskRetryChain.length > 1 ? (
+                      <Text color="gray" wrap="truncate-end">
+                        Retries:{" "}
+                        {selectedTaskRetryChain
+                          .map((task) => `${task.shortCode ?? task.taskId} ${task.state}`)
+                          .join(" -> ")}
+                      </Text>
+                    ) : null}
                     <Text color="gray">
-                      {selectedTask.projectId} | {selectedTask.sessionID ?? "-"}
+                      Instructions: {selectedTask.agentInstructions ?? (selectedTask.worktreeDirectory ? "none (a on the project selector adds AGENTS.md)" : "-")}
                     </Text>
+                    <Text color="gray">
+                      Next: {listNextTaskStates(selectedTask.state, activeProject?.taskTransitions).join(", ") || "-"}
+                    </Text>
+                    {selectedTask.state === "cancelled" ? (
+                      <Text color="gray">
+                        Cancelled{selectedTask.cancelledAt ? ` at ${formatTime(selectedTask.cancelledAt)}` : ""}: {selectedTask.cancelReason ?? "no reason given"}
+                      </Text>
+                    ) : null}
+                    {selectedTask.preflight?.map((check) => (
+                      <Text
+                        key={check.id}
+                        color={check.status === "fail" ? "red" : check.status === "pass" ? "green" : "gray"}
+                      >
+                        Pre-flight {check.status === "fail" ? "✗" : check.status === "pass" ? "✓" : "-"} {check.id}: {check.message}
+                      </Text>
+                    ))}
+                    {selectedTask.hookAnnotations?.slice(-3).map((annotation) => (
+                      <Text key={`${annotation.point}-${annotation.at}-${annotation.hook}`} color="gray">
+                        Hook {annotation.point} ({basename(annotation.hook)}): {annotation.message}
+                      </Text>
+                    ))}
+                    {selectedTask.conflictRisk && (selectedTask.state === "running" || selectedTask.state === "review") ? (
+                      <Text color={selectedTask.conflictRisk.level === "high" ? "red" : selectedTask.conflictRisk.level === "medium" ? "yellow" : "gray"}>
+                        {formatConflictRisk(selectedTask.conflictRisk)}
+                      </Text>
+                    ) : null}
+                    {selectedTask.state === "review" && selectedTask.worktreeChangedAt ? (
+                      <Text color="yellow">
+                        Worktree dirty: edited outside the agent at {formatTime(selectedTask.worktreeChangedAt)}
+                      </Text>
+                    ) : null}
+                    {selectedTask.remoteRef ? (
+                      <Text color="gray">
+                        Pushed: {selectedTask.remoteRef.remote} {selectedTask.remoteRef.ref} ({formatTime(selectedTask.remoteRef.pushedAt)})
+                      </Text>
+                    ) : null}
+                    {selectedTask.pullRequest ? (
+                      <Text color={selectedTask.pullRequest.state === "closed" ? "red" : "green"}>
+                        PR #{selectedTask.pullRequest.number} {selectedTask.pullRequest.state}: {selectedTask.pullRequest.url}
+                      </Text>
+                    ) : null}
+                    {selectedTask.merge ? (
+                      <Text color="green">
+                        Merged: {selectedTask.merge.strategy} into {selectedTask.merge.targetBranch} at {selectedTask.merge.commit.slice(0, 7)} ({formatTime(selectedTask.merge.mergedAt)})
+                      </Text>
+                    ) : null}
+                    {selectedTask.worktreeRemovedAt !== undefined ? (
+                      <Text color="gray">Worktree removed ({formatTime(selectedTask.worktreeRemovedAt)})</Text>
+                    ) : null}
+                    {selectedTask.promptContext ? (
+                      <Text color="gray">
+                        Repo context: {selectedTask.promptContext.relevantFiles.length > 0
+                          ? selectedTask.promptContext.relevantFiles.join(", ")
+                          : "layout only"}
+                      </Text>
+                    ) : null}
                   </>
                 ) : (
                   <Text color="yellow">Select a task to inspect details.</Text>
                 )}
               </Box>
 
+              {selectedTask ? (
+                <Box marginTop={1} flexDirection="column">
+                  <Text color="cyan">Comments ({selectedTask.comments?.length ?? 0})</Text>
+                  {selectedTask.comments && selectedTask.comments.length > COMMENT_PREVIEW_COUNT ? (
+                    <Text color="gray">
+                      {selectedTask.comments.length - COMMENT_PREVIEW_COUNT} earlier (ikanban comments {selectedTask.shortCode ?? selectedTask.taskId})
+                    </Text>
+                  ) : null}
+                  {selectedTask.comments?.length ? (
+                    selectedTask.comments.slice(-COMMENT_PREVIEW_COUNT).map((comment) => (
+                      <Text key={comment.id} wrap="truncate-end">
+                        <Text color={comment.kind === "agent" ? "green" : "cyan"}>
+                          #{comment.id} {comment.author}
+                        </Text>
+                        <Text color="gray">
+                          {" "}
+                          {formatTime(comment.createdAt)}
+                          {comment.updatedAt ? " (edited)" : ""}:
+                        </Text>{" "}
+                        {truncate(comment.body.replace(/\s+/g, " "), 160)}
+                      </Text>
+                    ))
+                  ) : (
+                    <Text color="gray">No comments; :comment {"<text>"} adds one.</Text>
+                  )}
+                </Box>
+              ) : null}
+
               <Box marginTop={1} flexDirection="column">
                 <Text color="cyan">Conversation</Text>
                 {taskMessages.length > 0 ? (
@@ -1310,27 +5046,96 @@ export function App({
                 )}
               </Box>
             </Box>
+            )}
           </Box>
         )}
       </Box>
 
       {newProjectPathInput !== undefined ? (
-        <Box marginTop={1}>
+        <Box marginTop={1} flexDirection="column">
           <Text color="cyan">
             New project path: {newProjectPathInput || " "}
           </Text>
+          {fieldError ? <Text color="red">{fieldError.message}</Text> : null}
         </Box>
       ) : null}
 
       {newTaskPromptInput !== undefined ? (
+        <Box marginTop={1} flexDirection="column">
+          <Text color="cyan">
+            {subtaskParentId ? `Subtask of ${formatTaskReference(subtaskParentId, tasks)}` : "New task prompt"}:{" "}
+            {newTaskPromptInput || " "}
+          </Text>
+          {fieldError ? <Text color="red">{fieldError.message}</Text> : null}
+          {newTaskRunHistory ? <Text color="gray">{formatRunHistoryHint(newTaskRunHistory)}</Text> : null}
+          {draftOffer ? <Text color="gray" wrap="truncate-end">{formatDraftOffer(draftOffer)}</Text> : null}
+        </Box>
+      ) : null}
+
+      {inlineRename && fieldError ? (
         <Box marginTop={1}>
-          <Text color="cyan">New task prompt: {newTaskPromptInput || " "}</Text>
+          <Text color="red">{fieldError.message}</Text>
+        </Box>
+      ) : null}
+
+      {bulkTaskPrompts ? (
+        <Box marginTop={1} flexDirection="column">
+          <Text color="cyan">
+            Create {bulkTaskPrompts.length} tasks from pasted lines?
+          </Text>
+          {bulkTaskPrompts.slice(0, MAX_BULK_PREVIEW_ROWS).map((prompt, index) => (
+            <Text key={`${index}-${prompt}`}>
+              {index + 1}. {truncate(prompt, 100)}
+            </Text>
+          ))}
+          {bulkTaskPrompts.length > MAX_BULK_PREVIEW_ROWS ? (
+            <Text color="gray">
+              ...and {bulkTaskPrompts.length - MAX_BULK_PREVIEW_ROWS} more
+            </Text>
+          ) : null}
+        </Box>
+      ) : null}
+
+      {planReview ? (
+        <Box marginTop={1} flexDirection="column">
+          <Text color="cyan">
+            Planned subtasks for {planReview.parentTaskId} ({planReview.items.length})
+          </Text>
+          {planReview.items.map((item, index) => {
+            const isSelected = index === planReview.selectedIndex;
+            const text =
+              isSelected && planReview.editingValue !== undefined
+                ? `${planReview.editingValue}_`
+                : item;
+            return (
+              <Text key={`${index}-${item}`} color={isSelected ? "green" : undefined}>
+                {isSelected ? ">" : " "} {index + 1}. {truncate(text, 100)}
+              </Text>
+            );
+          })}
+        </Box>
+      ) : null}
+
+      {similarTaskWarning ? (
+        <Box marginTop={1} flexDirection="column">
+          <Text color="yellow">Similar tasks exist:</Text>
+          {similarTaskWarning.matches.map((match) => (
+            <Text key={match.task.taskId} color="yellow">
+              - {match.task.shortCode ?? match.task.taskId}{" "}
+              {truncate(match.task.title ?? match.task.prompt ?? "", 60)} (
+              {Math.round(match.score * 100)}%)
+            </Text>
+          ))}
         </Box>
       ) : null}
 
       {modelPickerOpen ? (
         <Box marginTop={1} flexDirection="column">
-          <Text color="cyan">Select task model (Enter save, Esc cancel)</Text>
+          <Text color="cyan">
+            {modelPickerTaskId
+              ? `Select model for ${tasks.find((task) => task.taskId === modelPickerTaskId)?.shortCode ?? modelPickerTaskId} follow-ups (Enter save, Esc cancel)`
+              : "Select task model (Enter save, Esc cancel)"}
+          </Text>
           <Text color="gray">Filter: {modelFilterInput || "(none)"}</Text>
           {filteredModelOptions.length > 0 ? (
             visibleModelOptions(
@@ -1351,11 +5156,158 @@ export function App({
         </Box>
       ) : null}
 
-      {followUpPromptInput !== undefined ? (
+      {commandInput !== undefined ? (
+        <Box marginTop={1}>
+          <Text color="cyan">:{commandInput}_</Text>
+        </Box>
+      ) : null}
+
+      {quickAdd ? (
+        <Box marginTop={1} flexDirection="column">
+          <Text color="cyan">
+            Quick add | Project: {quickAdd.field === "project" ? `${quickAdd.projectQuery}_` : quickAddProject?.name ?? "-"}
+          </Text>
+          {quickAdd.field === "project" ? (
+            quickAddProjects.length > 0 ? (
+              quickAddProjects
+                .slice(Math.max(0, quickAdd.selectedIndex - 4), Math.max(0, quickAdd.selectedIndex - 4) + 5)
+                .map((project) => (
+                  <Text key={project.id} color={project.id === quickAddProject?.id ? "green" : undefined}>
+                    {project.id === quickAddProject?.id ? ">" : " "} {project.name} ({project.id})
+                  </Text>
+                ))
+            ) : (
+              <Text color="yellow">(no matching projects)</Text>
+            )
+          ) : (
+            <Text color="cyan">Title: {quickAdd.title}_</Text>
+          )}
+        </Box>
+      ) : null}
+
+      {checkpointPanel && selectedTask ? (
+        <Box marginTop={1} flexDirection="column">
+          <Text color="cyan">Checkpoints for {selectedTask.shortCode ?? selectedTask.taskId} (Enter roll worktree back, Esc close)</Text>
+          {[...(selectedTask.checkpoints ?? [])].reverse().slice(0, 10).map((checkpoint, index) => {
+            const isSelected = index === checkpointPanel.selectedIndex;
+            return (
+              <Text key={checkpoint.commit} color={isSelected ? "green" : undefined}>
+                {isSelected ? ">" : " "} {formatTime(checkpoint.createdAt)} {checkpoint.commit.slice(0, 8)} {checkpoint.label}
+              </Text>
+            );
+          })}
+        </Box>
+      ) : null}
+
+      {cancelReasonInput !== undefined ? (
+        <Box marginTop={1}>
+          <Text color="cyan">
+            Cancel reason (optional): {cancelReasonInput || " "}
+          </Text>
+        </Box>
+      ) : null}
+
+      {estimateInput !== undefined ? (
+        <Box marginTop={1}>
+          <Text color="cyan">
+            Estimate (points, empty clears): {estimateInput || " "}
+          </Text>
+        </Box>
+      ) : null}
+
+      {milestonePanel ? (
+        <Box marginTop={1} flexDirection="column">
+          <Text color="cyan">Milestones (Enter filter, a assign selected task, n new, x open/close, Esc close)</Text>
+          <Text color={milestonePanel.selectedIndex === 0 ? "green" : undefined}>
+            {milestonePanel.selectedIndex === 0 ? ">" : " "} All tasks
+          </Text>
+          {milestones.map((milestone, index) => {
+            const isSelected = milestonePanel.selectedIndex === index + 1;
+            return (
+              <Text
+                key={milestone.id}
+                color={isSelected ? "green" : isMilestoneOverdue(milestone) ? "red" : milestone.status === "closed" ? "gray" : undefined}
+              >
+                {isSelected ? ">" : " "} {formatMilestoneSummary(milestone, tasks)}
+              </Text>
+            );
+          })}
+          {milestonePanel.createInput !== undefined ? (
+            <Text color="cyan">
+              New milestone (name [YYYY-MM-DD]): {milestonePanel.createInput || " "}
+            </Text>
+          ) : null}
+          {milestonePanel.createInput !== undefined && fieldError ? (
+            <Text color="red">{fieldError.message}</Text>
+          ) : null}
+        </Box>
+      ) : null}
+
+      {changelogSinceInput !== undefined ? (
+        <Box marginTop={1}>
+          <Text color="cyan">
+            Changelog since tag (empty = latest tag): {changelogSinceInput || " "}
+          </Text>
+        </Box>
+      ) : null}
+
+      {attributionPathInput !== undefined ? (
         <Box marginTop={1}>
+          <Text color="cyan">
+            Which tasks touched file: {attributionPathInput || " "}
+          </Text>
+        </Box>
+      ) : null}
+
+      {attribution ? (
+        <Box marginTop={1} flexDirection="column">
+          <Text color="cyan">Tasks that touched {attribution.filePath} (Esc close)</Text>
+          {attribution.entries.length > 0 ? (
+            attribution.entries.map((entry) => (
+              <Text
+                key={`${entry.source}-${entry.commit ?? entry.taskRef}`}
+                color={entry.task?.state === "failed" ? statusColor("failed", palette) : undefined}
+              >
+                - {palette === "color-blind" && entry.task ? `${STATUS_GLYPHS[entry.task.state]} ` : ""}
+                {entry.taskRef} {entry.commit ? `${entry.commit} ` : ""}
+                {entry.committedAt ? `${formatTime(entry.committedAt)} ` : ""}
+                {truncate(entry.task?.title ?? entry.subject, 60)}
+                {entry.source === "worktree" ? " [unmerged]" : ""}
+              </Text>
+            ))
+          ) : (
+            <Text color="yellow">No ikanban task commits found for this file.</Text>
+          )}
+        </Box>
+      ) : null}
+
+      {followUpPromptInput !== undefined ? (
+        <Box marginTop={1} flexDirection="column">
           <Text color="cyan">
             Follow-up prompt: {followUpPromptInput || " "}
           </Text>
+          {draftOffer ? <Text color="gray" wrap="truncate-end">{formatDraftOffer(draftOffer)}</Text> : null}
+        </Box>
+      ) : null}
+
+      {tutorial && tutorialStep ? (
+        <Box marginTop={1} flexDirection="column" borderStyle="round" borderColor="yellow" paddingX={1}>
+          <Text color="yellow">
+            Tutorial {tutorial.stepIndex + 1}/{TUTORIAL_STEPS.length}: {tutorialStep.title}
+          </Text>
+          <Text>{tutorialStep.instructions}</Text>
+          <Text>
+            Keys:
+            {tutorialStep.keys.map((keyName) => (
+              <Text key={keyName}>
+                {" "}
+                <Text color="black" backgroundColor="yellow">
+                  {` ${keyName} `}
+                </Text>
+              </Text>
+            ))}
+            <Text color="gray"> (:tutorial off to close)</Text>
+          </Text>
         </Box>
       ) : null}
 
@@ -1366,7 +5318,28 @@ export function App({
             isCreatingTask: newTaskPromptInput !== undefined,
             isEditingTaskModel: modelPickerOpen,
             isFollowUpPrompt: followUpPromptInput !== undefined,
+            isEnteringAttributionPath: attributionPathInput !== undefined,
+            isEnteringChangelogSince: changelogSinceInput !== undefined,
+            isMilestonePanelOpen: milestonePanel !== undefined,
+            isCreatingPage: newPageTitleInput !== undefined,
+            isEnteringCommand: commandInput !== undefined,
+            isQuickAdding: quickAdd !== undefined,
+            isEnteringEstimate: estimateInput !== undefined,
+            isEnteringCancelReason: cancelReasonInput !== undefined,
+            isCheckpointPanelOpen: checkpointPanel !== undefined,
             isReviewDiffOpen: reviewDiff !== undefined,
+            isRenaming: inlineRename !== undefined,
+            isSimilarTaskWarningOpen: similarTaskWarning !== undefined,
+            isConfirmingBulkTasks: bulkTaskPrompts !== undefined,
+            isPlanReviewOpen: planReview !== undefined,
+            isEditingPlanItem: planReview?.editingValue !== undefined,
+            isFocusMode: focusMode,
+            isFailureViewOpen,
+            isDiffViewOpen: diffView !== undefined,
+            isCommitHistoryOpen: commitHistory !== undefined,
+            isCompareViewOpen: compareView !== undefined,
+            isQueueViewOpen,
+            isMetricsViewOpen: metricsReport !== undefined,
             logViewLevel,
             isLogViewOpen,
           })}
@@ -1378,6 +5351,14 @@ export function App({
           <Text color="yellow">{busyMessage}</Text>
         </Box>
       ) : null}
+
+      {macroRecording ? (
+        <Box marginTop={1}>
+          <Text color="red">
+            ● recording "{macroRecording.register} ({macroRecording.steps.length} keys, {MACRO_RECORD_KEY} to stop)
+          </Text>
+        </Box>
+      ) : null}
     </Box>
   );
 }
@@ -1389,36 +5370,143 @@ function keyboardHints(
     isCreatingTask: boolean;
     isEditingTaskModel: boolean;
     isFollowUpPrompt: boolean;
+    isEnteringAttributionPath: boolean;
+    isEnteringChangelogSince: boolean;
+    isMilestonePanelOpen: boolean;
+    isCreatingPage: boolean;
+    isEnteringCommand: boolean;
+    isQuickAdding: boolean;
+    isEnteringEstimate: boolean;
+    isEnteringCancelReason: boolean;
+    isCheckpointPanelOpen: boolean;
     isReviewDiffOpen: boolean;
+    isRenaming: boolean;
+    isSimilarTaskWarningOpen: boolean;
+    isConfirmingBulkTasks: boolean;
+    isPlanReviewOpen: boolean;
+    isEditingPlanItem: boolean;
+    isFocusMode: boolean;
+    isFailureViewOpen: boolean;
+    isDiffViewOpen: boolean;
+    isCommitHistoryOpen: boolean;
+    isCompareViewOpen: boolean;
+    isQueueViewOpen: boolean;
+    isMetricsViewOpen: boolean;
     logViewLevel: LogViewLevel;
     isLogViewOpen: boolean;
   },
 ): string {
   if (options.isLogViewOpen) {
-    return `Keys: j/k line | u/d page | g/G ends | v level:${options.logViewLevel} | l logs | q quit`;
+    return `Keys: j/k line | u/d page | g/G ends | v level:${options.logViewLevel} | z reading mode | l logs | q quit`;
+  }
+
+  if (options.isEnteringCommand) {
+    return "Keys: goto <short-code|id-prefix> | scope [path] | after [code|id] | block/unblock <code|id> | label add|rm|color|delete|filter | comment <text> / edit <n> <text> / rm <n> | hours <HH:MM-HH:MM> [mon-fri]|off | limit <tasks>|off | timeout <minutes|off> | tutorial [off] | archive | compare [code|id] | worktree rm|orphans|prune | metrics [day|week|month|heatmap] | Enter run | Esc cancel";
+  }
+
+  if (options.isQuickAdding) {
+    return "Keys: type to filter projects | Up/Down pick | Enter next / add | Backspace on empty title goes back | Esc cancel";
+  }
+
+  if (options.isMetricsViewOpen) {
+    return "Keys: d/w/m rollup by day/week/month | h activity heatmap | Esc close | q quit";
+  }
+
+  if (options.isQueueViewOpen) {
+    return "Keys: j/k move | K/J move earlier/later | c cancel | Q/Esc close | q quit";
+  }
+
+  if (options.isFailureViewOpen) {
+    return "Keys: j/k move | r retry | F/Esc close | q quit";
+  }
+
+  if (options.isDiffViewOpen) {
+    return "Keys: j/k scroll | u/d page | g/G ends | n/p next/prev file | f fold hunk | F fold all | v/Esc close | q quit";
+  }
+
+  if (options.isCompareViewOpen) {
+    return "Keys: j/k scroll | u/d page | Esc close | q quit";
+  }
+
+  if (options.isCommitHistoryOpen) {
+    return "Keys: j/k move | Enter show commit | h/Esc close | q quit";
+  }
+
+  if (options.isRenaming) {
+    return "Keys: type name | Enter save | Esc cancel";
+  }
+
+  if (options.isEditingPlanItem) {
+    return "Keys: type subtask | Enter save | Esc cancel";
+  }
+
+  if (options.isPlanReviewOpen) {
+    return "Keys: j/k move | e edit | x remove | Enter create subtasks | Esc discard";
+  }
+
+  if (options.isConfirmingBulkTasks) {
+    return "Keys: y/Enter create all | n/Esc discard";
+  }
+
+  if (options.isSimilarTaskWarningOpen) {
+    return "Keys: Enter create anyway | g jump to similar task | Esc keep editing";
+  }
+
+  if (options.isEnteringEstimate) {
+    return "Keys: type points | Enter save | Esc cancel";
+  }
+
+  if (options.isCheckpointPanelOpen) {
+    return "Keys: j/k move | Enter roll back to checkpoint | H/Esc close";
+  }
+
+  if (options.isEnteringCancelReason) {
+    return "Keys: type reason (optional) | Enter cancel task | Esc keep task";
+  }
+
+  if (options.isMilestonePanelOpen) {
+    return "Keys: j/k move | Enter filter | a assign | n new | x open/close | Esc close";
+  }
+
+  if (options.isEnteringChangelogSince) {
+    return "Keys: type tag | Enter copy changelog | Esc cancel";
+  }
+
+  if (route === "pages") {
+    return options.isCreatingPage
+      ? "Keys: type title | Enter create | Esc cancel"
+      : "Keys: j/k move | n new | e edit in $EDITOR | i include in prompts | J/K reorder | dd delete | l logs | Tab projects | q quit";
   }
 
   if (route === "project-selector") {
     return options.isCreatingProject
       ? "Keys: type path | Enter create | Esc cancel"
-      : "Keys: j/k move | Enter open | n new | d delete | l logs | Tab board | q quit";
+      : "Keys: j/k move | Enter open | n new | r rename | c changelog | h/H hooks on/off | a agent instructions | d delete | l logs | Tab board | q quit";
+  }
+
+  if (options.isEnteringAttributionPath) {
+    return "Keys: type file path | Enter trace | Esc cancel";
   }
 
   if (options.isFollowUpPrompt) {
-    return "Keys: type prompt | Enter send | Esc cancel";
+    return "Keys: type prompt | Enter send | Ctrl+R restore draft | Esc cancel";
   }
 
   if (options.isReviewDiffOpen) {
-    return "Keys: m merge | k keep in review | Esc close | l logs | q quit";
+    return "Keys: a approve | c request changes | m squash-merge | M fast-forward | b rebase | u/U push | o open PR | k keep in review | Esc close | l logs | q quit";
   }
 
   if (options.isEditingTaskModel) {
     return "Keys: type filter | Up/Down move | Backspace delete | Enter save | Esc cancel";
   }
 
+  if (options.isFocusMode && !options.isCreatingTask) {
+    return "Keys: j/k move in column | zz/Esc leave focus | n new | r review | p follow-up | l logs | q quit";
+  }
+
   return options.isCreatingTask
-    ? "Keys: type prompt | Enter run | Esc cancel"
-    : "Keys: j/k move | n new | o model | r review | p follow-up | m merge | dd delete | l logs | Tab projects | q quit";
+    ? "Keys: type prompt | Enter run | Ctrl+R restore draft | Esc cancel"
+    : "Keys: j/k move | n new | a subtask | x fold subtasks | o model | O task model | r review | v diff | h commits | R rename | b plan | zz focus | zc/zo collapse/open column | F failures | Q queue | p follow-up | m merge | c cancel | C show cancelled | H checkpoints | u push | e estimate | P priority | J/K reorder | M milestones | W who touched | t terminal | X export | Y copy link | A permissions | dd delete | "a/@a record/play macro | l logs | Tab pages | q quit";
 }
 
 async function ensureDefaultProject(
@@ -1441,6 +5529,11 @@ async function ensureDefaultProject(
   });
 }
 
+function formatPermissionRequester(request: PermissionRequest, tasks: TaskRuntime[]): string {
+  const task = tasks.find((candidate) => candidate.taskId === request.taskId);
+  return task?.shortCode ?? request.taskId ?? request.sessionID;
+}
+
 function relayOrchestratorEvent(
   event: TaskOrchestratorEvent,
   bus: RuntimeEventBus,
@@ -1547,7 +5640,51 @@ function relayOrchestratorEvent(
       });
       return;
     }
-    case "task.merged": {
+    case "task.permission.requested": {
+      bus.emit("session.permission.requested", {
+        taskId: event.taskId,
+        projectId: event.task.projectId,
+        sessionID: event.request.sessionID,
+        requestId: event.request.id,
+        permission: event.request.permission,
+        patterns: event.request.patterns,
+      });
+      return;
+    }
+    case "task.permission.replied": {
+      bus.emit("session.permission.replied", {
+        taskId: event.taskId,
+        projectId: event.task.projectId,
+        sessionID: event.request.sessionID,
+        requestId: event.request.id,
+        reply: event.reply,
+      });
+      return;
+    }
+    case "task.merged":
+    case "task.renamed":
+    case "task.pushed":
+    case "task.rebased":
+    case "task.worktree.changed":
+    case "task.milestone.changed":
+    case "task.labels.changed":
+    case "task.comment.created":
+    case "task.comment.updated":
+    case "task.comment.deleted":
+    case "task.priority.changed":
+    case "task.rank.changed":
+    case "task.estimate.changed":
+    case "task.progress.changed":
+    case "task.pr.changed":
+    case "task.model.changed":
+    case "task.timeout.changed":
+    case "task.dependencies.changed":
+    case "task.watchdog.fired":
+    case "task.review.decided":
+    case "task.risk.changed":
+    case "task.checkpoint.created":
+    case "task.checkpoint.restored":
+    case "task.cancelled": {
       return;
     }
   }
@@ -1565,11 +5702,18 @@ function getSdkMessagePreview(message: ConversationSdkSessionMessage): string {
 }
 
 function createTaskID(seed: string, prompt?: string): string {
-  const hash = simpleHash(seed + Date.now().toString()).slice(0, 7);
+  const hash = simpleHash(seed + (prompt ?? "") + Date.now().toString()).slice(0, 7);
   const promptSlice = prompt ? toSlug(prompt).slice(0, 16) : "";
   return promptSlice ? `${promptSlice}-${hash}` : `task-${hash}`;
 }
 
+function splitPastedPrompts(value: string): string[] {
+  return value
+    .split(/\r\n|\r|\n/)
+    .map((line) => line.replace(/^\s*(?:[-*+]|\d+[.)])\s+/, "").trim())
+    .filter((line) => line.length > 0);
+}
+
 function simpleHash(input: string): string {
   let h = 0;
   for (let i = 0; i < input.length; i++) {
@@ -1582,10 +5726,6 @@ function buildDefaultPrompt(projectName: string, taskID: string): string {
   return `Work on task ${taskID} in project ${projectName}. Analyze the repository and implement the next meaningful change with tests.`;
 }
 
-function buildRetryPrompt(taskID: string): string {
-  return `Retry task ${taskID}. Address the previous failure and continue with the same objective.`;
-}
-
 function formatModel(
   model: PromptModel | undefined,
   defaultLabel = "default",
@@ -1597,6 +5737,56 @@ function formatModel(
   return `${model.providerID}/${model.modelID}`;
 }
 
+function formatConflictRisk(risk: TaskConflictRisk): string {
+  const details = [
+    risk.overlappingTasks.length > 0 ? `overlaps ${risk.overlappingTasks.join(", ")}` : undefined,
+    risk.upstreamFiles.length > 0 ? `upstream also changed ${risk.upstreamFiles.slice(0, 3).join(", ")}${risk.upstreamFiles.length > 3 ? ", ..." : ""}` : undefined,
+  ].filter(Boolean);
+
+  return `Conflict risk: ${risk.level} (${risk.score}) | ${risk.changedFileCount} files${details.length > 0 ? ` | ${details.join(" | ")}` : ""} (${formatTime(risk.checkedAt)})`;
+}
+
+function formatDraftOffer(draft: TextDraft): string {
+  return `Unsaved draft from ${formatTime(draft.savedAt)}: ${draft.value.replace(/\s+/g, " ")} (Ctrl+R restore)`;
+}
+
+function formatSessionLabel(task: TaskRuntime): string {
+  if (!task.sessionID) {
+    return "-";
+  }
+
+  return task.sessionName ? `${task.sessionName} (${task.sessionID.slice(0, 8)})` : task.sessionID;
+}
+
+function formatMilestoneSummary(milestone: Milestone, tasks: TaskRuntime[]): string {
+  const progress = computeMilestoneProgress(milestone.id, tasks);
+  const points = sumEstimates(tasks.filter((task) => task.milestoneId === milestone.id));
+  return `${milestone.name} ${progress.done}/${progress.total} done${
+    points > 0 ? ` | ${formatPoints(points)}` : ""
+  }${
+    milestone.dueDate ? ` | due ${milestone.dueDate}` : ""
+  }${milestone.status === "closed" ? " | closed" : ""}`;
+}
+
+function describeClipboardFailure(result: Exclude<ClipboardResult, "copied">): string {
+  return result === "no-tool"
+    ? "No clipboard tool found (install wl-clipboard, xclip, or xsel)."
+    : "Clipboard not supported on this platform.";
+}
+
+function formatReviewDecision(
+  decision: "approved" | "changes_requested" | undefined,
+): string {
+  switch (decision) {
+    case "approved":
+      return "approved";
+    case "changes_requested":
+      return "changes requested";
+    default:
+      return "awaiting review";
+  }
+}
+
 function isSameModel(
   left: PromptModel | undefined,
   right: PromptModel | undefined,
@@ -1745,6 +5935,13 @@ function formatUnknownError(error: unknown): string {
   return "Unknown SDK error";
 }
 
+function filterQuickAddProjects(projects: ProjectRef[], query: string): ProjectRef[] {
+  const normalizedQuery = query.trim().toLowerCase();
+  return projects.filter((project) =>
+    fuzzyMatches(`${project.name} ${project.id}`.toLowerCase(), normalizedQuery),
+  );
+}
+
 function filterModelOptions(
   options: ModelOption[],
   query: string,
@@ -1867,6 +6064,31 @@ function toInkColor(tone: BannerTone): "blue" | "green" | "yellow" | "red" {
   }
 }
 
+/** Copies an export to the artifact store; the banner suffix says where it went or why it did not. */
+async function copyToArtifactStore(store: BlobStore | undefined, filePath: string): Promise<string> {
+  if (!store) {
+    return "";
+  }
+
+  const key = `exports/${basename(filePath)}`;
+  try {
+    await store.write(key, Bun.file(filePath));
+    return ` and ${store.describe(key)}`;
+  } catch (error) {
+    return ` (copy to ${store.describe(key)} failed: ${toErrorMessage(error)})`;
+  }
+}
+
+function formatTaskReference(taskId: string, tasks: TaskRuntime[]): string {
+  const task = tasks.find((candidate) => candidate.taskId === taskId);
+  return task ? formatTaskCardLabel(task) : taskId;
+}
+
+function formatWorkingHoursOpening(hours: WorkingHours): string {
+  const opensAt = nextWorkingHoursStart(hours, new Date());
+  return opensAt ? `, opens ${opensAt.toLocaleString(undefined, { weekday: "short", hour: "2-digit", minute: "2-digit" })}` : "";
+}
+
 function formatTime(timestamp: number): string {
   return new Date(timestamp).toLocaleTimeString();
 }
@@ -0,0 +1,38 @@
+export type ClipboardResult = "copied" | "no-tool" | "unsupported";
+
+const LINUX_CLIPBOARD_COMMANDS = [
+  ["wl-copy"],
+  ["xclip", "-selection", "clipboard"],
+  ["xsel", "--clipboard", "--input"],
+];
+
+export async function copyToClipboard(text: string): Promise<ClipboardResult> {
+  if (process.platform === "darwin") {
+    return (await pipeTo(["pbcopy"], text)) ? "copied" : "no-tool";
+  }
+
+  if (process.platform !== "linux") {
+    return "unsupported";
+  }
+
+  for (const command of LINUX_CLIPBOARD_COMMANDS) {
+    if (await pipeTo(command, text)) {
+      return "copied";
+    }
+  }
+
+  return "no-tool";
+}
+
+async function pipeTo(command: string[], text: string): Promise<boolean> {
+  try {
+    const child = Bun.spawn(command, {
+      stdin: new TextEncoder().encode(text),
+      stdout: "ignore",
+      stderr: "ignore",
+    });
+    return (await child.exited) === 0;
+  } catch {
+    return false;
+  }
+}
@@ -0,0 +1,33 @@
+import { mkdtemp, readFile, rm, writeFile } from "node:fs/promises";
+import { tmpdir } from "node:os";
+import { join } from "node:path";
+
+/**
+ * Opens `$VISUAL`/`$EDITOR` on a temporary file seeded with `initial` and
+ * returns the saved text, or undefined when the editor exits unsuccessfully.
+ * The caller must release raw mode on stdin before calling this.
+ */
+export async function editTextInExternalEditor(
+  initial: string,
+  fileName = "ikanban.md",
+): Promise<string | undefined> {
+  const editor = process.env.VISUAL || process.env.EDITOR || "vi";
+  const directory = await mkdtemp(join(tmpdir(), "ikanban-"));
+  const filePath = join(directory, fileName);
+
+  try {
+    await writeFile(filePath, initial);
+    const result = Bun.spawnSync(["sh", "-c", `${editor} "$1"`, "sh", filePath], {
+      stdin: "inherit",
+      stdout: "inherit",
+      stderr: "inherit",
+    });
+    if (result.exitCode !== 0) {
+      return undefined;
+    }
+
+    return await readFile(filePath, "utf8");
+  } finally {
+    await rm(directory, { recursive: true, force: true });
+  }
+}
@@ -0,0 +1,79 @@
+import type { ProjectRef } from "../domain/project";
+import { parseTaskShortCode, type TaskRuntime } from "../domain/task";
+
+export type GotoTarget =
+  | { kind: "task"; task: TaskRuntime; matchedBy: "shortCode" | "taskId" | "sessionID" }
+  | { kind: "project"; project: ProjectRef };
+
+export type GotoResolution =
+  | { status: "found"; target: GotoTarget }
+  | { status: "ambiguous"; candidates: string[] }
+  | { status: "not_found" };
+
+/**
+ * Resolves `:goto` queries: task short codes (exact), then task id, session id
+ * and project id/name prefixes. Prefix matches must be unambiguous.
+ */
+export function resolveGotoTarget(
+  query: string,
+  input: { projects: ProjectRef[]; tasks: TaskRuntime[] },
+): GotoResolution {
+  const normalizedQuery = query.trim();
+  if (!normalizedQuery) {
+    return { status: "not_found" };
+  }
+
+  const shortCode = parseTaskShortCode(normalizedQuery);
+  if (shortCode) {
+    const code = `${shortCode.prefix}-${shortCode.sequence}`;
+    const task = input.tasks.find((candidate) => candidate.shortCode === code);
+    if (task) {
+      return { status: "found", target: { kind: "task", task, matchedBy: "shortCode" } };
+    }
+  }
+
+  const lowerQuery = normalizedQuery.toLowerCase();
+  const targets: GotoTarget[] = [
+    ...input.tasks
+      .filter((task) => task.taskId.toLowerCase().startsWith(lowerQuery))
+      .map((task): GotoTarget => ({ kind: "task", task, matchedBy: "taskId" })),
+    ...input.tasks
+      .filter((task) => task.sessionID?.toLowerCase().startsWith(lowerQuery))
+      .map((task): GotoTarget => ({ kind: "task", task, matchedBy: "sessionID" })),
+    ...input.projects
+      .filter(
+        (project) =>
+          project.id.toLowerCase().startsWith(lowerQuery) ||
+          project.name.toLowerCase() === lowerQuery,
+      )
+      .map((project): GotoTarget => ({ kind: "project", project })),
+  ];
+
+  const exact = targets.find((target) =>
+    target.kind === "task"
+      ? target.task.taskId === normalizedQuery || target.task.sessionID === normalizedQuery
+      : target.project.id === normalizedQuery,
+  );
+  if (exact) {
+    return { status: "found", target: exact };
+  }
+
+  const [onlyTarget, ...rest] = targets;
+  if (!onlyTarget) {
+    return { status: "not_found" };
+  }
+
+  if (rest.length > 0) {
+    return { status: "ambiguous", candidates: targets.map(describeGotoTarget) };
+  }
+
+  return { status: "found", target: onlyTarget };
+}
+
+export function describeGotoTarget(target: GotoTarget): string {
+  if (target.kind === "project") {
+    return `project ${target.project.name}`;
+  }
+
+  return `task ${target.task.shortCode ?? target.task.taskId}`;
+}
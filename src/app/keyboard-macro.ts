@@ -0,0 +1,30 @@
+import type { Key } from "ink";
+
+/** One key press as the input handler received it, replayed as-is. */
+export type MacroStep = {
+  input: string;
+  key: Key;
+};
+
+export type MacroRecording = {
+  register: string;
+  steps: MacroStep[];
+};
+
+export type MacroReplay = {
+  register: string;
+  steps: MacroStep[];
+  index: number;
+};
+
+// `q` quits and `Q` opens the queue, so recording starts on `"` as vim names registers.
+export const MACRO_RECORD_KEY = '"';
+export const MACRO_PLAY_KEY = "@";
+
+export function isMacroRegister(value: string): boolean {
+  return /^[a-z]$/.test(value);
+}
+
+export function toMacroStep(input: string, key: Key): MacroStep {
+  return { input, key: { ...key } };
+}
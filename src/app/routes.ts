@@ -1,4 +1,4 @@
-export const APP_ROUTES = ["project-selector", "task-board"] as const;
+export const APP_ROUTES = ["project-selector", "task-board", "pages"] as const;
 
 export type AppRoute = (typeof APP_ROUTES)[number];
 
@@ -19,6 +19,11 @@ export const ROUTE_DESCRIPTORS: Record<AppRoute, AppRouteDescriptor> = {
     title: "Task Board",
     description: "Run and monitor orchestrated tasks.",
   },
+  pages: {
+    id: "pages",
+    title: "Pages",
+    description: "Project conventions and agent instructions.",
+  },
 };
 
 const routeOrder = [...APP_ROUTES];
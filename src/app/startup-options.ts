@@ -0,0 +1,93 @@
+import { parseDeepLink } from "../domain/deep-link";
+import { IkanbanError } from "../domain/errors";
+import type { AppRoute } from "./routes";
+
+export type StartupOptions = {
+  project?: string;
+  view?: AppRoute;
+  task?: string;
+  runTask?: string;
+  promptFile?: string;
+};
+
+const VIEW_ALIASES: Record<string, AppRoute> = {
+  projects: "project-selector",
+  "project-selector": "project-selector",
+  tasks: "task-board",
+  board: "task-board",
+  "task-board": "task-board",
+  pages: "pages",
+};
+
+const VALUE_FLAGS = {
+  "--project": "project",
+  "--view": "view",
+  "--task": "task",
+  "--run-task": "runTask",
+  "--prompt-file": "promptFile",
+} as const;
+
+/**
+ * Parses the navigation flags, e.g. `--project web --view tasks --task WEB-42`
+ * or `--run-task WEB-42 --prompt-file p.md`. An `ikanban://task/<id>` or
+ * `ikanban://session/<id>` argument focuses that task. Flags it does not know
+ * are left for other handlers (`--print-config`).
+ */
+export function parseStartupOptions(argv: string[]): StartupOptions {
+  const options: StartupOptions = {};
+
+  for (let index = 0; index < argv.length; index += 1) {
+    const arg = argv[index] ?? "";
+    const deepLink = parseDeepLink(arg);
+    if (deepLink) {
+      // Session ids resolve through :goto the same way task ids do.
+      options.task = deepLink.id;
+      continue;
+    }
+
+    const [flag, inlineValue] = arg.includes("=") ? splitOnce(arg, "=") : [arg, undefined];
+    if (!(flag in VALUE_FLAGS)) {
+      continue;
+    }
+
+    const value = inlineValue ?? argv[index + 1];
+    if (inlineValue === undefined) {
+      index += 1;
+    }
+    if (!value || value.startsWith("--")) {
+      throw new IkanbanError("validation", `${flag} needs a value.`, { field: flag });
+    }
+
+    const key = VALUE_FLAGS[flag as keyof typeof VALUE_FLAGS];
+    if (key === "view") {
+      const view = VIEW_ALIASES[value.toLowerCase()];
+      if (!view) {
+        throw new IkanbanError(
+          "validation",
+          `Unknown view ${value}; use one of ${Object.keys(VIEW_ALIASES).join(", ")}.`,
+          { field: flag },
+        );
+      }
+      options.view = view;
+    } else {
+      options[key] = value;
+    }
+  }
+
+  if (options.runTask && !options.promptFile) {
+    throw new IkanbanError("validation", "--run-task needs --prompt-file with the follow-up prompt.", {
+      field: "--prompt-file",
+    });
+  }
+
+  if (options.runTask && options.task && options.runTask !== options.task) {
+    throw new IkanbanError("validation", "--task and --run-task name different tasks.", { field: "--task" });
+  }
+
+  return options;
+}
+
+function splitOnce(value: string, separator: string): [string, string] {
+  const index = value.indexOf(separator);
+  return [value.slice(0, index), value.slice(index + separator.length)];
+}
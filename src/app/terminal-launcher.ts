@@ -0,0 +1,40 @@
+export type TerminalLaunchResult = "opened" | "failed";
+
+// A terminal that fails to start (unknown command, no display) exits within this window.
+const LAUNCH_CHECK_MS = 300;
+
+/**
+ * Opens a terminal emulator in `directory`. `template` is a shell command run
+ * from that directory, with `{dir}` standing for its path, e.g.
+ * `kitty --directory {dir}`; without one, macOS opens Terminal.app and other
+ * platforms run `$TERMINAL` or `x-terminal-emulator`.
+ */
+export async function openTerminalIn(directory: string, template?: string): Promise<TerminalLaunchResult> {
+  const command = (template ?? defaultTerminalCommand()).replaceAll("{dir}", '"$1"');
+
+  try {
+    const child = Bun.spawn(["sh", "-c", command, "sh", directory], {
+      cwd: directory,
+      stdin: "ignore",
+      stdout: "ignore",
+      stderr: "ignore",
+    });
+    child.unref();
+
+    const exitCode = await Promise.race([
+      child.exited,
+      new Promise<undefined>((resolve) => setTimeout(resolve, LAUNCH_CHECK_MS)),
+    ]);
+    return exitCode === undefined || exitCode === 0 ? "opened" : "failed";
+  } catch {
+    return "failed";
+  }
+}
+
+function defaultTerminalCommand(): string {
+  if (process.platform === "darwin") {
+    return "open -a Terminal {dir}";
+  }
+
+  return '"${TERMINAL:-x-terminal-emulator}"';
+}
@@ -0,0 +1,89 @@
+import { describe, expect, test } from "bun:test";
+import { EventEmitter } from "node:events";
+import { render } from "ink";
+
+import { clampRows, computePanelRows, isTerminalTooSmall, MIN_TERMINAL_SIZE } from "./terminal-size";
+import { TerminalTooSmallView } from "./views/terminal-too-small-view";
+
+// Just enough of a TTY for Ink to lay a frame out at a fixed size.
+function createStdout(columns: number, rows: number): { stdout: NodeJS.WriteStream; output: () => string } {
+  const chunks: string[] = [];
+  const stdout = Object.assign(new EventEmitter(), {
+    columns,
+    rows,
+    isTTY: true,
+    write(chunk: string) {
+      chunks.push(chunk);
+      return true;
+    },
+  });
+
+  return {
+    stdout: stdout as unknown as NodeJS.WriteStream,
+    // Frames are wrapped to the width and colored; compare the plain words.
+    output: () =>
+      chunks
+        .join("\n")
+        .replace(/\u001b\[[0-9;?]*[A-Za-z]/g, "")
+        .replace(/\s+/g, " "),
+  };
+}
+
+describe("isTerminalTooSmall", () => {
+  test("accepts exactly the minimum size", () => {
+    expect(MIN_TERMINAL_SIZE).toEqual({ columns: 40, rows: 12 });
+    expect(isTerminalTooSmall({ columns: 40, rows: 12 })).toBe(false);
+    expect(isTerminalTooSmall({ columns: 120, rows: 40 })).toBe(false);
+  });
+
+  test("rejects a terminal one column or one row short", () => {
+    expect(isTerminalTooSmall({ columns: 39, rows: 12 })).toBe(true);
+    expect(isTerminalTooSmall({ columns: 40, rows: 11 })).toBe(true);
+    expect(isTerminalTooSmall({ columns: 20, rows: 10 })).toBe(true);
+  });
+});
+
+describe("clampRows", () => {
+  test("prefers the preferred count, raised to the minimum, within what is available", () => {
+    expect(clampRows(10, 6, 20)).toBe(10);
+    expect(clampRows(3, 6, 20)).toBe(6);
+    expect(clampRows(30, 6, 20)).toBe(20);
+  });
+
+  test("never returns less than one row", () => {
+    expect(clampRows(-10, 8, -4)).toBe(1);
+    expect(clampRows(0, 0, 0)).toBe(1);
+  });
+});
+
+describe("computePanelRows", () => {
+  test("fits every panel inside a 20x10 frame", () => {
+    for (const isLogViewOpen of [false, true]) {
+      const rows = computePanelRows(10, isLogViewOpen);
+      expect(rows.log).toBe(6);
+      expect(rows.reviewDiff).toBe(4);
+      expect(rows.diff).toBe(4);
+    }
+  });
+
+  test("keeps one row per panel at a degenerate 0-row height", () => {
+    expect(computePanelRows(0, false)).toEqual({ log: 1, reviewDiff: 1, diff: 1 });
+    expect(computePanelRows(0, true)).toEqual({ log: 1, reviewDiff: 1, diff: 1 });
+  });
+
+  test("gives the panels their preferred size at the minimum terminal", () => {
+    expect(computePanelRows(MIN_TERMINAL_SIZE.rows, false)).toEqual({ log: 6, reviewDiff: 6, diff: 6 });
+  });
+});
+
+describe("TerminalTooSmallView", () => {
+  test("explains the minimum size in a 20x10 terminal", () => {
+    const size = { columns: 20, rows: 10 };
+    const { stdout, output } = createStdout(size.columns, size.rows);
+    const instance = render(<TerminalTooSmallView size={size} />, { stdout, debug: true, patchConsole: false });
+    instance.unmount();
+
+    expect(isTerminalTooSmall(size)).toBe(true);
+    expect(output()).toContain("Terminal too small (20x10). Resize to at least 40x12, or press q to quit.");
+  });
+});
@@ -0,0 +1,63 @@
+import { useEffect, useState } from "react";
+import { useStdout } from "ink";
+
+export type TerminalSize = {
+  columns: number;
+  rows: number;
+};
+
+// Below this the board, details and modal panels cannot lay out without overlapping.
+export const MIN_TERMINAL_SIZE: TerminalSize = { columns: 40, rows: 12 };
+
+/**
+ * Current terminal size, re-read on every `resize` so layout derived from it
+ * follows the window instead of keeping the size from the last state change.
+ */
+export function useTerminalSize(): TerminalSize {
+  const { stdout } = useStdout();
+  const [size, setSize] = useState<TerminalSize>(() => readTerminalSize(stdout));
+
+  useEffect(() => {
+    const handleResize = () => {
+      setSize(readTerminalSize(stdout));
+    };
+
+    stdout.on("resize", handleResize);
+    return () => {
+      stdout.off("resize", handleResize);
+    };
+  }, [stdout]);
+
+  return size;
+}
+
+export function isTerminalTooSmall(size: TerminalSize): boolean {
+  return size.columns < MIN_TERMINAL_SIZE.columns || size.rows < MIN_TERMINAL_SIZE.rows;
+}
+
+export type PanelRows = {
+  log: number;
+  reviewDiff: number;
+  diff: number;
+};
+
+/** Rows each scrolling panel gets in a frame `rows` tall; never less than one. */
+export function computePanelRows(rows: number, isLogViewOpen: boolean): PanelRows {
+  return {
+    log: clampRows(isLogViewOpen ? rows - 8 : Math.floor(rows / 3), 6, rows - 4),
+    reviewDiff: clampRows(rows - 20, 8, rows - 6),
+    diff: clampRows(rows - 10, 6, rows - 6),
+  };
+}
+
+/** Keeps a panel's preferred row count inside what the frame can actually show. */
+export function clampRows(preferred: number, minimum: number, available: number): number {
+  return Math.max(1, Math.min(Math.max(preferred, minimum), available));
+}
+
+function readTerminalSize(stdout: NodeJS.WriteStream): TerminalSize {
+  return {
+    columns: Math.max(stdout.columns ?? 80, 1),
+    rows: Math.max(stdout.rows ?? 24, 1),
+  };
+}
@@ -0,0 +1,83 @@
+import type { TaskRuntime } from "../domain/task";
+import type { AppRoute } from "./routes";
+
+/** The app state each tutorial step is checked against. */
+export type TutorialSnapshot = {
+  route: AppRoute;
+  projectCount: number;
+  activeProjectId?: string;
+  tasks: readonly TaskRuntime[];
+  selectedTaskId?: string;
+  /** Task whose review panel is open. */
+  reviewTaskId?: string;
+};
+
+export type TutorialStep = {
+  title: string;
+  instructions: string;
+  /** Keys to highlight, in the order they are pressed. */
+  keys: string[];
+  /** `startedAt` lets steps ignore tasks that existed before the tutorial began. */
+  isDone: (snapshot: TutorialSnapshot, startedAt: number) => boolean;
+};
+
+export const TUTORIAL_STEPS: TutorialStep[] = [
+  {
+    title: "Add a project",
+    instructions: "On the project selector (Tab switches views), press n, type the path of a git repository and press Enter.",
+    keys: ["n", "Enter"],
+    isDone: (snapshot) => snapshot.projectCount > 0,
+  },
+  {
+    title: "Open its task board",
+    instructions: "Select the project with j/k and press Enter to open its board.",
+    keys: ["j", "k", "Enter"],
+    isDone: (snapshot) => snapshot.route === "task-board" && snapshot.activeProjectId !== undefined,
+  },
+  {
+    title: "Add a task",
+    instructions: "Press n, describe a small change for the agent and press Enter. The task is queued in its own worktree.",
+    keys: ["n", "Enter"],
+    isDone: (snapshot, startedAt) => findTutorialTask(snapshot, startedAt) !== undefined,
+  },
+  {
+    title: "Move to your task",
+    instructions: "Move the selection onto the new card with j/k; the details panel follows it.",
+    keys: ["j", "k"],
+    isDone: (snapshot, startedAt) =>
+      snapshot.selectedTaskId !== undefined && snapshot.selectedTaskId === findTutorialTask(snapshot, startedAt)?.taskId,
+  },
+  {
+    title: "Watch the session start",
+    instructions: "The card moves to Running with ▶ once its agent session starts; press l to follow the logs.",
+    keys: ["l"],
+    isDone: (snapshot, startedAt) => findTutorialTask(snapshot, startedAt)?.sessionID !== undefined,
+  },
+  {
+    title: "Review the result",
+    instructions: "When the card reaches Review (⏳), press r to open the review panel; a approves and m merges.",
+    keys: ["r", "a", "m"],
+    isDone: (snapshot, startedAt) =>
+      snapshot.reviewTaskId !== undefined && snapshot.reviewTaskId === findTutorialTask(snapshot, startedAt)?.taskId,
+  },
+];
+
+/**
+ * The first step from `stepIndex` on that is not done yet; steps only count
+ * in order, so a later step done early waits for the ones before it.
+ */
+export function advanceTutorial(stepIndex: number, snapshot: TutorialSnapshot, startedAt: number): number {
+  let next = stepIndex;
+  while (next < TUTORIAL_STEPS.length && TUTORIAL_STEPS[next]!.isDone(snapshot, startedAt)) {
+    next += 1;
+  }
+
+  return next;
+}
+
+/** The newest task added to the active project since the tutorial started. */
+function findTutorialTask(snapshot: TutorialSnapshot, startedAt: number): TaskRuntime | undefined {
+  return snapshot.tasks
+    .filter((task) => task.projectId === snapshot.activeProjectId && task.createdAt >= startedAt)
+    .sort((left, right) => right.createdAt - left.createdAt)[0];
+}
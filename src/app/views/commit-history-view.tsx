@@ -0,0 +1,37 @@
+import { Box, Text } from "ink";
+
+import type { TaskCommit } from "../../runtime/worktree-manager";
+
+type CommitHistoryViewProps = {
+  taskLabel: string;
+  commits: TaskCommit[];
+  selectedIndex: number;
+};
+
+export function CommitHistoryView({ taskLabel, commits, selectedIndex }: CommitHistoryViewProps) {
+  return (
+    <Box flexDirection="column">
+      <Text color="cyan">
+        Commits {taskLabel} ({commits.length})
+      </Text>
+      {commits.length === 0 ? (
+        <Text color="yellow">No commits on this branch yet; uncommitted changes show in the diff view (v).</Text>
+      ) : null}
+      {commits.map((commit, index) => {
+        const isSelected = index === selectedIndex;
+        return (
+          <Box key={commit.hash} flexDirection="column">
+            <Text color={isSelected ? "green" : undefined} wrap="truncate-end">
+              {isSelected ? ">" : " "} {commit.hash.slice(0, 7)} {commit.subject}
+            </Text>
+            <Text color="gray" wrap="truncate-end">
+              {"    "}
+              {commit.author} · {new Date(commit.committedAt).toLocaleString()} · {commit.filesChanged} file
+              {commit.filesChanged === 1 ? "" : "s"} +{commit.additions} -{commit.deletions}
+            </Text>
+          </Box>
+        );
+      })}
+    </Box>
+  );
+}
@@ -0,0 +1,78 @@
+import { Box, Text } from "ink";
+
+import { sumDiffFiles, type DiffComparison, type DiffFile } from "../../domain/diff";
+
+type CompareViewProps = {
+  leftLabel: string;
+  rightLabel: string;
+  comparison: DiffComparison;
+  scrollOffset: number;
+  visibleRows: number;
+};
+
+type CompareRow = {
+  text: string;
+  color?: "cyan" | "gray" | "green" | "yellow" | "red";
+};
+
+export function CompareView({ leftLabel, rightLabel, comparison, scrollOffset, visibleRows }: CompareViewProps) {
+  const rows = buildCompareRows(comparison);
+  const visible = rows.slice(scrollOffset, scrollOffset + visibleRows);
+
+  return (
+    <Box flexDirection="column">
+      <Text color="cyan">
+        Compare A: {leftLabel} | B: {rightLabel}
+      </Text>
+      {visible.map((row, index) => (
+        <Text key={`${scrollOffset + index}`} color={row.color} wrap="truncate-end">
+          {row.text || " "}
+        </Text>
+      ))}
+      {rows.length > scrollOffset + visibleRows ? (
+        <Text color="gray">({rows.length - scrollOffset - visibleRows} more lines)</Text>
+      ) : null}
+    </Box>
+  );
+}
+
+export function buildCompareRows(comparison: DiffComparison): CompareRow[] {
+  const overlapping = comparison.shared.filter((file) => file.overlappingHunks > 0 && !file.identical).length;
+  const rows: CompareRow[] = [
+    {
+      text: `A ${formatChangeCounts(comparison.onlyLeft.concat(comparison.shared.map((file) => file.left)))} | B ${formatChangeCounts(comparison.onlyRight.concat(comparison.shared.map((file) => file.right)))} | ${overlapping} file${overlapping === 1 ? "" : "s"} with overlapping hunks`,
+      color: "gray",
+    },
+    { text: "" },
+    { text: `Both touched (${comparison.shared.length})`, color: "yellow" },
+  ];
+
+  for (const file of comparison.shared) {
+    const note = file.identical
+      ? "same change"
+      : file.overlappingHunks > 0
+        ? `⚠ ${file.overlappingHunks} overlapping hunk pair${file.overlappingHunks === 1 ? "" : "s"}`
+        : "separate hunks";
+    rows.push({
+      text: `  ${file.path}  A +${file.left.additions} -${file.left.deletions} | B +${file.right.additions} -${file.right.deletions} | ${note}`,
+      color: file.identical ? "green" : file.overlappingHunks > 0 ? "red" : undefined,
+    });
+  }
+
+  for (const [label, files] of [
+    ["Only A", comparison.onlyLeft],
+    ["Only B", comparison.onlyRight],
+  ] as const) {
+    rows.push({ text: "" }, { text: `${label} (${files.length})`, color: "yellow" });
+    for (const file of files) {
+      rows.push({ text: `  ${file.path}  ${file.status} +${file.additions} -${file.deletions}` });
+    }
+  }
+
+  return rows;
+}
+
+function formatChangeCounts(files: DiffFile[]): string {
+  const totals = sumDiffFiles(files);
+  return `${totals.files} file${totals.files === 1 ? "" : "s"} +${totals.additions} -${totals.deletions}`;
+}
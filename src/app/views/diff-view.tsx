@@ -0,0 +1,97 @@
+import { Box, Text } from "ink";
+
+import { formatDiffTotals, sumDiffFiles, type DiffFile } from "../../domain/diff";
+
+type DiffViewProps = {
+  taskLabel: string;
+  files: DiffFile[];
+  fileIndex: number;
+  foldedHunks: ReadonlySet<number>;
+  scrollOffset: number;
+  visibleRows: number;
+  /** Reloaded on worktree changes while the agent is still running. */
+  live: boolean;
+};
+
+export type DiffRow = {
+  kind: "hunk" | "folded" | "added" | "removed" | "context";
+  text: string;
+  hunkIndex: number;
+};
+
+export function DiffView({
+  taskLabel,
+  files,
+  fileIndex,
+  foldedHunks,
+  scrollOffset,
+  visibleRows,
+  live,
+}: DiffViewProps) {
+  const file = files[fileIndex];
+  if (!file) {
+    return (
+      <Box flexDirection="column">
+        <Text color="cyan">Diff {taskLabel}</Text>
+        <Text color="yellow">No changes against the base branch.</Text>
+      </Box>
+    );
+  }
+
+  const rows = buildDiffRows(file, foldedHunks);
+  const visible = rows.slice(scrollOffset, scrollOffset + visibleRows);
+
+  return (
+    <Box flexDirection="column">
+      <Text color="cyan">
+        Diff {taskLabel} | {formatDiffTotals(sumDiffFiles(files))}
+        {live ? " | live" : ""}
+      </Text>
+      <Text color="magentaBright">
+        File {fileIndex + 1}/{files.length}: {file.oldPath ? `${file.oldPath} -> ` : ""}
+        {file.path} ({file.status}, +{file.additions} -{file.deletions})
+      </Text>
+      {file.binary ? <Text color="gray">Binary file; no text diff.</Text> : null}
+      {visible.map((row, index) => (
+        <Text key={`${scrollOffset + index}`} color={DIFF_ROW_COLORS[row.kind]} wrap="truncate-end">
+          {row.text || " "}
+        </Text>
+      ))}
+      {rows.length > scrollOffset + visibleRows ? (
+        <Text color="gray">
+          ({rows.length - scrollOffset - visibleRows} more lines)
+        </Text>
+      ) : null}
+    </Box>
+  );
+}
+
+const DIFF_ROW_COLORS: Record<DiffRow["kind"], "cyan" | "gray" | "green" | "red" | undefined> = {
+  hunk: "cyan",
+  folded: "gray",
+  added: "green",
+  removed: "red",
+  context: undefined,
+};
+
+/** The current file's lines as shown: hunk headers, then either the hunk's lines or one folded marker. */
+export function buildDiffRows(file: DiffFile, foldedHunks: ReadonlySet<number>): DiffRow[] {
+  const rows: DiffRow[] = [];
+  file.hunks.forEach((hunk, hunkIndex) => {
+    const folded = foldedHunks.has(hunkIndex);
+    rows.push({ kind: "hunk", text: `${folded ? "▸" : "▾"} ${hunk.header}`, hunkIndex });
+    if (folded) {
+      rows.push({ kind: "folded", text: `  … ${hunk.lines.length} lines folded`, hunkIndex });
+      return;
+    }
+
+    for (const line of hunk.lines) {
+      rows.push({
+        kind: line.startsWith("+") ? "added" : line.startsWith("-") ? "removed" : "context",
+        text: line,
+        hunkIndex,
+      });
+    }
+  });
+  return rows;
+}
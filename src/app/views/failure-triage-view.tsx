@@ -0,0 +1,54 @@
+import { Box, Text } from "ink";
+
+import {
+  TASK_FAILURE_CLASS_LABELS,
+  type TaskFailureGroup,
+} from "../../domain/task-failure";
+import { formatTaskCardLabel } from "./task-board-view";
+
+type FailureTriageViewProps = {
+  groups: TaskFailureGroup[];
+  selectedIndex: number;
+  projectNames: Record<string, string>;
+};
+
+export function FailureTriageView({ groups, selectedIndex, projectNames }: FailureTriageViewProps) {
+  if (groups.length === 0) {
+    return <Text color="green">No failed tasks.</Text>;
+  }
+
+  let rowIndex = 0;
+
+  return (
+    <Box flexDirection="column">
+      {groups.map((group) => (
+        <Box
+          key={`${group.failureClass}:${group.projectId}`}
+          flexDirection="column"
+          marginBottom={1}
+        >
+          <Text color="red">
+            {TASK_FAILURE_CLASS_LABELS[group.failureClass]} · {projectNames[group.projectId] ?? group.projectId} (
+            {group.tasks.length})
+          </Text>
+          {group.tasks.map((task) => {
+            const isSelected = rowIndex === selectedIndex;
+            rowIndex += 1;
+            return (
+              <Box key={task.taskId} flexDirection="column">
+                <Text color={isSelected ? "green" : undefined}>
+                  {isSelected ? ">" : " "} {formatTaskCardLabel(task)}
+                </Text>
+                <Text color="gray" wrap="truncate-end">
+                  {"    "}
+                  {task.errorCode ? `[${task.errorCode}] ` : ""}
+                  {task.error ?? "Task failed."}
+                </Text>
+              </Box>
+            );
+          })}
+        </Box>
+      ))}
+    </Box>
+  );
+}
@@ -0,0 +1,168 @@
+import { Box, Text } from "ink";
+import { useMemo } from "react";
+
+import type { RuntimeLogEntry } from "../../runtime/event-bus";
+import { compactJson, getRawContext, type LogViewLevel } from "./log-view";
+
+type LogReaderViewProps = {
+  title: string;
+  entries: RuntimeLogEntry[];
+  level: LogViewLevel;
+  /** Entries scrolled back from the latest, as in the log panel. */
+  scrollOffset: number;
+  width: number;
+  height: number;
+  wrap: boolean;
+};
+
+type LogReaderRow = {
+  text: string;
+  entryIndex: number;
+  level: RuntimeLogEntry["level"];
+};
+
+const MINIMAP_WIDTH = 2;
+
+/** Full-screen transcript reader: entries reflowed to the terminal width with a minimap scrollbar. */
+export function LogReaderView({ title, entries, level, scrollOffset, width, height, wrap }: LogReaderViewProps) {
+  const filteredEntries = useMemo(
+    () => (level === "debug" ? entries : entries.filter((entry) => entry.level !== "debug")),
+    [entries, level],
+  );
+  const textWidth = Math.max(10, width - MINIMAP_WIDTH);
+  const rows = useMemo(
+    () => buildLogReaderRows(filteredEntries, level, textWidth, wrap),
+    [filteredEntries, level, textWidth, wrap],
+  );
+  const bodyHeight = Math.max(1, height - 2);
+
+  // The newest entry still in view sits at the bottom of the screen.
+  const lastEntryIndex = filteredEntries.length - 1 - Math.min(scrollOffset, Math.max(filteredEntries.length - 1, 0));
+  const end = rows.findLastIndex((row) => row.entryIndex <= lastEntryIndex) + 1;
+  const start = Math.max(0, end - bodyHeight);
+  const visible = rows.slice(start, end);
+  const minimap = buildMinimap(rows, start, end, bodyHeight);
+
+  return (
+    <Box flexDirection="column" width={width} height={height}>
+      <Text color="cyan" wrap="truncate-end">
+        Reading {title} ({level}) {filteredEntries.length} entries
+        {rows.length > 0 ? `, lines ${start + 1}-${end} of ${rows.length}` : ""} | wrap {wrap ? "on" : "off"}
+      </Text>
+      <Box flexDirection="row" height={bodyHeight}>
+        <Box flexDirection="column" width={textWidth}>
+          {visible.length > 0 ? (
+            visible.map((row, index) => (
+              <Text key={`${start + index}`} color={rowColor(row.level)} wrap="truncate-end">
+                {row.text || " "}
+              </Text>
+            ))
+          ) : (
+            <Text color="yellow">No log entries yet.</Text>
+          )}
+        </Box>
+        <Box flexDirection="column" width={MINIMAP_WIDTH} paddingLeft={1}>
+          {minimap.map((cell, index) => (
+            <Text key={index} color={cell.color}>
+              {cell.char}
+            </Text>
+          ))}
+        </Box>
+      </Box>
+      <Text color="gray" wrap="truncate-end">
+        Keys: j/k entry | u/d page | g/G ends | w wrap | v level:{level} | z or Esc leave reading mode
+      </Text>
+    </Box>
+  );
+}
+
+/**
+ * One row per screen line. Wrapped entries are reflowed on word boundaries
+ * and continuation rows are indented past the `time [level]` prefix; with
+ * wrapping off each source line is one row, cut at the screen edge.
+ */
+export function buildLogReaderRows(
+  entries: RuntimeLogEntry[],
+  level: LogViewLevel,
+  width: number,
+  wrap: boolean,
+): LogReaderRow[] {
+  const rows: LogReaderRow[] = [];
+  entries.forEach((entry, entryIndex) => {
+    const prefix = `${new Date(entry.emittedAt).toTimeString().slice(0, 8)} [${entry.level}] `;
+    const indent = " ".repeat(prefix.length);
+    const lines = entry.message.split("\n");
+    const rawContext = level === "debug" ? getRawContext(entry.raw) : undefined;
+    if (rawContext && (typeof rawContext !== "object" || Object.keys(rawContext).length > 0)) {
+      lines.push(compactJson(rawContext));
+    }
+
+    lines.forEach((line, lineIndex) => {
+      const segments = wrap ? reflowLine(line, Math.max(1, width - indent.length)) : [line];
+      segments.forEach((segment, segmentIndex) => {
+        rows.push({
+          text: `${lineIndex === 0 && segmentIndex === 0 ? prefix : indent}${segment}`,
+          entryIndex,
+          level: entry.level,
+        });
+      });
+    });
+  });
+
+  return rows;
+}
+
+/** Splits `line` into rows of at most `width` characters, breaking between words where it can. */
+export function reflowLine(line: string, width: number): string[] {
+  const segments: string[] = [];
+  let current = "";
+  for (const word of line.split(/\s+/).filter((candidate) => candidate.length > 0)) {
+    const candidate = current ? `${current} ${word}` : word;
+    if (candidate.length <= width) {
+      current = candidate;
+      continue;
+    }
+
+    if (current) {
+      segments.push(current);
+    }
+
+    let remaining = word;
+    while (remaining.length > width) {
+      segments.push(remaining.slice(0, width));
+      remaining = remaining.slice(width);
+    }
+    current = remaining;
+  }
+
+  segments.push(current);
+  return segments;
+}
+
+// Each cell stands for a slice of the transcript: the thumb marks what is on
+// screen, and slices holding errors or warnings are colored so they can be found.
+function buildMinimap(
+  rows: LogReaderRow[],
+  start: number,
+  end: number,
+  height: number,
+): { char: string; color?: "red" | "yellow" | "cyan" | "gray" }[] {
+  return Array.from({ length: height }, (_, cell) => {
+    const from = Math.floor((cell * rows.length) / height);
+    const to = Math.max(from + 1, Math.floor(((cell + 1) * rows.length) / height));
+    const slice = rows.slice(from, to);
+    const inView = rows.length > 0 && from < end && to > start;
+    const color = slice.some((row) => row.level === "error")
+      ? "red"
+      : slice.some((row) => row.level === "warn")
+        ? "yellow"
+        : inView
+          ? "cyan"
+          : "gray";
+    return { char: inView ? "█" : "│", color };
+  });
+}
+
+function rowColor(level: RuntimeLogEntry["level"]): "red" | "yellow" | "gray" | undefined {
+  return level === "error" ? "red" : level === "warn" ? "yellow" : level === "debug" ? "gray" : undefined;
+}
@@ -22,7 +22,7 @@ function safeJson(value: unknown): string {
 }
 
 // Compact single-line JSON for VirtualList
-function compactJson(value: unknown): string {
+export function compactJson(value: unknown): string {
   try {
     return JSON.stringify(value);
   } catch {
@@ -36,7 +36,7 @@ interface LogRowProps {
 }
 
 // Extract context from raw if it exists
-function getRawContext(raw: unknown): unknown {
+export function getRawContext(raw: unknown): unknown {
   if (raw && typeof raw === "object") {
     const obj = raw as Record<string, unknown>;
     if ("context" in obj) {
@@ -0,0 +1,89 @@
+import { Box, Text } from "ink";
+
+import {
+  formatSuccessRate,
+  type ActivityHeatmap,
+  type MetricsRollup,
+  type MetricsRollupRow,
+} from "../../domain/metrics";
+
+const HEATMAP_WEEKDAYS = ["Mon", "", "Wed", "", "Fri", "", "Sun"];
+const HEATMAP_SHADES = ["░", "▒", "▓", "█"];
+
+type MetricsViewProps = {
+  rollup: MetricsRollup;
+  rows: MetricsRollupRow[];
+  from?: string;
+  to?: string;
+};
+
+export function MetricsView({ rollup, rows, from, to }: MetricsViewProps) {
+  return (
+    <Box flexDirection="column">
+      <Text color="gray">
+        By {rollup}
+        {from || to ? ` | ${from ?? "start"} .. ${to ?? "today"}` : ""}
+      </Text>
+      {rows.length === 0 ? (
+        <Text color="yellow">No snapshots yet; one is recorded per project each day.</Text>
+      ) : (
+        <Box marginTop={1} flexDirection="column">
+          <Text color="cyan">{formatMetricsRow("Period", "Open", "Done", "Failed", "Success")}</Text>
+          {rows.map((row) => (
+            <Text key={row.period}>
+              {formatMetricsRow(
+                row.period,
+                String(row.openTasks),
+                String(row.completedTasks),
+                String(row.failedTasks),
+                formatSuccessRate(row.successRate),
+              )}
+            </Text>
+          ))}
+        </Box>
+      )}
+    </Box>
+  );
+}
+
+export function ActivityHeatmapView({ heatmap }: { heatmap: ActivityHeatmap }) {
+  return (
+    <Box flexDirection="column">
+      <Text color="gray">
+        Finished tasks per day | {heatmap.from} .. {heatmap.to} | {heatmap.total} total, busiest day {heatmap.max}
+      </Text>
+      <Box marginTop={1} flexDirection="column">
+        {heatmap.cells.map((row, weekday) => (
+          <Text key={weekday}>
+            <Text color="gray">{(HEATMAP_WEEKDAYS[weekday] ?? "").padEnd(4)}</Text>
+            {row.map((count, week) => (
+              <Text key={week} color={count ? "green" : "gray"}>
+                {formatHeatmapCell(count, heatmap.max)}{" "}
+              </Text>
+            ))}
+          </Text>
+        ))}
+      </Box>
+      <Text color="gray">
+        Less · {HEATMAP_SHADES.join(" ")} More
+      </Text>
+    </Box>
+  );
+}
+
+// Each shade is a quarter of the busiest day in the window.
+function formatHeatmapCell(count: number | undefined, max: number): string {
+  if (count === undefined) {
+    return " ";
+  }
+
+  if (count === 0 || max === 0) {
+    return "·";
+  }
+
+  return HEATMAP_SHADES[Math.min(HEATMAP_SHADES.length, Math.ceil((count / max) * HEATMAP_SHADES.length)) - 1] ?? "█";
+}
+
+function formatMetricsRow(period: string, open: string, done: string, failed: string, success: string): string {
+  return `${period.padEnd(14)} ${open.padStart(5)} ${done.padStart(5)} ${failed.padStart(7)} ${success.padStart(8)}`;
+}
@@ -0,0 +1,111 @@
+import { Box, Text } from "ink";
+
+import type { ProjectPage } from "../../domain/page";
+
+type PagesViewProps = {
+  pages: ProjectPage[];
+  selectedPageIndex: number;
+  maxBodyLines: number;
+};
+
+export function PagesView({ pages, selectedPageIndex, maxBodyLines }: PagesViewProps) {
+  if (pages.length === 0) {
+    return <Text color="yellow">No pages yet. Press n to add one.</Text>;
+  }
+
+  const selectedPage = pages[selectedPageIndex];
+  const bodyLines = (selectedPage?.body ?? "").split("\n");
+
+  return (
+    <Box flexDirection="row" columnGap={2}>
+      <Box flexDirection="column" width={28}>
+        {pages.map((page, index) => {
+          const isSelected = index === selectedPageIndex;
+          return (
+            <Text key={page.id} color={isSelected ? "green" : undefined} wrap="truncate-end">
+              {isSelected ? ">" : " "} {page.includeInPrompts ? "*" : " "} {page.title}
+            </Text>
+          );
+        })}
+      </Box>
+      <Box flexDirection="column" flexGrow={1}>
+        {selectedPage && selectedPage.body.trim().length > 0 ? (
+          <MarkdownLines lines={bodyLines.slice(0, maxBodyLines)} />
+        ) : (
+          <Text color="gray">(empty page - press e to edit)</Text>
+        )}
+        {bodyLines.length > maxBodyLines ? (
+          <Text color="gray">({bodyLines.length - maxBodyLines} more lines)</Text>
+        ) : null}
+      </Box>
+    </Box>
+  );
+}
+
+function MarkdownLines({ lines }: { lines: string[] }) {
+  let inCodeBlock = false;
+
+  return (
+    <>
+      {lines.map((line, index) => {
+        const key = `${index}-${line}`;
+        if (line.trimStart().startsWith("```")) {
+          inCodeBlock = !inCodeBlock;
+          return <Text key={key} color="gray">{line}</Text>;
+        }
+
+        if (inCodeBlock) {
+          return <Text key={key} color="yellow">{line || " "}</Text>;
+        }
+
+        const heading = /^(#{1,6})\s+(.*)$/.exec(line);
+        if (heading) {
+          return (
+            <Text key={key} bold color={heading[1] === "#" ? "cyanBright" : "cyan"}>
+              {heading[2]}
+            </Text>
+          );
+        }
+
+        const bullet = /^(\s*)[-*+]\s+(.*)$/.exec(line);
+        if (bullet) {
+          return (
+            <Text key={key}>
+              {bullet[1]}• <InlineMarkdown text={bullet[2] ?? ""} />
+            </Text>
+          );
+        }
+
+        if (line.startsWith(">")) {
+          return <Text key={key} color="gray" italic>{line.replace(/^>\s?/, "│ ")}</Text>;
+        }
+
+        return (
+          <Text key={key}>
+            <InlineMarkdown text={line || " "} />
+          </Text>
+        );
+      })}
+    </>
+  );
+}
+
+function InlineMarkdown({ text }: { text: string }) {
+  const parts = text.split(/(\*\*[^*]+\*\*|`[^`]+`)/g).filter((part) => part.length > 0);
+
+  return (
+    <>
+      {parts.map((part, index) => {
+        if (part.startsWith("**") && part.endsWith("**")) {
+          return <Text key={index} bold>{part.slice(2, -2)}</Text>;
+        }
+
+        if (part.startsWith("`") && part.endsWith("`")) {
+          return <Text key={index} color="yellow">{part.slice(1, -1)}</Text>;
+        }
+
+        return <Text key={index}>{part}</Text>;
+      })}
+    </>
+  );
+}
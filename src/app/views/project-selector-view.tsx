@@ -5,20 +5,39 @@ import type { ProjectRef } from "../../domain/project";
 type ProjectSelectorViewProps = {
   projects: ProjectRef[];
   selectedProjectIndex: number;
+  instructionFiles?: Record<string, string | undefined>;
+  renameValue?: string;
 };
 
-export function ProjectSelectorView({ projects, selectedProjectIndex }: ProjectSelectorViewProps) {
+export function ProjectSelectorView({
+  projects,
+  selectedProjectIndex,
+  instructionFiles = {},
+  renameValue,
+}: ProjectSelectorViewProps) {
   if (projects.length === 0) {
     return <Text color="yellow">No projects registered.</Text>;
   }
 
   return (
     <>
-      {projects.map((project, index) => (
-        <Text key={project.id} color={index === selectedProjectIndex ? "green" : undefined}>
-          {index === selectedProjectIndex ? ">" : " "} {project.name} ({project.id})
-        </Text>
-      ))}
+      {projects.map((project, index) => {
+        const isSelected = index === selectedProjectIndex;
+        if (isSelected && renameValue !== undefined) {
+          return (
+            <Text key={project.id} color="cyan">
+              &gt; {renameValue}_ ({project.id})
+            </Text>
+          );
+        }
+
+        return (
+          <Text key={project.id} color={isSelected ? "green" : undefined}>
+            {isSelected ? ">" : " "} {project.name} ({project.id})
+            <Text color="gray"> {instructionFiles[project.id] ?? "no AGENTS.md"}</Text>
+          </Text>
+        );
+      })}
     </>
   );
 }
@@ -0,0 +1,84 @@
+import { Box, Text } from "ink";
+
+import { formatEstimatedStart, type QueueEstimate } from "../../domain/task-queue";
+import type { TaskRuntime } from "../../domain/task";
+import { formatTaskCardLabel } from "./task-board-view";
+
+type QueueViewProps = {
+  running: TaskRuntime[];
+  queued: QueueEstimate[];
+  maxConcurrent: number;
+  maxConcurrentPerProject?: number;
+  maxConcurrentByProjectId?: Record<string, number>;
+  selectedIndex: number;
+  projectNames: Record<string, string>;
+  offHoursTaskIds?: ReadonlySet<string>;
+  /** Open blockers of queued tasks that wait on other tasks. */
+  blockersByTaskId?: ReadonlyMap<string, TaskRuntime[]>;
+};
+
+export function QueueView({
+  running,
+  queued,
+  maxConcurrent,
+  maxConcurrentPerProject,
+  maxConcurrentByProjectId = {},
+  selectedIndex,
+  projectNames,
+  offHoursTaskIds = new Set(),
+  blockersByTaskId = new Map(),
+}: QueueViewProps) {
+  const now = Date.now();
+
+  return (
+    <Box flexDirection="column">
+      <Text color="cyan">
+        Running ({running.length}/{maxConcurrent}
+        {maxConcurrentPerProject !== undefined ? `, at most ${maxConcurrentPerProject} per project` : ""}
+        {Object.entries(maxConcurrentByProjectId)
+          .map(([projectId, limit]) => `, ${projectNames[projectId] ?? projectId} at most ${limit}`)
+          .join("")}
+        )
+      </Text>
+      {running.length > 0 ? (
+        running.map((task) => (
+          <Text key={task.taskId} color="gray">
+            {"  "}
+            {formatTaskCardLabel(task)} · {projectNames[task.projectId] ?? task.projectId} · {task.state}
+          </Text>
+        ))
+      ) : (
+        <Text color="gray">  (none)</Text>
+      )}
+      <Box marginTop={1} flexDirection="column">
+        <Text color="yellow">Queued ({queued.length})</Text>
+        {queued.length > 0 ? (
+          queued.map((entry, index) => {
+            const isSelected = index === selectedIndex;
+            const blockers = blockersByTaskId.get(entry.task.taskId);
+            return (
+              <Text key={entry.task.taskId} color={isSelected ? "green" : undefined}>
+                {isSelected ? ">" : " "} #{entry.position} {formatTaskCardLabel(entry.task)} ·{" "}
+                {projectNames[entry.task.projectId] ?? entry.task.projectId} ·{" "}
+                {blockers
+                  ? formatBlockers(blockers)
+                  : offHoursTaskIds.has(entry.task.taskId)
+                    ? "waiting for working hours"
+                    : formatEstimatedStart(entry.estimatedStartAt, now)}
+              </Text>
+            );
+          })
+        ) : (
+          <Text color="gray">  Queue is empty.</Text>
+        )}
+      </Box>
+    </Box>
+  );
+}
+
+// A failed blocker needs a retry or a cancel before the task can start, so its state is spelled out.
+function formatBlockers(blockers: TaskRuntime[]): string {
+  return `blocked by ${blockers
+    .map((blocker) => `${blocker.state === "failed" ? "failed " : ""}${blocker.shortCode ?? blocker.taskId}`)
+    .join(", ")}`;
+}
@@ -1,46 +1,213 @@
 import { Box, Text } from "ink";
 
-import type { TaskRuntime, TaskState } from "../../domain/task";
+import { DEFAULT_BOARD_COLUMNS, findBoardColumn, isOverWipLimit, type BoardColumn } from "../../domain/board-column";
+import { computeVelocity, formatPoints, sumEstimates } from "../../domain/capacity";
+import type { Label } from "../../domain/label";
+import { STATUS_GLYPHS, statusColor, type Palette } from "../../domain/palette";
+import { formatSubtaskSummary, getSubtaskDepth, type SubtaskSummary } from "../../domain/subtasks";
+import { DEFAULT_CARD_FIELDS, type CardField } from "../../domain/task-card";
+import { estimateTaskProgress, formatProgressBar } from "../../domain/task-progress";
+import type { TaskPriority, TaskRuntime, TaskState } from "../../domain/task";
 
 type TaskBoardViewProps = {
   tasks: TaskRuntime[];
   selectedTaskIndex: number;
   pendingTaskModelLabel: string;
+  pendingTaskPathScope?: string;
+  /** Label of the task new tasks will wait on, set with `:after`. */
+  pendingTaskBlockerLabel?: string;
+  /** Queued tasks still waiting on an unfinished blocker. */
+  blockedTaskIds?: ReadonlySet<string>;
+  /** Queued tasks held back until their project's working hours. */
+  offHoursTaskIds?: ReadonlySet<string>;
+  /** Roll-up of each parent task's subtasks. */
+  subtaskSummaries?: ReadonlyMap<string, SubtaskSummary>;
+  /** Parents whose subtasks are folded away. */
+  collapsedTaskIds?: ReadonlySet<string>;
+  /** The project's labels, for the colored chips on cards. */
+  labelsById?: ReadonlyMap<string, Label>;
+  /** The color-blind palette also marks every card with its status glyph. */
+  palette?: Palette;
+  renameValue?: string;
+  focusMode?: boolean;
+  hiddenCancelledCount?: number;
+  /** Column ids folded to a narrow count. */
+  collapsedColumns?: ReadonlySet<string>;
+  /** The project's columns, from `resolveBoardColumns`. */
+  columns?: readonly BoardColumn[];
+  /** Mean finished run time, for the progress bar of running tasks without a plan. */
+  averageRunMs?: number;
+  /** Width the board may use; columns past it are panned out of view. */
+  width?: number;
+  /** Which optional card parts to show; badges follow the title in this order. */
+  cardFields?: readonly CardField[];
 };
 
-export function TaskBoardView({ tasks, selectedTaskIndex, pendingTaskModelLabel }: TaskBoardViewProps) {
+const COLUMN_WIDTH = 20;
+const COLLAPSED_COLUMN_WIDTH = 4;
+const COLUMN_GAP = 2;
+
+export function TaskBoardView({
+  tasks,
+  selectedTaskIndex,
+  pendingTaskModelLabel,
+  pendingTaskPathScope,
+  pendingTaskBlockerLabel,
+  blockedTaskIds = new Set(),
+  offHoursTaskIds = new Set(),
+  subtaskSummaries = new Map(),
+  collapsedTaskIds = new Set(),
+  labelsById = new Map(),
+  palette = "default",
+  renameValue,
+  focusMode = false,
+  hiddenCancelledCount = 0,
+  collapsedColumns = new Set(),
+  columns = DEFAULT_BOARD_COLUMNS,
+  averageRunMs,
+  width,
+  cardFields = DEFAULT_CARD_FIELDS,
+}: TaskBoardViewProps) {
   if (tasks.length === 0) {
     return (
       <Box flexDirection="column">
-        <Text color="gray">New task model: {pendingTaskModelLabel}</Text>
+        <Text color="gray">
+          New task model: {pendingTaskModelLabel}
+          {pendingTaskPathScope ? ` | scope ${pendingTaskPathScope}/` : ""}
+          {pendingTaskBlockerLabel ? ` | after ${pendingTaskBlockerLabel}` : ""}
+        </Text>
         <Text color="yellow">No tasks for active project.</Text>
+        {hiddenCancelledCount > 0 ? (
+          <Text color="gray">Cancelled: {hiddenCancelledCount} hidden (C to show)</Text>
+        ) : null}
       </Box>
     );
   }
 
-  const selectedTaskId = tasks[selectedTaskIndex]?.taskId;
-  const groupedTasks = groupTasksByColumn(tasks);
+  const cardContext = (task: TaskRuntime): TaskCardContext => ({
+    blocked: blockedTaskIds.has(task.taskId),
+    offHours: offHoursTaskIds.has(task.taskId),
+    subtasks: subtaskSummaries.get(task.taskId),
+    collapsed: collapsedTaskIds.has(task.taskId),
+    allStatusGlyphs: palette === "color-blind",
+  });
+  const priorityMarker = (task: TaskRuntime) => {
+    const marker = task.priority && cardFields.includes("priority") ? PRIORITY_MARKERS[task.priority] : undefined;
+    return marker ? <Text color={marker.color}> {marker.glyph}</Text> : null;
+  };
+  const labelChips = (task: TaskRuntime) =>
+    cardFields.includes("labels")
+      ? (task.labelIds ?? []).map((labelId) => {
+          const label = labelsById.get(labelId);
+          return label ? (
+            <Text key={labelId} color={label.color}>
+              {" "}
+              #{label.name}
+            </Text>
+          ) : null;
+        })
+      : null;
+  const selectedTask = tasks[selectedTaskIndex];
+  const selectedTaskId = selectedTask?.taskId;
+  const groupedTasks = groupTasksByColumn(tasks, columns);
+  const selectedColumn = selectedTask ? findBoardColumn(columns, selectedTask.state) : undefined;
+  const focusedColumn = focusMode ? selectedColumn : undefined;
+
+  if (focusedColumn) {
+    const columnTasks = groupedTasks.get(focusedColumn.id) ?? [];
+    return (
+      <Box flexDirection="column">
+        <Text color={isOverWipLimit(focusedColumn, columnTasks) ? "red" : columnColor(focusedColumn, palette)}>
+          Focus: {focusedColumn.name} ({formatColumnTotals(columnTasks, focusedColumn.wipLimit)})
+        </Text>
+        {columnTasks.map((task) => {
+          const isSelected = task.taskId === selectedTaskId;
+          return (
+            <Box key={task.taskId} flexDirection="column" marginTop={1}>
+              <Text color={isSelected ? "green" : statusColor(task.state, palette)}>
+                {isSelected ? ">" : " "} {isSelected && renameValue !== undefined ? `${renameValue}_` : formatTaskCard(task, cardFields, cardContext(task))}
+                {isSelected && renameValue !== undefined ? null : priorityMarker(task)}
+                {isSelected && renameValue !== undefined ? null : labelChips(task)}
+              </Text>
+              <Text color="gray">  {task.prompt ?? "(no description)"}</Text>
+            </Box>
+          );
+        })}
+      </Box>
+    );
+  }
+
+  // Cancelled is a collapsible section: it only takes space while it has visible tasks.
+  const boardColumns = columns.filter(
+    (column) =>
+      column.states.some((state) => state !== "cancelled") || (groupedTasks.get(column.id)?.length ?? 0) > 0,
+  );
+  const visibleColumns = panColumns(boardColumns, selectedColumn, collapsedColumns, width);
 
   return (
     <Box flexDirection="column">
       <Text color="gray">Press r to open review diff, m to merge, dd to delete selected task.</Text>
-      <Text color="gray">New task model: {pendingTaskModelLabel}</Text>
-      <Text color="cyan">Task board by status</Text>
-      <Box marginTop={1} flexDirection="row" flexWrap="wrap" columnGap={2} rowGap={1}>
-        {STATUS_COLUMNS.map((column) => {
-          const columnTasks = groupedTasks[column.key];
+      <Text color="gray">
+        New task model: {pendingTaskModelLabel}
+        {pendingTaskPathScope ? ` | scope ${pendingTaskPathScope}/` : ""}
+        {pendingTaskBlockerLabel ? ` | after ${pendingTaskBlockerLabel}` : ""}
+      </Text>
+      <Text color="cyan">
+        Task board by status
+        {sumEstimates(tasks) > 0 ? ` | velocity ${formatPoints(computeVelocity(tasks))}/week` : ""}
+      </Text>
+      <Box marginTop={1} flexDirection="row" columnGap={COLUMN_GAP}>
+        {visibleColumns.hiddenBefore > 0 ? <Text color="gray">◀{visibleColumns.hiddenBefore}</Text> : null}
+        {visibleColumns.columns.map((column) => {
+          const columnTasks = groupedTasks.get(column.id) ?? [];
+          if (collapsedColumns.has(column.id)) {
+            return (
+              <Box key={column.id} flexDirection="column" width={COLLAPSED_COLUMN_WIDTH}>
+                <Text color={column === selectedColumn ? "green" : columnColor(column, palette)}>
+                  {column === selectedColumn ? ">" : "|"}
+                  {columnTasks.length}
+                </Text>
+              </Box>
+            );
+          }
+
           return (
-            <Box key={column.key} flexDirection="column" width={20}>
-              <Text color={column.color}>
-                {column.label} ({columnTasks.length})
+            <Box key={column.id} flexDirection="column" width={COLUMN_WIDTH}>
+              <Text color={isOverWipLimit(column, columnTasks) ? "red" : columnColor(column, palette)}>
+                {column.name} ({formatColumnTotals(columnTasks, column.wipLimit)})
               </Text>
               {columnTasks.length > 0 ? (
                 columnTasks.map((task) => {
                   const isSelected = task.taskId === selectedTaskId;
+                  if (isSelected && renameValue !== undefined) {
+                    return (
+                      <Text key={task.taskId} color="cyan">
+                        &gt; {renameValue}_
+                      </Text>
+                    );
+                  }
+
+                  const progress = cardFields.includes("progress")
+                    ? estimateTaskProgress(task, averageRunMs)
+                    : undefined;
+                  // Subtasks sit right under a parent in the same column, so indent them.
+                  const indent = "  ".repeat(getSubtaskDepth(task, columnTasks));
                   return (
-                    <Text key={task.taskId} color={isSelected ? "green" : stateColor(task.state)}>
-                      {isSelected ? ">" : " "} {task.taskId}
-                    </Text>
+                    <Box key={task.taskId} flexDirection="column">
+                      <Text color={isSelected ? "green" : statusColor(task.state, palette)}>
+                        {isSelected ? ">" : " "} {indent}{formatTaskCard(task, cardFields, cardContext(task))}
+                        {priorityMarker(task)}
+                        {labelChips(task)}
+                      </Text>
+                      {progress ? (
+                        <Text color="gray">
+                          {"  "}
+                          {indent}
+                          {formatProgressBar(progress.fraction)}
+                          {progress.source === "elapsed" ? "~" : ""}
+                        </Text>
+                      ) : null}
+                    </Box>
                   );
                 })
               ) : (
@@ -49,87 +216,181 @@ export function TaskBoardView({ tasks, selectedTaskIndex, pendingTaskModelLabel
             </Box>
           );
         })}
+        {visibleColumns.hiddenAfter > 0 ? <Text color="gray">▶{visibleColumns.hiddenAfter}</Text> : null}
       </Box>
+      {hiddenCancelledCount > 0 ? (
+        <Text color="gray">Cancelled: {hiddenCancelledCount} hidden (C to show)</Text>
+      ) : null}
     </Box>
   );
 }
 
-type StatusColumn = {
-  key: "queued" | "running" | "review" | "finished" | "failed";
-  label: string;
-  states: TaskState[];
-  color: "yellow" | "cyan" | "magenta" | "green" | "red";
-};
+export function listColumnTasksForTask(
+  tasks: TaskRuntime[],
+  taskId: string | undefined,
+  columns: readonly BoardColumn[] = DEFAULT_BOARD_COLUMNS,
+): TaskRuntime[] {
+  const task = tasks.find((candidate) => candidate.taskId === taskId);
+  const column = task ? findBoardColumn(columns, task.state) : undefined;
+  if (!column) {
+    return [];
+  }
 
-const STATUS_COLUMNS: StatusColumn[] = [
-  {
-    key: "queued",
-    label: "Queued",
-    states: ["queued", "creating_worktree"],
-    color: "yellow",
-  },
-  {
-    key: "running",
-    label: "Running",
-    states: ["running"],
-    color: "cyan",
-  },
-  {
-    key: "review",
-    label: "Review",
-    states: ["review"],
-    color: "magenta",
-  },
-  {
-    key: "finished",
-    label: "Finished",
-    states: ["completed", "cleaning"],
-    color: "green",
-  },
-  {
-    key: "failed",
-    label: "Failed",
-    states: ["failed"],
-    color: "red",
-  },
-];
-
-function groupTasksByColumn(tasks: TaskRuntime[]): Record<StatusColumn["key"], TaskRuntime[]> {
-  const grouped: Record<StatusColumn["key"], TaskRuntime[]> = {
-    queued: [],
-    running: [],
-    review: [],
-    finished: [],
-    failed: [],
-  };
+  return tasks.filter((candidate) => column.states.includes(candidate.state));
+}
+
+export function findTaskColumn(
+  task: TaskRuntime,
+  columns: readonly BoardColumn[] = DEFAULT_BOARD_COLUMNS,
+): BoardColumn | undefined {
+  return findBoardColumn(columns, task.state);
+}
+
+/**
+ * The run of columns that fits in `width`, starting as far left as possible
+ * while still showing the selected column. Arrows for the hidden columns
+ * take a few cells, so they are reserved up front.
+ */
+function panColumns(
+  columns: BoardColumn[],
+  selectedColumn: BoardColumn | undefined,
+  collapsedColumns: ReadonlySet<string>,
+  width: number | undefined,
+): { columns: BoardColumn[]; hiddenBefore: number; hiddenAfter: number } {
+  if (width === undefined) {
+    return { columns, hiddenBefore: 0, hiddenAfter: 0 };
+  }
+
+  const available = width - 2 * (2 + COLUMN_GAP);
+  const widths = columns.map(
+    (column) => (collapsedColumns.has(column.id) ? COLLAPSED_COLUMN_WIDTH : COLUMN_WIDTH) + COLUMN_GAP,
+  );
+  const widthAt = (index: number) => widths[index] ?? 0;
+  const selectedIndex = selectedColumn ? Math.max(0, columns.indexOf(selectedColumn)) : 0;
 
+  let start = selectedIndex;
+  let end = selectedIndex + 1;
+  let used = widthAt(selectedIndex);
+  while (start > 0 && used + widthAt(start - 1) <= available) {
+    start -= 1;
+    used += widthAt(start);
+  }
+  while (end < columns.length && used + widthAt(end) <= available) {
+    used += widthAt(end);
+    end += 1;
+  }
+
+  return { columns: columns.slice(start, end), hiddenBefore: start, hiddenAfter: columns.length - end };
+}
+
+function columnColor(column: BoardColumn, palette: Palette): string {
+  return statusColor(column.states[0] ?? "cancelled", palette);
+}
+
+function groupTasksByColumn(tasks: TaskRuntime[], columns: readonly BoardColumn[]): Map<string, TaskRuntime[]> {
+  const grouped = new Map<string, TaskRuntime[]>(columns.map((column) => [column.id, []]));
   for (const task of tasks) {
-    const column = STATUS_COLUMNS.find((candidate) => candidate.states.includes(task.state));
+    const column = findBoardColumn(columns, task.state);
     if (!column) {
       continue;
     }
 
-    grouped[column.key].push(task);
+    grouped.get(column.id)?.push(task);
   }
 
   return grouped;
 }
 
-function stateColor(state: TaskState): "yellow" | "cyan" | "green" | "red" | "magenta" | undefined {
-  switch (state) {
-    case "queued":
-      return "yellow";
-    case "creating_worktree":
-      return "yellow";
-    case "running":
-      return "cyan";
-    case "review":
-      return "magenta";
-    case "completed":
-      return "green";
-    case "failed":
-      return "red";
-    case "cleaning":
-      return "yellow";
+// `3/4` against a WIP limit of four.
+function formatColumnTotals(tasks: TaskRuntime[], wipLimit?: number): string {
+  const points = sumEstimates(tasks);
+  const count = wipLimit !== undefined ? `${tasks.length}/${wipLimit}` : `${tasks.length}`;
+  return points > 0 ? `${count}, ${formatPoints(points)}` : count;
+}
+
+export function formatTaskCardLabel(task: TaskRuntime): string {
+  const label = task.title ?? task.taskId;
+  return task.shortCode ? `${task.shortCode} ${label}` : label;
+}
+
+/** What a card shows that depends on other tasks. */
+export type TaskCardContext = {
+  /** Queued and waiting on an unfinished blocker. */
+  blocked?: boolean;
+  /** Queued and waiting for working hours. */
+  offHours?: boolean;
+  subtasks?: SubtaskSummary;
+  /** Its subtasks are folded away. */
+  collapsed?: boolean;
+  /** Show a glyph for every state, whether or not the status field is on. */
+  allStatusGlyphs?: boolean;
+};
+
+/**
+ * The card's first line: the title, prefixed by the session status icon and
+ * short code and followed by badges when enabled. Blocked queued tasks show ⛔
+ * and ones waiting for working hours ☾ in place of the status icon.
+ */
+export function formatTaskCard(task: TaskRuntime, fields: readonly CardField[], context: TaskCardContext = {}): string {
+  const label = task.title ?? task.taskId;
+  const glyphs = context.allStatusGlyphs ? STATUS_GLYPHS : fields.includes("status") ? SESSION_STATUS_ICONS : undefined;
+  const icon = glyphs && (context.blocked ? "⛔" : context.offHours ? "☾" : glyphs[task.state]);
+  const code = task.shortCode && fields.includes("short-code") ? `${task.shortCode} ` : "";
+  return `${icon ? `${icon} ` : ""}${code}${label}${fields.map((field) => formatCardBadge(task, field, context)).join("")}`;
+}
+
+// Medium is the default and gets no marker.
+const PRIORITY_MARKERS: Partial<Record<TaskPriority, { glyph: string; color: "red" | "yellow" | "gray" }>> = {
+  urgent: { glyph: "‼", color: "red" },
+  high: { glyph: "↑", color: "yellow" },
+  low: { glyph: "↓", color: "gray" },
+};
+
+// ⏳ marks a finished agent session whose work is still waiting to be merged.
+const SESSION_STATUS_ICONS: Partial<Record<TaskState, string>> = {
+  running: "▶",
+  review: "⏳",
+  failed: "✖",
+};
+
+function formatCardBadge(task: TaskRuntime, field: CardField, context: TaskCardContext): string {
+  switch (field) {
+    case "estimate":
+      return task.estimate !== undefined ? ` ${formatPoints(task.estimate)}` : "";
+    case "session":
+      return task.sessionID ? " ●" : "";
+    case "conflict-risk":
+      return formatConflictRiskBadge(task);
+    case "pull-request":
+      return formatPullRequestBadge(task);
+    case "subtasks":
+      return context.subtasks ? ` ${context.collapsed ? "▸" : "▾"}${formatSubtaskSummary(context.subtasks)}` : "";
+    // Rendered as their own part of the card.
+    case "priority":
+    case "labels":
+    case "status":
+    case "short-code":
+    case "progress":
+      return "";
   }
 }
+
+/** Warning marker for open branches likely to conflict on merge; empty when the risk is low. */
+export function formatConflictRiskBadge(task: TaskRuntime): string {
+  if (!task.conflictRisk || (task.state !== "running" && task.state !== "review")) {
+    return "";
+  }
+
+  return task.conflictRisk.level === "low" ? "" : ` ⚠${task.conflictRisk.score}`;
+}
+
+/** `PR#12` while the pull request is open, with ✓ once merged and ✗ once closed unmerged. */
+export function formatPullRequestBadge(task: TaskRuntime): string {
+  const pullRequest = task.pullRequest;
+  if (!pullRequest) {
+    return "";
+  }
+
+  const mark = pullRequest.state === "merged" ? "✓" : pullRequest.state === "closed" ? "✗" : "";
+  return ` PR#${pullRequest.number}${mark}`;
+}
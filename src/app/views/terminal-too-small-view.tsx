@@ -0,0 +1,15 @@
+import { Box, Text } from "ink";
+
+import { MIN_TERMINAL_SIZE, type TerminalSize } from "../terminal-size";
+
+/** Shown in place of the whole UI while the terminal is below `MIN_TERMINAL_SIZE`. */
+export function TerminalTooSmallView({ size }: { size: TerminalSize }) {
+  return (
+    <Box flexDirection="column" width={size.columns} height={size.rows}>
+      <Text color="yellow" wrap="wrap">
+        Terminal too small ({size.columns}x{size.rows}). Resize to at least{" "}
+        {MIN_TERMINAL_SIZE.columns}x{MIN_TERMINAL_SIZE.rows}, or press q to quit.
+      </Text>
+    </Box>
+  );
+}
@@ -0,0 +1,89 @@
+import type { TaskRuntime, TaskState } from "./task";
+
+/**
+ * A task board column. Columns map onto the canonical task states, so the
+ * orchestrator keeps one lifecycle while each project names and groups the
+ * states its own way.
+ */
+export type BoardColumn = {
+  id: string;
+  name: string;
+  /** States shown in this column; the first one gives the column its color. */
+  states: TaskState[];
+  /** Cards the column should hold at most; the header turns red past it. */
+  wipLimit?: number;
+};
+
+export const DEFAULT_BOARD_COLUMNS: readonly BoardColumn[] = [
+  { id: "queued", name: "Queued", states: ["queued", "creating_worktree"] },
+  { id: "running", name: "Running", states: ["running"] },
+  { id: "review", name: "Review", states: ["review"] },
+  { id: "finished", name: "Finished", states: ["completed", "cleaning"] },
+  { id: "failed", name: "Failed", states: ["failed"] },
+  { id: "cancelled", name: "Cancelled", states: ["cancelled"] },
+];
+
+/**
+ * The columns a project's board shows, in order. States its own columns
+ * leave out keep their default column after them, so no task drops off the
+ * board.
+ */
+export function resolveBoardColumns(columns: readonly BoardColumn[] | undefined): BoardColumn[] {
+  if (!columns || columns.length === 0) {
+    return [...DEFAULT_BOARD_COLUMNS];
+  }
+
+  const covered = new Set(columns.flatMap((column) => column.states));
+  const ids = new Set(columns.map((column) => column.id));
+  const fallback = DEFAULT_BOARD_COLUMNS.map((column) => ({
+    ...column,
+    id: ids.has(column.id) ? `${column.id}:default` : column.id,
+    states: column.states.filter((state) => !covered.has(state)),
+  })).filter((column) => column.states.length > 0);
+
+  return [...columns, ...fallback];
+}
+
+export function findBoardColumn(columns: readonly BoardColumn[], state: TaskState): BoardColumn | undefined {
+  return columns.find((column) => column.states.includes(state));
+}
+
+export function isOverWipLimit(column: BoardColumn, tasks: readonly TaskRuntime[]): boolean {
+  return column.wipLimit !== undefined && tasks.length > column.wipLimit;
+}
+
+export function validateBoardColumns(columns: readonly BoardColumn[]): string[] {
+  const errors: string[] = [];
+  const ids = new Set<string>();
+  const states = new Set<TaskState>();
+
+  for (const column of columns) {
+    if (column.id.trim().length === 0) {
+      errors.push("Column id must be a non-empty string.");
+    } else if (ids.has(column.id)) {
+      errors.push(`Column id ${column.id} is used twice.`);
+    }
+    ids.add(column.id);
+
+    if (column.name.trim().length === 0) {
+      errors.push(`Column ${column.id} needs a name.`);
+    }
+
+    if (column.states.length === 0) {
+      errors.push(`Column ${column.id} must map to at least one task state.`);
+    }
+
+    for (const state of column.states) {
+      if (states.has(state)) {
+        errors.push(`Task state ${state} is mapped to more than one column.`);
+      }
+      states.add(state);
+    }
+
+    if (column.wipLimit !== undefined && (!Number.isInteger(column.wipLimit) || column.wipLimit <= 0)) {
+      errors.push(`Column ${column.id} WIP limit must be a positive integer.`);
+    }
+  }
+
+  return errors;
+}
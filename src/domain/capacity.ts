@@ -0,0 +1,29 @@
+import type { TaskRuntime } from "./task";
+
+const WEEK_MS = 7 * 24 * 60 * 60 * 1000;
+const DEFAULT_VELOCITY_WEEKS = 4;
+
+export function sumEstimates(tasks: TaskRuntime[]): number {
+  return tasks.reduce((total, task) => total + (task.estimate ?? 0), 0);
+}
+
+/**
+ * Average estimate points completed per week over the trailing window.
+ * Completion time is approximated by the task's last update.
+ */
+export function computeVelocity(
+  tasks: TaskRuntime[],
+  now = Date.now(),
+  weeks = DEFAULT_VELOCITY_WEEKS,
+): number {
+  const windowStart = now - weeks * WEEK_MS;
+  const completedPoints = sumEstimates(
+    tasks.filter((task) => task.state === "completed" && task.updatedAt >= windowStart),
+  );
+
+  return completedPoints / weeks;
+}
+
+export function formatPoints(points: number): string {
+  return `${Number.isInteger(points) ? points : points.toFixed(1)}pt`;
+}
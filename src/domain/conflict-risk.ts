@@ -0,0 +1,69 @@
+export type ConflictRiskLevel = "low" | "medium" | "high";
+
+export type TaskConflictRisk = {
+  score: number;
+  level: ConflictRiskLevel;
+  changedFileCount: number;
+  /** Short codes (or ids) of other open tasks touching the same files. */
+  overlappingTasks: string[];
+  /** Files this branch changed that the default branch also changed since they diverged. */
+  upstreamFiles: string[];
+  checkedAt: number;
+};
+
+export type BranchChangeSet = {
+  taskRef: string;
+  changedFiles: string[];
+  upstreamFiles: string[];
+};
+
+// A file the default branch also changed is a likelier conflict than one another open branch touches.
+const UPSTREAM_FILE_WEIGHT = 20;
+const SESSION_FILE_WEIGHT = 10;
+
+export function scoreConflictRisk(
+  target: BranchChangeSet,
+  others: BranchChangeSet[],
+  checkedAt: number = Date.now(),
+): TaskConflictRisk {
+  const changed = new Set(target.changedFiles);
+  const upstreamFiles = target.upstreamFiles.filter((file) => changed.has(file));
+  const sharedWithSessions = new Set<string>();
+  const overlappingTasks: string[] = [];
+
+  for (const other of others) {
+    const shared = other.changedFiles.filter((file) => changed.has(file));
+    if (shared.length === 0) {
+      continue;
+    }
+
+    overlappingTasks.push(other.taskRef);
+    for (const file of shared) {
+      sharedWithSessions.add(file);
+    }
+  }
+
+  const score = Math.min(
+    100,
+    upstreamFiles.length * UPSTREAM_FILE_WEIGHT + sharedWithSessions.size * SESSION_FILE_WEIGHT,
+  );
+
+  return {
+    score,
+    level: score >= 50 ? "high" : score >= 20 ? "medium" : "low",
+    changedFileCount: changed.size,
+    overlappingTasks,
+    upstreamFiles,
+    checkedAt,
+  };
+}
+
+export function isSameConflictRisk(left: TaskConflictRisk | undefined, right: TaskConflictRisk): boolean {
+  return (
+    left !== undefined &&
+    left.score === right.score &&
+    left.changedFileCount === right.changedFileCount &&
+    left.overlappingTasks.join(",") === right.overlappingTasks.join(",") &&
+    left.upstreamFiles.join(",") === right.upstreamFiles.join(",")
+  );
+}
@@ -28,3 +28,42 @@ export function updateSessionActivity(
     lastMessageAt: at,
   };
 }
+
+const SESSION_NAME_MAX_WORDS = 5;
+const SESSION_NAME_MAX_LENGTH = 48;
+
+/**
+ * Short human-readable session name from the first non-empty prompt line,
+ * e.g. "Fix flaky login redirect test" instead of a session UUID.
+ */
+export function summarizeSessionName(prompt: string): string | undefined {
+  const firstLine = prompt
+    .split("\n")
+    .map((line) => line.replace(/^[#>*\-\s]+/, "").trim())
+    .find((line) => line.length > 0);
+  if (!firstLine) {
+    return undefined;
+  }
+
+  const words = firstLine.split(/\s+/);
+  const summary = words.slice(0, SESSION_NAME_MAX_WORDS).join(" ").replace(/[.,:;!?]+$/, "");
+  if (summary.length <= SESSION_NAME_MAX_LENGTH) {
+    return summary;
+  }
+
+  return `${summary.slice(0, SESSION_NAME_MAX_LENGTH - 1).trimEnd()}…`;
+}
+
+/** Text parts of the last assistant message, e.g. the agent's closing summary of a run. */
+export function lastAssistantText(messages: ConversationSdkSessionMessage[]): string {
+  const assistantMessages = messages.filter((message) => message.info.role === "assistant");
+  const lastMessage = assistantMessages[assistantMessages.length - 1];
+  if (!lastMessage) {
+    return "";
+  }
+
+  return lastMessage.parts
+    .map((part) => (part.type === "text" ? part.text : ""))
+    .join("\n")
+    .trim();
+}
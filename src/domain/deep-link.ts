@@ -0,0 +1,31 @@
+export const DEEP_LINK_SCHEME = "ikanban";
+
+export type DeepLink = {
+  kind: "task" | "session";
+  id: string;
+};
+
+const DEEP_LINK_PATTERN = /^ikanban:\/\/(task|session)\/([^/?#\s]+)\/?$/i;
+
+export function formatDeepLink(link: DeepLink): string {
+  return `${DEEP_LINK_SCHEME}://${link.kind}/${encodeURIComponent(link.id)}`;
+}
+
+/** Parses `ikanban://task/<id>` and `ikanban://session/<id>`; anything else is undefined. */
+export function parseDeepLink(value: string): DeepLink | undefined {
+  const match = DEEP_LINK_PATTERN.exec(value.trim());
+  if (!match) {
+    return undefined;
+  }
+
+  const [, kind, id] = match;
+  if (!kind || !id) {
+    return undefined;
+  }
+
+  try {
+    return { kind: kind.toLowerCase() as DeepLink["kind"], id: decodeURIComponent(id) };
+  } catch {
+    return undefined;
+  }
+}
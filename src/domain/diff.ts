@@ -0,0 +1,190 @@
+export type DiffHunk = {
+  /** The `@@ -a,b +c,d @@ context` line. */
+  header: string;
+  oldStart: number;
+  oldLines: number;
+  newStart: number;
+  newLines: number;
+  lines: string[];
+};
+
+export type DiffFile = {
+  path: string;
+  /** Set when the file was renamed or copied. */
+  oldPath?: string;
+  status: "added" | "deleted" | "modified" | "renamed";
+  binary: boolean;
+  additions: number;
+  deletions: number;
+  hunks: DiffHunk[];
+  /** This file's part of the diff, from its `diff --git` line on. */
+  patch: string;
+};
+
+export type DiffTotals = {
+  files: number;
+  additions: number;
+  deletions: number;
+};
+
+const FILE_HEADER_PATTERN = /^diff --git a\/(.+) b\/(.+)$/;
+const HUNK_HEADER_PATTERN = /^@@ -(\d+)(?:,(\d+))? \+(\d+)(?:,(\d+))? @@/;
+
+/** Splits `git diff` output into files and hunks with per-file line counts. */
+export function parseUnifiedDiff(diff: string): DiffFile[] {
+  const files: DiffFile[] = [];
+  let file: DiffFile | undefined;
+  let hunk: DiffHunk | undefined;
+  let patchLines: string[] = [];
+
+  const finishFile = () => {
+    if (file) {
+      file.patch = patchLines.join("\n");
+      files.push(file);
+    }
+  };
+
+  for (const line of diff.split(/\r?\n/)) {
+    const fileHeader = FILE_HEADER_PATTERN.exec(line);
+    if (fileHeader) {
+      finishFile();
+      const [, oldPath = "", path = ""] = fileHeader;
+      file = { path, status: "modified", binary: false, additions: 0, deletions: 0, hunks: [], patch: "" };
+      if (oldPath !== path) {
+        file.oldPath = oldPath;
+        file.status = "renamed";
+      }
+      hunk = undefined;
+      patchLines = [line];
+      continue;
+    }
+
+    if (!file) {
+      continue;
+    }
+    patchLines.push(line);
+
+    const hunkHeader = HUNK_HEADER_PATTERN.exec(line);
+    if (hunkHeader) {
+      const [, oldStart, oldLines, newStart, newLines] = hunkHeader;
+      hunk = {
+        header: line,
+        oldStart: Number(oldStart),
+        oldLines: oldLines === undefined ? 1 : Number(oldLines),
+        newStart: Number(newStart),
+        newLines: newLines === undefined ? 1 : Number(newLines),
+        lines: [],
+      };
+      file.hunks.push(hunk);
+      continue;
+    }
+
+    if (hunk) {
+      hunk.lines.push(line);
+      if (line.startsWith("+")) {
+        file.additions += 1;
+      } else if (line.startsWith("-")) {
+        file.deletions += 1;
+      }
+      continue;
+    }
+
+    // Extended header lines between `diff --git` and the first hunk.
+    if (line.startsWith("new file mode")) {
+      file.status = "added";
+    } else if (line.startsWith("deleted file mode")) {
+      file.status = "deleted";
+    } else if (line.startsWith("Binary files ") || line === "GIT binary patch") {
+      file.binary = true;
+    } else if (line.startsWith("rename from ")) {
+      file.oldPath = line.slice("rename from ".length);
+      file.status = "renamed";
+    } else if (line.startsWith("rename to ")) {
+      file.path = line.slice("rename to ".length);
+    }
+  }
+
+  finishFile();
+  return files;
+}
+
+export function sumDiffFiles(files: DiffFile[]): DiffTotals {
+  return files.reduce(
+    (totals, file) => ({
+      files: totals.files + 1,
+      additions: totals.additions + file.additions,
+      deletions: totals.deletions + file.deletions,
+    }),
+    { files: 0, additions: 0, deletions: 0 },
+  );
+}
+
+export function formatDiffTotals(totals: DiffTotals): string {
+  return `${totals.files} file${totals.files === 1 ? "" : "s"} changed, +${totals.additions} -${totals.deletions}`;
+}
+
+/** How one file was changed by each of two attempts that both touched it. */
+export type DiffFileComparison = {
+  path: string;
+  left: DiffFile;
+  right: DiffFile;
+  /** Pairs of hunks, one from each side, that change overlapping lines of the base file. */
+  overlappingHunks: number;
+  /** Both sides made the same change. */
+  identical: boolean;
+};
+
+export type DiffComparison = {
+  onlyLeft: DiffFile[];
+  onlyRight: DiffFile[];
+  shared: DiffFileComparison[];
+};
+
+/**
+ * Diff of two diffs, e.g. two attempts at the same task: which files only one
+ * side touched and, for files both touched, whether their hunks collide. Hunk
+ * positions are compared in base-file lines, so attempts started from
+ * different base commits are only approximately aligned.
+ */
+export function compareDiffFiles(left: DiffFile[], right: DiffFile[]): DiffComparison {
+  const rightByPath = new Map(right.map((file) => [file.path, file]));
+  const leftPaths = new Set(left.map((file) => file.path));
+  const shared: DiffFileComparison[] = [];
+  const onlyLeft: DiffFile[] = [];
+
+  for (const file of left) {
+    const other = rightByPath.get(file.path);
+    if (!other) {
+      onlyLeft.push(file);
+      continue;
+    }
+
+    shared.push({
+      path: file.path,
+      left: file,
+      right: other,
+      overlappingHunks: file.hunks.reduce(
+        (count, hunk) => count + other.hunks.filter((otherHunk) => hunksOverlap(hunk, otherHunk)).length,
+        0,
+      ),
+      identical: file.binary === other.binary && hunkBodies(file) === hunkBodies(other),
+    });
+  }
+
+  return {
+    onlyLeft,
+    onlyRight: right.filter((file) => !leftPaths.has(file.path)),
+    shared,
+  };
+}
+
+function hunksOverlap(left: DiffHunk, right: DiffHunk): boolean {
+  // A pure insertion covers no base lines; treat it as touching the line it follows.
+  const leftEnd = left.oldStart + Math.max(left.oldLines, 1);
+  const rightEnd = right.oldStart + Math.max(right.oldLines, 1);
+  return left.oldStart < rightEnd && right.oldStart < leftEnd;
+}
+
+function hunkBodies(file: DiffFile): string {
+  return file.hunks.map((hunk) => hunk.lines.join("\n")).join("\n@@\n");
+}
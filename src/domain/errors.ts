@@ -0,0 +1,41 @@
+export const IKANBAN_ERROR_CODES = [
+  "not_found",
+  "validation",
+  "conflict",
+  "invalid_state",
+  "preflight_failed",
+  "hook_vetoed",
+  "scope_violation",
+  "executor_unavailable",
+  "timeout",
+] as const;
+
+export type IkanbanErrorCode = (typeof IKANBAN_ERROR_CODES)[number];
+
+/**
+ * Error with a stable, machine-readable code so callers can branch on the
+ * kind of failure instead of matching message text.
+ */
+export class IkanbanError extends Error {
+  readonly code: IkanbanErrorCode;
+  readonly field?: string;
+
+  constructor(code: IkanbanErrorCode, message: string, options: { field?: string; cause?: unknown } = {}) {
+    super(message, { cause: options.cause });
+    this.name = "IkanbanError";
+    this.code = code;
+    this.field = options.field;
+  }
+}
+
+export function isIkanbanErrorCode(value: unknown): value is IkanbanErrorCode {
+  return typeof value === "string" && (IKANBAN_ERROR_CODES as readonly string[]).includes(value);
+}
+
+export function getErrorCode(error: unknown): IkanbanErrorCode | undefined {
+  if (error && typeof error === "object" && "code" in error && isIkanbanErrorCode(error.code)) {
+    return error.code;
+  }
+
+  return undefined;
+}
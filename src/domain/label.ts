@@ -0,0 +1,57 @@
+/** Colors a label chip can take; the names are the terminal colors Ink renders. */
+export const LABEL_COLORS = ["red", "green", "yellow", "blue", "magenta", "cyan", "white", "gray"] as const;
+
+export type LabelColor = (typeof LABEL_COLORS)[number];
+
+export type Label = {
+  id: string;
+  projectId: string;
+  name: string;
+  color: LabelColor;
+  createdAt: number;
+};
+
+export function isLabelColor(value: unknown): value is LabelColor {
+  return typeof value === "string" && (LABEL_COLORS as readonly string[]).includes(value);
+}
+
+/** Labels are matched by name without regard to case, so `Bug` and `bug` are one label. */
+export function findLabelByName(labels: readonly Label[], name: string): Label | undefined {
+  const normalized = name.trim().toLowerCase();
+  return labels.find((label) => label.name.toLowerCase() === normalized);
+}
+
+export function validateLabelInvariants(label: Label): string[] {
+  const errors: string[] = [];
+
+  if (label.id.trim().length === 0) {
+    errors.push("Label id must be a non-empty string.");
+  }
+
+  if (label.projectId.trim().length === 0) {
+    errors.push("Label projectId must be a non-empty string.");
+  }
+
+  if (label.name.trim().length === 0 || /\s/.test(label.name)) {
+    errors.push("Label name must be a non-empty string without spaces.");
+  }
+
+  if (!isLabelColor(label.color)) {
+    errors.push(`Label color must be one of ${LABEL_COLORS.join(", ")}.`);
+  }
+
+  if (!Number.isFinite(label.createdAt) || label.createdAt <= 0) {
+    errors.push("Label createdAt must be a positive timestamp.");
+  }
+
+  return errors;
+}
+
+export function assertLabelInvariants(label: Label): void {
+  const errors = validateLabelInvariants(label);
+  if (errors.length === 0) {
+    return;
+  }
+
+  throw new Error(`Invalid Label: ${errors.join(" ")}`);
+}
@@ -0,0 +1,42 @@
+import { describe, expect, test } from "bun:test";
+
+import { buildActivityHeatmap, rollupMetricsSnapshots, type ProjectMetricsSnapshot } from "./metrics";
+
+function snapshot(date: string, completedTasks: number, failedTasks = 0): ProjectMetricsSnapshot {
+  const [year = 1970, month = 1, day = 1] = date.split("-").map(Number);
+  return {
+    projectId: "web",
+    date,
+    takenAt: new Date(year, month - 1, day, 12).getTime(),
+    openTasks: 0,
+    completedTasks,
+    failedTasks,
+  };
+}
+
+describe("buildActivityHeatmap", () => {
+  // Friday 2026-10-16, noon local time.
+  const now = new Date(2026, 9, 16, 12).getTime();
+
+  test("lays the window out in Monday-first weeks ending today", () => {
+    const heatmap = buildActivityHeatmap([], now, 14);
+
+    expect(heatmap.from).toBe("2026-10-03");
+    expect(heatmap.to).toBe("2026-10-16");
+    expect(heatmap.weeks).toEqual(["2026-09-28", "2026-10-05", "2026-10-12"]);
+    expect(heatmap.cells).toHaveLength(7);
+    // Saturday 2026-10-03 is the first day in the window; Saturday 2026-10-17 is after today.
+    expect(heatmap.cells[4]).toEqual([undefined, 0, 0]);
+    expect(heatmap.cells[5]).toEqual([0, 0, undefined]);
+  });
+
+  test("counts completed and failed tasks per day", () => {
+    const rows = rollupMetricsSnapshots([snapshot("2026-10-14", 3, 1), snapshot("2026-10-16", 1)], "day");
+    const heatmap = buildActivityHeatmap(rows, now, 14);
+
+    expect(heatmap.cells[2]?.[2]).toBe(4);
+    expect(heatmap.cells[4]?.[2]).toBe(1);
+    expect(heatmap.max).toBe(4);
+    expect(heatmap.total).toBe(5);
+  });
+});
@@ -0,0 +1,179 @@
+import type { TaskRuntime, TaskState } from "./task";
+
+const DAY_MS = 24 * 60 * 60 * 1000;
+const OPEN_TASK_STATES: readonly TaskState[] = ["queued", "creating_worktree", "running", "review"];
+
+export const METRICS_ROLLUPS = ["day", "week", "month"] as const;
+
+export type MetricsRollup = (typeof METRICS_ROLLUPS)[number];
+
+/** One project's numbers for one local calendar day. */
+export type ProjectMetricsSnapshot = {
+  projectId: string;
+  date: string;
+  takenAt: number;
+  openTasks: number;
+  completedTasks: number;
+  failedTasks: number;
+};
+
+export type MetricsRollupRow = {
+  period: string;
+  snapshotCount: number;
+  /** Open tasks at the last snapshot in the period. */
+  openTasks: number;
+  completedTasks: number;
+  failedTasks: number;
+  successRate?: number;
+};
+
+export function isMetricsRollup(value: unknown): value is MetricsRollup {
+  return typeof value === "string" && (METRICS_ROLLUPS as readonly string[]).includes(value);
+}
+
+export function formatSnapshotDate(timestamp: number): string {
+  const date = new Date(timestamp);
+  const month = String(date.getMonth() + 1).padStart(2, "0");
+  const day = String(date.getDate()).padStart(2, "0");
+  return `${date.getFullYear()}-${month}-${day}`;
+}
+
+/**
+ * Open tasks are counted at `now`; completions and failures since the previous
+ * snapshot (or over the trailing day for the first one), so summing snapshots
+ * does not double count. Finish time is approximated by the task's last update,
+ * as in velocity.
+ */
+export function computeProjectMetricsSnapshot(
+  projectId: string,
+  tasks: TaskRuntime[],
+  now = Date.now(),
+  since = now - DAY_MS,
+): ProjectMetricsSnapshot {
+  const projectTasks = tasks.filter((task) => task.projectId === projectId);
+  const finishedSince = (state: TaskState) =>
+    projectTasks.filter((task) => task.state === state && task.updatedAt > since && task.updatedAt <= now)
+      .length;
+
+  return {
+    projectId,
+    date: formatSnapshotDate(now),
+    takenAt: now,
+    openTasks: projectTasks.filter((task) => OPEN_TASK_STATES.includes(task.state)).length,
+    completedTasks: finishedSince("completed"),
+    failedTasks: finishedSince("failed"),
+  };
+}
+
+export function rollupMetricsSnapshots(
+  snapshots: ProjectMetricsSnapshot[],
+  rollup: MetricsRollup,
+): MetricsRollupRow[] {
+  const rowsByPeriod = new Map<string, MetricsRollupRow & { lastTakenAt: number }>();
+
+  for (const snapshot of [...snapshots].sort((left, right) => left.takenAt - right.takenAt)) {
+    const period = toRollupPeriod(snapshot.date, rollup);
+    const row = rowsByPeriod.get(period) ?? {
+      period,
+      snapshotCount: 0,
+      openTasks: 0,
+      completedTasks: 0,
+      failedTasks: 0,
+      lastTakenAt: 0,
+    };
+
+    row.snapshotCount += 1;
+    row.openTasks = snapshot.openTasks;
+    row.completedTasks += snapshot.completedTasks;
+    row.failedTasks += snapshot.failedTasks;
+    row.lastTakenAt = snapshot.takenAt;
+    rowsByPeriod.set(period, row);
+  }
+
+  return [...rowsByPeriod.values()]
+    .sort((left, right) => left.period.localeCompare(right.period))
+    .map(({ lastTakenAt: _lastTakenAt, ...row }) => {
+      const finished = row.completedTasks + row.failedTasks;
+      return { ...row, successRate: finished > 0 ? row.completedTasks / finished : undefined };
+    });
+}
+
+/** Finished runs per day in Monday-first week columns, like a contribution graph. */
+export type ActivityHeatmap = {
+  from: string;
+  to: string;
+  /** The Monday of each column. */
+  weeks: string[];
+  /** Seven weekday rows; `undefined` marks days outside the window. */
+  cells: (number | undefined)[][];
+  max: number;
+  total: number;
+};
+
+export const ACTIVITY_HEATMAP_DAYS = 90;
+
+export function getActivityHeatmapStart(now = Date.now(), days = ACTIVITY_HEATMAP_DAYS): string {
+  const start = new Date(now);
+  start.setDate(start.getDate() - (days - 1));
+  return formatSnapshotDate(start.getTime());
+}
+
+/**
+ * Lays day rollup rows out on a grid ending today. Snapshots are daily, so a
+ * cell is a day's completed plus failed tasks; there is no finer hour breakdown.
+ */
+export function buildActivityHeatmap(
+  rows: MetricsRollupRow[],
+  now = Date.now(),
+  days = ACTIVITY_HEATMAP_DAYS,
+): ActivityHeatmap {
+  const activityByDate = new Map(rows.map((row) => [row.period, row.completedTasks + row.failedTasks]));
+  const from = getActivityHeatmapStart(now, days);
+  const to = formatSnapshotDate(now);
+
+  const cursor = new Date(now);
+  cursor.setDate(cursor.getDate() - (days - 1));
+  cursor.setDate(cursor.getDate() - ((cursor.getDay() + 6) % 7));
+
+  const weeks: string[] = [];
+  const cells: (number | undefined)[][] = Array.from({ length: 7 }, () => []);
+  let max = 0;
+  let total = 0;
+
+  while (formatSnapshotDate(cursor.getTime()) <= to) {
+    weeks.push(formatSnapshotDate(cursor.getTime()));
+    for (let weekday = 0; weekday < 7; weekday += 1) {
+      const date = formatSnapshotDate(cursor.getTime());
+      const inWindow = date >= from && date <= to;
+      const count = inWindow ? (activityByDate.get(date) ?? 0) : undefined;
+      cells[weekday]?.push(count);
+      if (count !== undefined) {
+        max = Math.max(max, count);
+        total += count;
+      }
+      cursor.setDate(cursor.getDate() + 1);
+    }
+  }
+
+  return { from, to, weeks, cells, max, total };
+}
+
+export function formatSuccessRate(rate: number | undefined): string {
+  return rate === undefined ? "-" : `${Math.round(rate * 100)}%`;
+}
+
+// Weeks are keyed by their Monday so they sort and compare as plain dates.
+function toRollupPeriod(date: string, rollup: MetricsRollup): string {
+  if (rollup === "day") {
+    return date;
+  }
+
+  if (rollup === "month") {
+    return date.slice(0, 7);
+  }
+
+  const [year, month, day] = date.split("-").map(Number);
+  const value = new Date(year ?? 1970, (month ?? 1) - 1, day ?? 1);
+  value.setDate(value.getDate() - ((value.getDay() + 6) % 7));
+  return `${formatSnapshotDate(value.getTime())} wk`;
+}
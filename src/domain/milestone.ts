@@ -0,0 +1,83 @@
+import type { TaskRuntime } from "./task";
+
+export const MILESTONE_STATUSES = ["open", "closed"] as const;
+
+export type MilestoneStatus = (typeof MILESTONE_STATUSES)[number];
+
+export type Milestone = {
+  id: string;
+  projectId: string;
+  name: string;
+  dueDate?: string;
+  status: MilestoneStatus;
+  createdAt: number;
+};
+
+export type MilestoneProgress = {
+  done: number;
+  total: number;
+};
+
+const MILESTONE_DUE_DATE_PATTERN = /^\d{4}-\d{2}-\d{2}$/;
+
+export function computeMilestoneProgress(
+  milestoneId: string,
+  tasks: TaskRuntime[],
+): MilestoneProgress {
+  const assigned = tasks.filter((task) => task.milestoneId === milestoneId);
+  return {
+    done: assigned.filter((task) => task.state === "completed").length,
+    total: assigned.length,
+  };
+}
+
+export function isMilestoneOverdue(milestone: Milestone, now = Date.now()): boolean {
+  if (!milestone.dueDate || milestone.status === "closed") {
+    return false;
+  }
+
+  // Due dates are inclusive: overdue only once the whole day has passed.
+  return Date.parse(`${milestone.dueDate}T23:59:59`) < now;
+}
+
+export function validateMilestoneInvariants(milestone: Milestone): string[] {
+  const errors: string[] = [];
+
+  if (milestone.id.trim().length === 0) {
+    errors.push("Milestone id must be a non-empty string.");
+  }
+
+  if (milestone.projectId.trim().length === 0) {
+    errors.push("Milestone projectId must be a non-empty string.");
+  }
+
+  if (milestone.name.trim().length === 0) {
+    errors.push("Milestone name must be a non-empty string.");
+  }
+
+  if (
+    milestone.dueDate !== undefined &&
+    (!MILESTONE_DUE_DATE_PATTERN.test(milestone.dueDate) || Number.isNaN(Date.parse(milestone.dueDate)))
+  ) {
+    errors.push("Milestone dueDate must be a YYYY-MM-DD date.");
+  }
+
+  if (!MILESTONE_STATUSES.includes(milestone.status)) {
+    errors.push(`Milestone status must be one of ${MILESTONE_STATUSES.join(", ")}.`);
+  }
+
+  if (!Number.isFinite(milestone.createdAt) || milestone.createdAt <= 0) {
+    errors.push("Milestone createdAt must be a positive timestamp.");
+  }
+
+  return errors;
+}
+
+export function assertMilestoneInvariants(milestone: Milestone): void {
+  const errors = validateMilestoneInvariants(milestone);
+  if (errors.length === 0) {
+    return;
+  }
+
+  throw new Error(`Invalid Milestone: ${errors.join(" ")}`);
+}
@@ -0,0 +1,56 @@
+export type ProjectPage = {
+  id: string;
+  projectId: string;
+  title: string;
+  body: string;
+  order: number;
+  includeInPrompts: boolean;
+  createdAt: number;
+  updatedAt: number;
+};
+
+export function validateProjectPageInvariants(page: ProjectPage): string[] {
+  const errors: string[] = [];
+
+  if (page.id.trim().length === 0) {
+    errors.push("Page id must be a non-empty string.");
+  }
+
+  if (page.projectId.trim().length === 0) {
+    errors.push("Page projectId must be a non-empty string.");
+  }
+
+  if (page.title.trim().length === 0) {
+    errors.push("Page title must be a non-empty string.");
+  }
+
+  if (!Number.isInteger(page.order) || page.order < 0) {
+    errors.push("Page order must be a non-negative integer.");
+  }
+
+  if (!Number.isFinite(page.createdAt) || page.createdAt <= 0) {
+    errors.push("Page createdAt must be a positive timestamp.");
+  }
+
+  if (!Number.isFinite(page.updatedAt) || page.updatedAt < page.createdAt) {
+    errors.push("Page updatedAt cannot be earlier than createdAt.");
+  }
+
+  return errors;
+}
+
+export function assertProjectPageInvariants(page: ProjectPage): void {
+  const errors = validateProjectPageInvariants(page);
+  if (errors.length === 0) {
+    return;
+  }
+
+  throw new Error(`Invalid ProjectPage: ${errors.join(" ")}`);
+}
+
+export function formatPagesForPrompt(pages: ProjectPage[]): string {
+  return [
+    "## Project pages",
+    ...pages.map((page) => `### ${page.title}\n\n${page.body.trim()}`),
+  ].join("\n\n");
+}
@@ -0,0 +1,52 @@
+import type { TaskState } from "./task";
+
+export const PALETTES = ["default", "color-blind"] as const;
+
+export type Palette = (typeof PALETTES)[number];
+
+export function isPalette(value: unknown): value is Palette {
+  return typeof value === "string" && (PALETTES as readonly string[]).includes(value);
+}
+
+/**
+ * Status colors per palette. The color-blind one uses the Okabe-Ito colors,
+ * which stay apart under the common forms of color blindness.
+ */
+const STATUS_COLORS: Record<Palette, Record<TaskState, string>> = {
+  default: {
+    queued: "yellow",
+    creating_worktree: "yellow",
+    running: "cyan",
+    review: "magenta",
+    completed: "green",
+    failed: "red",
+    cancelled: "gray",
+    cleaning: "yellow",
+  },
+  "color-blind": {
+    queued: "#E69F00",
+    creating_worktree: "#E69F00",
+    running: "#56B4E9",
+    review: "#CC79A7",
+    completed: "#0072B2",
+    failed: "#D55E00",
+    cancelled: "gray",
+    cleaning: "#E69F00",
+  },
+};
+
+/** One glyph per state, so status never depends on color alone. */
+export const STATUS_GLYPHS: Record<TaskState, string> = {
+  queued: "⏸",
+  creating_worktree: "⏸",
+  running: "▶",
+  review: "⏳",
+  completed: "✓",
+  failed: "✖",
+  cancelled: "⊘",
+  cleaning: "✓",
+};
+
+export function statusColor(state: TaskState, palette: Palette = "default"): string {
+  return STATUS_COLORS[palette][state];
+}
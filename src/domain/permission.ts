@@ -0,0 +1,42 @@
+export const PERMISSION_REPLIES = ["once", "always", "reject"] as const;
+
+export type PermissionReply = (typeof PERMISSION_REPLIES)[number];
+
+/** A tool use the agent is blocked on until the user allows or rejects it. */
+export type PermissionRequest = {
+  id: string;
+  sessionID: string;
+  taskId?: string;
+  /** Tool or capability asked for, e.g. `bash` or `edit`. */
+  permission: string;
+  patterns: string[];
+  requestedAt: number;
+};
+
+/** Reads the `permission.asked` event properties; malformed events yield undefined. */
+export function parsePermissionAsked(properties: unknown, now: number = Date.now()): PermissionRequest | undefined {
+  if (!properties || typeof properties !== "object") {
+    return undefined;
+  }
+
+  const record = properties as Record<string, unknown>;
+  if (typeof record.id !== "string" || typeof record.sessionID !== "string") {
+    return undefined;
+  }
+
+  return {
+    id: record.id,
+    sessionID: record.sessionID,
+    permission: typeof record.permission === "string" ? record.permission : "tool",
+    patterns: Array.isArray(record.patterns)
+      ? record.patterns.filter((pattern): pattern is string => typeof pattern === "string")
+      : [],
+    requestedAt: now,
+  };
+}
+
+export function describePermissionRequest(request: PermissionRequest): string {
+  return request.patterns.length > 0
+    ? `${request.permission}: ${request.patterns.join(", ")}`
+    : request.permission;
+}
@@ -1,7 +1,19 @@
+import type { BoardColumn } from "./board-column";
+import type { TaskTransitionRules } from "./task";
+import type { WorkingHours } from "./working-hours";
+
 export type ProjectRef = {
   id: string;
   rootDirectory: string;
   name: string;
+  taskTransitions?: TaskTransitionRules;
+  workingHours?: WorkingHours;
+  /** Running tasks allowed at once in this project; undefined falls back to the global per-project limit. */
+  maxConcurrentTasks?: number;
+  /** Board columns in place of the default ones; see `board-column`. */
+  columns?: BoardColumn[];
+  /** Highest task short-code sequence handed out; it only goes up, so codes are never reused. */
+  taskSequence?: number;
   createdAt: number;
 };
 
@@ -9,6 +21,11 @@ export type CreateProjectRefInput = {
   id: string;
   rootDirectory: string;
   name: string;
+  taskTransitions?: TaskTransitionRules;
+  workingHours?: WorkingHours;
+  maxConcurrentTasks?: number;
+  columns?: BoardColumn[];
+  taskSequence?: number;
   createdAt?: number;
 };
 
@@ -17,6 +34,11 @@ export function createProjectRef(input: CreateProjectRefInput): ProjectRef {
     id: input.id.trim(),
     rootDirectory: input.rootDirectory,
     name: input.name.trim(),
+    taskTransitions: input.taskTransitions,
+    workingHours: input.workingHours,
+    maxConcurrentTasks: input.maxConcurrentTasks,
+    columns: input.columns,
+    taskSequence: input.taskSequence,
     createdAt: input.createdAt ?? Date.now(),
   };
 
@@ -42,6 +64,17 @@ export function validateProjectRefInvariants(project: ProjectRef): string[] {
     errors.push("Project rootDirectory must be an absolute path.");
   }
 
+  if (
+    project.maxConcurrentTasks !== undefined &&
+    (!Number.isInteger(project.maxConcurrentTasks) || project.maxConcurrentTasks < 1)
+  ) {
+    errors.push("Project maxConcurrentTasks must be a positive integer when provided.");
+  }
+
+  if (project.taskSequence !== undefined && (!Number.isInteger(project.taskSequence) || project.taskSequence < 0)) {
+    errors.push("Project taskSequence must be a non-negative integer when provided.");
+  }
+
   if (!Number.isFinite(project.createdAt) || project.createdAt <= 0) {
     errors.push("Project createdAt must be a positive timestamp.");
   }
@@ -0,0 +1,63 @@
+import type { TaskRuntime } from "./task";
+import { findSimilarTasks } from "./task-similarity";
+
+/** How long past runs took and how often they succeeded, shown before starting a new one. */
+export type RunHistoryHint = {
+  /** "similar" when enough past tasks resemble the prompt, otherwise the whole project. */
+  scope: "similar" | "project";
+  sampleSize: number;
+  averageRunMs?: number;
+  successRate?: number;
+};
+
+// Looser than the duplicate warning: this only needs tasks of the same kind, not the same task.
+const SIMILAR_RUN_THRESHOLD = 0.3;
+const MIN_SIMILAR_RUNS = 3;
+
+/**
+ * Summarizes the project's finished runs (completed or failed), preferring the
+ * ones whose prompt resembles `prompt`; undefined until the project has any.
+ */
+export function computeRunHistoryHint(
+  tasks: readonly TaskRuntime[],
+  projectId: string,
+  prompt: string,
+): RunHistoryHint | undefined {
+  const finished = tasks.filter(
+    (task) => task.projectId === projectId && (task.state === "completed" || task.state === "failed"),
+  );
+  const similar = findSimilarTasks(finished, prompt, SIMILAR_RUN_THRESHOLD).map((match) => match.task);
+  if (similar.length >= MIN_SIMILAR_RUNS) {
+    return summarizeRuns(similar, "similar");
+  }
+
+  return finished.length > 0 ? summarizeRuns(finished, "project") : undefined;
+}
+
+export function formatRunHistoryHint(hint: RunHistoryHint): string {
+  const subject = hint.scope === "similar" ? "Similar tasks" : "Tasks in this project";
+  const duration =
+    hint.averageRunMs === undefined ? "took an unknown time" : `took ~${formatRunMinutes(hint.averageRunMs)}`;
+  const success = hint.successRate === undefined ? "" : `, ${Math.round(hint.successRate * 100)}% success`;
+  return `${subject} ${duration}${success} (${hint.sampleSize} run${hint.sampleSize === 1 ? "" : "s"})`;
+}
+
+function summarizeRuns(tasks: TaskRuntime[], scope: RunHistoryHint["scope"]): RunHistoryHint {
+  const durations = tasks
+    .filter((task) => task.runStartedAt !== undefined && task.runFinishedAt !== undefined)
+    .map((task) => task.runFinishedAt! - task.runStartedAt!)
+    .filter((duration) => duration >= 0);
+
+  return {
+    scope,
+    sampleSize: tasks.length,
+    averageRunMs:
+      durations.length > 0 ? durations.reduce((total, duration) => total + duration, 0) / durations.length : undefined,
+    successRate: tasks.filter((task) => task.state === "completed").length / tasks.length,
+  };
+}
+
+function formatRunMinutes(durationMs: number): string {
+  const minutes = Math.max(1, Math.round(durationMs / 60_000));
+  return minutes < 60 ? `${minutes} min` : `${Math.floor(minutes / 60)}h${String(minutes % 60).padStart(2, "0")}m`;
+}
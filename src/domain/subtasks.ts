@@ -0,0 +1,115 @@
+import type { TaskRuntime } from "./task";
+
+/** How many of a task's subtasks, at any depth, are completed. */
+export type SubtaskSummary = {
+  completed: number;
+  total: number;
+};
+
+/** Direct subtasks of `parentTaskId`, keeping only the latest attempt of retried ones. */
+export function listSubtasks(tasks: readonly TaskRuntime[], parentTaskId: string): TaskRuntime[] {
+  return latestAttempts(tasks.filter((task) => task.parentTaskId === parentTaskId));
+}
+
+/**
+ * Rolls completion up from every level below `taskId`, so a parent of two
+ * subtasks that have three subtasks each counts eight. Cancelled subtasks
+ * are dropped from the total; undefined when the task has none.
+ */
+export function summarizeSubtasks(tasks: readonly TaskRuntime[], taskId: string): SubtaskSummary | undefined {
+  const summary: SubtaskSummary = { completed: 0, total: 0 };
+  const pending = [taskId];
+  const seen = new Set<string>();
+  while (pending.length > 0) {
+    const current = pending.pop()!;
+    if (seen.has(current)) {
+      continue;
+    }
+
+    seen.add(current);
+    for (const subtask of listSubtasks(tasks, current)) {
+      if (subtask.state !== "cancelled") {
+        summary.total += 1;
+        summary.completed += subtask.state === "completed" ? 1 : 0;
+      }
+      pending.push(subtask.taskId);
+    }
+  }
+
+  return summary.total > 0 ? summary : undefined;
+}
+
+export function formatSubtaskSummary(summary: SubtaskSummary): string {
+  return `${summary.completed}/${summary.total}`;
+}
+
+/**
+ * Reorders `tasks` so each task is directly followed by its subtasks, depth
+ * first. Tasks whose parent is not in the list keep their place, and subtasks
+ * of a parent in `collapsedTaskIds` are left out.
+ */
+export function orderTasksByHierarchy(
+  tasks: readonly TaskRuntime[],
+  collapsedTaskIds: ReadonlySet<string> = new Set(),
+): TaskRuntime[] {
+  const taskIds = new Set(tasks.map((task) => task.taskId));
+  const childrenByParent = new Map<string, TaskRuntime[]>();
+  for (const task of tasks) {
+    if (task.parentTaskId && task.parentTaskId !== task.taskId && taskIds.has(task.parentTaskId)) {
+      childrenByParent.set(task.parentTaskId, [...(childrenByParent.get(task.parentTaskId) ?? []), task]);
+    }
+  }
+
+  const ordered: TaskRuntime[] = [];
+  const visited = new Set<string>();
+  const visit = (task: TaskRuntime) => {
+    if (visited.has(task.taskId)) {
+      return;
+    }
+
+    visited.add(task.taskId);
+    ordered.push(task);
+    if (!collapsedTaskIds.has(task.taskId)) {
+      for (const child of childrenByParent.get(task.taskId) ?? []) {
+        visit(child);
+      }
+    }
+  };
+
+  for (const task of tasks) {
+    if (!task.parentTaskId || !taskIds.has(task.parentTaskId)) {
+      visit(task);
+    }
+  }
+
+  return ordered;
+}
+
+/** How many of the task's ancestors are in `tasks`; used to indent subtasks under their parent. */
+export function getSubtaskDepth(task: TaskRuntime, tasks: readonly TaskRuntime[]): number {
+  const tasksById = new Map(tasks.map((candidate) => [candidate.taskId, candidate]));
+  let depth = 0;
+  let parent = task.parentTaskId ? tasksById.get(task.parentTaskId) : undefined;
+  const seen = new Set([task.taskId]);
+  while (parent && !seen.has(parent.taskId)) {
+    seen.add(parent.taskId);
+    depth += 1;
+    parent = parent.parentTaskId ? tasksById.get(parent.parentTaskId) : undefined;
+  }
+
+  return depth;
+}
+
+// Retries carry the original's parent, so only the newest run of each chain counts.
+function latestAttempts(tasks: TaskRuntime[]): TaskRuntime[] {
+  const latestByChain = new Map<string, TaskRuntime>();
+  for (const task of tasks) {
+    const chainId = task.retryOf ?? task.taskId;
+    const current = latestByChain.get(chainId);
+    if (!current || (task.retryAttempt ?? 0) > (current.retryAttempt ?? 0)) {
+      latestByChain.set(chainId, task);
+    }
+  }
+
+  return tasks.filter((task) => latestByChain.get(task.retryOf ?? task.taskId) === task);
+}
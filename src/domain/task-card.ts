@@ -0,0 +1,30 @@
+/** Optional parts of a board card; the title is always shown. */
+export const CARD_FIELDS = [
+  "status",
+  "short-code",
+  "estimate",
+  "session",
+  "conflict-risk",
+  "pull-request",
+  "progress",
+  "subtasks",
+  "priority",
+  "labels",
+] as const;
+
+export type CardField = (typeof CARD_FIELDS)[number];
+
+export const DEFAULT_CARD_FIELDS: readonly CardField[] = [
+  "status",
+  "short-code",
+  "conflict-risk",
+  "pull-request",
+  "progress",
+  "subtasks",
+  "priority",
+  "labels",
+];
+
+export function isCardField(value: unknown): value is CardField {
+  return typeof value === "string" && (CARD_FIELDS as readonly string[]).includes(value);
+}
@@ -0,0 +1,40 @@
+import { lastAssistantText, type ConversationSdkSessionMessage } from "./conversation";
+
+/** Who wrote a comment: a person reviewing the board, or the agent summarizing its run. */
+export const TASK_COMMENT_KINDS = ["user", "agent"] as const;
+
+export type TaskCommentKind = (typeof TASK_COMMENT_KINDS)[number];
+
+/** One entry in a task's discussion thread; `body` is markdown. */
+export type TaskComment = {
+  /** Numbered from 1 within the task, so `:comment rm 2` can refer to it. */
+  id: number;
+  kind: TaskCommentKind;
+  author: string;
+  body: string;
+  createdAt: number;
+  updatedAt?: number;
+};
+
+export const TASK_COMMENT_MAX_LENGTH = 4000;
+
+export function isTaskCommentKind(value: unknown): value is TaskCommentKind {
+  return typeof value === "string" && (TASK_COMMENT_KINDS as readonly string[]).includes(value);
+}
+
+export function nextTaskCommentId(comments: readonly TaskComment[] | undefined): number {
+  return (comments ?? []).reduce((highest, comment) => Math.max(highest, comment.id), 0) + 1;
+}
+
+/**
+ * The agent's closing message, used as the comment it leaves when a run
+ * reaches review; undefined when the run ended without any text.
+ */
+export function summarizeRunForComment(messages: ConversationSdkSessionMessage[]): string | undefined {
+  const text = lastAssistantText(messages);
+  if (!text) {
+    return undefined;
+  }
+
+  return text.length <= TASK_COMMENT_MAX_LENGTH ? text : `${text.slice(0, TASK_COMMENT_MAX_LENGTH - 1).trimEnd()}…`;
+}
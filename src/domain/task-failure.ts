@@ -0,0 +1,119 @@
+import type { IkanbanErrorCode } from "./errors";
+import type { TaskRuntime } from "./task";
+
+export const TASK_FAILURE_CLASSES = [
+  "spawn_error",
+  "timeout",
+  "agent_error",
+  "verify_failed",
+  "disconnect",
+  "unknown",
+] as const;
+
+export type TaskFailureClass = (typeof TASK_FAILURE_CLASSES)[number];
+
+export type TaskFailureGroup = {
+  failureClass: TaskFailureClass;
+  projectId: string;
+  tasks: TaskRuntime[];
+};
+
+export const TASK_FAILURE_CLASS_LABELS: Record<TaskFailureClass, string> = {
+  spawn_error: "Spawn error",
+  timeout: "Timeout",
+  agent_error: "Agent error",
+  verify_failed: "Verify failed",
+  disconnect: "Disconnect",
+  unknown: "Unclassified",
+};
+
+// Failures a fresh run can plausibly get past; the rest would fail the same way again.
+export const RETRYABLE_FAILURE_CLASSES: readonly TaskFailureClass[] = [
+  "timeout",
+  "disconnect",
+  "agent_error",
+  "unknown",
+];
+
+// Checked in order; the first matching class wins.
+const FAILURE_PATTERNS: ReadonlyArray<[TaskFailureClass, RegExp]> = [
+  ["disconnect", /ECONNREFUSED|ECONNRESET|EPIPE|socket hang up|fetch failed|disconnect|connection (?:closed|lost|refused)|network/i],
+  ["timeout", /ETIMEDOUT|timed? ?out|deadline exceeded/i],
+  ["spawn_error", /ENOENT|EACCES|spawn|worktree|not a git repository|git (?:worktree|checkout|branch)/i],
+  ["verify_failed", /verif(?:y|ication)|tests? failed|lint(?:ing)? failed|check(?:s)? failed|exit code [1-9]/i],
+  ["agent_error", /session|agent|model|provider|opencode|prompt|rate limit|\b(?:4|5)\d\d\b/i],
+];
+
+// Error codes are authoritative; message patterns only cover failures without one.
+const FAILURE_CLASS_BY_ERROR_CODE: Partial<Record<IkanbanErrorCode, TaskFailureClass>> = {
+  preflight_failed: "spawn_error",
+  executor_unavailable: "spawn_error",
+  hook_vetoed: "verify_failed",
+  scope_violation: "verify_failed",
+  timeout: "timeout",
+};
+
+export function classifyTaskFailure(
+  error: string | undefined,
+  errorCode?: IkanbanErrorCode,
+): TaskFailureClass {
+  const codedClass = errorCode ? FAILURE_CLASS_BY_ERROR_CODE[errorCode] : undefined;
+  if (codedClass) {
+    return codedClass;
+  }
+
+  if (!error || error.trim().length === 0) {
+    return "unknown";
+  }
+
+  for (const [failureClass, pattern] of FAILURE_PATTERNS) {
+    if (pattern.test(error)) {
+      return failureClass;
+    }
+  }
+
+  return "unknown";
+}
+
+/** The original task and all its retries, oldest attempt first. */
+export function listRetryChain(task: TaskRuntime, tasks: TaskRuntime[]): TaskRuntime[] {
+  const rootId = task.retryOf ?? task.taskId;
+  return tasks
+    .filter((candidate) => candidate.taskId === rootId || candidate.retryOf === rootId)
+    .sort(
+      (left, right) =>
+        (left.retryAttempt ?? 0) - (right.retryAttempt ?? 0) || left.createdAt - right.createdAt,
+    );
+}
+
+export function groupFailedTasks(tasks: TaskRuntime[]): TaskFailureGroup[] {
+  const groups = new Map<string, TaskFailureGroup>();
+
+  for (const task of tasks) {
+    if (task.state !== "failed") {
+      continue;
+    }
+
+    const failureClass = classifyTaskFailure(task.error, task.errorCode);
+    const key = `${failureClass}\u0000${task.projectId}`;
+    const group = groups.get(key);
+    if (group) {
+      group.tasks.push(task);
+    } else {
+      groups.set(key, { failureClass, projectId: task.projectId, tasks: [task] });
+    }
+  }
+
+  return [...groups.values()]
+    .map((group) => ({
+      ...group,
+      tasks: [...group.tasks].sort((left, right) => right.updatedAt - left.updatedAt),
+    }))
+    .sort(
+      (left, right) =>
+        right.tasks.length - left.tasks.length ||
+        TASK_FAILURE_CLASSES.indexOf(left.failureClass) -
+          TASK_FAILURE_CLASSES.indexOf(right.failureClass) ||
+        left.projectId.localeCompare(right.projectId),
+    );
+}
@@ -0,0 +1,68 @@
+import type { TaskRuntime } from "./task";
+
+/** Steps of the plan (todo list) the agent keeps for the current run. */
+export type TaskPlanProgress = {
+  completed: number;
+  total: number;
+};
+
+export type TaskProgressEstimate = {
+  /** 0..1 */
+  fraction: number;
+  source: "plan" | "elapsed";
+};
+
+// An elapsed-time guess never claims a run is done; only the session going idle does.
+const MAX_ELAPSED_FRACTION = 0.95;
+
+/** Reads a `todo.updated` event; cancelled steps do not count toward the total. */
+export function parseTodoUpdated(properties: unknown): TaskPlanProgress | undefined {
+  if (!properties || typeof properties !== "object") {
+    return undefined;
+  }
+
+  const todos = (properties as Record<string, unknown>).todos;
+  if (!Array.isArray(todos)) {
+    return undefined;
+  }
+
+  const statuses = todos.map((todo) =>
+    todo && typeof todo === "object" ? (todo as Record<string, unknown>).status : undefined,
+  );
+  return {
+    completed: statuses.filter((status) => status === "completed").length,
+    total: statuses.filter((status) => status !== "cancelled").length,
+  };
+}
+
+/**
+ * Rough progress of a running task: the share of plan steps done when the
+ * agent keeps a plan, otherwise elapsed time against the average finished run.
+ */
+export function estimateTaskProgress(
+  task: TaskRuntime,
+  averageRunMs: number | undefined,
+  now: number = Date.now(),
+): TaskProgressEstimate | undefined {
+  if (task.state !== "running") {
+    return undefined;
+  }
+
+  if (task.planProgress && task.planProgress.total > 0) {
+    return { fraction: task.planProgress.completed / task.planProgress.total, source: "plan" };
+  }
+
+  if (averageRunMs === undefined || averageRunMs <= 0 || task.runStartedAt === undefined) {
+    return undefined;
+  }
+
+  return {
+    fraction: Math.min(Math.max(now - task.runStartedAt, 0) / averageRunMs, MAX_ELAPSED_FRACTION),
+    source: "elapsed",
+  };
+}
+
+export function formatProgressBar(fraction: number, width = 10): string {
+  const filled = Math.round(Math.min(Math.max(fraction, 0), 1) * width);
+  return `${"█".repeat(filled)}${"░".repeat(width - filled)} ${Math.round(fraction * 100)}%`;
+}
@@ -0,0 +1,93 @@
+import type { TaskRuntime } from "./task";
+
+export type QueueSnapshot = {
+  queued: TaskRuntime[];
+  running: TaskRuntime[];
+  maxConcurrent: number;
+  /** Cap on running tasks within one project; undefined means only the global limit applies. */
+  maxConcurrentPerProject?: number;
+  /** Projects with their own cap, which takes the place of `maxConcurrentPerProject`. */
+  maxConcurrentByProjectId?: Record<string, number>;
+};
+
+export type QueueEstimate = {
+  task: TaskRuntime;
+  position: number;
+  estimatedStartAt?: number;
+};
+
+/** Mean wall time of finished agent runs; undefined until at least one run has finished. */
+export function averageRunDurationMs(tasks: TaskRuntime[]): number | undefined {
+  const durations = tasks
+    .filter((task) => task.runStartedAt !== undefined && task.runFinishedAt !== undefined)
+    .map((task) => task.runFinishedAt! - task.runStartedAt!)
+    .filter((duration) => duration >= 0);
+  if (durations.length === 0) {
+    return undefined;
+  }
+
+  return durations.reduce((total, duration) => total + duration, 0) / durations.length;
+}
+
+/**
+ * Simulates the scheduler: each concurrency slot frees up when its running task
+ * is expected to finish, and queued tasks take the earliest free slot in order.
+ */
+export function estimateQueueStartTimes(
+  snapshot: QueueSnapshot,
+  averageRunMs: number | undefined,
+  now: number = Date.now(),
+): QueueEstimate[] {
+  if (averageRunMs === undefined) {
+    return snapshot.queued.map((task, index) => ({ task, position: index + 1 }));
+  }
+
+  const slots = snapshot.running
+    .map((task) => Math.max((task.runStartedAt ?? now) + averageRunMs, now))
+    .slice(0, snapshot.maxConcurrent);
+  while (slots.length < snapshot.maxConcurrent) {
+    slots.push(now);
+  }
+
+  // Per-project slots only matter once a project has reached its own cap.
+  const projectSlots = new Map<string, number[]>();
+  for (const task of snapshot.running) {
+    const endsAt = Math.max((task.runStartedAt ?? now) + averageRunMs, now);
+    projectSlots.set(task.projectId, [...(projectSlots.get(task.projectId) ?? []), endsAt]);
+  }
+
+  return snapshot.queued.map((task, index) => {
+    slots.sort((left, right) => left - right);
+    const ownSlots = (projectSlots.get(task.projectId) ?? []).sort((left, right) => left - right);
+    const projectLimit = getProjectConcurrencyLimit(snapshot, task.projectId);
+    const projectFull = projectLimit !== undefined && ownSlots.length >= projectLimit;
+    const estimatedStartAt = Math.max(slots[0] ?? now, projectFull ? (ownSlots[0] ?? now) : now);
+    slots[0] = estimatedStartAt + averageRunMs;
+    if (projectFull) {
+      ownSlots[0] = estimatedStartAt + averageRunMs;
+    } else {
+      ownSlots.push(estimatedStartAt + averageRunMs);
+    }
+    projectSlots.set(task.projectId, ownSlots);
+    return { task, position: index + 1, estimatedStartAt };
+  });
+}
+
+export function getProjectConcurrencyLimit(snapshot: QueueSnapshot, projectId: string): number | undefined {
+  return snapshot.maxConcurrentByProjectId?.[projectId] ?? snapshot.maxConcurrentPerProject;
+}
+
+export function formatEstimatedStart(estimatedStartAt: number | undefined, now: number = Date.now()): string {
+  if (estimatedStartAt === undefined) {
+    return "start unknown";
+  }
+
+  const remainingMinutes = Math.round((estimatedStartAt - now) / 60_000);
+  if (remainingMinutes <= 0) {
+    return "starts next";
+  }
+
+  return remainingMinutes < 60
+    ? `starts in ~${remainingMinutes}m`
+    : `starts in ~${Math.floor(remainingMinutes / 60)}h${String(remainingMinutes % 60).padStart(2, "0")}m`;
+}
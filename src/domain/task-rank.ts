@@ -0,0 +1,49 @@
+import { comparePriority, type TaskRuntime } from "./task";
+
+// Ranks are base-36 strings compared character by character, so a rank can
+// always be found between two others by growing it one digit.
+const RANK_DIGITS = "0123456789abcdefghijklmnopqrstuvwxyz";
+
+/** A rank that sorts after `before` and before `after`; either end may be open. */
+export function rankBetween(before: string | undefined, after: string | undefined): string {
+  let rank = "";
+  let upper = after;
+  for (let index = 0; ; index += 1) {
+    const low = before !== undefined && index < before.length ? RANK_DIGITS.indexOf(before[index]!) : 0;
+    const high = upper !== undefined && index < upper.length ? RANK_DIGITS.indexOf(upper[index]!) : RANK_DIGITS.length;
+    if (high - low > 1) {
+      return rank + RANK_DIGITS[Math.floor((low + high) / 2)];
+    }
+
+    rank += RANK_DIGITS[low];
+    if (high - low === 1) {
+      // Already below `after` at this digit, so later digits are free.
+      upper = undefined;
+    }
+  }
+}
+
+/** `count` evenly spaced ranks, in order, for tasks that have none yet. */
+export function rankSequence(count: number): string[] {
+  const width = Math.max(1, Math.ceil(Math.log(count + 1) / Math.log(RANK_DIGITS.length)));
+  const space = RANK_DIGITS.length ** width;
+  return Array.from({ length: count }, (_, index) =>
+    Math.floor(((index + 1) * space) / (count + 1))
+      .toString(RANK_DIGITS.length)
+      .padStart(width, "0"),
+  );
+}
+
+/** Ranked tasks come first in rank order; unranked ones keep their existing order after them. */
+export function compareRank(left: TaskRuntime, right: TaskRuntime): number {
+  if (left.rank === undefined || right.rank === undefined) {
+    return left.rank === undefined ? (right.rank === undefined ? 0 : 1) : -1;
+  }
+
+  return left.rank < right.rank ? -1 : left.rank > right.rank ? 1 : 0;
+}
+
+/** Order of cards within a board column: priority first, then the manual rank. */
+export function compareBoardOrder(left: TaskRuntime, right: TaskRuntime): number {
+  return comparePriority(left, right) || compareRank(left, right);
+}
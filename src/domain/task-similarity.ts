@@ -0,0 +1,60 @@
+import type { TaskRuntime } from "./task";
+
+export type SimilarTaskMatch = {
+  task: TaskRuntime;
+  score: number;
+};
+
+const DEFAULT_SIMILARITY_THRESHOLD = 0.5;
+const MIN_TOKEN_LENGTH = 3;
+
+export function findSimilarTasks(
+  tasks: readonly TaskRuntime[],
+  text: string,
+  threshold: number = DEFAULT_SIMILARITY_THRESHOLD,
+): SimilarTaskMatch[] {
+  const queryTokens = tokenize(text);
+  if (queryTokens.size === 0) {
+    return [];
+  }
+
+  return tasks
+    .map((task) => ({
+      task,
+      score: diceCoefficient(queryTokens, tokenize(describeTask(task))),
+    }))
+    .filter((match) => match.score >= threshold)
+    .sort((left, right) => right.score - left.score);
+}
+
+function describeTask(task: TaskRuntime): string {
+  const parts = [task.title, task.prompt].filter(
+    (part): part is string => typeof part === "string" && part.length > 0,
+  );
+
+  return parts.length > 0 ? parts.join(" ") : task.taskId.replace(/[-_]+/g, " ");
+}
+
+function tokenize(text: string): Set<string> {
+  return new Set(
+    text
+      .toLowerCase()
+      .split(/[^a-z0-9]+/)
+      .filter((token) => token.length >= MIN_TOKEN_LENGTH),
+  );
+}
+
+function diceCoefficient(left: Set<string>, right: Set<string>): number {
+  if (left.size === 0 || right.size === 0) {
+    return 0;
+  }
+
+  let shared = 0;
+  for (const token of left) {
+    if (right.has(token)) {
+      shared += 1;
+    }
+  }
+
+  return (2 * shared) / (left.size + right.size);
+}
@@ -0,0 +1,44 @@
+export type TaskWatchdogReason = "timeout" | "stall";
+
+export type TaskWatchdogInput = {
+  runStartedAt: number;
+  lastOutputAt: number;
+  timeoutMs?: number;
+  stallTimeoutMs?: number;
+  now: number;
+};
+
+export type TaskWatchdogVerdict = {
+  reason: TaskWatchdogReason;
+  limitMs: number;
+};
+
+/** Returns why a running task should be stopped, or undefined while it is within both limits. */
+export function checkTaskWatchdog(input: TaskWatchdogInput): TaskWatchdogVerdict | undefined {
+  if (input.timeoutMs !== undefined && input.now - input.runStartedAt >= input.timeoutMs) {
+    return { reason: "timeout", limitMs: input.timeoutMs };
+  }
+
+  if (input.stallTimeoutMs !== undefined && input.now - input.lastOutputAt >= input.stallTimeoutMs) {
+    return { reason: "stall", limitMs: input.stallTimeoutMs };
+  }
+
+  return undefined;
+}
+
+export function describeWatchdogFailure(verdict: TaskWatchdogVerdict): string {
+  return verdict.reason === "timeout"
+    ? `Run timed out after ${formatWatchdogLimit(verdict.limitMs)}.`
+    : `Run stalled: no agent output for ${formatWatchdogLimit(verdict.limitMs)}.`;
+}
+
+export function formatWatchdogLimit(limitMs: number): string {
+  const totalMinutes = Math.round(limitMs / 60_000);
+  if (totalMinutes < 1) {
+    return `${Math.max(Math.round(limitMs / 1000), 1)}s`;
+  }
+
+  return totalMinutes < 60
+    ? `${totalMinutes}m`
+    : `${Math.floor(totalMinutes / 60)}h${String(totalMinutes % 60).padStart(2, "0")}m`;
+}
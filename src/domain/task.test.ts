@@ -0,0 +1,54 @@
+import { describe, expect, test } from "bun:test";
+
+import { listOpenBlockers, type TaskRuntime } from "./task";
+
+function task(taskId: string, overrides: Partial<TaskRuntime> = {}): TaskRuntime {
+  return { taskId, projectId: "web", state: "queued", createdAt: 1, updatedAt: 1, ...overrides };
+}
+
+describe("listOpenBlockers", () => {
+  test("keeps a task blocked while its blocker is open", () => {
+    const blocker = task("a", { state: "running" });
+    const blocked = task("b", { blockedBy: ["a"] });
+
+    expect(listOpenBlockers(blocked, [blocker, blocked])).toEqual([blocker]);
+  });
+
+  test("treats completed, cancelled and deleted blockers as resolved", () => {
+    const blocked = task("d", { blockedBy: ["a", "b", "gone"] });
+    const tasks = [task("a", { state: "completed" }), task("b", { state: "cancelled" }), blocked];
+
+    expect(listOpenBlockers(blocked, tasks)).toEqual([]);
+  });
+
+  test("waits on a failed blocker that has not been retried", () => {
+    const blocker = task("a", { state: "failed" });
+    const blocked = task("b", { blockedBy: ["a"] });
+
+    expect(listOpenBlockers(blocked, [blocker, blocked])).toEqual([blocker]);
+  });
+
+  test("follows a failed blocker to its latest retry", () => {
+    const blocked = task("b", { blockedBy: ["a"] });
+    const failed = task("a", { state: "failed" });
+    const firstRetry = task("a-r1", { state: "failed", retryOf: "a", retryAttempt: 1 });
+    const secondRetry = task("a-r2", { state: "running", retryOf: "a", retryAttempt: 2 });
+
+    expect(listOpenBlockers(blocked, [failed, firstRetry, secondRetry, blocked])).toEqual([secondRetry]);
+    expect(
+      listOpenBlockers(blocked, [failed, firstRetry, { ...secondRetry, state: "completed" }, blocked]),
+    ).toEqual([]);
+  });
+
+  test("follows a blocker that is itself a retry to later attempts", () => {
+    const blocked = task("b", { blockedBy: ["a-r1"] });
+    const tasks = [
+      task("a", { state: "failed" }),
+      task("a-r1", { state: "failed", retryOf: "a", retryAttempt: 1 }),
+      task("a-r2", { state: "completed", retryOf: "a", retryAttempt: 2 }),
+      blocked,
+    ];
+
+    expect(listOpenBlockers(blocked, tasks)).toEqual([]);
+  });
+});
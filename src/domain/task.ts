@@ -1,3 +1,8 @@
+import type { TaskConflictRisk } from "./conflict-risk";
+import { IkanbanError, type IkanbanErrorCode } from "./errors";
+import type { TaskComment } from "./task-comment";
+import type { TaskPlanProgress } from "./task-progress";
+
 export const TASK_STATES = [
   "queued",
   "creating_worktree",
@@ -5,18 +10,148 @@ export const TASK_STATES = [
   "review",
   "completed",
   "failed",
+  "cancelled",
   "cleaning",
 ] as const;
 
 export type TaskState = (typeof TASK_STATES)[number];
 
+export type TaskReviewDecision = "approved" | "changes_requested";
+
+export type TaskReviewRecord = {
+  reviewer: string;
+  requestedAt: number;
+  decision?: TaskReviewDecision;
+  decidedAt?: number;
+  note?: string;
+};
+
+export type TaskRemoteRef = {
+  remote: string;
+  ref: string;
+  pushedAt: number;
+};
+
+export const PULL_REQUEST_STATES = ["open", "closed", "merged"] as const;
+
+/** GitHub pull request opened for the task branch. */
+export type TaskPullRequest = {
+  /** `owner/repo` */
+  repository: string;
+  number: number;
+  url: string;
+  state: (typeof PULL_REQUEST_STATES)[number];
+  openedAt: number;
+  checkedAt: number;
+};
+
+/** Lowest first; `P` on the board cycles through them in this order. */
+export const TASK_PRIORITIES = ["low", "medium", "high", "urgent"] as const;
+
+export type TaskPriority = (typeof TASK_PRIORITIES)[number];
+
+export function isTaskPriority(value: unknown): value is TaskPriority {
+  return typeof value === "string" && (TASK_PRIORITIES as readonly string[]).includes(value);
+}
+
+/** Tasks without a priority rank as medium. */
+export function comparePriority(left: TaskRuntime, right: TaskRuntime): number {
+  return TASK_PRIORITIES.indexOf(right.priority ?? "medium") - TASK_PRIORITIES.indexOf(left.priority ?? "medium");
+}
+
+export function nextTaskPriority(priority: TaskPriority | undefined): TaskPriority {
+  return TASK_PRIORITIES[(TASK_PRIORITIES.indexOf(priority ?? "medium") + 1) % TASK_PRIORITIES.length]!;
+}
+
+export const MERGE_STRATEGIES = ["squash", "fast-forward"] as const;
+
+export type MergeStrategy = (typeof MERGE_STRATEGIES)[number];
+
+/** How a task branch landed on the project's default branch. */
+export type TaskMergeRecord = {
+  strategy: MergeStrategy;
+  branch: string;
+  targetBranch: string;
+  /** Tip of the target branch after the merge. */
+  commit: string;
+  mergedAt: number;
+};
+
+export type TaskHookAnnotation = {
+  point: string;
+  hook: string;
+  message: string;
+  at: number;
+};
+
+/** Snapshot commit of a worktree on the task's hidden checkpoint ref. */
+export type TaskCheckpoint = {
+  commit: string;
+  tree: string;
+  label: string;
+  createdAt: number;
+};
+
+export type TaskPreflightCheck = {
+  id: string;
+  status: "pass" | "fail" | "skip";
+  message: string;
+};
+
 export type TaskRuntime = {
   taskId: string;
   projectId: string;
   state: TaskState;
+  title?: string;
+  shortCode?: string;
+  prompt?: string;
+  parentTaskId?: string;
+  /** Tasks that must be completed before this one may start. */
+  blockedBy?: string[];
+  /** First task of the retry chain this task re-runs; unset on the original. */
+  retryOf?: string;
+  retryAttempt?: number;
+  milestoneId?: string;
+  /** Ids of the project labels attached to the task. */
+  labelIds?: string[];
+  priority?: TaskPriority;
+  /** Manual position within its board column among tasks of the same priority; see `task-rank`. */
+  rank?: string;
+  estimate?: number;
+  /** Repository-relative directory the task is limited to, e.g. `crates/ikanban-tui`. */
+  pathScope?: string;
+  /** Wall-clock limit for one run of this task; overrides the orchestrator default. */
+  timeoutMs?: number;
+  promptContext?: {
+    treeSummary: string[];
+    relevantFiles: string[];
+  };
   worktreeDirectory?: string;
+  /** Set once the worktree is removed; `worktreeDirectory` is kept as a record of where it was. */
+  worktreeRemovedAt?: number;
   sessionID?: string;
+  sessionName?: string;
+  /** Instruction file (AGENTS.md / CLAUDE.md) found in the worktree when the agent started. */
+  agentInstructions?: string;
+  runStartedAt?: number;
+  runFinishedAt?: number;
+  /** Plan steps the agent reported for the current run. */
+  planProgress?: TaskPlanProgress;
+  review?: TaskReviewRecord;
+  remoteRef?: TaskRemoteRef;
+  merge?: TaskMergeRecord;
+  pullRequest?: TaskPullRequest;
+  preflight?: TaskPreflightCheck[];
+  hookAnnotations?: TaskHookAnnotation[];
+  checkpoints?: TaskCheckpoint[];
+  /** Discussion thread, oldest first. */
+  comments?: TaskComment[];
+  worktreeChangedAt?: number;
+  conflictRisk?: TaskConflictRisk;
   error?: string;
+  errorCode?: IkanbanErrorCode;
+  cancelReason?: string;
+  cancelledAt?: number;
   model?: {
     providerID: string;
     modelID: string;
@@ -26,20 +161,56 @@ export type TaskRuntime = {
 };
 
 export const TASK_STATE_TRANSITIONS: Record<TaskState, readonly TaskState[]> = {
-  queued: ["creating_worktree", "failed"],
-  creating_worktree: ["running", "failed"],
-  running: ["review", "failed", "cleaning"],
-  review: ["running", "completed", "failed", "cleaning"],
+  queued: ["creating_worktree", "failed", "cancelled"],
+  creating_worktree: ["running", "failed", "cancelled"],
+  running: ["review", "failed", "cancelled", "cleaning"],
+  review: ["running", "completed", "failed", "cancelled", "cleaning"],
   completed: ["cleaning"],
-  failed: ["cleaning"],
+  failed: ["cancelled", "cleaning"],
+  cancelled: [],
   cleaning: ["completed", "failed"],
 };
 
+const TASK_SHORT_CODE_PATTERN = /^([A-Z0-9]+)-([1-9][0-9]*)$/;
+
 type TransitionTaskRuntimeOptions = {
   at?: number;
   error?: string;
 };
 
+/**
+ * Per-project narrowing of TASK_STATE_TRANSITIONS. Rules can only remove
+ * built-in moves, and moving to "failed" always stays possible so errors are
+ * never stranded in a state.
+ */
+export type TaskTransitionRules = Partial<Record<TaskState, readonly TaskState[]>>;
+
+export function listNextTaskStates(from: TaskState, rules?: TaskTransitionRules): TaskState[] {
+  const allowed = rules?.[from];
+  return TASK_STATE_TRANSITIONS[from].filter(
+    (to) => !allowed || to === "failed" || allowed.includes(to),
+  );
+}
+
+export function assertTaskTransitionAllowed(
+  from: TaskState,
+  to: TaskState,
+  rules?: TaskTransitionRules,
+): void {
+  assertTaskStateTransition(from, to);
+
+  const nextStates = listNextTaskStates(from, rules);
+  if (nextStates.includes(to)) {
+    return;
+  }
+
+  throw new IkanbanError(
+    "invalid_state",
+    `Project rules do not allow ${from} -> ${to} (allowed: ${nextStates.join(", ") || "none"}).`,
+    { field: "state" },
+  );
+}
+
 export function canTransitionTaskState(from: TaskState, to: TaskState): boolean {
   return TASK_STATE_TRANSITIONS[from].includes(to);
 }
@@ -49,7 +220,9 @@ export function assertTaskStateTransition(from: TaskState, to: TaskState): void
     return;
   }
 
-  throw new Error(`Invalid task state transition: ${from} -> ${to}`);
+  throw new IkanbanError("invalid_state", `Invalid task state transition: ${from} -> ${to}`, {
+    field: "state",
+  });
 }
 
 export function transitionTaskState(
@@ -71,6 +244,100 @@ export function transitionTaskState(
   return nextTask;
 }
 
+export function buildTaskShortCodePrefix(projectId: string): string {
+  const prefix = projectId.toUpperCase().replace(/[^A-Z0-9]+/g, "").slice(0, 4);
+  return prefix || "TASK";
+}
+
+export function formatTaskShortCode(prefix: string, sequence: number): string {
+  return `${prefix}-${sequence}`;
+}
+
+export function parseTaskShortCode(
+  value: string,
+): { prefix: string; sequence: number } | undefined {
+  const match = TASK_SHORT_CODE_PATTERN.exec(value.trim().toUpperCase());
+  if (!match) {
+    return undefined;
+  }
+
+  const [, prefix, sequence] = match;
+  if (!prefix || !sequence) {
+    return undefined;
+  }
+
+  return { prefix, sequence: Number(sequence) };
+}
+
+/**
+ * Normalizes a task path scope to a repository-relative directory without
+ * leading `./` or trailing `/`. Empty input means "no scope".
+ */
+export function normalizePathScope(value: string | undefined): string | undefined {
+  const trimmed = value?.trim().replace(/\\/g, "/").replace(/^(\.\/)+/, "").replace(/\/+$/, "");
+  if (!trimmed || trimmed === ".") {
+    return undefined;
+  }
+
+  if (trimmed.startsWith("/") || trimmed.split("/").includes("..")) {
+    throw new IkanbanError("validation", `Path scope must be relative to the repository root: ${value}.`, {
+      field: "pathScope",
+    });
+  }
+
+  return trimmed;
+}
+
+export function isPathInScope(filePath: string, pathScope: string): boolean {
+  return filePath === pathScope || filePath.startsWith(`${pathScope}/`);
+}
+
+/**
+ * Blockers still holding `task` back. A retried blocker stands for its latest
+ * attempt, so a failure that was retried waits on the retry. Completed and
+ * cancelled blockers are resolved, and deleted ones no longer count.
+ */
+export function listOpenBlockers(task: TaskRuntime, tasks: readonly TaskRuntime[]): TaskRuntime[] {
+  return (task.blockedBy ?? []).flatMap((blockerId) => {
+    const blocker = tasks.find((candidate) => candidate.taskId === blockerId);
+    const attempt = blocker ? findLatestRetry(blocker, tasks) : undefined;
+    return attempt && attempt.state !== "completed" && attempt.state !== "cancelled" ? [attempt] : [];
+  });
+}
+
+/** The newest retry of `task`, or `task` itself when it has not been retried since. */
+export function findLatestRetry(task: TaskRuntime, tasks: readonly TaskRuntime[]): TaskRuntime {
+  const rootId = task.retryOf ?? task.taskId;
+  return tasks.reduce(
+    (latest, candidate) =>
+      candidate.retryOf === rootId && (candidate.retryAttempt ?? 0) > (latest.retryAttempt ?? 0) ? candidate : latest,
+    task,
+  );
+}
+
+/** Whether `task` would wait on itself, directly or through other blockers, if it were blocked by `blockerId`. */
+export function wouldCreateDependencyCycle(
+  taskId: string,
+  blockerId: string,
+  findTask: (taskId: string) => TaskRuntime | undefined,
+): boolean {
+  const pending = [blockerId];
+  const seen = new Set<string>();
+  while (pending.length > 0) {
+    const current = pending.pop()!;
+    if (current === taskId) {
+      return true;
+    }
+
+    if (!seen.has(current)) {
+      seen.add(current);
+      pending.push(...(findTask(current)?.blockedBy ?? []));
+    }
+  }
+
+  return false;
+}
+
 export function validateTaskRuntimeInvariants(task: TaskRuntime): string[] {
   const errors: string[] = [];
 
@@ -82,6 +349,22 @@ export function validateTaskRuntimeInvariants(task: TaskRuntime): string[] {
     errors.push("TaskRuntime projectId must be a non-empty string.");
   }
 
+  if (task.title !== undefined && task.title.trim().length === 0) {
+    errors.push("TaskRuntime title must be a non-empty string when provided.");
+  }
+
+  if (task.estimate !== undefined && (!Number.isFinite(task.estimate) || task.estimate < 0)) {
+    errors.push("TaskRuntime estimate must be a non-negative number when provided.");
+  }
+
+  if (task.timeoutMs !== undefined && (!Number.isFinite(task.timeoutMs) || task.timeoutMs <= 0)) {
+    errors.push("TaskRuntime timeoutMs must be a positive number when provided.");
+  }
+
+  if (task.shortCode !== undefined && !parseTaskShortCode(task.shortCode)) {
+    errors.push("TaskRuntime shortCode must look like PREFIX-123.");
+  }
+
   if (!Number.isFinite(task.createdAt) || task.createdAt <= 0) {
     errors.push("TaskRuntime createdAt must be a positive timestamp.");
   }
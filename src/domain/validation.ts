@@ -0,0 +1,57 @@
+import { IkanbanError } from "./errors";
+
+export const FIELD_LENGTH_LIMITS = {
+  name: 80,
+  title: 120,
+  prompt: 20_000,
+} as const;
+
+export type ValidationIssue = {
+  field: string;
+  message: string;
+};
+
+export function validateRequiredText(
+  value: string | undefined,
+  field: keyof typeof FIELD_LENGTH_LIMITS,
+  label: string,
+): ValidationIssue | undefined {
+  const trimmed = value?.trim() ?? "";
+  if (!trimmed) {
+    return { field, message: `${label} is required.` };
+  }
+
+  const limit = FIELD_LENGTH_LIMITS[field];
+  if (trimmed.length > limit) {
+    return { field, message: `${label} must be at most ${limit} characters (got ${trimmed.length}).` };
+  }
+
+  return undefined;
+}
+
+export function validateName(value: string | undefined, label = "Name"): ValidationIssue | undefined {
+  return validateRequiredText(value, "name", label);
+}
+
+export function validateTitle(value: string | undefined, label = "Title"): ValidationIssue | undefined {
+  return validateRequiredText(value, "title", label);
+}
+
+export function validatePrompt(value: string | undefined, label = "Prompt"): ValidationIssue | undefined {
+  return validateRequiredText(value, "prompt", label);
+}
+
+/** Throws the issue as a coded validation error so UIs can place it next to the field. */
+export function assertValid(issue: ValidationIssue | undefined): void {
+  if (issue) {
+    throw new IkanbanError("validation", issue.message, { field: issue.field });
+  }
+}
+
+export function toValidationIssue(error: unknown): ValidationIssue | undefined {
+  if (error instanceof IkanbanError && error.code === "validation" && error.field) {
+    return { field: error.field, message: error.message };
+  }
+
+  return undefined;
+}
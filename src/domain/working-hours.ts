@@ -0,0 +1,134 @@
+import { IkanbanError } from "./errors";
+import type { TaskRuntime } from "./task";
+
+export const WEEKDAYS = ["sun", "mon", "tue", "wed", "thu", "fri", "sat"] as const;
+
+/**
+ * Local-time window in which a project's queued tasks start and its
+ * notifications are sent. `end` before `start` spans midnight, and equal
+ * times mean the whole day.
+ */
+export type WorkingHours = {
+  /** `HH:MM`, 24-hour local time. */
+  start: string;
+  end: string;
+  /** Days the window opens on, 0 for Sunday; every day when unset. */
+  days?: number[];
+};
+
+export function parseClockTime(value: string): number | undefined {
+  const match = /^(\d{1,2}):(\d{2})$/.exec(value.trim());
+  if (!match) {
+    return undefined;
+  }
+
+  const hours = Number(match[1]);
+  const minutes = Number(match[2]);
+  return hours < 24 && minutes < 60 ? hours * 60 + minutes : undefined;
+}
+
+export function isWorkingHours(value: unknown): value is WorkingHours {
+  if (!value || typeof value !== "object") {
+    return false;
+  }
+
+  const record = value as Record<string, unknown>;
+  return (
+    typeof record.start === "string" &&
+    parseClockTime(record.start) !== undefined &&
+    typeof record.end === "string" &&
+    parseClockTime(record.end) !== undefined &&
+    (record.days === undefined ||
+      (Array.isArray(record.days) &&
+        record.days.every((day) => typeof day === "number" && Number.isInteger(day) && day >= 0 && day < 7)))
+  );
+}
+
+export function isWithinWorkingHours(hours: WorkingHours, at: Date): boolean {
+  const start = parseClockTime(hours.start) ?? 0;
+  const end = parseClockTime(hours.end) ?? 0;
+  const minutes = at.getHours() * 60 + at.getMinutes();
+  const day = at.getDay();
+  const opensOn = (weekday: number) => hours.days === undefined || hours.days.includes(weekday);
+
+  if (start === end) {
+    return opensOn(day);
+  }
+
+  if (start < end) {
+    return opensOn(day) && minutes >= start && minutes < end;
+  }
+
+  // Overnight: the evening belongs to today's window, the small hours to yesterday's.
+  return (opensOn(day) && minutes >= start) || (opensOn((day + 6) % 7) && minutes < end);
+}
+
+/** When the window next opens after `from`; undefined when it never does. */
+export function nextWorkingHoursStart(hours: WorkingHours, from: Date): Date | undefined {
+  const start = parseClockTime(hours.start) ?? 0;
+  for (let offset = 0; offset <= 7; offset += 1) {
+    const candidate = new Date(from);
+    candidate.setDate(from.getDate() + offset);
+    candidate.setHours(Math.floor(start / 60), start % 60, 0, 0);
+    if (candidate > from && (hours.days === undefined || hours.days.includes(candidate.getDay()))) {
+      return candidate;
+    }
+  }
+
+  return undefined;
+}
+
+/**
+ * Queued tasks of a project outside its working hours wait for the window to
+ * open; urgent tasks never do.
+ */
+export function isDeferredByWorkingHours(
+  task: Pick<TaskRuntime, "priority">,
+  hours: WorkingHours | undefined,
+  at: Date = new Date(),
+): boolean {
+  return hours !== undefined && task.priority !== "urgent" && !isWithinWorkingHours(hours, at);
+}
+
+/** Reads `09:00-18:00` and an optional day list such as `mon-fri` or `mon,wed,fri`. */
+export function parseWorkingHoursSpec(range: string, days?: string): WorkingHours {
+  const [start = "", end = ""] = range.split("-");
+  if (parseClockTime(start) === undefined || parseClockTime(end) === undefined) {
+    throw new IkanbanError("validation", `Working hours must look like 09:00-18:00, got ${range}.`, {
+      field: "workingHours",
+    });
+  }
+
+  return { start: start.trim(), end: end.trim(), days: days ? parseWeekdays(days) : undefined };
+}
+
+export function formatWorkingHours(hours: WorkingHours): string {
+  const days = hours.days
+    ? [...hours.days].sort((left, right) => left - right).map((day) => WEEKDAYS[day]).join(",")
+    : "every day";
+  return `${hours.start}-${hours.end} ${days}`;
+}
+
+function parseWeekdays(value: string): number[] {
+  const days = new Set<number>();
+  for (const part of value.toLowerCase().split(",")) {
+    const [from = "", to] = part.trim().split("-");
+    const first = WEEKDAYS.indexOf(from as (typeof WEEKDAYS)[number]);
+    const last = to === undefined ? first : WEEKDAYS.indexOf(to as (typeof WEEKDAYS)[number]);
+    if (first < 0 || last < 0) {
+      throw new IkanbanError("validation", `Unknown days ${part}; use ${WEEKDAYS.join(", ")} or a range like mon-fri.`, {
+        field: "workingHours",
+      });
+    }
+
+    // A range may wrap past Saturday, e.g. fri-mon.
+    for (let day = first; ; day = (day + 1) % 7) {
+      days.add(day);
+      if (day === last) {
+        break;
+      }
+    }
+  }
+
+  return [...days].sort((left, right) => left - right);
+}
@@ -5,60 +5,454 @@ import { join, resolve } from "node:path";
 import { render } from "ink";
 
 import { App } from "./app/App";
+import { resolveGotoTarget } from "./app/goto";
+import { compareDiffFiles, type DiffFile } from "./domain/diff";
+import { listSubtasks, summarizeSubtasks } from "./domain/subtasks";
+import type { TaskRuntime } from "./domain/task";
+import { parseStartupOptions, type StartupOptions } from "./app/startup-options";
+import {
+  loadAppConfig,
+  loadAppConfigFile,
+  mergeAppConfigEnv,
+  type AppConfig,
+} from "./runtime/app-config";
+import { createBlobStore, type BlobStore } from "./runtime/blob-store";
+import { ConfigReloader } from "./runtime/config-reloader";
+import { ConflictRiskMonitor } from "./runtime/conflict-risk";
 import { ConversationManager } from "./runtime/conversation-manager";
+import { DraftStore } from "./runtime/draft-store";
 import { RuntimeEventBus } from "./runtime/event-bus";
+import { runGitHook } from "./runtime/git-hooks";
+import { GitHubClient, PullRequestMonitor } from "./runtime/github";
+import { LifecycleHookRunner } from "./runtime/lifecycle-hooks";
+import { MetricsSnapshotter, MetricsStore } from "./runtime/metrics-store";
+import { LabelRegistry } from "./runtime/label-registry";
+import { MilestoneRegistry } from "./runtime/milestone-registry";
 import { OpenCodeRuntime } from "./runtime/opencode-runtime";
+import { PageRegistry } from "./runtime/page-registry";
 import { ProjectRegistry } from "./runtime/project-registry";
 import type { RuntimeLogger, RuntimeLogRecord } from "./runtime/runtime-logger";
+import {
+  checkStateFiles,
+  formatStateFileReport,
+  mirrorStateBackups,
+  type StateFileCheck,
+} from "./runtime/state-integrity";
+import { REDACTED, SecretRedactor } from "./runtime/secret-redactor";
 import { TaskRegistry } from "./runtime/task-registry";
 import { TaskOrchestrator } from "./runtime/task-orchestrator";
+import { TaskPlanner } from "./runtime/task-planner";
+import { WebBoardServer } from "./runtime/web-server";
+import { WorktreeGarbageCollector } from "./runtime/worktree-gc";
 import { WorktreeManager } from "./runtime/worktree-manager";
+import { WorktreeWatcher } from "./runtime/worktree-watcher";
 
+// `ikanban hook <name> ...` is called by the git hooks installed from the project selector. It runs
+// before the config is read, so a broken config file cannot block commits and pushes in every repo.
+if (process.argv[2] === "hook") {
+  const stdin = process.argv[3] === "pre-push" ? await Bun.stdin.text().catch(() => "") : "";
+  const hookTaskRegistry = new TaskRegistry({
+    stateFilePath: resolve(join(homedir(), ".ikanban", "tasks.json")),
+  });
+  process.exit(await runGitHook(process.argv[3] ?? "", process.argv.slice(4), hookTaskRegistry, stdin));
+}
+
+const configFilePath = resolve(
+  process.env.IKANBAN_CONFIG ?? join(homedir(), ".ikanban", "config.toml"),
+);
+const config = loadAppConfig(mergeAppConfigEnv(await loadAppConfigFile(configFilePath)));
+
+if (process.argv.includes("--print-config")) {
+  console.log(`# config file: ${configFilePath}`);
+  const printable = {
+    ...config,
+    github: { ...config.github, token: config.github.token && REDACTED },
+    web: { ...config.web, token: config.web.token && REDACTED },
+  };
+  console.log(JSON.stringify(printable, null, 2));
+  process.exit(0);
+}
+
+let artifactStore: BlobStore | undefined;
+try {
+  artifactStore = config.storage.url ? createBlobStore({ ...config.storage, url: config.storage.url }) : undefined;
+} catch (error) {
+  console.error(error instanceof Error ? error.message : String(error));
+  process.exit(2);
+}
+
+let startupOptions: StartupOptions;
+let startupPrompt: string | undefined;
+try {
+  startupOptions = parseStartupOptions(process.argv.slice(2));
+  startupPrompt = startupOptions.promptFile
+    ? await Bun.file(resolve(startupOptions.promptFile)).text()
+    : undefined;
+} catch (error) {
+  console.error(error instanceof Error ? error.message : String(error));
+  process.exit(2);
+}
 const eventBus = new RuntimeEventBus();
-const logger = createEventBusLogger(eventBus);
-const runtime = new OpenCodeRuntime({ logger });
+const redactor = new SecretRedactor(config.redaction);
+const logger = createEventBusLogger(eventBus, redactor);
+const runtime = new OpenCodeRuntime({
+  ...config.opencode,
+  logger,
+});
 const projectRegistry = new ProjectRegistry({
   stateFilePath: resolve(join(homedir(), ".ikanban", "projects.json")),
+  allowedRootDirectories: config.projects.allowedRootDirectories,
 });
 const taskRegistry = new TaskRegistry({
   stateFilePath: resolve(join(homedir(), ".ikanban", "tasks.json")),
 });
+
+const milestoneRegistry = new MilestoneRegistry({
+  stateFilePath: resolve(join(homedir(), ".ikanban", "milestones.json")),
+});
+const labelRegistry = new LabelRegistry({
+  stateFilePath: resolve(join(homedir(), ".ikanban", "labels.json")),
+});
+const pageRegistry = new PageRegistry({
+  stateFilePath: resolve(join(homedir(), ".ikanban", "pages.json")),
+});
+const draftStore = new DraftStore({
+  stateFilePath: resolve(join(homedir(), ".ikanban", "drafts.json")),
+});
+const metricsStore = new MetricsStore({
+  stateFilePath: resolve(join(homedir(), ".ikanban", "metrics.json")),
+});
+
+// Each file is loaded by a throwaway registry so a corrupt file cannot poison the live ones.
+const stateFileChecks: StateFileCheck[] = [
+  {
+    filePath: resolve(join(homedir(), ".ikanban", "projects.json")),
+    load: (stateFilePath) =>
+      new ProjectRegistry({
+        stateFilePath,
+        allowedRootDirectories: config.projects.allowedRootDirectories,
+      }).listProjects(),
+  },
+  {
+    filePath: resolve(join(homedir(), ".ikanban", "tasks.json")),
+    load: (stateFilePath) => new TaskRegistry({ stateFilePath }).listTasks(),
+  },
+  {
+    filePath: resolve(join(homedir(), ".ikanban", "milestones.json")),
+    load: (stateFilePath) => new MilestoneRegistry({ stateFilePath }).listMilestones(),
+  },
+  {
+    filePath: resolve(join(homedir(), ".ikanban", "labels.json")),
+    load: (stateFilePath) => new LabelRegistry({ stateFilePath }).listLabels(),
+  },
+  {
+    filePath: resolve(join(homedir(), ".ikanban", "pages.json")),
+    load: (stateFilePath) => new PageRegistry({ stateFilePath }).listPages(""),
+  },
+  {
+    filePath: resolve(join(homedir(), ".ikanban", "drafts.json")),
+    load: (stateFilePath) => new DraftStore({ stateFilePath }).get(""),
+  },
+  {
+    filePath: resolve(join(homedir(), ".ikanban", "metrics.json")),
+    load: (stateFilePath) => new MetricsStore({ stateFilePath }).getLatestSnapshot(""),
+  },
+];
+const stateBackupDirectory = resolve(join(homedir(), ".ikanban", "backups"));
+
+// `ikanban doctor [--repair]` checks the state files and restores corrupt ones from backup.
+if (process.argv[2] === "doctor") {
+  const reports = await checkStateFiles(stateFileChecks, {
+    backupDirectory: stateBackupDirectory,
+    repair: process.argv.includes("--repair"),
+    mirror: artifactStore,
+  });
+  for (const report of reports) {
+    console.log(formatStateFileReport(report));
+  }
+  process.exit(reports.some((report) => report.status === "corrupt") ? 1 : 0);
+}
+
+// Refreshes the known-good backups; failures show up in the log view. The mirror
+// copy uploads in the background so a slow or unreachable store cannot hold up startup.
+const stateFileReports = await checkStateFiles(stateFileChecks, { backupDirectory: stateBackupDirectory, logger });
+if (artifactStore) {
+  void mirrorStateBackups(artifactStore, stateFileReports, logger);
+}
 const worktreeManager = new WorktreeManager(runtime, { logger });
+
+// `ikanban diff <code|id>` prints a task's changes against the base branch as JSON, split into files and hunks.
+if (process.argv[2] === "diff") {
+  const resolution = resolveGotoTarget(process.argv[3] ?? "", {
+    projects: await projectRegistry.listProjects(),
+    tasks: await taskRegistry.listTasks(),
+  });
+  const task = resolution.status === "found" && resolution.target.kind === "task" ? resolution.target.task : undefined;
+  const project = task ? await projectRegistry.getProject(task.projectId) : undefined;
+  if (!task || !project || !task.worktreeDirectory) {
+    console.error(
+      task ? `Task ${task.taskId} has no worktree to diff.` : "Usage: ikanban diff <task code or id>",
+    );
+    process.exit(2);
+  }
+
+  const result = await worktreeManager.getTaskWorktreeDiff({
+    projectDirectory: project.rootDirectory,
+    taskId: task.taskId,
+    worktreeDirectory: task.worktreeDirectory,
+    pathScope: task.pathScope,
+  });
+  console.log(JSON.stringify(redactor.redact(result, project.id), null, 2));
+  process.exit(0);
+}
+
+// `ikanban commits <code|id> [hash]` prints the commits on a task branch, or one commit's files and hunks, as JSON.
+if (process.argv[2] === "commits") {
+  const resolution = resolveGotoTarget(process.argv[3] ?? "", {
+    projects: await projectRegistry.listProjects(),
+    tasks: await taskRegistry.listTasks(),
+  });
+  const task = resolution.status === "found" && resolution.target.kind === "task" ? resolution.target.task : undefined;
+  const project = task ? await projectRegistry.getProject(task.projectId) : undefined;
+  if (!task || !project || !task.worktreeDirectory || task.worktreeRemovedAt !== undefined) {
+    console.error(
+      task ? `Task ${task.taskId} has no worktree to read commits from.` : "Usage: ikanban commits <task code or id> [hash]",
+    );
+    process.exit(2);
+  }
+
+  const hash = process.argv[4];
+  const result = hash
+    ? await worktreeManager.getTaskCommitFiles(task.worktreeDirectory, hash)
+    : await worktreeManager.listTaskCommits({
+        projectDirectory: project.rootDirectory,
+        worktreeDirectory: task.worktreeDirectory,
+      });
+  console.log(JSON.stringify(redactor.redact(result, project.id), null, 2));
+  process.exit(0);
+}
+
+// `ikanban compare <a> <b>` prints which files each task changed and where their hunks overlap, as JSON.
+if (process.argv[2] === "compare") {
+  const lookup = { projects: await projectRegistry.listProjects(), tasks: await taskRegistry.listTasks() };
+  const diffs: DiffFile[][] = [];
+  for (const query of [process.argv[3] ?? "", process.argv[4] ?? ""]) {
+    const resolution = resolveGotoTarget(query, lookup);
+    const task = resolution.status === "found" && resolution.target.kind === "task" ? resolution.target.task : undefined;
+    const project = task ? await projectRegistry.getProject(task.projectId) : undefined;
+    if (!task || !project || !task.worktreeDirectory || task.worktreeRemovedAt !== undefined) {
+      console.error(task ? `Task ${task.taskId} has no worktree to compare.` : "Usage: ikanban compare <task code or id> <task code or id>");
+      process.exit(2);
+    }
+
+    const diff = await worktreeManager.getTaskWorktreeDiff({
+      projectDirectory: project.rootDirectory,
+      taskId: task.taskId,
+      worktreeDirectory: task.worktreeDirectory,
+      pathScope: task.pathScope,
+    });
+    diffs.push(redactor.redact(diff.files, project.id));
+  }
+
+  const comparison = compareDiffFiles(diffs[0] ?? [], diffs[1] ?? []);
+  console.log(
+    JSON.stringify(
+      {
+        onlyA: comparison.onlyLeft.map(({ patch: _patch, hunks: _hunks, ...file }) => file),
+        onlyB: comparison.onlyRight.map(({ patch: _patch, hunks: _hunks, ...file }) => file),
+        shared: comparison.shared.map((file) => ({
+          path: file.path,
+          a: { additions: file.left.additions, deletions: file.left.deletions },
+          b: { additions: file.right.additions, deletions: file.right.deletions },
+          overlappingHunks: file.overlappingHunks,
+          identical: file.identical,
+        })),
+      },
+      null,
+      2,
+    ),
+  );
+  process.exit(0);
+}
+
+// `ikanban subtasks <task>` prints the task's subtask tree with rolled-up completion, as JSON.
+if (process.argv[2] === "subtasks") {
+  const tasks = await taskRegistry.listTasks();
+  const resolution = resolveGotoTarget(process.argv[3] ?? "", { projects: await projectRegistry.listProjects(), tasks });
+  if (resolution.status !== "found" || resolution.target.kind !== "task") {
+    console.error("Usage: ikanban subtasks <task code or id>");
+    process.exit(2);
+  }
+
+  const describe = (task: TaskRuntime): unknown => ({
+    taskId: task.taskId,
+    shortCode: task.shortCode,
+    title: task.title,
+    state: task.state,
+    progress: summarizeSubtasks(tasks, task.taskId),
+    subtasks: listSubtasks(tasks, task.taskId).map(describe),
+  });
+  const { task } = resolution.target;
+  console.log(JSON.stringify(redactor.redact(describe(task), task.projectId), null, 2));
+  process.exit(0);
+}
+
+// `ikanban comments <task>` prints the task's comment thread, oldest first, as JSON.
+if (process.argv[2] === "comments") {
+  const tasks = await taskRegistry.listTasks();
+  const resolution = resolveGotoTarget(process.argv[3] ?? "", { projects: await projectRegistry.listProjects(), tasks });
+  if (resolution.status !== "found" || resolution.target.kind !== "task") {
+    console.error("Usage: ikanban comments <task code or id>");
+    process.exit(2);
+  }
+
+  const { task } = resolution.target;
+  console.log(JSON.stringify(redactor.redact(task.comments ?? [], task.projectId), null, 2));
+  process.exit(0);
+}
+
 const conversationManager = new ConversationManager(runtime, { logger });
+const worktreeWatcher = new WorktreeWatcher({ logger });
+const lifecycleHooks = new LifecycleHookRunner({
+  hooks: config.hooks.commands,
+  timeoutMs: config.hooks.timeoutMs,
+  logger,
+});
+const github = new GitHubClient({ token: config.github.token });
 const orchestrator = new TaskOrchestrator({
   projectRegistry,
   taskRegistry,
   worktreeManager,
   conversationManager,
+  worktreeWatcher,
+  pageRegistry,
+  lifecycleHooks,
+  redactor,
+  github,
 }, {
+  maxConcurrent: config.tasks.maxConcurrent,
+  maxConcurrentPerProject: config.tasks.maxConcurrentPerProject,
+  retryMaxAttempts: config.tasks.retryMaxAttempts,
+  retryBackoffMs: config.tasks.retryBackoffMs,
+  runTimeoutMs: config.tasks.runTimeoutMs,
+  stallTimeoutMs: config.tasks.stallTimeoutMs,
+  cleanupOnSuccess: config.tasks.cleanupOnSuccess,
+  cleanupOnFailure: config.tasks.cleanupOnFailure,
+  worktreeRetentionMs: toRetentionMs(config.tasks.worktreeRetentionDays),
+  enrichPromptWithRepoContext: config.tasks.promptContext,
+  enforcePathScope: config.tasks.enforcePathScope,
+  preflightCheckRemote: config.tasks.preflightCheckRemote,
+  reviewer: config.review.reviewer,
   logger,
 });
+const taskPlanner = new TaskPlanner(conversationManager, { logger });
+const conflictRiskMonitor = new ConflictRiskMonitor(orchestrator, {
+  intervalMs: config.tasks.conflictRiskIntervalMs,
+  logger,
+});
+const pullRequestMonitor = new PullRequestMonitor(orchestrator, {
+  intervalMs: config.github.pollIntervalMs,
+  logger,
+});
+const worktreeGarbageCollector = new WorktreeGarbageCollector(orchestrator, { logger });
+const metricsSnapshotter = new MetricsSnapshotter(metricsStore, { projectRegistry, taskRegistry }, { logger });
 
-render(
+const services = {
+  runtime,
+  projectRegistry,
+  milestoneRegistry,
+  labelRegistry,
+  pageRegistry,
+  draftStore,
+  metricsStore,
+  orchestrator,
+  taskPlanner,
+  worktreeManager,
+  eventBus,
+};
+const renderApp = (appConfig: AppConfig) => (
   <App
-    services={{
-      runtime,
-      projectRegistry,
-      orchestrator,
-      worktreeManager,
-      eventBus,
-    }}
+    services={services}
     defaultProjectDirectory={process.cwd()}
-  />,
+    initialRoute={
+      startupOptions.view ?? (startupOptions.project || startupOptions.task || startupOptions.runTask ? "task-board" : undefined)
+    }
+    startup={{
+      project: startupOptions.project,
+      task: startupOptions.task ?? startupOptions.runTask,
+      followUpPrompt: startupOptions.runTask ? startupPrompt : undefined,
+    }}
+    notificationMode={appConfig.notifications.mode}
+    terminalCommand={appConfig.terminal.command}
+    cardFields={appConfig.board.cardFields}
+    palette={appConfig.board.palette}
+    exportDirectory={resolve(join(homedir(), ".ikanban", "exports"))}
+    artifactStore={artifactStore}
+  />
 );
+const app = render(renderApp(config));
+conflictRiskMonitor.start();
+pullRequestMonitor.start();
+worktreeGarbageCollector.start();
+metricsSnapshotter.start();
+if (config.web.port !== undefined) {
+  new WebBoardServer(
+    { orchestrator, projectRegistry, eventBus, redactor },
+    { hostname: config.web.hostname, port: config.web.port, token: config.web.token, logger },
+  ).start();
+}
+
+const configReloader = new ConfigReloader({
+  filePath: configFilePath,
+  config,
+  logger,
+  onApply: (nextConfig) => {
+    orchestrator.reconfigure({
+      maxConcurrent: nextConfig.tasks.maxConcurrent,
+      maxConcurrentPerProject: nextConfig.tasks.maxConcurrentPerProject,
+      retryMaxAttempts: nextConfig.tasks.retryMaxAttempts,
+      retryBackoffMs: nextConfig.tasks.retryBackoffMs,
+      runTimeoutMs: nextConfig.tasks.runTimeoutMs,
+      stallTimeoutMs: nextConfig.tasks.stallTimeoutMs,
+      cleanupOnSuccess: nextConfig.tasks.cleanupOnSuccess,
+      cleanupOnFailure: nextConfig.tasks.cleanupOnFailure,
+      worktreeRetentionMs: toRetentionMs(nextConfig.tasks.worktreeRetentionDays),
+      enrichPromptWithRepoContext: nextConfig.tasks.promptContext,
+      enforcePathScope: nextConfig.tasks.enforcePathScope,
+      preflightCheckRemote: nextConfig.tasks.preflightCheckRemote,
+      reviewer: nextConfig.review.reviewer,
+    });
+    lifecycleHooks.setHooks(nextConfig.hooks.commands, nextConfig.hooks.timeoutMs);
+    redactor.configure(nextConfig.redaction);
+    conflictRiskMonitor.setIntervalMs(nextConfig.tasks.conflictRiskIntervalMs);
+    github.setToken(nextConfig.github.token);
+    pullRequestMonitor.setIntervalMs(nextConfig.github.pollIntervalMs);
+    app.rerender(renderApp(nextConfig));
+  },
+});
+configReloader.start();
+// SIGHUP forces a reload, e.g. when the config lives on a filesystem without change events.
+process.on("SIGHUP", () => {
+  void configReloader.reload();
+});
+
+function toRetentionMs(days: number | undefined): number | undefined {
+  return days === undefined ? undefined : days * 24 * 60 * 60 * 1000;
+}
 
-function createEventBusLogger(eventBus: RuntimeEventBus): RuntimeLogger {
+// Runtime records are redacted regardless of project: they can quote any project's agent output.
+function createEventBusLogger(eventBus: RuntimeEventBus, redactor: SecretRedactor): RuntimeLogger {
   return {
     log(record: RuntimeLogRecord): void {
       eventBus.emit("log.appended", {
         level: record.level,
-        message: record.message,
+        message: redactor.redactText(record.message),
         source: record.source,
         eventType: "runtime.log",
-        raw: {
+        raw: redactor.redact({
           context: record.context,
           error: record.error,
-        },
+        }),
       });
     },
   };
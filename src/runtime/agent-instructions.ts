@@ -0,0 +1,61 @@
+import { copyFile } from "node:fs/promises";
+import { join } from "node:path";
+
+/** Instruction files opencode loads from a worktree root, in lookup order. */
+export const AGENT_INSTRUCTION_FILES = ["AGENTS.md", "CLAUDE.md"] as const;
+
+export type AgentInstructionsCheck = {
+  status: "present" | "copied" | "missing";
+  fileName?: string;
+};
+
+export async function findAgentInstructions(directory: string): Promise<string | undefined> {
+  for (const fileName of AGENT_INSTRUCTION_FILES) {
+    if (await Bun.file(join(directory, fileName)).exists()) {
+      return fileName;
+    }
+  }
+
+  return undefined;
+}
+
+/** Reads the project's instruction file, or an empty AGENTS.md to create when there is none. */
+export async function readAgentInstructions(
+  projectDirectory: string,
+): Promise<{ filePath: string; content: string; exists: boolean }> {
+  const fileName = await findAgentInstructions(projectDirectory);
+  const filePath = join(projectDirectory, fileName ?? AGENT_INSTRUCTION_FILES[0]);
+
+  return {
+    filePath,
+    content: fileName ? await Bun.file(filePath).text() : "",
+    exists: fileName !== undefined,
+  };
+}
+
+export async function writeAgentInstructions(filePath: string, content: string): Promise<void> {
+  await Bun.write(filePath, content.endsWith("\n") ? content : `${content}\n`);
+}
+
+/**
+ * Makes sure a fresh worktree carries the project's instruction file. A file
+ * that is committed is already checked out; an uncommitted one is copied in so
+ * the agent still sees it.
+ */
+export async function ensureWorktreeAgentInstructions(input: {
+  projectDirectory: string;
+  worktreeDirectory: string;
+}): Promise<AgentInstructionsCheck> {
+  const worktreeFile = await findAgentInstructions(input.worktreeDirectory);
+  if (worktreeFile) {
+    return { status: "present", fileName: worktreeFile };
+  }
+
+  const projectFile = await findAgentInstructions(input.projectDirectory);
+  if (!projectFile) {
+    return { status: "missing" };
+  }
+
+  await copyFile(join(input.projectDirectory, projectFile), join(input.worktreeDirectory, projectFile));
+  return { status: "copied", fileName: projectFile };
+}
@@ -1,5 +1,12 @@
 import { delimiter, isAbsolute, resolve } from "node:path";
 
+import { isPalette, PALETTES, type Palette } from "../domain/palette";
+import { CARD_FIELDS, DEFAULT_CARD_FIELDS, isCardField, type CardField } from "../domain/task-card";
+import type { LifecycleHookCommands } from "./lifecycle-hooks";
+import {
+  isTerminalNotificationMode,
+  type TerminalNotificationMode,
+} from "./terminal-notification";
 import type { WorktreeCleanupPolicy } from "./worktree-manager";
 
 export type AppConfig = {
@@ -10,16 +17,203 @@ export type AppConfig = {
   };
   tasks: {
     maxConcurrent: number;
+    maxConcurrentPerProject?: number;
+    retryMaxAttempts: number;
+    retryBackoffMs: number;
+    runTimeoutMs?: number;
+    stallTimeoutMs?: number;
     cleanupOnSuccess: WorktreeCleanupPolicy;
     cleanupOnFailure: WorktreeCleanupPolicy;
+    /** Days a finished task keeps its worktree; unset keeps worktrees until removed by hand. */
+    worktreeRetentionDays?: number;
+    promptContext: boolean;
+    enforcePathScope: boolean;
+    preflightCheckRemote: boolean;
+    conflictRiskIntervalMs: number;
   };
   projects: {
     allowedRootDirectories: string[];
   };
+  review: {
+    reviewer: string;
+  };
+  notifications: {
+    mode: TerminalNotificationMode;
+  };
+  hooks: {
+    commands: LifecycleHookCommands;
+    timeoutMs?: number;
+  };
+  redaction: {
+    envNames: string[];
+    allowProjects: string[];
+  };
+  terminal: {
+    command?: string;
+  };
+  board: {
+    /** Optional card parts, in display order. */
+    cardFields: CardField[];
+    palette: Palette;
+  };
+  github: {
+    token?: string;
+    pollIntervalMs: number;
+  };
+  storage: {
+    /** Off-host store for backups and exports: `s3://bucket/prefix` or a directory. */
+    url?: string;
+    endpoint?: string;
+    region?: string;
+  };
+  web: {
+    /** The read-only web UI is served only when a port is set. */
+    port?: number;
+    hostname: string;
+    token?: string;
+  };
 };
 
 export type AppConfigEnv = Record<string, string | undefined>;
 
+// config.toml keys, by table, and the environment variable each one backs.
+const CONFIG_FILE_KEYS: Record<string, Record<string, string>> = {
+  opencode: {
+    hostname: "IKANBAN_OPENCODE_HOSTNAME",
+    port: "IKANBAN_OPENCODE_PORT",
+    timeout_ms: "IKANBAN_OPENCODE_TIMEOUT_MS",
+  },
+  tasks: {
+    max_concurrent: "IKANBAN_TASK_MAX_CONCURRENT",
+    max_concurrent_per_project: "IKANBAN_TASK_MAX_CONCURRENT_PER_PROJECT",
+    retry_max_attempts: "IKANBAN_TASK_RETRY_MAX_ATTEMPTS",
+    retry_backoff_ms: "IKANBAN_TASK_RETRY_BACKOFF_MS",
+    run_timeout_ms: "IKANBAN_TASK_RUN_TIMEOUT_MS",
+    stall_timeout_ms: "IKANBAN_TASK_STALL_TIMEOUT_MS",
+    cleanup_on_success: "IKANBAN_TASK_CLEANUP_ON_SUCCESS",
+    cleanup_on_failure: "IKANBAN_TASK_CLEANUP_ON_FAILURE",
+    worktree_retention_days: "IKANBAN_WORKTREE_RETENTION_DAYS",
+    prompt_context: "IKANBAN_TASK_PROMPT_CONTEXT",
+    enforce_path_scope: "IKANBAN_TASK_ENFORCE_PATH_SCOPE",
+    preflight_check_remote: "IKANBAN_PREFLIGHT_CHECK_REMOTE",
+    conflict_risk_interval_ms: "IKANBAN_CONFLICT_RISK_INTERVAL_MS",
+  },
+  projects: {
+    allowed_paths: "IKANBAN_ALLOWED_PROJECT_PATHS",
+  },
+  review: {
+    reviewer: "IKANBAN_REVIEWER",
+  },
+  notifications: {
+    mode: "IKANBAN_NOTIFY",
+  },
+  hooks: {
+    task_created: "IKANBAN_HOOKS_TASK_CREATED",
+    session_finished: "IKANBAN_HOOKS_SESSION_FINISHED",
+    before_merge: "IKANBAN_HOOKS_BEFORE_MERGE",
+    timeout_ms: "IKANBAN_HOOK_TIMEOUT_MS",
+  },
+  redaction: {
+    env_names: "IKANBAN_REDACT_ENV_NAMES",
+    allow_projects: "IKANBAN_REDACT_ALLOW_PROJECTS",
+  },
+  terminal: {
+    command: "IKANBAN_TERMINAL_COMMAND",
+  },
+  board: {
+    card_fields: "IKANBAN_BOARD_CARD_FIELDS",
+    palette: "IKANBAN_BOARD_PALETTE",
+  },
+  github: {
+    token: "IKANBAN_GITHUB_TOKEN",
+    poll_interval_ms: "IKANBAN_GITHUB_POLL_INTERVAL_MS",
+  },
+  storage: {
+    url: "IKANBAN_STORAGE_URL",
+    endpoint: "IKANBAN_STORAGE_ENDPOINT",
+    region: "IKANBAN_STORAGE_REGION",
+  },
+  web: {
+    port: "IKANBAN_WEB_PORT",
+    hostname: "IKANBAN_WEB_HOSTNAME",
+    token: "IKANBAN_WEB_TOKEN",
+  },
+};
+
+/**
+ * Reads a TOML config file into the environment variables it stands for, so
+ * file values go through the same parsing as env vars and env vars can
+ * override them. A missing file yields no values.
+ */
+export async function loadAppConfigFile(filePath: string): Promise<AppConfigEnv> {
+  const configFile = Bun.file(filePath);
+  if (!(await configFile.exists())) {
+    return {};
+  }
+
+  const content = await configFile.text();
+
+  let parsed: Record<string, unknown>;
+  try {
+    parsed = Bun.TOML.parse(content) as Record<string, unknown>;
+  } catch (error) {
+    throw new Error(`Invalid config file ${filePath}: ${String(error)}`);
+  }
+
+  const values: AppConfigEnv = {};
+  for (const [table, tableValue] of Object.entries(parsed)) {
+    const keys = CONFIG_FILE_KEYS[table];
+    if (!keys || !tableValue || typeof tableValue !== "object" || Array.isArray(tableValue)) {
+      throw new Error(`Unknown config table [${table}] in ${filePath}.`);
+    }
+
+    for (const [key, value] of Object.entries(tableValue)) {
+      const variable = keys[key];
+      if (!variable) {
+        throw new Error(`Unknown config key ${table}.${key} in ${filePath}.`);
+      }
+
+      values[variable] = Array.isArray(value) ? value.map(String).join(delimiter) : String(value);
+    }
+  }
+
+  return values;
+}
+
+/** Layers env vars over config file values; both fall back to the built-in defaults. */
+export function mergeAppConfigEnv(fileValues: AppConfigEnv, env: AppConfigEnv = process.env): AppConfigEnv {
+  const merged: AppConfigEnv = { ...fileValues };
+  for (const [variable, value] of Object.entries(env)) {
+    if (value !== undefined && value.trim().length > 0) {
+      merged[variable] = value;
+    }
+  }
+
+  return merged;
+}
+
+// Settings under these sections are bound at startup (server connection, path policy, storage backend, web listener).
+const RESTART_REQUIRED_SECTIONS = ["opencode", "projects", "storage", "web"] as const;
+
+export type AppConfigChange = {
+  path: string;
+  requiresRestart: boolean;
+};
+
+export function diffAppConfig(previous: AppConfig, next: AppConfig): AppConfigChange[] {
+  const previousValues = flattenConfig(previous);
+  const nextValues = flattenConfig(next);
+  const paths = new Set([...previousValues.keys(), ...nextValues.keys()]);
+
+  return [...paths]
+    .filter((path) => previousValues.get(path) !== nextValues.get(path))
+    .sort((left, right) => left.localeCompare(right))
+    .map((path) => ({
+      path,
+      requiresRestart: RESTART_REQUIRED_SECTIONS.some((section) => path.startsWith(`${section}.`)),
+    }));
+}
+
 export function loadAppConfig(env: AppConfigEnv = process.env): AppConfig {
   const hostname = parseOptionalString(env.IKANBAN_OPENCODE_HOSTNAME);
   const port = parseOptionalPositiveInteger(env.IKANBAN_OPENCODE_PORT, "IKANBAN_OPENCODE_PORT");
@@ -32,9 +226,67 @@ export function loadAppConfig(env: AppConfigEnv = process.env): AppConfig {
     "IKANBAN_TASK_MAX_CONCURRENT",
     2,
   ) ?? 2;
+  const maxConcurrentPerProject = parseOptionalPositiveInteger(
+    env.IKANBAN_TASK_MAX_CONCURRENT_PER_PROJECT,
+    "IKANBAN_TASK_MAX_CONCURRENT_PER_PROJECT",
+  );
+  const retryMaxAttempts = parseOptionalNonNegativeInteger(
+    env.IKANBAN_TASK_RETRY_MAX_ATTEMPTS,
+    "IKANBAN_TASK_RETRY_MAX_ATTEMPTS",
+    0,
+  );
+  const retryBackoffMs = parseOptionalPositiveInteger(
+    env.IKANBAN_TASK_RETRY_BACKOFF_MS,
+    "IKANBAN_TASK_RETRY_BACKOFF_MS",
+    30_000,
+  ) ?? 30_000;
+  const runTimeoutMs = parseOptionalPositiveInteger(
+    env.IKANBAN_TASK_RUN_TIMEOUT_MS,
+    "IKANBAN_TASK_RUN_TIMEOUT_MS",
+  );
+  const stallTimeoutMs = parseOptionalPositiveInteger(
+    env.IKANBAN_TASK_STALL_TIMEOUT_MS,
+    "IKANBAN_TASK_STALL_TIMEOUT_MS",
+  );
   const cleanupOnSuccess = parseCleanupPolicy(env.IKANBAN_TASK_CLEANUP_ON_SUCCESS, "keep");
   const cleanupOnFailure = parseCleanupPolicy(env.IKANBAN_TASK_CLEANUP_ON_FAILURE, "keep");
+  const worktreeRetentionDays = parseOptionalPositiveInteger(
+    env.IKANBAN_WORKTREE_RETENTION_DAYS,
+    "IKANBAN_WORKTREE_RETENTION_DAYS",
+  );
+  const promptContext = parseOptionalBoolean(
+    env.IKANBAN_TASK_PROMPT_CONTEXT,
+    "IKANBAN_TASK_PROMPT_CONTEXT",
+    false,
+  );
+  const enforcePathScope = parseOptionalBoolean(
+    env.IKANBAN_TASK_ENFORCE_PATH_SCOPE,
+    "IKANBAN_TASK_ENFORCE_PATH_SCOPE",
+    false,
+  );
+  const preflightCheckRemote = parseOptionalBoolean(
+    env.IKANBAN_PREFLIGHT_CHECK_REMOTE,
+    "IKANBAN_PREFLIGHT_CHECK_REMOTE",
+    false,
+  );
+  const conflictRiskIntervalMs = parseOptionalPositiveInteger(
+    env.IKANBAN_CONFLICT_RISK_INTERVAL_MS,
+    "IKANBAN_CONFLICT_RISK_INTERVAL_MS",
+    120_000,
+  ) ?? 120_000;
   const allowedRootDirectories = parseAllowedProjectRoots(env.IKANBAN_ALLOWED_PROJECT_PATHS);
+  const reviewer =
+    parseOptionalString(env.IKANBAN_REVIEWER) ?? parseOptionalString(env.USER) ?? "reviewer";
+  const notificationMode = parseNotificationMode(env.IKANBAN_NOTIFY, "off");
+  const hookTimeoutMs = parseOptionalPositiveInteger(
+    env.IKANBAN_HOOK_TIMEOUT_MS,
+    "IKANBAN_HOOK_TIMEOUT_MS",
+  );
+  const githubPollIntervalMs = parseOptionalPositiveInteger(
+    env.IKANBAN_GITHUB_POLL_INTERVAL_MS,
+    "IKANBAN_GITHUB_POLL_INTERVAL_MS",
+    300_000,
+  ) ?? 300_000;
 
   return {
     opencode: {
@@ -44,12 +296,62 @@ export function loadAppConfig(env: AppConfigEnv = process.env): AppConfig {
     },
     tasks: {
       maxConcurrent,
+      maxConcurrentPerProject,
+      retryMaxAttempts,
+      retryBackoffMs,
+      runTimeoutMs,
+      stallTimeoutMs,
       cleanupOnSuccess,
       cleanupOnFailure,
+      worktreeRetentionDays,
+      promptContext,
+      enforcePathScope,
+      preflightCheckRemote,
+      conflictRiskIntervalMs,
     },
     projects: {
       allowedRootDirectories,
     },
+    review: {
+      reviewer,
+    },
+    notifications: {
+      mode: notificationMode,
+    },
+    hooks: {
+      commands: {
+        "task.created": parseHookExecutables(env.IKANBAN_HOOKS_TASK_CREATED),
+        "session.finished": parseHookExecutables(env.IKANBAN_HOOKS_SESSION_FINISHED),
+        "before.merge": parseHookExecutables(env.IKANBAN_HOOKS_BEFORE_MERGE),
+      },
+      timeoutMs: hookTimeoutMs,
+    },
+    redaction: {
+      envNames: parseDelimitedList(env.IKANBAN_REDACT_ENV_NAMES),
+      allowProjects: parseDelimitedList(env.IKANBAN_REDACT_ALLOW_PROJECTS),
+    },
+    terminal: {
+      command: parseOptionalString(env.IKANBAN_TERMINAL_COMMAND),
+    },
+    board: {
+      cardFields: parseCardFields(env.IKANBAN_BOARD_CARD_FIELDS),
+      palette: parsePalette(env.IKANBAN_BOARD_PALETTE),
+    },
+    github: {
+      token: parseOptionalString(env.IKANBAN_GITHUB_TOKEN) ?? parseOptionalString(env.GITHUB_TOKEN),
+      pollIntervalMs: githubPollIntervalMs,
+    },
+    storage: {
+      url: parseOptionalString(env.IKANBAN_STORAGE_URL),
+      endpoint: parseOptionalString(env.IKANBAN_STORAGE_ENDPOINT),
+      region: parseOptionalString(env.IKANBAN_STORAGE_REGION),
+    },
+    web: {
+      port: parseOptionalPositiveInteger(env.IKANBAN_WEB_PORT, "IKANBAN_WEB_PORT"),
+      // Loopback unless asked otherwise: the board shows task titles and agent output.
+      hostname: parseOptionalString(env.IKANBAN_WEB_HOSTNAME) ?? "127.0.0.1",
+      token: parseOptionalString(env.IKANBAN_WEB_TOKEN),
+    },
   };
 }
 
@@ -80,6 +382,63 @@ function parseOptionalPositiveInteger(
   return parsed;
 }
 
+function parseOptionalNonNegativeInteger(
+  value: string | undefined,
+  variable: string,
+  fallback: number,
+): number {
+  if (value === undefined || value.trim().length === 0) {
+    return fallback;
+  }
+
+  const parsed = Number(value);
+
+  if (!Number.isInteger(parsed) || parsed < 0) {
+    throw new Error(`${variable} must be a non-negative integer.`);
+  }
+
+  return parsed;
+}
+
+function parseOptionalBoolean(
+  value: string | undefined,
+  variable: string,
+  fallback: boolean,
+): boolean {
+  if (value === undefined || value.trim().length === 0) {
+    return fallback;
+  }
+
+  const normalized = value.trim().toLowerCase();
+
+  if (["1", "true", "yes", "on"].includes(normalized)) {
+    return true;
+  }
+
+  if (["0", "false", "no", "off"].includes(normalized)) {
+    return false;
+  }
+
+  throw new Error(`${variable} must be a boolean (true/false).`);
+}
+
+function parseNotificationMode(
+  value: string | undefined,
+  fallback: TerminalNotificationMode,
+): TerminalNotificationMode {
+  if (value === undefined || value.trim().length === 0) {
+    return fallback;
+  }
+
+  const normalized = value.trim().toLowerCase();
+
+  if (!isTerminalNotificationMode(normalized)) {
+    throw new Error("IKANBAN_NOTIFY must be one of 'off', 'bell', 'osc9', or 'osc777'.");
+  }
+
+  return normalized;
+}
+
 function parseCleanupPolicy(
   value: string | undefined,
   fallback: WorktreeCleanupPolicy,
@@ -97,6 +456,50 @@ function parseCleanupPolicy(
   return normalized;
 }
 
+function parsePalette(value: string | undefined): Palette {
+  if (value === undefined || value.trim().length === 0) {
+    return "default";
+  }
+
+  const normalized = value.trim().toLowerCase();
+  if (!isPalette(normalized)) {
+    throw new Error(`IKANBAN_BOARD_PALETTE must be one of ${PALETTES.join(", ")}.`);
+  }
+
+  return normalized;
+}
+
+function parseCardFields(value: string | undefined): CardField[] {
+  if (value === undefined || value.trim().length === 0) {
+    return [...DEFAULT_CARD_FIELDS];
+  }
+
+  const fields = parseDelimitedList(value).map((entry) => entry.toLowerCase());
+  const unknown = fields.filter((field) => !isCardField(field));
+  if (unknown.length > 0) {
+    throw new Error(
+      `IKANBAN_BOARD_CARD_FIELDS has unknown fields (${unknown.join(", ")}); use ${CARD_FIELDS.join(", ")}.`,
+    );
+  }
+
+  return [...new Set(fields.filter(isCardField))];
+}
+
+function parseHookExecutables(value: string | undefined): string[] {
+  return parseDelimitedList(value);
+}
+
+function parseDelimitedList(value: string | undefined): string[] {
+  if (!value || value.trim().length === 0) {
+    return [];
+  }
+
+  return value
+    .split(delimiter)
+    .map((entry) => entry.trim())
+    .filter((entry) => entry.length > 0);
+}
+
 function parseAllowedProjectRoots(value: string | undefined): string[] {
   if (!value || value.trim().length === 0) {
     return [];
@@ -116,3 +519,15 @@ function parseAllowedProjectRoots(value: string | undefined): string[] {
 
   return [...new Set(roots)].sort((left, right) => left.localeCompare(right));
 }
+
+function flattenConfig(value: unknown, prefix = "", values = new Map<string, string>()): Map<string, string> {
+  if (value && typeof value === "object" && !Array.isArray(value)) {
+    for (const [key, child] of Object.entries(value)) {
+      flattenConfig(child, prefix ? `${prefix}.${key}` : key, values);
+    }
+    return values;
+  }
+
+  values.set(prefix, JSON.stringify(value) ?? "undefined");
+  return values;
+}
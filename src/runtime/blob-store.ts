@@ -0,0 +1,120 @@
+import { mkdir } from "node:fs/promises";
+import { dirname, join, resolve } from "node:path";
+
+import { IkanbanError } from "../domain/errors";
+
+/** Where backups and exported artifacts are kept, addressed by slash-separated keys. */
+export type BlobStore = {
+  /** Where `key` lives, for messages. */
+  describe(key: string): string;
+  exists(key: string): Promise<boolean>;
+  /** The stored bytes, or undefined when nothing is stored under `key`. */
+  read(key: string): Promise<Uint8Array | undefined>;
+  write(key: string, data: Blob | Uint8Array | string): Promise<void>;
+};
+
+export type BlobStoreOptions = {
+  /** `s3://bucket/prefix`, `file:///path` or a plain directory path. */
+  url: string;
+  /** S3-compatible endpoint, e.g. a MinIO or R2 URL; AWS when unset. */
+  endpoint?: string;
+  region?: string;
+};
+
+export class LocalBlobStore implements BlobStore {
+  private readonly directory: string;
+
+  constructor(directory: string) {
+    this.directory = resolve(directory);
+  }
+
+  describe(key: string): string {
+    return this.pathFor(key);
+  }
+
+  async exists(key: string): Promise<boolean> {
+    return Bun.file(this.pathFor(key)).exists();
+  }
+
+  async read(key: string): Promise<Uint8Array | undefined> {
+    const file = Bun.file(this.pathFor(key));
+    return (await file.exists()) ? new Uint8Array(await file.arrayBuffer()) : undefined;
+  }
+
+  async write(key: string, data: Blob | Uint8Array | string): Promise<void> {
+    const filePath = this.pathFor(key);
+    await mkdir(dirname(filePath), { recursive: true });
+    await Bun.write(filePath, data);
+  }
+
+  private pathFor(key: string): string {
+    return join(this.directory, ...normalizeKey(key).split("/"));
+  }
+}
+
+/** Credentials come from the usual `S3_*` or `AWS_*` environment variables. */
+export class S3BlobStore implements BlobStore {
+  private readonly client: Bun.S3Client;
+  private readonly bucket: string;
+  private readonly prefix: string;
+
+  constructor(options: { bucket: string; prefix?: string; endpoint?: string; region?: string }) {
+    this.bucket = options.bucket;
+    this.prefix = options.prefix ? normalizeKey(options.prefix) : "";
+    this.client = new Bun.S3Client({ bucket: options.bucket, endpoint: options.endpoint, region: options.region });
+  }
+
+  describe(key: string): string {
+    return `s3://${this.bucket}/${this.keyFor(key)}`;
+  }
+
+  async exists(key: string): Promise<boolean> {
+    return this.client.exists(this.keyFor(key));
+  }
+
+  async read(key: string): Promise<Uint8Array | undefined> {
+    const file = this.client.file(this.keyFor(key));
+    return (await file.exists()) ? new Uint8Array(await file.arrayBuffer()) : undefined;
+  }
+
+  async write(key: string, data: Blob | Uint8Array | string): Promise<void> {
+    await this.client.write(this.keyFor(key), data);
+  }
+
+  private keyFor(key: string): string {
+    return this.prefix ? `${this.prefix}/${normalizeKey(key)}` : normalizeKey(key);
+  }
+}
+
+export function createBlobStore(options: BlobStoreOptions): BlobStore {
+  if (options.url.startsWith("s3://")) {
+    const [bucket = "", ...prefix] = options.url.slice("s3://".length).split("/");
+    if (!bucket) {
+      throw new IkanbanError("validation", `Storage URL has no bucket: ${options.url}`, { field: "url" });
+    }
+
+    return new S3BlobStore({ bucket, prefix: prefix.join("/"), endpoint: options.endpoint, region: options.region });
+  }
+
+  if (options.url.startsWith("file://")) {
+    return new LocalBlobStore(new URL(options.url).pathname);
+  }
+
+  if (/^[a-z][a-z0-9+.-]*:\/\//i.test(options.url)) {
+    throw new IkanbanError("validation", `Unsupported storage URL: ${options.url} (use s3:// or a directory).`, {
+      field: "url",
+    });
+  }
+
+  return new LocalBlobStore(options.url);
+}
+
+// Keys never climb out of the store's directory or prefix.
+function normalizeKey(key: string): string {
+  const segments = key.split("/").filter((segment) => segment.length > 0 && segment !== ".");
+  if (segments.length === 0 || segments.includes("..")) {
+    throw new IkanbanError("validation", `Invalid storage key: ${key}`, { field: "key" });
+  }
+
+  return segments.join("/");
+}
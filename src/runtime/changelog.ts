@@ -0,0 +1,124 @@
+import type { TaskRuntime } from "../domain/task";
+import { parseTaskCommitRef } from "./task-attribution";
+
+export type ChangelogSection = "feature" | "bug" | "chore";
+
+export type ChangelogEntry = {
+  section: ChangelogSection;
+  taskRef: string;
+  title: string;
+  commit: string;
+};
+
+export type BuildChangelogInput = {
+  projectDirectory: string;
+  projectName: string;
+  tasks: TaskRuntime[];
+  /** Tag (or any revision) to start from; defaults to the latest reachable tag. */
+  since?: string;
+};
+
+export type ProjectChangelog = {
+  since?: string;
+  entries: ChangelogEntry[];
+  markdown: string;
+};
+
+const FIELD_SEPARATOR = "\u001f";
+const RECORD_SEPARATOR = "\u001e";
+const SECTION_TITLES: Record<ChangelogSection, string> = {
+  feature: "Features",
+  bug: "Bug Fixes",
+  chore: "Chores",
+};
+const SECTION_ORDER: ChangelogSection[] = ["feature", "bug", "chore"];
+
+export async function buildProjectChangelog(input: BuildChangelogInput): Promise<ProjectChangelog> {
+  const since = input.since?.trim() || (await findLatestTag(input.projectDirectory));
+  const range = since ? `${since}..HEAD` : "HEAD";
+  const log = await Bun.$`git -C ${input.projectDirectory} log --format=%h${FIELD_SEPARATOR}%s${FIELD_SEPARATOR}%b${RECORD_SEPARATOR} ${range}`
+    .nothrow()
+    .quiet();
+  if (log.exitCode !== 0) {
+    throw new Error(`Failed to read history for ${range}: ${log.stderr.toString().trim()}`);
+  }
+
+  const entries: ChangelogEntry[] = [];
+  for (const record of log.stdout.toString().split(RECORD_SEPARATOR)) {
+    const [commit, subject, body] = record.trim().split(FIELD_SEPARATOR);
+    if (!commit || !subject) {
+      continue;
+    }
+
+    const taskRef = parseTaskCommitRef(subject, body);
+    if (!taskRef) {
+      continue;
+    }
+
+    const task = input.tasks.find(
+      (candidate) => candidate.shortCode === taskRef || candidate.taskId === taskRef,
+    );
+    const title = task?.title ?? firstLine(task?.prompt) ?? subject;
+    entries.push({
+      section: classifyChangelogSection(title),
+      taskRef,
+      title,
+      commit,
+    });
+  }
+
+  return {
+    since,
+    entries,
+    markdown: formatChangelogMarkdown(input.projectName, since, entries),
+  };
+}
+
+export function classifyChangelogSection(title: string): ChangelogSection {
+  if (/\b(fix|fixes|fixed|bug|hotfix|regression|crash)\b/i.test(title)) {
+    return "bug";
+  }
+
+  if (/\b(chore|refactor|docs?|ci|deps|bump|cleanup|rename|lint)\b/i.test(title)) {
+    return "chore";
+  }
+
+  return "feature";
+}
+
+export function formatChangelogMarkdown(
+  projectName: string,
+  since: string | undefined,
+  entries: ChangelogEntry[],
+): string {
+  const lines = [`## ${projectName} changes${since ? ` since ${since}` : ""}`];
+
+  if (entries.length === 0) {
+    lines.push("", "No merged tasks.");
+  }
+
+  for (const section of SECTION_ORDER) {
+    const sectionEntries = entries.filter((entry) => entry.section === section);
+    if (sectionEntries.length === 0) {
+      continue;
+    }
+
+    lines.push("", `### ${SECTION_TITLES[section]}`, "");
+    for (const entry of sectionEntries) {
+      lines.push(`- ${entry.taskRef} ${entry.title} (${entry.commit})`);
+    }
+  }
+
+  return `${lines.join("\n")}\n`;
+}
+
+async function findLatestTag(projectDirectory: string): Promise<string | undefined> {
+  const result = await Bun.$`git -C ${projectDirectory} describe --tags --abbrev=0`.nothrow().quiet();
+  const tag = result.stdout.toString().trim();
+  return result.exitCode === 0 && tag ? tag : undefined;
+}
+
+function firstLine(value: string | undefined): string | undefined {
+  const line = value?.split("\n")[0]?.trim();
+  return line ? line : undefined;
+}
@@ -0,0 +1,150 @@
+import { watch, type FSWatcher } from "node:fs";
+import { basename, dirname } from "node:path";
+
+import {
+  diffAppConfig,
+  loadAppConfig,
+  loadAppConfigFile,
+  mergeAppConfigEnv,
+  type AppConfig,
+  type AppConfigEnv,
+} from "./app-config";
+import { noopRuntimeLogger, toStructuredError, type RuntimeLogger } from "./runtime-logger";
+
+const DEFAULT_DEBOUNCE_MS = 300;
+
+export type ConfigReloadResult = {
+  applied: string[];
+  rejected: string[];
+};
+
+export type ConfigReloaderOptions = {
+  filePath: string;
+  config: AppConfig;
+  onApply: (config: AppConfig, changedPaths: string[]) => void;
+  env?: AppConfigEnv;
+  debounceMs?: number;
+  logger?: RuntimeLogger;
+};
+
+/**
+ * Re-reads the config file when it changes (or on demand) and hands settings
+ * that are safe to change at runtime to `onApply`. Changes that need a restart
+ * are logged and the running values are kept.
+ */
+export class ConfigReloader {
+  private readonly filePath: string;
+  private readonly onApply: ConfigReloaderOptions["onApply"];
+  private readonly env: AppConfigEnv;
+  private readonly debounceMs: number;
+  private readonly logger: RuntimeLogger;
+  private config: AppConfig;
+  private watcher?: FSWatcher;
+  private timer?: ReturnType<typeof setTimeout>;
+
+  constructor(options: ConfigReloaderOptions) {
+    this.filePath = options.filePath;
+    this.config = options.config;
+    this.onApply = options.onApply;
+    this.env = options.env ?? process.env;
+    this.debounceMs = options.debounceMs ?? DEFAULT_DEBOUNCE_MS;
+    this.logger = options.logger ?? noopRuntimeLogger;
+  }
+
+  start(): void {
+    if (this.watcher) {
+      return;
+    }
+
+    const fileName = basename(this.filePath);
+    try {
+      // Watch the directory: editors often replace the file, and it may not exist yet.
+      this.watcher = watch(dirname(this.filePath), (_event, changed) => {
+        if (changed?.toString() === fileName) {
+          this.queueReload();
+        }
+      });
+    } catch (error) {
+      this.logger.log({
+        level: "warn",
+        source: "config-reloader",
+        message: "Unable to watch config file; use SIGHUP to reload.",
+        context: { filePath: this.filePath },
+        error: toStructuredError(error),
+      });
+      return;
+    }
+
+    this.watcher.unref();
+  }
+
+  close(): void {
+    if (this.timer) {
+      clearTimeout(this.timer);
+      this.timer = undefined;
+    }
+
+    this.watcher?.close();
+    this.watcher = undefined;
+  }
+
+  async reload(): Promise<ConfigReloadResult> {
+    let next: AppConfig;
+    try {
+      next = loadAppConfig(mergeAppConfigEnv(await loadAppConfigFile(this.filePath), this.env));
+    } catch (error) {
+      this.logger.log({
+        level: "error",
+        source: "config-reloader",
+        message: "Config reload failed; keeping the current configuration.",
+        context: { filePath: this.filePath },
+        error: toStructuredError(error),
+      });
+      return { applied: [], rejected: [] };
+    }
+
+    const changes = diffAppConfig(this.config, next);
+    const rejected = changes.filter((change) => change.requiresRestart).map((change) => change.path);
+    const applied = changes.filter((change) => !change.requiresRestart).map((change) => change.path);
+
+    if (rejected.length > 0) {
+      this.logger.log({
+        level: "warn",
+        source: "config-reloader",
+        message: `Ignored config changes that need a restart: ${rejected.join(", ")}.`,
+        context: { filePath: this.filePath },
+      });
+    }
+
+    if (applied.length === 0) {
+      return { applied, rejected };
+    }
+
+    this.config = {
+      ...next,
+      opencode: this.config.opencode,
+      projects: this.config.projects,
+      storage: this.config.storage,
+    };
+    this.onApply(this.config, applied);
+    this.logger.log({
+      level: "info",
+      source: "config-reloader",
+      message: `Reloaded config: ${applied.join(", ")}.`,
+      context: { filePath: this.filePath },
+    });
+
+    return { applied, rejected };
+  }
+
+  private queueReload(): void {
+    if (this.timer) {
+      clearTimeout(this.timer);
+    }
+
+    this.timer = setTimeout(() => {
+      this.timer = undefined;
+      void this.reload();
+    }, this.debounceMs);
+  }
+}
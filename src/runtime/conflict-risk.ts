@@ -0,0 +1,110 @@
+import type { BranchChangeSet } from "../domain/conflict-risk";
+import { noopRuntimeLogger, toStructuredError, type RuntimeLogger } from "./runtime-logger";
+
+const DEFAULT_INTERVAL_MS = 120_000;
+
+/**
+ * Files a task worktree changed since it left the default branch (committed,
+ * uncommitted and untracked), plus files the default branch changed since then.
+ */
+export async function collectBranchChanges(input: {
+  taskRef: string;
+  projectDirectory: string;
+  worktreeDirectory: string;
+}): Promise<BranchChangeSet> {
+  const defaultBranch = (
+    await Bun.$`git -C ${input.projectDirectory} rev-parse --abbrev-ref HEAD`.quiet().text()
+  ).trim();
+  const mergeBase = (
+    await Bun.$`git -C ${input.worktreeDirectory} merge-base HEAD ${defaultBranch}`.quiet().text()
+  ).trim();
+
+  const [branchFiles, untrackedFiles, upstreamFiles] = await Promise.all([
+    Bun.$`git -C ${input.worktreeDirectory} diff --name-only ${mergeBase}`.quiet().text(),
+    Bun.$`git -C ${input.worktreeDirectory} ls-files --others --exclude-standard`.quiet().text(),
+    Bun.$`git -C ${input.projectDirectory} diff --name-only ${mergeBase} ${defaultBranch}`.quiet().text(),
+  ]);
+
+  return {
+    taskRef: input.taskRef,
+    changedFiles: [...new Set([...splitLines(branchFiles), ...splitLines(untrackedFiles)])],
+    upstreamFiles: splitLines(upstreamFiles),
+  };
+}
+
+type ConflictRiskRefresher = {
+  refreshConflictRisk(): Promise<void>;
+};
+
+/** Re-scores open task branches on an interval. */
+export class ConflictRiskMonitor {
+  private readonly target: ConflictRiskRefresher;
+  private readonly logger: RuntimeLogger;
+  private intervalMs: number;
+  private timer?: ReturnType<typeof setInterval>;
+  private running = false;
+
+  constructor(target: ConflictRiskRefresher, options: { intervalMs?: number; logger?: RuntimeLogger } = {}) {
+    this.target = target;
+    this.intervalMs = options.intervalMs ?? DEFAULT_INTERVAL_MS;
+    this.logger = options.logger ?? noopRuntimeLogger;
+  }
+
+  start(): void {
+    if (this.timer) {
+      return;
+    }
+
+    this.timer = setInterval(() => {
+      void this.refresh();
+    }, this.intervalMs);
+    this.timer.unref();
+  }
+
+  stop(): void {
+    if (this.timer) {
+      clearInterval(this.timer);
+      this.timer = undefined;
+    }
+  }
+
+  setIntervalMs(intervalMs: number): void {
+    if (intervalMs === this.intervalMs) {
+      return;
+    }
+
+    this.intervalMs = intervalMs;
+    if (this.timer) {
+      this.stop();
+      this.start();
+    }
+  }
+
+  async refresh(): Promise<void> {
+    // A slow git pass must not pile up behind itself.
+    if (this.running) {
+      return;
+    }
+
+    this.running = true;
+    try {
+      await this.target.refreshConflictRisk();
+    } catch (error) {
+      this.logger.log({
+        level: "warn",
+        source: "conflict-risk",
+        message: "Conflict risk refresh failed.",
+        error: toStructuredError(error),
+      });
+    } finally {
+      this.running = false;
+    }
+  }
+}
+
+function splitLines(output: string): string[] {
+  return output
+    .split("\n")
+    .map((line) => line.trim())
+    .filter((line) => line.length > 0);
+}
@@ -4,6 +4,8 @@ import {
   type ConversationSdkSessionMessage,
   type ConversationSessionMeta,
 } from "../domain/conversation";
+import { parsePermissionAsked, type PermissionReply, type PermissionRequest } from "../domain/permission";
+import { parseTodoUpdated, type TaskPlanProgress } from "../domain/task-progress";
 import type { OpenCodeRuntime } from "./opencode-runtime";
 import { noopRuntimeLogger, toStructuredError, type RuntimeLogger } from "./runtime-logger";
 
@@ -72,11 +74,30 @@ export type PromptExecutionResult = {
 
 type PromptMessageHandler = (message: ConversationSdkSessionMessage) => void;
 
+type PromptWaitHandlers = {
+  timeoutMs?: number;
+  onMessage?: PromptMessageHandler;
+  onPermission?: (request: PermissionRequest) => void;
+  onPlanProgress?: (progress: TaskPlanProgress) => void;
+};
+
 export type ListConversationMessagesInput = {
   sessionID: string;
   worktreeDirectory?: string;
 };
 
+export type AbortConversationSessionInput = {
+  sessionID: string;
+  worktreeDirectory?: string;
+};
+
+export type ReplyToPermissionInput = {
+  sessionID: string;
+  requestID: string;
+  reply: PermissionReply;
+  worktreeDirectory?: string;
+};
+
 export type SubscribeToConversationEventsInput = {
   sessionID?: string;
   worktreeDirectory?: string;
@@ -143,7 +164,7 @@ export class ConversationManager {
   }
 
   async sendInitialPromptAndAwaitMessages(
-    input: SendInitialPromptInput & { timeoutMs?: number; onMessage?: PromptMessageHandler },
+    input: SendInitialPromptInput & PromptWaitHandlers,
   ): Promise<PromptExecutionResult> {
     return this.sendPromptAndAwaitMessages(
       input,
@@ -153,7 +174,7 @@ export class ConversationManager {
   }
 
   async sendFollowUpPromptAndAwaitMessages(
-    input: SendFollowUpPromptInput & { timeoutMs?: number; onMessage?: PromptMessageHandler },
+    input: SendFollowUpPromptInput & PromptWaitHandlers,
   ): Promise<PromptExecutionResult> {
     return this.sendPromptAndAwaitMessages(
       input,
@@ -178,6 +199,31 @@ export class ConversationManager {
     return messages;
   }
 
+  async abortSession(input: AbortConversationSessionInput): Promise<void> {
+    const sessionID = normalizeSessionID(input.sessionID);
+    const worktreeDirectory = this.resolveDirectoryForSession(sessionID, input.worktreeDirectory);
+    const client = await this.runtime.getClient(worktreeDirectory);
+    await readDataOrThrow<boolean>(
+      client.session.abort({
+        sessionID,
+      }),
+      "Failed to abort conversation session",
+    );
+  }
+
+  async replyToPermission(input: ReplyToPermissionInput): Promise<void> {
+    const sessionID = normalizeSessionID(input.sessionID);
+    const worktreeDirectory = this.resolveDirectoryForSession(sessionID, input.worktreeDirectory);
+    const client = await this.runtime.getClient(worktreeDirectory);
+    await readDataOrThrow<boolean>(
+      client.permission.reply({
+        requestID: input.requestID,
+        reply: input.reply,
+      }),
+      "Failed to reply to permission request",
+    );
+  }
+
   async subscribeToEvents(
     input: SubscribeToConversationEventsInput,
   ): Promise<ConversationEventSubscription> {
@@ -214,7 +260,7 @@ export class ConversationManager {
   }
 
   private async sendPromptAndAwaitMessages(
-    input: SendInitialPromptInput & { timeoutMs?: number; onMessage?: PromptMessageHandler },
+    input: SendInitialPromptInput & PromptWaitHandlers,
     failureMessage: string,
     logSource: string,
   ): Promise<PromptExecutionResult> {
@@ -230,6 +276,8 @@ export class ConversationManager {
     }
     const resolvedAgent = normalizeOptionalAgent(input.agent);
     const onMessage = input.onMessage;
+    // While a permission is pending the agent is blocked on the user, not stalled.
+    const pendingPermissionIDs = new Set<string>();
     const existingMessages = await this.listConversationMessages({
       sessionID,
       worktreeDirectory,
@@ -316,7 +364,30 @@ export class ConversationManager {
           if (isMessageStreamEvent(event.type)) {
             await pollForNewMessages();
           }
+
+          if (event.type === "permission.asked") {
+            const request = parsePermissionAsked(event.properties);
+            if (request) {
+              pendingPermissionIDs.add(request.id);
+              input.onPermission?.(request);
+            }
+          }
+
+          if (event.type === "todo.updated") {
+            const progress = parseTodoUpdated(event.properties);
+            if (progress) {
+              input.onPlanProgress?.(progress);
+            }
+          }
+
+          if (event.type === "permission.replied") {
+            const requestID = asRecord(event.properties)?.requestID;
+            if (typeof requestID === "string") {
+              pendingPermissionIDs.delete(requestID);
+            }
+          }
         },
+        isBlocked: () => pendingPermissionIDs.size > 0,
         onTick: async () => {
           await pollForNewMessages();
         },
@@ -681,6 +752,7 @@ async function waitForSessionIdle(
     onEvent?: (event: { type: string; properties?: unknown }) => Promise<void>;
     onSessionEvent?: (event: { type: string; properties?: unknown }) => Promise<void>;
     onTick?: () => Promise<void>;
+    isBlocked?: () => boolean;
   },
 ): Promise<{ idle: boolean; errorMessage?: string }> {
   const deadline = Date.now() + timeoutMs;
@@ -688,6 +760,10 @@ async function waitForSessionIdle(
   let sawSessionActivity = false;
 
   while (Date.now() < nextDeadline) {
+    if (hooks?.isBlocked?.()) {
+      nextDeadline = Date.now() + timeoutMs;
+    }
+
     const remainingMs = Math.max(1, nextDeadline - Date.now());
     const next = await nextEventWithTimeout(iterator, Math.min(1_000, remainingMs));
 
@@ -0,0 +1,136 @@
+import { mkdir } from "node:fs/promises";
+import { dirname } from "node:path";
+
+const DRAFT_STORE_STATE_VERSION = 1;
+const DEFAULT_SAVE_DELAY_MS = 2_000;
+
+export type TextDraft = {
+  key: string;
+  value: string;
+  savedAt: number;
+};
+
+type DraftStoreState = {
+  version: number;
+  drafts: TextDraft[];
+};
+
+export type DraftStoreOptions = {
+  stateFilePath: string;
+  saveDelayMs?: number;
+};
+
+/**
+ * Scratch storage for text that is still being typed, keyed by the field it
+ * belongs to (e.g. `follow-up:<taskId>`). Saves are batched so typing does not
+ * hit the disk on every key, but a crash loses at most a few seconds of input.
+ */
+export class DraftStore {
+  private readonly options: DraftStoreOptions;
+  private readonly draftsByKey = new Map<string, TextDraft>();
+  private loadPromise?: Promise<void>;
+  private loaded = false;
+  private saveTimer?: ReturnType<typeof setTimeout>;
+
+  constructor(options: DraftStoreOptions) {
+    this.options = options;
+  }
+
+  async get(key: string): Promise<TextDraft | undefined> {
+    await this.ensureLoaded();
+    return this.draftsByKey.get(key);
+  }
+
+  save(key: string, value: string): void {
+    this.draftsByKey.set(key, { key, value, savedAt: Date.now() });
+    this.scheduleSave();
+  }
+
+  async discard(key: string): Promise<void> {
+    await this.ensureLoaded();
+    if (!this.draftsByKey.delete(key)) {
+      return;
+    }
+
+    await this.flush();
+  }
+
+  async flush(): Promise<void> {
+    if (this.saveTimer) {
+      clearTimeout(this.saveTimer);
+      this.saveTimer = undefined;
+    }
+
+    await this.ensureLoaded();
+    await this.persist();
+  }
+
+  private scheduleSave(): void {
+    if (this.saveTimer) {
+      return;
+    }
+
+    this.saveTimer = setTimeout(() => {
+      this.saveTimer = undefined;
+      void this.flush();
+    }, this.options.saveDelayMs ?? DEFAULT_SAVE_DELAY_MS);
+  }
+
+  private async ensureLoaded(): Promise<void> {
+    if (this.loaded) {
+      return;
+    }
+
+    if (!this.loadPromise) {
+      this.loadPromise = this.loadState().finally(() => {
+        this.loaded = true;
+        this.loadPromise = undefined;
+      });
+    }
+
+    await this.loadPromise;
+  }
+
+  private async loadState(): Promise<void> {
+    const stateFile = Bun.file(this.options.stateFilePath);
+    if (!(await stateFile.exists())) {
+      return;
+    }
+
+    const fileContent = await stateFile.text();
+    if (!fileContent.trim()) {
+      return;
+    }
+
+    const parsedValue = JSON.parse(fileContent) as Partial<DraftStoreState>;
+    if (parsedValue.version !== DRAFT_STORE_STATE_VERSION || !Array.isArray(parsedValue.drafts)) {
+      return;
+    }
+
+    for (const draftLike of parsedValue.drafts) {
+      if (typeof draftLike.key !== "string" || typeof draftLike.value !== "string") {
+        continue;
+      }
+
+      // Keep anything typed before the file finished loading.
+      if (!this.draftsByKey.has(draftLike.key)) {
+        this.draftsByKey.set(draftLike.key, {
+          key: draftLike.key,
+          value: draftLike.value,
+          savedAt: Number(draftLike.savedAt) || 0,
+        });
+      }
+    }
+  }
+
+  private async persist(): Promise<void> {
+    await mkdir(dirname(this.options.stateFilePath), { recursive: true });
+
+    const state: DraftStoreState = {
+      version: DRAFT_STORE_STATE_VERSION,
+      drafts: [...this.draftsByKey.values()],
+    };
+
+    await Bun.write(this.options.stateFilePath, `${JSON.stringify(state, null, 2)}\n`);
+  }
+}
@@ -1,5 +1,6 @@
 import type { TaskState } from "../domain/task";
 import type { ConversationSdkSessionMessage } from "../domain/conversation";
+import type { PermissionReply } from "../domain/permission";
 import type { WorktreeCleanupPolicy } from "./worktree-manager";
 
 export type RuntimeEventMap = {
@@ -71,6 +72,21 @@ export type RuntimeEventMap = {
     sessionID: string;
     sdkMessage: ConversationSdkSessionMessage;
   };
+  "session.permission.requested": {
+    taskId: string;
+    projectId: string;
+    sessionID: string;
+    requestId: string;
+    permission: string;
+    patterns: string[];
+  };
+  "session.permission.replied": {
+    taskId: string;
+    projectId: string;
+    sessionID: string;
+    requestId: string;
+    reply: PermissionReply;
+  };
   "log.appended": {
     level: "debug" | "info" | "warn" | "error";
     message: string;
@@ -118,11 +134,19 @@ export type RuntimeLogEntry = {
   raw?: unknown;
 };
 
+/** Narrows a subscription to one entity, e.g. the task or session a detail view shows. */
+export type RuntimeEventScope = {
+  taskId?: string;
+  /** Only session events carry a session id, so task and worktree events are skipped. */
+  sessionID?: string;
+};
+
 type ListenerDisposer = () => void;
 
 type ListenerRegistration<TListener> = {
   listener: TListener;
   types?: ReadonlySet<RuntimeEventType>;
+  scope?: RuntimeEventScope;
 };
 
 export class RuntimeEventBus {
@@ -154,20 +178,26 @@ export class RuntimeEventBus {
     return event;
   }
 
-  subscribe(listener: RuntimeEventListener, options?: { types?: readonly RuntimeEventType[] }): ListenerDisposer {
-    return this.register(this.listeners, listener, options?.types);
+  subscribe(
+    listener: RuntimeEventListener,
+    options?: { types?: readonly RuntimeEventType[] } & RuntimeEventScope,
+  ): ListenerDisposer {
+    return this.register(this.listeners, listener, options?.types, toScope(options));
   }
 
   subscribeToUiUpdates(
     listener: (update: RuntimeUiUpdate) => void,
-    options?: { types?: readonly Exclude<RuntimeEventType, "log.appended">[] },
+    options?: { types?: readonly Exclude<RuntimeEventType, "log.appended">[]; taskId?: string },
   ): ListenerDisposer {
     const types = options?.types ? new Set<RuntimeEventType>(options.types) : undefined;
-    return this.register(this.uiListeners, listener, types);
+    return this.register(this.uiListeners, listener, types, toScope(options));
   }
 
-  subscribeToLogs(listener: (entry: RuntimeLogEntry) => void): ListenerDisposer {
-    return this.register(this.logListeners, listener);
+  subscribeToLogs(
+    listener: (entry: RuntimeLogEntry) => void,
+    options?: { taskId?: string },
+  ): ListenerDisposer {
+    return this.register(this.logListeners, listener, undefined, toScope(options));
   }
 
   clear(): void {
@@ -188,6 +218,10 @@ export class RuntimeEventBus {
         continue;
       }
 
+      if (!matchesScope(registration.scope, event.payload as { taskId?: string; sessionID?: string })) {
+        continue;
+      }
+
       registration.listener(event);
     }
   }
@@ -200,6 +234,10 @@ export class RuntimeEventBus {
         continue;
       }
 
+      if (!matchesScope(registration.scope, update)) {
+        continue;
+      }
+
       registration.listener(update);
     }
   }
@@ -208,6 +246,10 @@ export class RuntimeEventBus {
     const registrations = Array.from(this.logListeners.values());
 
     for (const registration of registrations) {
+      if (!matchesScope(registration.scope, entry)) {
+        continue;
+      }
+
       registration.listener(entry);
     }
   }
@@ -216,11 +258,13 @@ export class RuntimeEventBus {
     target: Map<number, ListenerRegistration<TListener>>,
     listener: TListener,
     types?: Iterable<RuntimeEventType>,
+    scope?: RuntimeEventScope,
   ): ListenerDisposer {
     const id = this.nextListenerId++;
     target.set(id, {
       listener,
       types: types ? new Set(types) : undefined,
+      scope,
     });
 
     let active = true;
@@ -235,6 +279,29 @@ export class RuntimeEventBus {
   }
 }
 
+function toScope(options: RuntimeEventScope | undefined): RuntimeEventScope | undefined {
+  if (!options?.taskId && !options?.sessionID) {
+    return undefined;
+  }
+
+  return { taskId: options.taskId, sessionID: options.sessionID };
+}
+
+function matchesScope(
+  scope: RuntimeEventScope | undefined,
+  subject: { taskId?: string; sessionID?: string },
+): boolean {
+  if (!scope) {
+    return true;
+  }
+
+  if (scope.taskId !== undefined && subject.taskId !== scope.taskId) {
+    return false;
+  }
+
+  return scope.sessionID === undefined || subject.sessionID === scope.sessionID;
+}
+
 function toUiUpdate(event: RuntimeEventEnvelope<Exclude<RuntimeEventType, "log.appended">>): RuntimeUiUpdate {
   const payload = event.payload as { taskId: string; projectId: string };
   const [scope, action] = event.type.split(".") as ["task" | "worktree" | "session", string];
@@ -300,13 +367,19 @@ function toDefaultLogMessage(event: RuntimeEventEnvelope<Exclude<RuntimeEventTyp
     case "worktree.cleanup":
       return `Worktree cleanup (${String(payload.policy)}) removed=${String(payload.removed)}.`;
     case "session.created":
-      return `Session ${String(payload.sessionID)} created.`;
+      return typeof payload.title === "string"
+        ? `Session "${payload.title}" (${String(payload.sessionID)}) created.`
+        : `Session ${String(payload.sessionID)} created.`;
     case "session.prompt.submitted":
       return `Prompt submitted to session ${String(payload.sessionID)}.`;
     case "session.message.received": {
       const messagePayload = payload as RuntimeEventMap["session.message.received"];
       return `Message ${String(messagePayload.sdkMessage.info.id)} received for session ${String(payload.sessionID)}.`;
     }
+    case "session.permission.requested":
+      return `Session ${String(payload.sessionID)} asks for ${String(payload.permission)} permission.`;
+    case "session.permission.replied":
+      return `Permission ${String(payload.requestId)} answered: ${String(payload.reply)}.`;
   }
 }
 
@@ -0,0 +1,228 @@
+import { chmod, mkdir, readFile, rename, rm, writeFile } from "node:fs/promises";
+import { isAbsolute, join, resolve } from "node:path";
+
+import type { TaskRuntime } from "../domain/task";
+
+export const MANAGED_GIT_HOOKS = ["commit-msg", "pre-push"] as const;
+
+export type ManagedGitHook = (typeof MANAGED_GIT_HOOKS)[number];
+
+export type GitHooksResult = {
+  hooksDirectory: string;
+  hooks: ManagedGitHook[];
+  backedUp: ManagedGitHook[];
+};
+
+type TaskSource = {
+  listTasks(): Promise<TaskRuntime[]>;
+};
+
+const MANAGED_HOOK_MARKER = "# ikanban-managed-hook";
+const BACKUP_SUFFIX = ".ikanban-backup";
+const TASK_TRAILER_KEY = "Task";
+// Pushing is blocked for branches whose task ended in one of these states. Failed
+// branches stay pushable so they can be shared for debugging.
+const PUSH_BLOCKED_STATES: ReadonlySet<TaskRuntime["state"]> = new Set(["cancelled"]);
+
+export function defaultGitHookCommand(): string {
+  const entry = process.argv[1];
+  const parts = entry ? [process.execPath, resolve(entry)] : [process.execPath];
+  return parts.map(shellQuote).join(" ");
+}
+
+export async function installTaskGitHooks(
+  projectDirectory: string,
+  command = defaultGitHookCommand(),
+): Promise<GitHooksResult> {
+  const hooksDirectory = await resolveHooksDirectory(projectDirectory);
+  const backedUp: ManagedGitHook[] = [];
+
+  await mkdir(hooksDirectory, { recursive: true });
+
+  for (const hook of MANAGED_GIT_HOOKS) {
+    const hookPath = join(hooksDirectory, hook);
+    const existing = await readFile(hookPath, "utf8").catch(() => undefined);
+    if (existing !== undefined && !existing.includes(MANAGED_HOOK_MARKER)) {
+      await rename(hookPath, `${hookPath}${BACKUP_SUFFIX}`);
+      backedUp.push(hook);
+    }
+
+    await writeFile(hookPath, buildHookScript(hook, command));
+    await chmod(hookPath, 0o755);
+  }
+
+  return { hooksDirectory, hooks: [...MANAGED_GIT_HOOKS], backedUp };
+}
+
+export async function uninstallTaskGitHooks(projectDirectory: string): Promise<GitHooksResult> {
+  const hooksDirectory = await resolveHooksDirectory(projectDirectory);
+  const removed: ManagedGitHook[] = [];
+  const restored: ManagedGitHook[] = [];
+
+  for (const hook of MANAGED_GIT_HOOKS) {
+    const hookPath = join(hooksDirectory, hook);
+    const existing = await readFile(hookPath, "utf8").catch(() => undefined);
+    if (existing === undefined || !existing.includes(MANAGED_HOOK_MARKER)) {
+      continue;
+    }
+
+    await rm(hookPath, { force: true });
+    removed.push(hook);
+
+    const backupPath = `${hookPath}${BACKUP_SUFFIX}`;
+    if (await Bun.file(backupPath).exists()) {
+      await rename(backupPath, hookPath);
+      restored.push(hook);
+    }
+  }
+
+  return { hooksDirectory, hooks: removed, backedUp: restored };
+}
+
+/**
+ * Entry point for `ikanban hook <name> ...`, invoked by the installed hook scripts.
+ * Returns the process exit code. Internal errors let the commit or push through
+ * with a warning: only a task's state should ever stop one.
+ */
+export async function runGitHook(
+  hook: string,
+  args: string[],
+  taskSource: TaskSource,
+  stdin: string,
+): Promise<number> {
+  try {
+    return await dispatchGitHook(hook, args, taskSource, stdin);
+  } catch (error) {
+    console.error(`ikanban: ${hook} hook skipped: ${error instanceof Error ? error.message : String(error)}`);
+    return 0;
+  }
+}
+
+async function dispatchGitHook(
+  hook: string,
+  args: string[],
+  taskSource: TaskSource,
+  stdin: string,
+): Promise<number> {
+  const tasks = await taskSource.listTasks();
+
+  switch (hook) {
+    case "commit-msg": {
+      const messageFile = args[0];
+      if (!messageFile) {
+        return 0;
+      }
+
+      const task = await findTaskForWorktree(tasks, process.cwd());
+      if (!task?.shortCode) {
+        return 0;
+      }
+
+      const message = await readFile(messageFile, "utf8");
+      await writeFile(messageFile, appendTaskTrailer(message, task.shortCode));
+      return 0;
+    }
+    case "pre-push": {
+      const pushedBranches = parsePrePushBranches(stdin);
+      for (const task of tasks) {
+        if (!PUSH_BLOCKED_STATES.has(task.state) || !task.worktreeDirectory) {
+          continue;
+        }
+
+        const branch = await readBranch(task.worktreeDirectory);
+        if (branch && pushedBranches.includes(branch)) {
+          console.error(
+            `ikanban: refusing to push ${branch}; task ${task.shortCode ?? task.taskId} is ${task.state}.`,
+          );
+          return 1;
+        }
+      }
+
+      return 0;
+    }
+    default:
+      console.error(`ikanban: unknown hook ${hook}.`);
+      return 0;
+  }
+}
+
+export function appendTaskTrailer(message: string, shortCode: string): string {
+  const trailer = `${TASK_TRAILER_KEY}: ${shortCode}`;
+  if (message.split("\n").some((line) => line.trim() === trailer)) {
+    return message;
+  }
+
+  const body = message.replace(/\s+$/, "");
+  return `${body}\n\n${trailer}\n`;
+}
+
+function parsePrePushBranches(stdin: string): string[] {
+  return stdin
+    .split("\n")
+    .map((line) => line.trim().split(/\s+/)[0] ?? "")
+    .filter((ref) => ref.startsWith("refs/heads/"))
+    .map((ref) => ref.slice("refs/heads/".length));
+}
+
+async function findTaskForWorktree(
+  tasks: TaskRuntime[],
+  directory: string,
+): Promise<TaskRuntime | undefined> {
+  const topLevel = (await Bun.$`git -C ${directory} rev-parse --show-toplevel`.nothrow().quiet().text()).trim();
+  if (!topLevel) {
+    return undefined;
+  }
+
+  return tasks.find(
+    (task) => task.worktreeDirectory && resolve(task.worktreeDirectory) === resolve(topLevel),
+  );
+}
+
+async function readBranch(directory: string): Promise<string | undefined> {
+  const result = await Bun.$`git -C ${directory} rev-parse --abbrev-ref HEAD`.nothrow().quiet();
+  const branch = result.stdout.toString().trim();
+  return result.exitCode === 0 && branch && branch !== "HEAD" ? branch : undefined;
+}
+
+async function resolveHooksDirectory(projectDirectory: string): Promise<string> {
+  // --git-path honours core.hooksPath and resolves to the common dir shared by all worktrees.
+  const hooksPath = (await Bun.$`git -C ${projectDirectory} rev-parse --git-path hooks`.text()).trim();
+  if (!hooksPath) {
+    throw new Error(`Failed to resolve git hooks directory for ${projectDirectory}.`);
+  }
+
+  return isAbsolute(hooksPath) ? hooksPath : resolve(projectDirectory, hooksPath);
+}
+
+function buildHookScript(hook: ManagedGitHook, command: string): string {
+  const lines = [
+    "#!/bin/sh",
+    MANAGED_HOOK_MARKER,
+    "# Installed by ikanban; remove with the project selector uninstall action.",
+  ];
+
+  if (hook === "pre-push") {
+    lines.push(
+      'input=$(cat)',
+      `if [ -x "$0${BACKUP_SUFFIX}" ]; then`,
+      `  printf '%s\\n' "$input" | "$0${BACKUP_SUFFIX}" "$@" || exit $?`,
+      "fi",
+      `printf '%s\\n' "$input" | ${command} hook ${hook} "$@"`,
+    );
+  } else {
+    lines.push(
+      `if [ -x "$0${BACKUP_SUFFIX}" ]; then`,
+      `  "$0${BACKUP_SUFFIX}" "$@" || exit $?`,
+      "fi",
+      `${command} hook ${hook} "$@"`,
+    );
+  }
+
+  // 127 means ikanban itself is gone; never block git because of that.
+  lines.push("status=$?", '[ "$status" -eq 127 ] && exit 0', 'exit "$status"', "");
+  return lines.join("\n");
+}
+
+function shellQuote(value: string): string {
+  return `'${value.replace(/'/g, `'\\''`)}'`;
+}
@@ -0,0 +1,184 @@
+import { IkanbanError } from "../domain/errors";
+import type { TaskPullRequest } from "../domain/task";
+import { noopRuntimeLogger, toStructuredError, type RuntimeLogger } from "./runtime-logger";
+
+const DEFAULT_API_URL = "https://api.github.com";
+const DEFAULT_POLL_INTERVAL_MS = 300_000;
+
+export type GitHubRepository = {
+  owner: string;
+  repo: string;
+};
+
+export type CreatePullRequestInput = {
+  head: string;
+  base: string;
+  title: string;
+  body?: string;
+};
+
+export type GitHubPullRequest = Pick<TaskPullRequest, "number" | "url" | "state">;
+
+const GITHUB_REMOTE_PATTERNS = [
+  /^https:\/\/(?:[^@/]+@)?github\.com\/([^/]+)\/([^/]+?)(?:\.git)?\/?$/,
+  /^(?:ssh:\/\/)?git@github\.com[:/]([^/]+)\/([^/]+?)(?:\.git)?\/?$/,
+];
+
+/** `owner/repo` of a github.com remote URL (https or ssh); other hosts yield undefined. */
+export function parseGitHubRemote(url: string): GitHubRepository | undefined {
+  for (const pattern of GITHUB_REMOTE_PATTERNS) {
+    const match = pattern.exec(url.trim());
+    if (match?.[1] && match[2]) {
+      return { owner: match[1], repo: match[2] };
+    }
+  }
+
+  return undefined;
+}
+
+/** The GitHub repository behind `remote` and the branch checked out in the project, which PRs target. */
+export async function resolveGitHubTarget(
+  projectDirectory: string,
+  remote: string,
+): Promise<{ repository: GitHubRepository; baseBranch: string }> {
+  const remoteUrl = (await Bun.$`git -C ${projectDirectory} remote get-url ${remote}`.quiet().text()).trim();
+  const repository = parseGitHubRemote(remoteUrl);
+  if (!repository) {
+    throw new IkanbanError("validation", `Remote ${remote} (${remoteUrl}) is not a github.com repository.`);
+  }
+
+  const baseBranch = (await Bun.$`git -C ${projectDirectory} rev-parse --abbrev-ref HEAD`.quiet().text()).trim();
+  return { repository, baseBranch };
+}
+
+/** Minimal GitHub REST client for opening task pull requests and following their state. */
+export class GitHubClient {
+  private token?: string;
+  private readonly apiUrl: string;
+
+  constructor(options: { token?: string; apiUrl?: string } = {}) {
+    this.token = options.token;
+    this.apiUrl = options.apiUrl ?? DEFAULT_API_URL;
+  }
+
+  setToken(token?: string): void {
+    this.token = token;
+  }
+
+  async createPullRequest(repository: GitHubRepository, input: CreatePullRequestInput): Promise<GitHubPullRequest> {
+    return toPullRequest(
+      await this.request("POST", `/repos/${repository.owner}/${repository.repo}/pulls`, input),
+    );
+  }
+
+  async getPullRequest(repository: GitHubRepository, number: number): Promise<GitHubPullRequest> {
+    return toPullRequest(await this.request("GET", `/repos/${repository.owner}/${repository.repo}/pulls/${number}`));
+  }
+
+  private async request(method: string, path: string, body?: unknown): Promise<Record<string, unknown>> {
+    if (!this.token) {
+      throw new IkanbanError("validation", "No GitHub token configured; set IKANBAN_GITHUB_TOKEN.", {
+        field: "IKANBAN_GITHUB_TOKEN",
+      });
+    }
+
+    const response = await fetch(`${this.apiUrl}${path}`, {
+      method,
+      headers: {
+        Accept: "application/vnd.github+json",
+        Authorization: `Bearer ${this.token}`,
+        "X-GitHub-Api-Version": "2022-11-28",
+        ...(body ? { "Content-Type": "application/json" } : {}),
+      },
+      body: body ? JSON.stringify(body) : undefined,
+    });
+    const payload = (await response.json().catch(() => ({}))) as Record<string, unknown>;
+    if (!response.ok) {
+      const detail = typeof payload.message === "string" ? payload.message : response.statusText;
+      throw new Error(`GitHub ${method} ${path} failed (${response.status}): ${detail}`);
+    }
+
+    return payload;
+  }
+}
+
+function toPullRequest(payload: Record<string, unknown>): GitHubPullRequest {
+  if (typeof payload.number !== "number" || typeof payload.html_url !== "string") {
+    throw new Error("GitHub returned a pull request without a number or URL.");
+  }
+
+  return {
+    number: payload.number,
+    url: payload.html_url,
+    state: payload.merged_at ? "merged" : payload.state === "closed" ? "closed" : "open",
+  };
+}
+
+type PullRequestRefresher = {
+  refreshPullRequests(): Promise<void>;
+};
+
+/** Polls open task pull requests so merges and closes made on GitHub show on the board. */
+export class PullRequestMonitor {
+  private readonly target: PullRequestRefresher;
+  private readonly logger: RuntimeLogger;
+  private intervalMs: number;
+  private timer?: ReturnType<typeof setInterval>;
+  private running = false;
+
+  constructor(target: PullRequestRefresher, options: { intervalMs?: number; logger?: RuntimeLogger } = {}) {
+    this.target = target;
+    this.intervalMs = options.intervalMs ?? DEFAULT_POLL_INTERVAL_MS;
+    this.logger = options.logger ?? noopRuntimeLogger;
+  }
+
+  start(): void {
+    if (this.timer) {
+      return;
+    }
+
+    this.timer = setInterval(() => {
+      void this.refresh();
+    }, this.intervalMs);
+    this.timer.unref();
+  }
+
+  stop(): void {
+    if (this.timer) {
+      clearInterval(this.timer);
+      this.timer = undefined;
+    }
+  }
+
+  setIntervalMs(intervalMs: number): void {
+    if (intervalMs === this.intervalMs) {
+      return;
+    }
+
+    this.intervalMs = intervalMs;
+    if (this.timer) {
+      this.stop();
+      this.start();
+    }
+  }
+
+  async refresh(): Promise<void> {
+    if (this.running) {
+      return;
+    }
+
+    this.running = true;
+    try {
+      await this.target.refreshPullRequests();
+    } catch (error) {
+      this.logger.log({
+        level: "warn",
+        source: "github",
+        message: "Pull request refresh failed.",
+        error: toStructuredError(error),
+      });
+    } finally {
+      this.running = false;
+    }
+  }
+}
@@ -0,0 +1,166 @@
+import { mkdir } from "node:fs/promises";
+import { dirname } from "node:path";
+
+import { IkanbanError } from "../domain/errors";
+import {
+  assertLabelInvariants,
+  findLabelByName,
+  isLabelColor,
+  LABEL_COLORS,
+  type Label,
+  type LabelColor,
+} from "../domain/label";
+import { assertValid, validateName } from "../domain/validation";
+
+const LABEL_REGISTRY_STATE_VERSION = 1;
+
+type LabelRegistryState = {
+  version: number;
+  labels: Label[];
+};
+
+export type LabelRegistryOptions = {
+  stateFilePath: string;
+};
+
+export type CreateLabelInput = {
+  projectId: string;
+  name: string;
+  /** Defaults to the next color not yet used in the project. */
+  color?: LabelColor;
+};
+
+export class LabelRegistry {
+  private readonly options: LabelRegistryOptions;
+  private readonly labelsById = new Map<string, Label>();
+  private loadPromise?: Promise<void>;
+  private loaded = false;
+
+  constructor(options: LabelRegistryOptions) {
+    this.options = options;
+  }
+
+  async listLabels(projectId?: string): Promise<Label[]> {
+    await this.ensureLoaded();
+    return [...this.labelsById.values()]
+      .filter((label) => projectId === undefined || label.projectId === projectId)
+      .sort((left, right) => left.name.localeCompare(right.name));
+  }
+
+  async createLabel(input: CreateLabelInput): Promise<Label> {
+    await this.ensureLoaded();
+    assertValid(validateName(input.name, "Label name"));
+    if (/\s/.test(input.name.trim())) {
+      throw new IkanbanError("validation", "Label names cannot contain spaces.", { field: "name" });
+    }
+
+    const projectId = input.projectId.trim();
+    const projectLabels = await this.listLabels(projectId);
+    if (findLabelByName(projectLabels, input.name)) {
+      throw new IkanbanError("conflict", `Label ${input.name.trim()} already exists.`, { field: "name" });
+    }
+
+    const createdAt = Date.now();
+    const usedColors = new Set(projectLabels.map((label) => label.color));
+    const label: Label = {
+      id: `${projectId}-label-${createdAt.toString(36)}`,
+      projectId,
+      name: input.name.trim(),
+      color: input.color ?? LABEL_COLORS.find((color) => !usedColors.has(color)) ?? "white",
+      createdAt,
+    };
+
+    assertLabelInvariants(label);
+    this.labelsById.set(label.id, label);
+    await this.persist();
+
+    return label;
+  }
+
+  async setLabelColor(labelId: string, color: LabelColor): Promise<Label> {
+    await this.ensureLoaded();
+
+    const label = this.labelsById.get(labelId.trim());
+    if (!label) {
+      throw new IkanbanError("not_found", `Label not found: ${labelId}`);
+    }
+
+    const nextLabel: Label = { ...label, color };
+    assertLabelInvariants(nextLabel);
+    this.labelsById.set(nextLabel.id, nextLabel);
+    await this.persist();
+
+    return nextLabel;
+  }
+
+  async removeLabel(labelId: string): Promise<boolean> {
+    await this.ensureLoaded();
+
+    const removed = this.labelsById.delete(labelId.trim());
+    if (removed) {
+      await this.persist();
+    }
+
+    return removed;
+  }
+
+  private async ensureLoaded(): Promise<void> {
+    if (this.loaded) {
+      return;
+    }
+
+    if (!this.loadPromise) {
+      this.loadPromise = this.loadState().finally(() => {
+        this.loaded = true;
+        this.loadPromise = undefined;
+      });
+    }
+
+    await this.loadPromise;
+  }
+
+  private async loadState(): Promise<void> {
+    const stateFile = Bun.file(this.options.stateFilePath);
+    if (!(await stateFile.exists())) {
+      return;
+    }
+
+    const fileContent = await stateFile.text();
+    if (!fileContent.trim()) {
+      return;
+    }
+
+    const parsedValue = JSON.parse(fileContent) as Partial<LabelRegistryState>;
+    if (parsedValue.version !== LABEL_REGISTRY_STATE_VERSION) {
+      throw new Error(`Unsupported label registry state version: ${parsedValue.version ?? "unknown"}.`);
+    }
+
+    if (!Array.isArray(parsedValue.labels)) {
+      throw new Error("Invalid label registry state: labels must be an array.");
+    }
+
+    for (const labelLike of parsedValue.labels) {
+      const label: Label = {
+        id: String(labelLike.id),
+        projectId: String(labelLike.projectId),
+        name: String(labelLike.name),
+        color: isLabelColor(labelLike.color) ? labelLike.color : "white",
+        createdAt: Number(labelLike.createdAt),
+      };
+
+      assertLabelInvariants(label);
+      this.labelsById.set(label.id, label);
+    }
+  }
+
+  private async persist(): Promise<void> {
+    await mkdir(dirname(this.options.stateFilePath), { recursive: true });
+
+    const state: LabelRegistryState = {
+      version: LABEL_REGISTRY_STATE_VERSION,
+      labels: [...this.labelsById.values()],
+    };
+
+    await Bun.write(this.options.stateFilePath, `${JSON.stringify(state, null, 2)}\n`);
+  }
+}
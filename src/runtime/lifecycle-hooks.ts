@@ -0,0 +1,180 @@
+import { IkanbanError } from "../domain/errors";
+import { noopRuntimeLogger, type RuntimeLogger } from "./runtime-logger";
+
+export const LIFECYCLE_HOOK_POINTS = ["task.created", "session.finished", "before.merge"] as const;
+
+export type LifecycleHookPoint = (typeof LIFECYCLE_HOOK_POINTS)[number];
+
+export type LifecycleHookCommands = Partial<Record<LifecycleHookPoint, string[]>>;
+
+export type LifecycleHookAnnotation = {
+  hook: string;
+  message: string;
+};
+
+export type LifecycleHookOutcome = {
+  allowed: boolean;
+  annotations: LifecycleHookAnnotation[];
+  vetoedBy?: string;
+  reason?: string;
+};
+
+export type LifecycleHookRunnerOptions = {
+  hooks: LifecycleHookCommands;
+  timeoutMs?: number;
+  logger?: RuntimeLogger;
+};
+
+type HookResponse = {
+  allow?: unknown;
+  message?: unknown;
+  annotation?: unknown;
+};
+
+const DEFAULT_HOOK_TIMEOUT_MS = 10_000;
+
+export class LifecycleHookVetoError extends IkanbanError {
+  readonly point: LifecycleHookPoint;
+  readonly hook: string;
+
+  constructor(point: LifecycleHookPoint, hook: string, reason?: string) {
+    super("hook_vetoed", `Hook ${hook} vetoed ${point}${reason ? `: ${reason}` : "."}`);
+    this.name = "LifecycleHookVetoError";
+    this.point = point;
+    this.hook = hook;
+  }
+}
+
+/**
+ * Runs the external executables configured for a lifecycle point. Each hook
+ * gets `{ point, ...payload }` as JSON on stdin. A non-zero exit or
+ * `{"allow": false}` vetoes the operation; `{"annotation": "..."}` (or plain
+ * stdout) annotates it. Hooks run in order and the first veto stops the chain.
+ */
+export class LifecycleHookRunner {
+  private hooks: LifecycleHookCommands;
+  private timeoutMs: number;
+  private readonly logger: RuntimeLogger;
+
+  constructor(options: LifecycleHookRunnerOptions) {
+    this.hooks = options.hooks;
+    this.timeoutMs = options.timeoutMs ?? DEFAULT_HOOK_TIMEOUT_MS;
+    this.logger = options.logger ?? noopRuntimeLogger;
+  }
+
+  setHooks(hooks: LifecycleHookCommands, timeoutMs?: number): void {
+    this.hooks = hooks;
+    this.timeoutMs = timeoutMs ?? DEFAULT_HOOK_TIMEOUT_MS;
+  }
+
+  hasHooks(point: LifecycleHookPoint): boolean {
+    return (this.hooks[point]?.length ?? 0) > 0;
+  }
+
+  async run(
+    point: LifecycleHookPoint,
+    payload: Record<string, unknown>,
+    options: { cwd?: string } = {},
+  ): Promise<LifecycleHookOutcome> {
+    const annotations: LifecycleHookAnnotation[] = [];
+    const input = JSON.stringify({ point, ...payload });
+
+    for (const hook of this.hooks[point] ?? []) {
+      const result = await this.runHook(hook, point, input, options.cwd);
+      if (result.annotation) {
+        annotations.push({ hook, message: result.annotation });
+      }
+
+      if (!result.allowed) {
+        this.logger.log({
+          level: "warn",
+          source: "lifecycle-hooks",
+          message: `Hook vetoed ${point}.`,
+          context: { hook, point, reason: result.reason },
+        });
+        return { allowed: false, annotations, vetoedBy: hook, reason: result.reason };
+      }
+    }
+
+    return { allowed: true, annotations };
+  }
+
+  private async runHook(
+    hook: string,
+    point: LifecycleHookPoint,
+    input: string,
+    cwd?: string,
+  ): Promise<{ allowed: boolean; annotation?: string; reason?: string }> {
+    let timedOut = false;
+    let child: ReturnType<typeof Bun.spawn>;
+
+    try {
+      child = Bun.spawn([hook], {
+        cwd,
+        env: { ...process.env, IKANBAN_HOOK_POINT: point },
+        stdin: new Blob([input]),
+        stdout: "pipe",
+        stderr: "pipe",
+      });
+    } catch (error) {
+      // Hooks are policy; one that cannot start must not silently let the operation through.
+      return { allowed: false, reason: `failed to start: ${String(error)}` };
+    }
+
+    const timer = setTimeout(() => {
+      timedOut = true;
+      child.kill();
+    }, this.timeoutMs);
+
+    try {
+      const [exitCode, stdout, stderr] = await Promise.all([
+        child.exited,
+        new Response(child.stdout as ReadableStream).text(),
+        new Response(child.stderr as ReadableStream).text(),
+      ]);
+
+      if (timedOut) {
+        return { allowed: false, reason: `timed out after ${this.timeoutMs}ms` };
+      }
+
+      const response = parseHookResponse(stdout);
+      const message = typeof response?.message === "string" ? response.message.trim() : undefined;
+      const annotation =
+        typeof response?.annotation === "string"
+          ? response.annotation.trim()
+          : response
+            ? undefined
+            : stdout.trim() || undefined;
+
+      if (exitCode !== 0) {
+        return {
+          allowed: false,
+          annotation,
+          reason: message || stderr.trim() || `exited with code ${exitCode}`,
+        };
+      }
+
+      if (response?.allow === false) {
+        return { allowed: false, annotation, reason: message };
+      }
+
+      return { allowed: true, annotation: annotation ?? message };
+    } finally {
+      clearTimeout(timer);
+    }
+  }
+}
+
+function parseHookResponse(stdout: string): HookResponse | undefined {
+  const trimmed = stdout.trim();
+  if (!trimmed.startsWith("{")) {
+    return undefined;
+  }
+
+  try {
+    const parsed: unknown = JSON.parse(trimmed);
+    return parsed && typeof parsed === "object" ? (parsed as HookResponse) : undefined;
+  } catch {
+    return undefined;
+  }
+}
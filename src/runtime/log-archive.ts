@@ -0,0 +1,183 @@
+import { mkdir } from "node:fs/promises";
+import { join } from "node:path";
+
+import type { ConversationSdkSessionMessage } from "../domain/conversation";
+import type { TaskRuntime } from "../domain/task";
+import type { RuntimeLogEntry } from "./event-bus";
+
+export type LogArchiveInput = {
+  /** The task's runs, oldest first: the original task and its retries. */
+  runs: TaskRuntime[];
+  projectName?: string;
+  /** Runtime log records kept for these tasks. */
+  logs: RuntimeLogEntry[];
+  /** Loaded one run at a time, so only one session's messages are held at once. */
+  loadMessages: (task: TaskRuntime) => Promise<ConversationSdkSessionMessage[]>;
+  exportedAt?: number;
+};
+
+export type LogArchiveResult = {
+  filePath: string;
+  entryCount: number;
+};
+
+type ZipEntryRecord = {
+  name: Uint8Array;
+  crc: number;
+  size: number;
+  offset: number;
+};
+
+const ZIP_VERSION = 20;
+// Bit 11: entry names are UTF-8.
+const ZIP_UTF8_FLAG = 0x0800;
+
+/**
+ * Writes `<code>-logs.zip` with one folder per run (the task record and its
+ * session messages as JSON), the runtime log records as JSON lines and a
+ * `metadata.json` summary, for attaching to bug reports.
+ */
+export async function exportTaskLogArchive(
+  directory: string,
+  input: LogArchiveInput,
+): Promise<LogArchiveResult> {
+  const [firstRun] = input.runs;
+  if (!firstRun) {
+    throw new Error("No task runs to archive.");
+  }
+
+  const exportedAt = input.exportedAt ?? Date.now();
+  await mkdir(directory, { recursive: true });
+  const filePath = join(directory, `${sanitizeFileName(firstRun.shortCode ?? firstRun.taskId)}-logs.zip`);
+  const zip = new ZipStreamWriter(filePath, new Date(exportedAt));
+
+  try {
+    zip.add(
+      "metadata.json",
+      JSON.stringify(
+        {
+          exportedAt: new Date(exportedAt).toISOString(),
+          project: { id: firstRun.projectId, name: input.projectName },
+          runs: input.runs.map((run) => ({
+            taskId: run.taskId,
+            shortCode: run.shortCode,
+            state: run.state,
+            sessionID: run.sessionID,
+            retryAttempt: run.retryAttempt,
+            createdAt: new Date(run.createdAt).toISOString(),
+            error: run.error,
+          })),
+        },
+        null,
+        2,
+      ),
+    );
+
+    for (const [index, run] of input.runs.entries()) {
+      const folder = `${index + 1}-${sanitizeFileName(run.taskId)}`;
+      zip.add(`${folder}/task.json`, JSON.stringify(run, null, 2));
+      if (run.sessionID) {
+        zip.add(`${folder}/messages.json`, JSON.stringify(await input.loadMessages(run), null, 2));
+      }
+    }
+
+    zip.add("runtime-log.jsonl", input.logs.map((entry) => JSON.stringify(entry)).join("\n"));
+  } finally {
+    await zip.close();
+  }
+
+  return { filePath, entryCount: zip.entryCount };
+}
+
+/**
+ * Minimal zip writer: entries are stored uncompressed and written to the file
+ * as they are added, so the archive is never held in memory as a whole.
+ */
+class ZipStreamWriter {
+  private readonly sink: ReturnType<ReturnType<typeof Bun.file>["writer"]>;
+  private readonly entries: ZipEntryRecord[] = [];
+  private readonly dosTime: number;
+  private readonly dosDate: number;
+  private offset = 0;
+  private closed = false;
+
+  constructor(filePath: string, modifiedAt: Date) {
+    this.sink = Bun.file(filePath).writer();
+    this.dosTime =
+      (modifiedAt.getHours() << 11) | (modifiedAt.getMinutes() << 5) | Math.floor(modifiedAt.getSeconds() / 2);
+    this.dosDate =
+      ((Math.max(modifiedAt.getFullYear(), 1980) - 1980) << 9) | ((modifiedAt.getMonth() + 1) << 5) | modifiedAt.getDate();
+  }
+
+  get entryCount(): number {
+    return this.entries.length;
+  }
+
+  add(name: string, content: string): void {
+    const nameBytes = new TextEncoder().encode(name);
+    const data = new TextEncoder().encode(content);
+    const entry: ZipEntryRecord = { name: nameBytes, crc: Bun.hash.crc32(data), size: data.byteLength, offset: this.offset };
+
+    const header = new DataView(new ArrayBuffer(30));
+    header.setUint32(0, 0x04034b50, true);
+    header.setUint16(4, ZIP_VERSION, true);
+    header.setUint16(6, ZIP_UTF8_FLAG, true);
+    header.setUint16(8, 0, true);
+    header.setUint16(10, this.dosTime, true);
+    header.setUint16(12, this.dosDate, true);
+    header.setUint32(14, entry.crc, true);
+    header.setUint32(18, entry.size, true);
+    header.setUint32(22, entry.size, true);
+    header.setUint16(26, nameBytes.byteLength, true);
+    header.setUint16(28, 0, true);
+
+    this.write(new Uint8Array(header.buffer));
+    this.write(nameBytes);
+    this.write(data);
+    this.entries.push(entry);
+  }
+
+  async close(): Promise<void> {
+    if (this.closed) {
+      return;
+    }
+    this.closed = true;
+
+    const directoryOffset = this.offset;
+    for (const entry of this.entries) {
+      const record = new DataView(new ArrayBuffer(46));
+      record.setUint32(0, 0x02014b50, true);
+      record.setUint16(4, ZIP_VERSION, true);
+      record.setUint16(6, ZIP_VERSION, true);
+      record.setUint16(8, ZIP_UTF8_FLAG, true);
+      record.setUint16(10, 0, true);
+      record.setUint16(12, this.dosTime, true);
+      record.setUint16(14, this.dosDate, true);
+      record.setUint32(16, entry.crc, true);
+      record.setUint32(20, entry.size, true);
+      record.setUint32(24, entry.size, true);
+      record.setUint16(28, entry.name.byteLength, true);
+      record.setUint32(42, entry.offset, true);
+      this.write(new Uint8Array(record.buffer));
+      this.write(entry.name);
+    }
+
+    const end = new DataView(new ArrayBuffer(22));
+    end.setUint32(0, 0x06054b50, true);
+    end.setUint16(8, this.entries.length, true);
+    end.setUint16(10, this.entries.length, true);
+    end.setUint32(12, this.offset - directoryOffset, true);
+    end.setUint32(16, directoryOffset, true);
+    this.write(new Uint8Array(end.buffer));
+    await this.sink.end();
+  }
+
+  private write(bytes: Uint8Array): void {
+    this.sink.write(bytes);
+    this.offset += bytes.byteLength;
+  }
+}
+
+function sanitizeFileName(value: string): string {
+  return value.replace(/[^A-Za-z0-9_-]/g, "_");
+}
@@ -0,0 +1,213 @@
+import { mkdir } from "node:fs/promises";
+import { dirname } from "node:path";
+
+import {
+  computeProjectMetricsSnapshot,
+  formatSnapshotDate,
+  rollupMetricsSnapshots,
+  type MetricsRollup,
+  type MetricsRollupRow,
+  type ProjectMetricsSnapshot,
+} from "../domain/metrics";
+import type { ProjectRegistry } from "./project-registry";
+import { noopRuntimeLogger, toStructuredError, type RuntimeLogger } from "./runtime-logger";
+import type { TaskRegistry } from "./task-registry";
+
+const METRICS_STORE_STATE_VERSION = 1;
+const DEFAULT_CHECK_INTERVAL_MS = 60 * 60 * 1000;
+
+type MetricsStoreState = {
+  version: number;
+  snapshots: ProjectMetricsSnapshot[];
+};
+
+export type MetricsStoreOptions = {
+  stateFilePath: string;
+};
+
+export type MetricsQuery = {
+  projectId: string;
+  /** Inclusive `YYYY-MM-DD` bounds. */
+  from?: string;
+  to?: string;
+  rollup?: MetricsRollup;
+};
+
+/** Daily per-project metrics snapshots, kept so trends survive task deletion. */
+export class MetricsStore {
+  private readonly options: MetricsStoreOptions;
+  private readonly snapshots: ProjectMetricsSnapshot[] = [];
+  private loadPromise?: Promise<void>;
+  private loaded = false;
+
+  constructor(options: MetricsStoreOptions) {
+    this.options = options;
+  }
+
+  async getLatestSnapshot(projectId: string): Promise<ProjectMetricsSnapshot | undefined> {
+    await this.ensureLoaded();
+    return this.snapshots
+      .filter((snapshot) => snapshot.projectId === projectId)
+      .reduce<ProjectMetricsSnapshot | undefined>(
+        (latest, snapshot) => (!latest || snapshot.takenAt > latest.takenAt ? snapshot : latest),
+        undefined,
+      );
+  }
+
+  /** Stores a snapshot, replacing any earlier one for the same project and day. */
+  async record(snapshot: ProjectMetricsSnapshot): Promise<void> {
+    await this.ensureLoaded();
+
+    const existingIndex = this.snapshots.findIndex(
+      (candidate) => candidate.projectId === snapshot.projectId && candidate.date === snapshot.date,
+    );
+    if (existingIndex >= 0) {
+      this.snapshots[existingIndex] = snapshot;
+    } else {
+      this.snapshots.push(snapshot);
+    }
+
+    await this.persist();
+  }
+
+  async query(input: MetricsQuery): Promise<MetricsRollupRow[]> {
+    await this.ensureLoaded();
+
+    const matching = this.snapshots.filter(
+      (snapshot) =>
+        snapshot.projectId === input.projectId &&
+        (input.from === undefined || snapshot.date >= input.from) &&
+        (input.to === undefined || snapshot.date <= input.to),
+    );
+
+    return rollupMetricsSnapshots(matching, input.rollup ?? "day");
+  }
+
+  private async ensureLoaded(): Promise<void> {
+    if (this.loaded) {
+      return;
+    }
+
+    if (!this.loadPromise) {
+      this.loadPromise = this.loadState().finally(() => {
+        this.loaded = true;
+        this.loadPromise = undefined;
+      });
+    }
+
+    await this.loadPromise;
+  }
+
+  private async loadState(): Promise<void> {
+    const stateFile = Bun.file(this.options.stateFilePath);
+    if (!(await stateFile.exists())) {
+      return;
+    }
+
+    const fileContent = await stateFile.text();
+    if (!fileContent.trim()) {
+      return;
+    }
+
+    const parsedValue = JSON.parse(fileContent) as Partial<MetricsStoreState>;
+    if (parsedValue.version !== METRICS_STORE_STATE_VERSION) {
+      throw new Error(`Unsupported metrics store state version: ${parsedValue.version ?? "unknown"}.`);
+    }
+
+    if (!Array.isArray(parsedValue.snapshots)) {
+      throw new Error("Invalid metrics store state: snapshots must be an array.");
+    }
+
+    for (const snapshotLike of parsedValue.snapshots) {
+      this.snapshots.push({
+        projectId: String(snapshotLike.projectId),
+        date: String(snapshotLike.date),
+        takenAt: Number(snapshotLike.takenAt),
+        openTasks: Number(snapshotLike.openTasks) || 0,
+        completedTasks: Number(snapshotLike.completedTasks) || 0,
+        failedTasks: Number(snapshotLike.failedTasks) || 0,
+      });
+    }
+  }
+
+  private async persist(): Promise<void> {
+    await mkdir(dirname(this.options.stateFilePath), { recursive: true });
+
+    const state: MetricsStoreState = {
+      version: METRICS_STORE_STATE_VERSION,
+      snapshots: this.snapshots,
+    };
+
+    await Bun.write(this.options.stateFilePath, `${JSON.stringify(state, null, 2)}\n`);
+  }
+}
+
+type MetricsSource = {
+  projectRegistry: Pick<ProjectRegistry, "listProjects">;
+  taskRegistry: Pick<TaskRegistry, "listTasks">;
+};
+
+/**
+ * Takes one snapshot per project per day. The check runs at startup and then
+ * hourly, so a day is still recorded when the TUI was closed at midnight.
+ */
+export class MetricsSnapshotter {
+  private readonly store: MetricsStore;
+  private readonly source: MetricsSource;
+  private readonly logger: RuntimeLogger;
+  private readonly intervalMs: number;
+  private timer?: ReturnType<typeof setInterval>;
+
+  constructor(
+    store: MetricsStore,
+    source: MetricsSource,
+    options: { intervalMs?: number; logger?: RuntimeLogger } = {},
+  ) {
+    this.store = store;
+    this.source = source;
+    this.intervalMs = options.intervalMs ?? DEFAULT_CHECK_INTERVAL_MS;
+    this.logger = options.logger ?? noopRuntimeLogger;
+  }
+
+  start(): void {
+    if (this.timer) {
+      return;
+    }
+
+    void this.snapshotDue();
+    this.timer = setInterval(() => {
+      void this.snapshotDue();
+    }, this.intervalMs);
+    this.timer.unref();
+  }
+
+  stop(): void {
+    if (this.timer) {
+      clearInterval(this.timer);
+      this.timer = undefined;
+    }
+  }
+
+  async snapshotDue(now = Date.now()): Promise<void> {
+    const today = formatSnapshotDate(now);
+
+    try {
+      const tasks = await this.source.taskRegistry.listTasks();
+      for (const project of await this.source.projectRegistry.listProjects()) {
+        const latest = await this.store.getLatestSnapshot(project.id);
+        if (latest?.date === today) {
+          continue;
+        }
+
+        await this.store.record(computeProjectMetricsSnapshot(project.id, tasks, now, latest?.takenAt));
+      }
+    } catch (error) {
+      this.logger.log({
+        level: "warn",
+        source: "metrics-snapshotter",
+        message: "Failed to record metrics snapshots.",
+        error: toStructuredError(error),
+      });
+    }
+  }
+}
@@ -0,0 +1,157 @@
+import { mkdir } from "node:fs/promises";
+import { dirname } from "node:path";
+
+import {
+  assertMilestoneInvariants,
+  type Milestone,
+  type MilestoneStatus,
+} from "../domain/milestone";
+import { assertValid, validateName } from "../domain/validation";
+
+const MILESTONE_REGISTRY_STATE_VERSION = 1;
+
+type MilestoneRegistryState = {
+  version: number;
+  milestones: Milestone[];
+};
+
+export type MilestoneRegistryOptions = {
+  stateFilePath: string;
+};
+
+export type CreateMilestoneInput = {
+  projectId: string;
+  name: string;
+  dueDate?: string;
+};
+
+export class MilestoneRegistry {
+  private readonly options: MilestoneRegistryOptions;
+  private readonly milestonesById = new Map<string, Milestone>();
+  private loadPromise?: Promise<void>;
+  private loaded = false;
+
+  constructor(options: MilestoneRegistryOptions) {
+    this.options = options;
+  }
+
+  async listMilestones(projectId?: string): Promise<Milestone[]> {
+    await this.ensureLoaded();
+    return [...this.milestonesById.values()]
+      .filter((milestone) => projectId === undefined || milestone.projectId === projectId)
+      .sort((left, right) => {
+        const leftDue = left.dueDate ?? "9999-12-31";
+        const rightDue = right.dueDate ?? "9999-12-31";
+        return leftDue.localeCompare(rightDue) || left.createdAt - right.createdAt;
+      });
+  }
+
+  async createMilestone(input: CreateMilestoneInput): Promise<Milestone> {
+    await this.ensureLoaded();
+    assertValid(validateName(input.name, "Milestone name"));
+
+    const createdAt = Date.now();
+    const milestone: Milestone = {
+      id: `${input.projectId.trim()}-ms-${createdAt.toString(36)}`,
+      projectId: input.projectId.trim(),
+      name: input.name.trim(),
+      dueDate: input.dueDate?.trim() || undefined,
+      status: "open",
+      createdAt,
+    };
+
+    assertMilestoneInvariants(milestone);
+    this.milestonesById.set(milestone.id, milestone);
+    await this.persist();
+
+    return milestone;
+  }
+
+  async setMilestoneStatus(milestoneId: string, status: MilestoneStatus): Promise<Milestone> {
+    await this.ensureLoaded();
+
+    const milestone = this.milestonesById.get(milestoneId.trim());
+    if (!milestone) {
+      throw new Error(`Milestone not found: ${milestoneId}`);
+    }
+
+    const nextMilestone: Milestone = { ...milestone, status };
+    assertMilestoneInvariants(nextMilestone);
+    this.milestonesById.set(nextMilestone.id, nextMilestone);
+    await this.persist();
+
+    return nextMilestone;
+  }
+
+  async removeMilestone(milestoneId: string): Promise<boolean> {
+    await this.ensureLoaded();
+
+    const removed = this.milestonesById.delete(milestoneId.trim());
+    if (removed) {
+      await this.persist();
+    }
+
+    return removed;
+  }
+
+  private async ensureLoaded(): Promise<void> {
+    if (this.loaded) {
+      return;
+    }
+
+    if (!this.loadPromise) {
+      this.loadPromise = this.loadState().finally(() => {
+        this.loaded = true;
+        this.loadPromise = undefined;
+      });
+    }
+
+    await this.loadPromise;
+  }
+
+  private async loadState(): Promise<void> {
+    const stateFile = Bun.file(this.options.stateFilePath);
+    if (!(await stateFile.exists())) {
+      return;
+    }
+
+    const fileContent = await stateFile.text();
+    if (!fileContent.trim()) {
+      return;
+    }
+
+    const parsedValue = JSON.parse(fileContent) as Partial<MilestoneRegistryState>;
+    if (parsedValue.version !== MILESTONE_REGISTRY_STATE_VERSION) {
+      throw new Error(`Unsupported milestone registry state version: ${parsedValue.version ?? "unknown"}.`);
+    }
+
+    if (!Array.isArray(parsedValue.milestones)) {
+      throw new Error("Invalid milestone registry state: milestones must be an array.");
+    }
+
+    for (const milestoneLike of parsedValue.milestones) {
+      const milestone: Milestone = {
+        id: String(milestoneLike.id),
+        projectId: String(milestoneLike.projectId),
+        name: String(milestoneLike.name),
+        dueDate: typeof milestoneLike.dueDate === "string" ? milestoneLike.dueDate : undefined,
+        status: milestoneLike.status === "closed" ? "closed" : "open",
+        createdAt: Number(milestoneLike.createdAt),
+      };
+
+      assertMilestoneInvariants(milestone);
+      this.milestonesById.set(milestone.id, milestone);
+    }
+  }
+
+  private async persist(): Promise<void> {
+    await mkdir(dirname(this.options.stateFilePath), { recursive: true });
+
+    const state: MilestoneRegistryState = {
+      version: MILESTONE_REGISTRY_STATE_VERSION,
+      milestones: [...this.milestonesById.values()],
+    };
+
+    await Bun.write(this.options.stateFilePath, `${JSON.stringify(state, null, 2)}\n`);
+  }
+}
@@ -7,6 +7,7 @@ import {
   type OpencodeClientConfig,
 } from "@opencode-ai/sdk/v2/client";
 
+import { IkanbanError } from "../domain/errors";
 import {
   noopRuntimeLogger,
   toStructuredError,
@@ -102,7 +103,11 @@ export class OpenCodeRuntime {
           message: "Failed to start OpenCode runtime.",
           error: toStructuredError(error),
         });
-        throw error;
+        throw new IkanbanError(
+          "executor_unavailable",
+          `Failed to start OpenCode runtime: ${error instanceof Error ? error.message : String(error)}`,
+          { cause: error },
+        );
       })
       .finally(() => {
         this.startPromise = undefined;
@@ -0,0 +1,195 @@
+import { mkdir } from "node:fs/promises";
+import { dirname } from "node:path";
+
+import { assertProjectPageInvariants, type ProjectPage } from "../domain/page";
+import { assertValid, validateTitle } from "../domain/validation";
+
+const PAGE_REGISTRY_STATE_VERSION = 1;
+
+type PageRegistryState = {
+  version: number;
+  pages: ProjectPage[];
+};
+
+export type PageRegistryOptions = {
+  stateFilePath: string;
+};
+
+export type UpdateProjectPageInput = Partial<Pick<ProjectPage, "title" | "body" | "includeInPrompts">>;
+
+export class PageRegistry {
+  private readonly options: PageRegistryOptions;
+  private readonly pagesById = new Map<string, ProjectPage>();
+  private loadPromise?: Promise<void>;
+  private loaded = false;
+
+  constructor(options: PageRegistryOptions) {
+    this.options = options;
+  }
+
+  async listPages(projectId: string): Promise<ProjectPage[]> {
+    await this.ensureLoaded();
+    return this.listProjectPages(projectId);
+  }
+
+  async createPage(projectId: string, title: string): Promise<ProjectPage> {
+    await this.ensureLoaded();
+    assertValid(validateTitle(title, "Page title"));
+
+    const now = Date.now();
+    const page: ProjectPage = {
+      id: `${projectId.trim()}-page-${now.toString(36)}`,
+      projectId: projectId.trim(),
+      title: title.trim(),
+      body: "",
+      order: this.listProjectPages(projectId).length,
+      includeInPrompts: false,
+      createdAt: now,
+      updatedAt: now,
+    };
+
+    assertProjectPageInvariants(page);
+    this.pagesById.set(page.id, page);
+    await this.persist();
+
+    return page;
+  }
+
+  async updatePage(pageId: string, input: UpdateProjectPageInput): Promise<ProjectPage> {
+    await this.ensureLoaded();
+
+    const page = this.getPageOrThrow(pageId);
+    if (input.title !== undefined) {
+      assertValid(validateTitle(input.title, "Page title"));
+    }
+    const nextPage: ProjectPage = {
+      ...page,
+      title: input.title?.trim() ?? page.title,
+      body: input.body ?? page.body,
+      includeInPrompts: input.includeInPrompts ?? page.includeInPrompts,
+      updatedAt: Date.now(),
+    };
+
+    assertProjectPageInvariants(nextPage);
+    this.pagesById.set(nextPage.id, nextPage);
+    await this.persist();
+
+    return nextPage;
+  }
+
+  async movePage(pageId: string, offset: -1 | 1): Promise<void> {
+    await this.ensureLoaded();
+
+    const page = this.getPageOrThrow(pageId);
+    const pages = this.listProjectPages(page.projectId);
+    const index = pages.findIndex((candidate) => candidate.id === page.id);
+    const swapWith = pages[index + offset];
+    if (!swapWith) {
+      return;
+    }
+
+    pages[index] = swapWith;
+    pages[index + offset] = page;
+    this.renumber(pages);
+    await this.persist();
+  }
+
+  async removePage(pageId: string): Promise<boolean> {
+    await this.ensureLoaded();
+
+    const page = this.pagesById.get(pageId.trim());
+    if (!page) {
+      return false;
+    }
+
+    this.pagesById.delete(page.id);
+    this.renumber(this.listProjectPages(page.projectId));
+    await this.persist();
+
+    return true;
+  }
+
+  private listProjectPages(projectId: string): ProjectPage[] {
+    return [...this.pagesById.values()]
+      .filter((page) => page.projectId === projectId)
+      .sort((left, right) => left.order - right.order || left.createdAt - right.createdAt);
+  }
+
+  private renumber(pages: ProjectPage[]): void {
+    pages.forEach((page, order) => {
+      this.pagesById.set(page.id, { ...page, order });
+    });
+  }
+
+  private getPageOrThrow(pageId: string): ProjectPage {
+    const page = this.pagesById.get(pageId.trim());
+    if (!page) {
+      throw new Error(`Page not found: ${pageId}`);
+    }
+
+    return page;
+  }
+
+  private async ensureLoaded(): Promise<void> {
+    if (this.loaded) {
+      return;
+    }
+
+    if (!this.loadPromise) {
+      this.loadPromise = this.loadState().finally(() => {
+        this.loaded = true;
+        this.loadPromise = undefined;
+      });
+    }
+
+    await this.loadPromise;
+  }
+
+  private async loadState(): Promise<void> {
+    const stateFile = Bun.file(this.options.stateFilePath);
+    if (!(await stateFile.exists())) {
+      return;
+    }
+
+    const fileContent = await stateFile.text();
+    if (!fileContent.trim()) {
+      return;
+    }
+
+    const parsedValue = JSON.parse(fileContent) as Partial<PageRegistryState>;
+    if (parsedValue.version !== PAGE_REGISTRY_STATE_VERSION) {
+      throw new Error(`Unsupported page registry state version: ${parsedValue.version ?? "unknown"}.`);
+    }
+
+    if (!Array.isArray(parsedValue.pages)) {
+      throw new Error("Invalid page registry state: pages must be an array.");
+    }
+
+    for (const pageLike of parsedValue.pages) {
+      const page: ProjectPage = {
+        id: String(pageLike.id),
+        projectId: String(pageLike.projectId),
+        title: String(pageLike.title),
+        body: typeof pageLike.body === "string" ? pageLike.body : "",
+        order: Number(pageLike.order),
+        includeInPrompts: pageLike.includeInPrompts === true,
+        createdAt: Number(pageLike.createdAt),
+        updatedAt: Number(pageLike.updatedAt),
+      };
+
+      assertProjectPageInvariants(page);
+      this.pagesById.set(page.id, page);
+    }
+  }
+
+  private async persist(): Promise<void> {
+    await mkdir(dirname(this.options.stateFilePath), { recursive: true });
+
+    const state: PageRegistryState = {
+      version: PAGE_REGISTRY_STATE_VERSION,
+      pages: [...this.pagesById.values()],
+    };
+
+    await Bun.write(this.options.stateFilePath, `${JSON.stringify(state, null, 2)}\n`);
+  }
+}
@@ -0,0 +1,71 @@
+import { afterEach, beforeEach, describe, expect, test } from "bun:test";
+import { mkdtemp, rm } from "node:fs/promises";
+import { tmpdir } from "node:os";
+import { join } from "node:path";
+
+import { ProjectRegistry } from "./project-registry";
+
+let directory: string;
+let stateFilePath: string;
+
+beforeEach(async () => {
+  directory = await mkdtemp(join(tmpdir(), "ikanban-projects-"));
+  stateFilePath = join(directory, "projects.json");
+  await Bun.write(
+    stateFilePath,
+    JSON.stringify({
+      version: 1,
+      activeProjectId: null,
+      projects: [{ id: "web", name: "web", rootDirectory: "/repo/web", createdAt: 1 }],
+    }),
+  );
+});
+
+afterEach(async () => {
+  await rm(directory, { recursive: true, force: true });
+});
+
+describe("allocateTaskSequence", () => {
+  test("starts above the highest sequence already in use", async () => {
+    const registry = new ProjectRegistry({ stateFilePath });
+
+    expect(await registry.allocateTaskSequence("web", 3)).toBe(4);
+  });
+
+  test("never goes back when the newest tasks are gone", async () => {
+    const registry = new ProjectRegistry({ stateFilePath });
+    await registry.allocateTaskSequence("web", 3);
+
+    expect(await registry.allocateTaskSequence("web", 0)).toBe(5);
+  });
+
+  test("keeps counting across restarts", async () => {
+    await new ProjectRegistry({ stateFilePath }).allocateTaskSequence("web");
+    const reloaded = new ProjectRegistry({ stateFilePath });
+
+    expect(await reloaded.allocateTaskSequence("web")).toBe(2);
+    expect((await reloaded.getProject("web"))?.taskSequence).toBe(2);
+  });
+
+  test("rejects unknown projects", async () => {
+    const registry = new ProjectRegistry({ stateFilePath });
+
+    await expect(registry.allocateTaskSequence("api")).rejects.toThrow("Unknown project id: api");
+  });
+});
+
+describe("setProjectMaxConcurrentTasks", () => {
+  test("keeps the project's own limit across restarts", async () => {
+    await new ProjectRegistry({ stateFilePath }).setProjectMaxConcurrentTasks("web", 1);
+
+    expect((await new ProjectRegistry({ stateFilePath }).getProject("web"))?.maxConcurrentTasks).toBe(1);
+  });
+
+  test("clears the limit and rejects non-positive ones", async () => {
+    const registry = new ProjectRegistry({ stateFilePath });
+    await registry.setProjectMaxConcurrentTasks("web", 3);
+
+    expect((await registry.setProjectMaxConcurrentTasks("web", undefined)).maxConcurrentTasks).toBeUndefined();
+    await expect(registry.setProjectMaxConcurrentTasks("web", 0)).rejects.toThrow("maxConcurrentTasks");
+  });
+});
@@ -1,7 +1,12 @@
 import { mkdir, stat } from "node:fs/promises";
 import { dirname, isAbsolute, join, relative, resolve } from "node:path";
 
+import { validateBoardColumns, type BoardColumn } from "../domain/board-column";
+import { IkanbanError } from "../domain/errors";
 import { createProjectRef, type CreateProjectRefInput, type ProjectRef } from "../domain/project";
+import { TASK_STATES, type TaskState, type TaskTransitionRules } from "../domain/task";
+import { assertValid, validateName } from "../domain/validation";
+import { isWorkingHours, type WorkingHours } from "../domain/working-hours";
 
 const REGISTRY_STATE_VERSION = 1;
 
@@ -53,8 +58,10 @@ function assertAllowedProjectRoot(rootDirectory: string, allowedRootDirectories:
   );
 
   if (!isAllowed) {
-    throw new Error(
+    throw new IkanbanError(
+      "validation",
       `Project rootDirectory is not allowed: ${rootDirectory}. Allowed roots: ${allowedRootDirectories.join(", ")}`,
+      { field: "rootDirectory" },
     );
   }
 }
@@ -63,24 +70,24 @@ export async function assertAbsoluteRepositoryRoot(rootDirectory: string): Promi
   const trimmedDirectory = rootDirectory.trim();
 
   if (trimmedDirectory.length === 0) {
-    throw new Error("Project rootDirectory must be a non-empty string.");
+    throw new IkanbanError("validation", "Project rootDirectory must be a non-empty string.", { field: "rootDirectory" });
   }
 
   if (!isAbsolute(trimmedDirectory)) {
-    throw new Error("Project rootDirectory must be an absolute path.");
+    throw new IkanbanError("validation", "Project rootDirectory must be an absolute path.", { field: "rootDirectory" });
   }
 
   const normalizedDirectory = resolve(trimmedDirectory);
   const directoryStats = await stat(normalizedDirectory).catch(() => undefined);
 
   if (!directoryStats || !directoryStats.isDirectory()) {
-    throw new Error(`Project rootDirectory does not exist as a directory: ${normalizedDirectory}`);
+    throw new IkanbanError("validation", `Project rootDirectory does not exist as a directory: ${normalizedDirectory}`, { field: "rootDirectory" });
   }
 
   const gitPath = join(normalizedDirectory, ".git");
   const gitStats = await stat(gitPath).catch(() => undefined);
   if (!gitStats) {
-    throw new Error(`Project rootDirectory must be a repository root containing .git: ${normalizedDirectory}`);
+    throw new IkanbanError("validation", `Project rootDirectory must be a repository root containing .git: ${normalizedDirectory}`, { field: "rootDirectory" });
   }
 
   return normalizedDirectory;
@@ -102,6 +109,8 @@ export class ProjectRegistry {
   async addProject(input: CreateProjectRefInput): Promise<ProjectRef> {
     await this.ensureLoaded();
 
+    assertValid(validateName(input.name, "Project name"));
+
     const projectId = input.id.trim();
     if (this.projectsById.has(projectId)) {
       throw new Error(`Project id already exists: ${projectId}`);
@@ -110,7 +119,9 @@ export class ProjectRegistry {
     const normalizedDirectory = await assertAbsoluteRepositoryRoot(input.rootDirectory);
     assertAllowedProjectRoot(normalizedDirectory, this.allowedRootDirectories);
     if (this.findProjectByRoot(normalizedDirectory)) {
-      throw new Error(`Project rootDirectory is already registered: ${normalizedDirectory}`);
+      throw new IkanbanError("conflict", `Project rootDirectory is already registered: ${normalizedDirectory}`, {
+        field: "rootDirectory",
+      });
     }
 
     const project = createProjectRef({
@@ -149,6 +160,91 @@ export class ProjectRegistry {
     return true;
   }
 
+  async renameProject(projectId: string, name: string): Promise<ProjectRef> {
+    await this.ensureLoaded();
+
+    const normalizedProjectId = projectId.trim();
+    const project = this.projectsById.get(normalizedProjectId);
+    if (!project) {
+      throw new Error(`Unknown project id: ${normalizedProjectId}`);
+    }
+
+    assertValid(validateName(name, "Project name"));
+
+    const renamedProject = createProjectRef({
+      ...project,
+      name,
+    });
+
+    this.projectsById.set(renamedProject.id, renamedProject);
+    await this.persist();
+
+    return renamedProject;
+  }
+
+  /** Clears the working hours when `workingHours` is undefined. */
+  async setProjectWorkingHours(projectId: string, workingHours: WorkingHours | undefined): Promise<ProjectRef> {
+    await this.ensureLoaded();
+
+    const normalizedProjectId = projectId.trim();
+    const project = this.projectsById.get(normalizedProjectId);
+    if (!project) {
+      throw new Error(`Unknown project id: ${normalizedProjectId}`);
+    }
+
+    const updatedProject = createProjectRef({
+      ...project,
+      workingHours,
+    });
+
+    this.projectsById.set(updatedProject.id, updatedProject);
+    await this.persist();
+
+    return updatedProject;
+  }
+
+  /** Clears the project's own limit when `maxConcurrentTasks` is undefined. */
+  async setProjectMaxConcurrentTasks(projectId: string, maxConcurrentTasks: number | undefined): Promise<ProjectRef> {
+    await this.ensureLoaded();
+
+    const normalizedProjectId = projectId.trim();
+    const project = this.projectsById.get(normalizedProjectId);
+    if (!project) {
+      throw new Error(`Unknown project id: ${normalizedProjectId}`);
+    }
+
+    const updatedProject = createProjectRef({
+      ...project,
+      maxConcurrentTasks,
+    });
+
+    this.projectsById.set(updatedProject.id, updatedProject);
+    await this.persist();
+
+    return updatedProject;
+  }
+
+  /**
+   * Hands out the project's next task short-code sequence. `floor` is the
+   * highest sequence its tasks already use, for projects saved before the
+   * counter was kept.
+   */
+  async allocateTaskSequence(projectId: string, floor = 0): Promise<number> {
+    await this.ensureLoaded();
+
+    const normalizedProjectId = projectId.trim();
+    const project = this.projectsById.get(normalizedProjectId);
+    if (!project) {
+      throw new Error(`Unknown project id: ${normalizedProjectId}`);
+    }
+
+    const taskSequence = Math.max(project.taskSequence ?? 0, floor) + 1;
+    this.projectsById.set(project.id, createProjectRef({ ...project, taskSequence }));
+    await this.persist();
+
+    return taskSequence;
+  }
+
   async listProjects(): Promise<ProjectRef[]> {
     await this.ensureLoaded();
     return this.listProjectsSnapshot();
@@ -259,6 +355,11 @@ export class ProjectRegistry {
         id: String(projectLike.id),
         name: String(projectLike.name),
         rootDirectory: String(projectLike.rootDirectory),
+        taskTransitions: parseTaskTransitionRules(projectLike.taskTransitions),
+        workingHours: parseWorkingHours(projectLike.workingHours),
+        maxConcurrentTasks: parseMaxConcurrentTasks(projectLike.maxConcurrentTasks),
+        columns: parseBoardColumns(projectLike.columns),
+        taskSequence: parseTaskSequence(projectLike.taskSequence),
         createdAt: Number(projectLike.createdAt),
       }),
     );
@@ -305,3 +406,97 @@ export class ProjectRegistry {
     return projects.find((project) => project.rootDirectory === rootDirectory);
   }
 }
+
+function parseBoardColumns(value: unknown): BoardColumn[] | undefined {
+  if (value === undefined) {
+    return undefined;
+  }
+
+  if (!Array.isArray(value)) {
+    throw new Error("Invalid project registry state: columns must be an array.");
+  }
+
+  const columns = value.map((columnLike): BoardColumn => {
+    const record = (columnLike && typeof columnLike === "object" ? columnLike : {}) as Record<string, unknown>;
+    const states = Array.isArray(record.states) ? record.states : [];
+    const invalidState = states.find((state) => !isTaskState(state));
+    if (invalidState !== undefined) {
+      throw new Error(`Invalid project registry state: unknown task state ${String(invalidState)} in columns.`);
+    }
+
+    return {
+      id: typeof record.id === "string" ? record.id : "",
+      name: typeof record.name === "string" ? record.name : "",
+      states: states as TaskState[],
+      wipLimit: record.wipLimit === undefined ? undefined : Number(record.wipLimit),
+    };
+  });
+  const errors = validateBoardColumns(columns);
+  if (errors.length > 0) {
+    throw new Error(`Invalid project registry state: ${errors.join(" ")}`);
+  }
+
+  return columns;
+}
+
+function parseTaskSequence(value: unknown): number | undefined {
+  if (value === undefined) {
+    return undefined;
+  }
+
+  if (typeof value !== "number" || !Number.isInteger(value) || value < 0) {
+    throw new Error("Invalid project registry state: taskSequence must be a non-negative integer.");
+  }
+
+  return value;
+}
+
+function parseMaxConcurrentTasks(value: unknown): number | undefined {
+  if (value === undefined) {
+    return undefined;
+  }
+
+  if (typeof value !== "number" || !Number.isInteger(value) || value < 1) {
+    throw new Error("Invalid project registry state: maxConcurrentTasks must be a positive integer.");
+  }
+
+  return value;
+}
+
+function parseWorkingHours(value: unknown): WorkingHours | undefined {
+  if (value === undefined) {
+    return undefined;
+  }
+
+  if (!isWorkingHours(value)) {
+    throw new Error("Invalid project registry state: workingHours needs HH:MM start and end and days 0-6.");
+  }
+
+  return { start: value.start, end: value.end, days: value.days };
+}
+
+function parseTaskTransitionRules(value: unknown): TaskTransitionRules | undefined {
+  if (!value || typeof value !== "object" || Array.isArray(value)) {
+    return undefined;
+  }
+
+  const rules: TaskTransitionRules = {};
+  for (const [from, targets] of Object.entries(value)) {
+    if (!isTaskState(from) || !Array.isArray(targets)) {
+      throw new Error(`Invalid project registry state: bad taskTransitions entry for ${from}.`);
+    }
+
+    const invalidTarget = targets.find((target) => !isTaskState(target));
+    if (invalidTarget !== undefined) {
+      throw new Error(`Invalid project registry state: unknown task state ${String(invalidTarget)}.`);
+    }
+
+    rules[from] = targets as TaskState[];
+  }
+
+  return rules;
+}
+
+function isTaskState(value: unknown): value is TaskState {
+  return typeof value === "string" && (TASK_STATES as readonly string[]).includes(value);
+}
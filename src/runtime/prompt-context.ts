@@ -0,0 +1,146 @@
+export type RepositoryPromptContext = {
+  treeSummary: string[];
+  relevantFiles: string[];
+};
+
+export type GatherPromptContextInput = {
+  directory: string;
+  prompt: string;
+  /** Limits the gathered files to this repository-relative directory. */
+  pathScope?: string;
+  maxTreeEntries?: number;
+  maxRelevantFiles?: number;
+};
+
+const DEFAULT_MAX_TREE_ENTRIES = 12;
+const DEFAULT_MAX_RELEVANT_FILES = 10;
+const MIN_KEYWORD_LENGTH = 4;
+const KEYWORD_STOP_WORDS = new Set([
+  "about",
+  "after",
+  "also",
+  "when",
+  "with",
+  "that",
+  "this",
+  "from",
+  "into",
+  "make",
+  "should",
+  "there",
+  "they",
+  "them",
+  "then",
+  "than",
+  "what",
+  "which",
+  "while",
+  "would",
+  "could",
+  "have",
+  "were",
+  "will",
+  "your",
+  "task",
+  "tasks",
+  "implement",
+  "please",
+]);
+
+export async function gatherPromptContext(
+  input: GatherPromptContextInput,
+): Promise<RepositoryPromptContext> {
+  const pathspec = input.pathScope ? ["--", input.pathScope] : [];
+  const output = await Bun.$`git -C ${input.directory} ls-files ${pathspec}`.text();
+  const files = output
+    .split(/\r?\n/)
+    .map((line) => line.trim())
+    .filter((line) => line.length > 0);
+
+  return {
+    treeSummary: summarizeTree(files, input.maxTreeEntries ?? DEFAULT_MAX_TREE_ENTRIES, input.pathScope),
+    relevantFiles: findRelevantFiles(
+      files,
+      extractKeywords(input.prompt),
+      input.maxRelevantFiles ?? DEFAULT_MAX_RELEVANT_FILES,
+    ),
+  };
+}
+
+export function formatPromptContext(context: RepositoryPromptContext): string {
+  const sections: string[] = [];
+
+  if (context.treeSummary.length > 0) {
+    sections.push(["Repository layout:", ...context.treeSummary.map((entry) => `- ${entry}`)].join("\n"));
+  }
+
+  if (context.relevantFiles.length > 0) {
+    sections.push(
+      ["Possibly relevant files:", ...context.relevantFiles.map((file) => `- ${file}`)].join("\n"),
+    );
+  }
+
+  return sections.join("\n\n");
+}
+
+export function appendPromptContext(prompt: string, context: RepositoryPromptContext): string {
+  const formattedContext = formatPromptContext(context);
+  if (!formattedContext) {
+    return prompt;
+  }
+
+  return `${prompt}\n\n---\nRepository context gathered by iKanban:\n\n${formattedContext}`;
+}
+
+export function appendPathScope(prompt: string, pathScope: string | undefined): string {
+  if (!pathScope) {
+    return prompt;
+  }
+
+  return `${prompt}\n\n---\nScope: only modify files under \`${pathScope}/\`.`;
+}
+
+function summarizeTree(files: string[], maxEntries: number, pathScope?: string): string[] {
+  const countsByEntry = new Map<string, number>();
+  // Inside a scope, summarize one level below it rather than the repository root.
+  const scopePrefix = pathScope ? `${pathScope}/` : "";
+
+  for (const file of files) {
+    const rootPrefix = file.startsWith(scopePrefix) ? scopePrefix : "";
+    const relativeFile = file.slice(rootPrefix.length);
+    const separatorIndex = relativeFile.indexOf("/");
+    const entry = `${rootPrefix}${separatorIndex >= 0 ? `${relativeFile.slice(0, separatorIndex)}/` : relativeFile}`;
+    countsByEntry.set(entry, (countsByEntry.get(entry) ?? 0) + 1);
+  }
+
+  return [...countsByEntry.entries()]
+    .sort((left, right) => right[1] - left[1] || left[0].localeCompare(right[0]))
+    .slice(0, maxEntries)
+    .map(([entry, count]) => (entry.endsWith("/") ? `${entry} (${count} files)` : entry));
+}
+
+function extractKeywords(prompt: string): string[] {
+  const keywords = prompt
+    .toLowerCase()
+    .split(/[^a-z0-9_]+/)
+    .filter((token) => token.length >= MIN_KEYWORD_LENGTH && !KEYWORD_STOP_WORDS.has(token));
+
+  return [...new Set(keywords)];
+}
+
+function findRelevantFiles(files: string[], keywords: string[], maxFiles: number): string[] {
+  if (keywords.length === 0) {
+    return [];
+  }
+
+  return files
+    .map((file) => {
+      const normalizedFile = file.toLowerCase();
+      const score = keywords.filter((keyword) => normalizedFile.includes(keyword)).length;
+      return { file, score };
+    })
+    .filter((candidate) => candidate.score > 0)
+    .sort((left, right) => right.score - left.score || left.file.localeCompare(right.file))
+    .slice(0, maxFiles)
+    .map((candidate) => candidate.file);
+}
@@ -0,0 +1,160 @@
+import { stat } from "node:fs/promises";
+import { isAbsolute, join } from "node:path";
+
+import { IkanbanError } from "../domain/errors";
+import type { TaskPreflightCheck } from "../domain/task";
+
+const LOCK_FILE_NAMES = ["index.lock", "HEAD.lock", "config.lock", "packed-refs.lock"] as const;
+const REMOTE_CHECK_TIMEOUT_MS = 10_000;
+
+export type RepositoryPreflightInput = {
+  directory: string;
+  checkRemote?: boolean;
+};
+
+export type RepositoryPreflightReport = {
+  directory: string;
+  ok: boolean;
+  checks: TaskPreflightCheck[];
+};
+
+export class RepositoryPreflightError extends IkanbanError {
+  readonly report: RepositoryPreflightReport;
+
+  constructor(report: RepositoryPreflightReport) {
+    super("preflight_failed", formatPreflightFailure(report));
+    this.name = "RepositoryPreflightError";
+    this.report = report;
+  }
+}
+
+export async function runRepositoryPreflight(
+  input: RepositoryPreflightInput,
+): Promise<RepositoryPreflightReport> {
+  const directory = input.directory;
+  const checks: TaskPreflightCheck[] = [];
+  const finish = (): RepositoryPreflightReport => ({
+    directory,
+    ok: checks.every((check) => check.status !== "fail"),
+    checks,
+  });
+  const skipRemaining = (ids: string[], reason: string) => {
+    for (const id of ids) {
+      checks.push({ id, status: "skip", message: reason });
+    }
+  };
+
+  const isDirectory = await stat(directory)
+    .then((stats) => stats.isDirectory())
+    .catch(() => false);
+  if (!isDirectory) {
+    checks.push({ id: "path_exists", status: "fail", message: `${directory} does not exist or is not a directory.` });
+    skipRemaining(["git_repository", "default_branch", "no_lock_files", "remote_reachable"], "Project path is missing.");
+    return finish();
+  }
+  checks.push({ id: "path_exists", status: "pass", message: directory });
+
+  const insideWorkTree = await runGit(directory, ["rev-parse", "--is-inside-work-tree"]);
+  if (!insideWorkTree.ok || insideWorkTree.output !== "true") {
+    checks.push({ id: "git_repository", status: "fail", message: `${directory} is not a git work tree.` });
+    skipRemaining(["default_branch", "no_lock_files", "remote_reachable"], "Not a git repository.");
+    return finish();
+  }
+  checks.push({ id: "git_repository", status: "pass", message: "Git work tree detected." });
+
+  const branch = await runGit(directory, ["symbolic-ref", "--quiet", "--short", "HEAD"]);
+  const headCommit = await runGit(directory, ["rev-parse", "--verify", "--quiet", "HEAD"]);
+  if (!branch.ok || !branch.output) {
+    checks.push({ id: "default_branch", status: "fail", message: "HEAD is detached; check out a branch first." });
+  } else if (!headCommit.ok) {
+    checks.push({ id: "default_branch", status: "fail", message: `Branch ${branch.output} has no commits yet.` });
+  } else {
+    checks.push({ id: "default_branch", status: "pass", message: branch.output });
+  }
+
+  const gitDirs = await Promise.all([
+    runGit(directory, ["rev-parse", "--git-dir"]),
+    runGit(directory, ["rev-parse", "--git-common-dir"]),
+  ]);
+  const lockFiles: string[] = [];
+  for (const gitDir of new Set(gitDirs.filter((result) => result.ok).map((result) => result.output))) {
+    const absoluteGitDir = isAbsolute(gitDir) ? gitDir : join(directory, gitDir);
+    for (const name of LOCK_FILE_NAMES) {
+      const lockPath = join(absoluteGitDir, name);
+      if (await Bun.file(lockPath).exists()) {
+        lockFiles.push(lockPath);
+      }
+    }
+  }
+  checks.push(
+    lockFiles.length > 0
+      ? {
+        id: "no_lock_files",
+        status: "fail",
+        message: `Git lock files present (another git process running or crashed): ${lockFiles.join(", ")}`,
+      }
+      : { id: "no_lock_files", status: "pass", message: "No git lock files." },
+  );
+
+  if (!input.checkRemote) {
+    skipRemaining(["remote_reachable"], "Remote check disabled.");
+    return finish();
+  }
+
+  const remotes = await runGit(directory, ["remote"]);
+  const remoteNames = remotes.ok ? remotes.output.split("\n").filter(Boolean) : [];
+  const remote = remoteNames.includes("origin") ? "origin" : remoteNames[0];
+  if (!remote) {
+    skipRemaining(["remote_reachable"], "No remote configured.");
+    return finish();
+  }
+
+  const reachable = await runGit(directory, ["ls-remote", "--heads", remote], REMOTE_CHECK_TIMEOUT_MS);
+  checks.push(
+    reachable.ok
+      ? { id: "remote_reachable", status: "pass", message: `${remote} is reachable.` }
+      : { id: "remote_reachable", status: "fail", message: `${remote} is not reachable: ${reachable.output || "unknown error"}` },
+  );
+
+  return finish();
+}
+
+export function formatPreflightFailure(report: RepositoryPreflightReport): string {
+  const failures = report.checks
+    .filter((check) => check.status === "fail")
+    .map((check) => `${check.id}: ${check.message}`);
+
+  return `Repository pre-flight failed for ${report.directory}. ${failures.join(" ")}`;
+}
+
+async function runGit(
+  directory: string,
+  args: string[],
+  timeoutMs?: number,
+): Promise<{ ok: boolean; output: string }> {
+  const command = Bun.$`git -C ${directory} ${args}`
+    .env({ ...process.env, GIT_TERMINAL_PROMPT: "0" })
+    .quiet()
+    .nothrow();
+
+  let timer: ReturnType<typeof setTimeout> | undefined;
+  const timeout = new Promise<undefined>((resolve) => {
+    if (timeoutMs !== undefined) {
+      timer = setTimeout(() => resolve(undefined), timeoutMs);
+    }
+  });
+
+  try {
+    const result = await Promise.race([command, timeout]);
+    if (!result) {
+      return { ok: false, output: `timed out after ${timeoutMs}ms` };
+    }
+
+    const output = result.exitCode === 0 ? result.stdout.toString() : result.stderr.toString();
+    return { ok: result.exitCode === 0, output: output.trim() };
+  } finally {
+    if (timer) {
+      clearTimeout(timer);
+    }
+  }
+}
@@ -0,0 +1,107 @@
+import type { AppConfigEnv } from "./app-config";
+
+export const REDACTED = "[REDACTED]";
+
+// Shorter values (ports, flags, usernames) would mask ordinary text.
+const MIN_ENV_VALUE_LENGTH = 8;
+
+// Token shapes that are secrets wherever they appear, configured or not.
+const TOKEN_PATTERNS: readonly RegExp[] = [
+  /\b(?:AKIA|ASIA)[0-9A-Z]{16}\b/g,
+  /(?<=aws_secret_access_key["']?\s*[=:]\s*["']?)[A-Za-z0-9/+=]{40}/gi,
+  /\bgh[pousr]_[A-Za-z0-9]{36,255}\b/g,
+  /\bgithub_pat_[A-Za-z0-9_]{22,255}\b/g,
+];
+
+export type SecretRedactorOptions = {
+  /** Environment variables whose values are masked, e.g. `OPENAI_API_KEY`. */
+  envNames?: string[];
+  /** Projects whose agent output is left as is. */
+  allowProjects?: string[];
+  env?: AppConfigEnv;
+};
+
+/**
+ * Masks configured environment variable values and well-known token shapes
+ * (AWS access keys, GitHub tokens) in agent output and log records before
+ * they are broadcast or exported.
+ */
+export class SecretRedactor {
+  private envValuePattern?: RegExp;
+  private allowProjects = new Set<string>();
+  private readonly env: AppConfigEnv;
+
+  constructor(options: SecretRedactorOptions = {}) {
+    this.env = options.env ?? process.env;
+    this.configure(options);
+  }
+
+  configure(options: Pick<SecretRedactorOptions, "envNames" | "allowProjects">): void {
+    const values = (options.envNames ?? [])
+      .map((name) => this.env[name]?.trim())
+      .filter((value): value is string => value !== undefined && value.length >= MIN_ENV_VALUE_LENGTH)
+      // Longest first, so a value containing another is masked whole.
+      .sort((left, right) => right.length - left.length)
+      .map(escapeRegExp);
+    this.envValuePattern = values.length > 0 ? new RegExp(values.join("|"), "g") : undefined;
+    this.allowProjects = new Set(options.allowProjects ?? []);
+  }
+
+  isEnabledFor(projectId?: string): boolean {
+    return projectId === undefined || !this.allowProjects.has(projectId);
+  }
+
+  redactText(text: string, projectId?: string): string {
+    if (!this.isEnabledFor(projectId)) {
+      return text;
+    }
+
+    let redacted = this.envValuePattern ? text.replace(this.envValuePattern, REDACTED) : text;
+    for (const pattern of TOKEN_PATTERNS) {
+      redacted = redacted.replace(pattern, REDACTED);
+    }
+
+    return redacted;
+  }
+
+  /** Copies `value` with every string in it redacted; objects without secrets come back unchanged. */
+  redact<TValue>(value: TValue, projectId?: string): TValue {
+    if (!this.isEnabledFor(projectId)) {
+      return value;
+    }
+
+    return this.redactValue(value, projectId) as TValue;
+  }
+
+  private redactValue(value: unknown, projectId?: string): unknown {
+    if (typeof value === "string") {
+      return this.redactText(value, projectId);
+    }
+
+    if (Array.isArray(value)) {
+      let changed = false;
+      const items = value.map((item) => {
+        const next = this.redactValue(item, projectId);
+        changed ||= next !== item;
+        return next;
+      });
+      return changed ? items : value;
+    }
+
+    if (value && typeof value === "object") {
+      let changed = false;
+      const entries = Object.entries(value).map(([key, item]) => {
+        const next = this.redactValue(item, projectId);
+        changed ||= next !== item;
+        return [key, next] as const;
+      });
+      return changed ? Object.fromEntries(entries) : value;
+    }
+
+    return value;
+  }
+}
+
+function escapeRegExp(value: string): string {
+  return value.replace(/[.*+?^${}()|[\]\\]/g, "\\$&");
+}
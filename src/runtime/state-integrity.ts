@@ -0,0 +1,200 @@
+import { mkdir, rename, unlink } from "node:fs/promises";
+import { basename, join } from "node:path";
+
+import type { BlobStore } from "./blob-store";
+import { noopRuntimeLogger, toStructuredError, type RuntimeLogger } from "./runtime-logger";
+
+/** A state file plus a loader that parses it the way the owning registry does. */
+export type StateFileCheck = {
+  filePath: string;
+  load: (filePath: string) => Promise<unknown>;
+};
+
+export type StateFileReport = {
+  filePath: string;
+  status: "ok" | "missing" | "corrupt" | "restored";
+  error?: string;
+  backupPath?: string;
+  movedTo?: string;
+};
+
+export type CheckStateFilesOptions = {
+  backupDirectory: string;
+  /** Restore corrupt files from their last known-good backup. */
+  repair?: boolean;
+  /** Off-host copy of the backups, used when the local backup is missing or unusable. */
+  mirror?: BlobStore;
+  logger?: RuntimeLogger;
+  now?: number;
+};
+
+/**
+ * Loads every state file through its registry. Files that load are copied to
+ * the backup directory, and the mirror if set, as the last known-good version;
+ * with `repair`, a file that fails is moved aside and replaced by that backup
+ * if the backup loads.
+ */
+export async function checkStateFiles(
+  checks: StateFileCheck[],
+  options: CheckStateFilesOptions,
+): Promise<StateFileReport[]> {
+  const logger = options.logger ?? noopRuntimeLogger;
+  const reports: StateFileReport[] = [];
+  await mkdir(options.backupDirectory, { recursive: true });
+
+  for (const check of checks) {
+    const backupPath = join(options.backupDirectory, basename(check.filePath));
+    if (!(await Bun.file(check.filePath).exists())) {
+      reports.push({ filePath: check.filePath, status: "missing" });
+      continue;
+    }
+
+    const error = await tryLoad(check, check.filePath);
+    if (!error) {
+      await Bun.write(backupPath, Bun.file(check.filePath));
+      await mirrorBackup(options.mirror, backupPath, logger);
+      reports.push({ filePath: check.filePath, status: "ok", backupPath });
+      continue;
+    }
+
+    logger.log({
+      level: "error",
+      source: "state-integrity",
+      message: `State file failed its integrity check: ${check.filePath}.`,
+      context: { filePath: check.filePath },
+      error: toStructuredError(error),
+    });
+
+    const report: StateFileReport = { filePath: check.filePath, status: "corrupt", error: toMessage(error) };
+    let backupUsable = (await Bun.file(backupPath).exists()) && !(await tryLoad(check, backupPath));
+    if (!backupUsable && options.mirror) {
+      backupUsable = await fetchMirroredBackup(options.mirror, check, backupPath, logger);
+    }
+    if (backupUsable) {
+      report.backupPath = backupPath;
+    }
+
+    if (options.repair && backupUsable) {
+      const movedTo = `${check.filePath}.corrupt-${options.now ?? Date.now()}`;
+      await rename(check.filePath, movedTo);
+      await Bun.write(check.filePath, Bun.file(backupPath));
+      report.status = "restored";
+      report.movedTo = movedTo;
+      logger.log({
+        level: "warn",
+        source: "state-integrity",
+        message: `Restored ${check.filePath} from its last known-good backup.`,
+        context: { filePath: check.filePath, backupPath, movedTo },
+      });
+    }
+
+    reports.push(report);
+  }
+
+  return reports;
+}
+
+export function formatStateFileReport(report: StateFileReport): string {
+  switch (report.status) {
+    case "ok":
+      return `ok        ${report.filePath}`;
+    case "missing":
+      return `missing   ${report.filePath} (nothing stored yet)`;
+    case "restored":
+      return `restored  ${report.filePath} from ${report.backupPath} (corrupt copy kept at ${report.movedTo})`;
+    case "corrupt":
+      return [
+        `corrupt   ${report.filePath}: ${report.error}`,
+        report.backupPath
+          ? `          run \`ikanban doctor --repair\` to restore ${report.backupPath}`
+          : "          no usable backup; fix the file by hand or move it aside to start empty",
+      ].join("\n");
+  }
+}
+
+/**
+ * Copies the backups of files that loaded to the mirror, one at a time. Kept
+ * apart from `checkStateFiles` so startup can run it in the background;
+ * failures are logged, not thrown.
+ */
+export async function mirrorStateBackups(
+  mirror: BlobStore,
+  reports: StateFileReport[],
+  logger: RuntimeLogger = noopRuntimeLogger,
+): Promise<void> {
+  for (const report of reports) {
+    if (report.status === "ok" && report.backupPath) {
+      await mirrorBackup(mirror, report.backupPath, logger);
+    }
+  }
+}
+
+async function mirrorBackup(mirror: BlobStore | undefined, backupPath: string, logger: RuntimeLogger): Promise<void> {
+  if (!mirror) {
+    return;
+  }
+
+  const key = `backups/${basename(backupPath)}`;
+  try {
+    await mirror.write(key, Bun.file(backupPath));
+  } catch (error) {
+    logger.log({
+      level: "warn",
+      source: "state-integrity",
+      message: `Could not copy ${backupPath} to ${mirror.describe(key)}.`,
+      error: toStructuredError(error),
+    });
+  }
+}
+
+/** Downloads the mirrored backup over the local one and reports whether it loads. */
+async function fetchMirroredBackup(
+  mirror: BlobStore,
+  check: StateFileCheck,
+  backupPath: string,
+  logger: RuntimeLogger,
+): Promise<boolean> {
+  const key = `backups/${basename(backupPath)}`;
+  try {
+    const data = await mirror.read(key);
+    if (!data) {
+      return false;
+    }
+
+    const downloadPath = `${backupPath}.download`;
+    await Bun.write(downloadPath, data);
+    if (await tryLoad(check, downloadPath)) {
+      await unlink(downloadPath);
+      return false;
+    }
+
+    await rename(downloadPath, backupPath);
+    logger.log({
+      level: "info",
+      source: "state-integrity",
+      message: `Fetched the backup of ${check.filePath} from ${mirror.describe(key)}.`,
+    });
+    return true;
+  } catch (error) {
+    logger.log({
+      level: "warn",
+      source: "state-integrity",
+      message: `Could not fetch ${mirror.describe(key)}.`,
+      error: toStructuredError(error),
+    });
+    return false;
+  }
+}
+
+async function tryLoad(check: StateFileCheck, filePath: string): Promise<unknown> {
+  try {
+    await check.load(filePath);
+    return undefined;
+  } catch (error) {
+    return error ?? new Error("Unknown error.");
+  }
+}
+
+function toMessage(error: unknown): string {
+  return error instanceof Error ? error.message : String(error);
+}
@@ -0,0 +1,113 @@
+import { isAbsolute, relative } from "node:path";
+
+import type { TaskRuntime } from "../domain/task";
+
+export type TaskAttributionEntry = {
+  taskRef: string;
+  task?: TaskRuntime;
+  source: "merged" | "worktree";
+  commit?: string;
+  committedAt?: number;
+  subject: string;
+};
+
+export type AttributeFileInput = {
+  projectDirectory: string;
+  filePath: string;
+  tasks: TaskRuntime[];
+  limit?: number;
+};
+
+const FIELD_SEPARATOR = "\u001f";
+const RECORD_SEPARATOR = "\u001e";
+const DEFAULT_LIMIT = 20;
+// Matches buildTaskCommitMessage output: "[WEB-42] task-id (ikanban)" or "task-id (ikanban)".
+const TASK_COMMIT_SUBJECT_PATTERN = /^(?:\[([A-Z0-9]+-[1-9][0-9]*)\] )?([A-Za-z0-9_-]+) \(ikanban\)$/;
+const TASK_TRAILER_PATTERN = /^Task: ([A-Z0-9]+-[1-9][0-9]*)$/m;
+
+/**
+ * Lists the tasks that touched a file: squash-merged task commits from the
+ * project history, newest first, plus uncommitted edits in live task worktrees.
+ */
+export async function attributeFileToTasks(
+  input: AttributeFileInput,
+): Promise<TaskAttributionEntry[]> {
+  const filePath = normalizeFilePath(input.projectDirectory, input.filePath);
+  const entries: TaskAttributionEntry[] = [];
+
+  for (const task of input.tasks) {
+    if (!task.worktreeDirectory || (task.state !== "running" && task.state !== "review")) {
+      continue;
+    }
+
+    const status = await Bun.$`git -C ${task.worktreeDirectory} status --porcelain -- ${filePath}`
+      .nothrow()
+      .quiet();
+    if (status.exitCode === 0 && status.stdout.toString().trim()) {
+      entries.push({
+        taskRef: task.shortCode ?? task.taskId,
+        task,
+        source: "worktree",
+        subject: `uncommitted changes (${task.state})`,
+      });
+    }
+  }
+
+  const log = await Bun.$`git -C ${input.projectDirectory} log --follow --format=%H${FIELD_SEPARATOR}%ct${FIELD_SEPARATOR}%s${FIELD_SEPARATOR}%b${RECORD_SEPARATOR} -- ${filePath}`
+    .nothrow()
+    .quiet();
+  if (log.exitCode !== 0) {
+    throw new Error(`Failed to read history for ${filePath}: ${log.stderr.toString().trim()}`);
+  }
+
+  const limit = input.limit ?? DEFAULT_LIMIT;
+  for (const record of log.stdout.toString().split(RECORD_SEPARATOR)) {
+    if (entries.length >= limit) {
+      break;
+    }
+
+    const [commit, committedAt, subject, body] = record.trim().split(FIELD_SEPARATOR);
+    if (!commit || !subject) {
+      continue;
+    }
+
+    const taskRef = parseTaskCommitRef(subject, body);
+    if (!taskRef) {
+      continue;
+    }
+
+    entries.push({
+      taskRef,
+      task: input.tasks.find((task) => task.shortCode === taskRef || task.taskId === taskRef),
+      source: "merged",
+      commit: commit.slice(0, 12),
+      committedAt: Number(committedAt) * 1000,
+      subject,
+    });
+  }
+
+  return entries;
+}
+
+export function parseTaskCommitRef(subject: string, body = ""): string | undefined {
+  const subjectMatch = TASK_COMMIT_SUBJECT_PATTERN.exec(subject.trim());
+  if (subjectMatch) {
+    return subjectMatch[1] ?? subjectMatch[2];
+  }
+
+  return TASK_TRAILER_PATTERN.exec(body)?.[1];
+}
+
+function normalizeFilePath(projectDirectory: string, filePath: string): string {
+  const trimmed = filePath.trim();
+  if (!trimmed) {
+    throw new Error("File path is required.");
+  }
+
+  const relativePath = isAbsolute(trimmed) ? relative(projectDirectory, trimmed) : trimmed;
+  if (relativePath.startsWith("..")) {
+    throw new Error(`${trimmed} is outside the project.`);
+  }
+
+  return relativePath;
+}
@@ -0,0 +1,293 @@
+import { describe, expect, test } from "bun:test";
+
+import { IkanbanError } from "../domain/errors";
+import type { ProjectRef } from "../domain/project";
+import type { TaskRuntime } from "../domain/task";
+import { TaskOrchestrator } from "./task-orchestrator";
+import type { MergeTaskWorktreeResult, RebaseTaskWorktreeResult } from "./worktree-manager";
+
+type Dependencies = ConstructorParameters<typeof TaskOrchestrator>[0];
+
+const project: ProjectRef = {
+  id: "web",
+  rootDirectory: "/repo/web",
+  name: "web",
+  createdAt: 1,
+};
+
+function reviewTask(overrides: Partial<TaskRuntime> = {}): TaskRuntime {
+  return {
+    taskId: "task-1",
+    projectId: project.id,
+    state: "review",
+    shortCode: "WEB-1",
+    prompt: "Add a footer",
+    worktreeDirectory: "/repo/web-worktrees/task-1",
+    review: { reviewer: "reviewer", requestedAt: 2, decision: "approved", decidedAt: 3 },
+    createdAt: 1,
+    updatedAt: 3,
+    ...overrides,
+  };
+}
+
+// Stand-ins for the registries and git; each test overrides what it exercises.
+function createOrchestrator(
+  tasks: TaskRuntime[],
+  overrides: {
+    project?: ProjectRef;
+    worktreeManager?: Partial<Dependencies["worktreeManager"]>;
+    conversationManager?: Partial<Dependencies["conversationManager"]>;
+    github?: Partial<NonNullable<Dependencies["github"]>>;
+    options?: ConstructorParameters<typeof TaskOrchestrator>[1];
+  } = {},
+): TaskOrchestrator {
+  const activeProject = overrides.project ?? project;
+  let taskSequence = activeProject.taskSequence ?? 0;
+  const dependencies = {
+    projectRegistry: {
+      getProject: async (projectId: string) => (projectId === activeProject.id ? activeProject : undefined),
+      getActiveProject: async () => activeProject,
+      listProjects: async () => [activeProject],
+      setProjectWorkingHours: async () => activeProject,
+      allocateTaskSequence: async (_projectId: string, floor = 0) => (taskSequence = Math.max(taskSequence, floor) + 1),
+    },
+    taskRegistry: {
+      listTasks: async () => tasks,
+      upsertTask: async (task: TaskRuntime) => task,
+      removeTask: async () => true,
+    },
+    worktreeManager: overrides.worktreeManager ?? {},
+    conversationManager: overrides.conversationManager ?? {},
+    github: overrides.github,
+  } as unknown as Dependencies;
+
+  return new TaskOrchestrator(dependencies, overrides.options);
+}
+
+// A working-hours window that never opens keeps new tasks queued instead of starting runs.
+const closedProject: ProjectRef = { ...project, workingHours: { start: "00:00", end: "00:00", days: [] } };
+
+function nextEnqueuedTask(orchestrator: TaskOrchestrator): Promise<TaskRuntime> {
+  return new Promise((resolve) => {
+    const unsubscribe = orchestrator.subscribe((event) => {
+      if (event.type === "task.enqueued") {
+        unsubscribe();
+        resolve(event.task);
+      }
+    });
+  });
+}
+
+describe("mergeTask", () => {
+  test("keeps the task in review when the fast-forward is rejected, so it can be rebased", async () => {
+    const rebase: RebaseTaskWorktreeResult = {
+      taskId: "task-1",
+      branch: "ikanban/task-1",
+      onto: "main",
+      status: "rebased",
+      conflictedFiles: [],
+    };
+    const orchestrator = createOrchestrator([reviewTask()], {
+      worktreeManager: {
+        mergeTaskWorktree: async (): Promise<MergeTaskWorktreeResult> => {
+          throw new IkanbanError("conflict", "Cannot fast-forward main to ikanban/task-1.");
+        },
+        rebaseTaskWorktree: async () => rebase,
+      },
+    });
+    await orchestrator.initialize();
+
+    await expect(orchestrator.mergeTask("task-1", { strategy: "fast-forward" })).rejects.toThrow(
+      "Cannot fast-forward",
+    );
+    expect(orchestrator.listTasks().find((task) => task.taskId === "task-1")?.state).toBe("review");
+    expect(await orchestrator.rebaseTask("task-1")).toEqual(rebase);
+  });
+
+  test("keeps the task in review when the project checkout has local changes", async () => {
+    const orchestrator = createOrchestrator([reviewTask()], {
+      worktreeManager: {
+        mergeTaskWorktree: async (): Promise<MergeTaskWorktreeResult> => {
+          throw new IkanbanError("invalid_state", "/repo/web has uncommitted changes on main.");
+        },
+      },
+    });
+    await orchestrator.initialize();
+
+    await expect(orchestrator.mergeTask("task-1")).rejects.toThrow("uncommitted changes");
+    expect(orchestrator.listTasks().find((task) => task.taskId === "task-1")?.state).toBe("review");
+  });
+
+  test("fails the task on errors other than a rejected merge", async () => {
+    const orchestrator = createOrchestrator([reviewTask()], {
+      worktreeManager: {
+        mergeTaskWorktree: async (): Promise<MergeTaskWorktreeResult> => {
+          throw new Error("Failed to determine branch for worktree.");
+        },
+      },
+    });
+    await orchestrator.initialize();
+
+    await expect(orchestrator.mergeTask("task-1")).rejects.toThrow("Failed to determine branch");
+    expect(orchestrator.listTasks().find((task) => task.taskId === "task-1")?.state).toBe("failed");
+  });
+});
+
+describe("worktree garbage collection", () => {
+  const checkpoints = [{ commit: "abc1230000", tree: "t1", label: "after edit", createdAt: 1 }];
+  const finishedTask = () => reviewTask({ state: "completed", checkpoints, updatedAt: 1 });
+
+  test("keeps checkpoints when the worktree could not be removed", async () => {
+    const orchestrator = createOrchestrator([finishedTask()], {
+      worktreeManager: {
+        cleanupTaskWorktree: async () => ({ policy: "remove" as const, taskId: "task-1", removed: false }),
+      },
+    });
+    await orchestrator.initialize();
+
+    const task = await orchestrator.removeTaskWorktree("task-1");
+    expect(task.checkpoints).toEqual(checkpoints);
+    expect(task.worktreeRemovedAt).toBeUndefined();
+  });
+
+  test("skips finished tasks whose worktree has uncommitted changes", async () => {
+    let cleanups = 0;
+    const orchestrator = createOrchestrator([finishedTask()], {
+      options: { worktreeRetentionMs: 1_000 },
+      worktreeManager: {
+        hasLocalChanges: async () => true,
+        cleanupTaskWorktree: async () => {
+          cleanups += 1;
+          return { policy: "remove" as const, taskId: "task-1", removed: true };
+        },
+      },
+    });
+    await orchestrator.initialize();
+
+    expect(await orchestrator.collectFinishedWorktrees(10_000)).toEqual([]);
+    expect(cleanups).toBe(0);
+    expect(orchestrator.listTasks()[0]?.checkpoints).toEqual(checkpoints);
+  });
+});
+
+describe("refreshPullRequests", () => {
+  test("keeps following the other pull requests when one cannot be fetched", async () => {
+    const openPullRequest = (number: number) => ({
+      repository: "acme/web",
+      number,
+      url: `https://github.com/acme/web/pull/${number}`,
+      state: "open" as const,
+      openedAt: 1,
+      checkedAt: 1,
+    });
+    const orchestrator = createOrchestrator(
+      [
+        reviewTask({ taskId: "task-1", pullRequest: openPullRequest(1) }),
+        reviewTask({ taskId: "task-2", shortCode: "WEB-2", pullRequest: openPullRequest(2) }),
+      ],
+      {
+        github: {
+          getPullRequest: async (_repository, number) => {
+            if (number === 1) {
+              throw new Error("GitHub API 404: Not Found");
+            }
+            return { number, url: `https://github.com/acme/web/pull/${number}`, state: "merged" };
+          },
+        },
+      },
+    );
+    await orchestrator.initialize();
+
+    await orchestrator.refreshPullRequests();
+
+    const states = orchestrator.listTasks().map((task) => [task.taskId, task.pullRequest?.state]);
+    expect(states).toContainEqual(["task-1", "open"]);
+    expect(states).toContainEqual(["task-2", "merged"]);
+  });
+});
+
+describe("rollbackToCheckpoint", () => {
+  const checkpoints = [
+    { commit: "abc1230000", tree: "t1", label: "after edit", createdAt: 1 },
+    { commit: "abc1240000", tree: "t2", label: "after test", createdAt: 2 },
+  ];
+
+  test("rejects an empty or too-short commit prefix", async () => {
+    const orchestrator = createOrchestrator([reviewTask({ checkpoints })]);
+    await orchestrator.initialize();
+
+    await expect(orchestrator.rollbackToCheckpoint("task-1", "  ")).rejects.toMatchObject({ code: "validation" });
+    await expect(orchestrator.rollbackToCheckpoint("task-1", "abc")).rejects.toMatchObject({ code: "validation" });
+  });
+
+  test("refuses a prefix that matches more than one checkpoint", async () => {
+    const orchestrator = createOrchestrator([reviewTask({ checkpoints })]);
+    await orchestrator.initialize();
+
+    await expect(orchestrator.rollbackToCheckpoint("task-1", "abc12")).rejects.toMatchObject({ code: "conflict" });
+  });
+});
+
+describe("retryTask", () => {
+  test("carries the source task's title over to the retry", async () => {
+    const failed = reviewTask({ state: "failed", title: "Renamed footer task", error: "Tests failed." });
+    const orchestrator = createOrchestrator([failed], { project: closedProject });
+    await orchestrator.initialize();
+
+    const enqueued = nextEnqueuedTask(orchestrator);
+    void orchestrator.retryTask("task-1").catch(() => undefined);
+
+    const retry = await enqueued;
+    expect(retry.retryOf).toBe("task-1");
+    expect(retry.title).toBe("Renamed footer task");
+    expect(orchestrator.listTasks().find((task) => task.taskId === retry.taskId)?.title).toBe("Renamed footer task");
+  });
+});
+
+describe("task short codes", () => {
+  test("are not reused after the newest task is deleted", async () => {
+    const orchestrator = createOrchestrator([], { project: closedProject });
+    await orchestrator.initialize();
+
+    const firstEnqueued = nextEnqueuedTask(orchestrator);
+    void orchestrator.runTask({ taskId: "first", projectId: project.id, initialPrompt: "One" }).catch(() => undefined);
+    const first = await firstEnqueued;
+    await orchestrator.deleteTask("first");
+
+    const secondEnqueued = nextEnqueuedTask(orchestrator);
+    void orchestrator.runTask({ taskId: "second", projectId: project.id, initialPrompt: "Two" }).catch(() => undefined);
+    const second = await secondEnqueued;
+
+    expect(first.shortCode).toBeDefined();
+    expect(second.shortCode).toBeDefined();
+    expect(second.shortCode).not.toBe(first.shortCode);
+  });
+
+  test("are not used up by a rejected create", async () => {
+    const orchestrator = createOrchestrator([], { project: closedProject });
+    await orchestrator.initialize();
+
+    const firstEnqueued = nextEnqueuedTask(orchestrator);
+    void orchestrator.runTask({ taskId: "first", projectId: project.id, initialPrompt: "One" }).catch(() => undefined);
+    expect((await firstEnqueued).shortCode).toBe("WEB-1");
+
+    await expect(
+      orchestrator.runTask({ taskId: "first", projectId: project.id, initialPrompt: "Again" }),
+    ).rejects.toThrow("already queued or running");
+
+    const secondEnqueued = nextEnqueuedTask(orchestrator);
+    void orchestrator.runTask({ taskId: "second", projectId: project.id, initialPrompt: "Two" }).catch(() => undefined);
+    expect((await secondEnqueued).shortCode).toBe("WEB-2");
+  });
+
+  test("stay the same when a completed task is run again", async () => {
+    const completed = reviewTask({ state: "completed", shortCode: "WEB-7" });
+    const orchestrator = createOrchestrator([completed], { project: closedProject });
+    await orchestrator.initialize();
+
+    const enqueued = nextEnqueuedTask(orchestrator);
+    void orchestrator.runTask({ taskId: "task-1", projectId: project.id, initialPrompt: "Again" }).catch(() => undefined);
+
+    expect((await enqueued).shortCode).toBe("WEB-7");
+  });
+});
@@ -1,13 +1,64 @@
+import { resolve } from "node:path";
+
 import type { ConversationSessionMeta } from "../domain/conversation";
 import type { ConversationSdkSessionMessage } from "../domain/conversation";
+import { summarizeSessionName } from "../domain/conversation";
 import {
   assertTaskRuntimeInvariants,
   assertTaskStateTransition,
+  assertTaskTransitionAllowed,
+  buildTaskShortCodePrefix,
+  formatTaskShortCode,
+  isPathInScope,
+  listOpenBlockers,
+  normalizePathScope,
+  parseTaskShortCode,
   transitionTaskState,
+  wouldCreateDependencyCycle,
+  type MergeStrategy,
+  type TaskCheckpoint,
+  type TaskHookAnnotation,
+  type TaskPriority,
+  type TaskPullRequest,
+  type TaskReviewDecision,
+  type TaskReviewRecord,
   type TaskRuntime,
   type TaskState,
 } from "../domain/task";
+import { getErrorCode, IkanbanError, type IkanbanErrorCode } from "../domain/errors";
+import { classifyTaskFailure, RETRYABLE_FAILURE_CLASSES } from "../domain/task-failure";
+import { rankBetween, rankSequence } from "../domain/task-rank";
+import {
+  nextTaskCommentId,
+  summarizeRunForComment,
+  TASK_COMMENT_MAX_LENGTH,
+  type TaskComment,
+} from "../domain/task-comment";
+import {
+  checkTaskWatchdog,
+  describeWatchdogFailure,
+  type TaskWatchdogReason,
+} from "../domain/task-watchdog";
 import type { ProjectRef } from "../domain/project";
+import { isDeferredByWorkingHours, nextWorkingHoursStart, type WorkingHours } from "../domain/working-hours";
+import type { PermissionReply, PermissionRequest } from "../domain/permission";
+import type { TaskPlanProgress } from "../domain/task-progress";
+import type { QueueSnapshot } from "../domain/task-queue";
+import {
+  isSameConflictRisk,
+  scoreConflictRisk,
+  type BranchChangeSet,
+  type TaskConflictRisk,
+} from "../domain/conflict-risk";
+import { collectBranchChanges } from "./conflict-risk";
+import { resolveGitHubTarget, type GitHubClient, type GitHubPullRequest } from "./github";
+import { ensureWorktreeAgentInstructions } from "./agent-instructions";
+import {
+  createWorktreeCheckpoint,
+  deleteWorktreeCheckpoints,
+  restoreWorktreeCheckpoint,
+} from "./worktree-checkpoints";
+import { assertValid, validatePrompt, validateTitle } from "../domain/validation";
 import type { TaskRegistry } from "./task-registry";
 import type { ProjectRegistry } from "./project-registry";
 import type {
@@ -19,37 +70,132 @@ import type {
   CleanupTaskWorktreeResult,
   ManagedWorktree,
   MergeTaskWorktreeResult,
+  PushTaskBranchResult,
+  RebaseTaskWorktreeResult,
   WorktreeCleanupPolicy,
   WorktreeManager,
 } from "./worktree-manager";
 import { resolveCleanupPolicy } from "./worktree-manager";
+import { appendPathScope, appendPromptContext, gatherPromptContext } from "./prompt-context";
+import { RepositoryPreflightError, runRepositoryPreflight } from "./repository-preflight";
+import type { WorktreeChange, WorktreeWatcher } from "./worktree-watcher";
+import type { PageRegistry } from "./page-registry";
+import {
+  LifecycleHookVetoError,
+  type LifecycleHookPoint,
+  type LifecycleHookRunner,
+} from "./lifecycle-hooks";
+import { formatPagesForPrompt } from "../domain/page";
 import { noopRuntimeLogger, toStructuredError, type RuntimeLogger } from "./runtime-logger";
-
-type ProjectRegistryLike = Pick<ProjectRegistry, "getProject" | "getActiveProject">;
+import type { SecretRedactor } from "./secret-redactor";
+
+type ProjectRegistryLike = Pick<
+  ProjectRegistry,
+  | "getProject"
+  | "getActiveProject"
+  | "listProjects"
+  | "setProjectWorkingHours"
+  | "setProjectMaxConcurrentTasks"
+  | "allocateTaskSequence"
+>;
 type TaskRegistryLike = Pick<TaskRegistry, "listTasks" | "upsertTask" | "removeTask">;
 
 type WorktreeManagerLike = Pick<
   WorktreeManager,
-  "createTaskWorktree" | "cleanupTaskWorktree" | "getTaskWorktreeDirectory" | "mergeTaskWorktree"
+  | "createTaskWorktree"
+  | "cleanupTaskWorktree"
+  | "listWorktrees"
+  | "removeWorktree"
+  | "hasLocalChanges"
+  | "getTaskWorktreeDirectory"
+  | "mergeTaskWorktree"
+  | "rebaseTaskWorktree"
+  | "pushTaskBranch"
 >;
 
+type WorktreeWatcherLike = Pick<WorktreeWatcher, "watch" | "unwatch">;
+
+type PageRegistryLike = Pick<PageRegistry, "listPages">;
+
+type LifecycleHookRunnerLike = Pick<LifecycleHookRunner, "run">;
+
+type SecretRedactorLike = Pick<SecretRedactor, "redact">;
+
+type GitHubClientLike = Pick<GitHubClient, "createPullRequest" | "getPullRequest">;
+
+const MAX_HOOK_ANNOTATIONS = 20;
+const MAX_CHECKPOINTS = 50;
+// Agents often run tools in quick bursts; one snapshot per burst is enough to roll back to.
+const CHECKPOINT_MIN_INTERVAL_MS = 30_000;
+// Same floor as git's own abbreviated hashes.
+const MIN_CHECKPOINT_PREFIX_LENGTH = 4;
+const DEFAULT_RETRY_BACKOFF_MS = 30_000;
+const WATCHDOG_INTERVAL_MS = 5_000;
+const FINISHED_TASK_STATES: readonly TaskState[] = ["completed", "failed", "cancelled"];
+
 type ConversationManagerLike = Pick<
   ConversationManager,
-  "createTaskSession" | "sendInitialPromptAndAwaitMessages" | "sendFollowUpPromptAndAwaitMessages" | "getTaskSessionID"
+  | "createTaskSession"
+  | "sendInitialPromptAndAwaitMessages"
+  | "sendFollowUpPromptAndAwaitMessages"
+  | "getTaskSessionID"
+  | "listConversationMessages"
+  | "abortSession"
+  | "replyToPermission"
 >;
 
 export type TaskOrchestratorOptions = {
   maxConcurrent?: number;
+  maxConcurrentPerProject?: number;
+  /** Automatic retries of a failed run; 0 disables them. */
+  retryMaxAttempts?: number;
+  /** Delay before the first automatic retry; doubles for each later one. */
+  retryBackoffMs?: number;
+  /** Default wall-clock limit for one run; a task's own `timeoutMs` overrides it. */
+  runTimeoutMs?: number;
+  /** Fails a run that has produced no agent output for this long. */
+  stallTimeoutMs?: number;
   cleanupOnSuccess?: WorktreeCleanupPolicy;
   cleanupOnFailure?: WorktreeCleanupPolicy;
+  /** Worktrees of tasks finished longer ago than this are removed by collectFinishedWorktrees(); unset keeps them. */
+  worktreeRetentionMs?: number;
+  enrichPromptWithRepoContext?: boolean;
+  enforcePathScope?: boolean;
+  preflightCheckRemote?: boolean;
+  reviewer?: string;
   logger?: RuntimeLogger;
 };
 
+export type ReconfigurableTaskOrchestratorOptions = Pick<
+  TaskOrchestratorOptions,
+  | "maxConcurrent"
+  | "maxConcurrentPerProject"
+  | "retryMaxAttempts"
+  | "retryBackoffMs"
+  | "runTimeoutMs"
+  | "stallTimeoutMs"
+  | "cleanupOnSuccess"
+  | "cleanupOnFailure"
+  | "worktreeRetentionMs"
+  | "enrichPromptWithRepoContext"
+  | "enforcePathScope"
+  | "preflightCheckRemote"
+  | "reviewer"
+>;
+
 export type RunTaskInput = {
   taskId: string;
   initialPrompt: string;
   projectId?: string;
+  parentTaskId?: string;
+  /** Tasks of the same project that must be completed before this one starts. */
+  blockedBy?: string[];
+  priority?: TaskPriority;
+  retryOf?: string;
+  retryAttempt?: number;
   title?: string;
+  pathScope?: string;
+  timeoutMs?: number;
   startCommand?: string;
   model?: SendInitialPromptInput["model"];
   cleanupOnSuccess?: WorktreeCleanupPolicy;
@@ -129,6 +275,12 @@ export type TaskOrchestratorEvent =
       error: string;
       task: TaskRuntime;
     }
+  | {
+      type: "task.cancelled";
+      taskId: string;
+      reason?: string;
+      task: TaskRuntime;
+    }
   | {
       type: "task.review";
       taskId: string;
@@ -139,6 +291,139 @@ export type TaskOrchestratorEvent =
       taskId: string;
       branch: string;
       task: TaskRuntime;
+    }
+  | {
+      type: "task.renamed";
+      taskId: string;
+      task: TaskRuntime;
+    }
+  | {
+      type: "task.estimate.changed";
+      taskId: string;
+      estimate?: number;
+      task: TaskRuntime;
+    }
+  | {
+      type: "task.model.changed";
+      taskId: string;
+      model?: TaskRuntime["model"];
+      task: TaskRuntime;
+    }
+  | {
+      type: "task.timeout.changed";
+      taskId: string;
+      timeoutMs?: number;
+      task: TaskRuntime;
+    }
+  | {
+      type: "task.dependencies.changed";
+      taskId: string;
+      blockedBy: string[];
+      task: TaskRuntime;
+    }
+  | {
+      type: "task.watchdog.fired";
+      taskId: string;
+      reason: TaskWatchdogReason;
+      limitMs: number;
+      task: TaskRuntime;
+    }
+  | {
+      type: "task.pr.changed";
+      taskId: string;
+      pullRequest: TaskPullRequest;
+      task: TaskRuntime;
+    }
+  | {
+      type: "task.progress.changed";
+      taskId: string;
+      progress: TaskPlanProgress;
+      task: TaskRuntime;
+    }
+  | {
+      type: "task.permission.requested";
+      taskId: string;
+      request: PermissionRequest;
+      task: TaskRuntime;
+    }
+  | {
+      type: "task.permission.replied";
+      taskId: string;
+      request: PermissionRequest;
+      reply: PermissionReply;
+      task: TaskRuntime;
+    }
+  | {
+      type: "task.milestone.changed";
+      taskId: string;
+      milestoneId?: string;
+      task: TaskRuntime;
+    }
+  | {
+      type: "task.priority.changed";
+      taskId: string;
+      priority?: TaskPriority;
+      task: TaskRuntime;
+    }
+  | {
+      type: "task.rank.changed";
+      taskId: string;
+      rank: string;
+      task: TaskRuntime;
+    }
+  | {
+      type: "task.labels.changed";
+      taskId: string;
+      labelIds: string[];
+      task: TaskRuntime;
+    }
+  | {
+      type: "task.comment.created" | "task.comment.updated" | "task.comment.deleted";
+      taskId: string;
+      comment: TaskComment;
+      task: TaskRuntime;
+    }
+  | {
+      type: "task.worktree.changed";
+      taskId: string;
+      paths: string[];
+      task: TaskRuntime;
+    }
+  | {
+      type: "task.pushed";
+      taskId: string;
+      push: PushTaskBranchResult;
+      task: TaskRuntime;
+    }
+  | {
+      type: "task.rebased";
+      taskId: string;
+      rebase: RebaseTaskWorktreeResult;
+      task: TaskRuntime;
+    }
+  | {
+      type: "task.checkpoint.created";
+      taskId: string;
+      checkpoint: TaskCheckpoint;
+      task: TaskRuntime;
+    }
+  | {
+      type: "task.checkpoint.restored";
+      taskId: string;
+      checkpoint: TaskCheckpoint;
+      task: TaskRuntime;
+    }
+  | {
+      type: "task.risk.changed";
+      taskId: string;
+      risk: TaskConflictRisk;
+      task: TaskRuntime;
+    }
+  | {
+      type: "task.review.decided";
+      taskId: string;
+      decision: TaskReviewDecision;
+      task: TaskRuntime;
     };
 
 type QueueEntry = {
@@ -147,6 +432,14 @@ type QueueEntry = {
   reject: (reason?: unknown) => void;
 };
 
+type TaskWatchdog = {
+  timer: ReturnType<typeof setInterval>;
+  lastOutputAt: number;
+  // Limits in force when the run started; a task's own timeoutMs is read on every check.
+  runTimeoutMs?: number;
+  stallTimeoutMs?: number;
+};
+
 type CleanupExecutionResult = {
   task: TaskRuntime;
   cleanup?: CleanupTaskWorktreeResult;
@@ -157,13 +450,38 @@ export class TaskOrchestrator {
   private readonly taskRegistry?: TaskRegistryLike;
   private readonly worktreeManager: WorktreeManagerLike;
   private readonly conversationManager: ConversationManagerLike;
-  private readonly maxConcurrent: number;
-  private readonly cleanupOnSuccess: WorktreeCleanupPolicy;
-  private readonly cleanupOnFailure: WorktreeCleanupPolicy;
+  private readonly worktreeWatcher?: WorktreeWatcherLike;
+  private readonly pageRegistry?: PageRegistryLike;
+  private readonly lifecycleHooks?: LifecycleHookRunnerLike;
+  private readonly redactor?: SecretRedactorLike;
+  private readonly github?: GitHubClientLike;
+  // Reconfigurable at runtime; see reconfigure().
+  private maxConcurrent: number;
+  private maxConcurrentPerProject?: number;
+  private retryMaxAttempts: number;
+  private retryBackoffMs: number;
+  private runTimeoutMs?: number;
+  private stallTimeoutMs?: number;
+  private cleanupOnSuccess: WorktreeCleanupPolicy;
+  private cleanupOnFailure: WorktreeCleanupPolicy;
+  private worktreeRetentionMs?: number;
+  private enrichPromptWithRepoContext: boolean;
+  private enforcePathScope: boolean;
+  private preflightCheckRemote: boolean;
+  private reviewer: string;
   private readonly logger: RuntimeLogger;
   private readonly tasksById = new Map<string, TaskRuntime>();
   private readonly taskQueue: QueueEntry[] = [];
   private readonly runningTaskIds = new Set<string>();
+  private readonly checkpointChains = new Map<string, Promise<void>>();
+  private readonly lastCheckpointAt = new Map<string, number>();
+  private readonly watchdogs = new Map<string, TaskWatchdog>();
+  private readonly watchdogFailures = new Map<string, IkanbanError>();
+  private readonly pendingPermissions = new Map<string, PermissionRequest>();
+  private readonly workingHoursByProjectId = new Map<string, WorkingHours>();
+  private readonly maxConcurrentByProjectId = new Map<string, number>();
+  private workingHoursTimer?: ReturnType<typeof setTimeout>;
+  private workingHoursWakeAt?: number;
   private readonly listeners = new Set<(event: TaskOrchestratorEvent) => void>();
   private initialized = false;
   private initializationPromise?: Promise<void>;
@@ -174,6 +492,11 @@ export class TaskOrchestrator {
       taskRegistry?: TaskRegistryLike;
       worktreeManager: WorktreeManagerLike;
       conversationManager: ConversationManagerLike;
+      worktreeWatcher?: WorktreeWatcherLike;
+      pageRegistry?: PageRegistryLike;
+      lifecycleHooks?: LifecycleHookRunnerLike;
+      redactor?: SecretRedactorLike;
+      github?: GitHubClientLike;
     },
     options: TaskOrchestratorOptions = {},
   ) {
@@ -181,9 +504,25 @@ export class TaskOrchestrator {
     this.taskRegistry = dependencies.taskRegistry;
     this.worktreeManager = dependencies.worktreeManager;
     this.conversationManager = dependencies.conversationManager;
+    this.worktreeWatcher = dependencies.worktreeWatcher;
+    this.pageRegistry = dependencies.pageRegistry;
+    this.lifecycleHooks = dependencies.lifecycleHooks;
+    this.redactor = dependencies.redactor;
+    this.github = dependencies.github;
     this.maxConcurrent = normalizeMaxConcurrent(options.maxConcurrent);
+    this.maxConcurrentPerProject =
+      options.maxConcurrentPerProject === undefined ? undefined : normalizeMaxConcurrent(options.maxConcurrentPerProject);
+    this.retryMaxAttempts = options.retryMaxAttempts ?? 0;
+    this.retryBackoffMs = options.retryBackoffMs ?? DEFAULT_RETRY_BACKOFF_MS;
+    this.runTimeoutMs = options.runTimeoutMs;
+    this.stallTimeoutMs = options.stallTimeoutMs;
     this.cleanupOnSuccess = resolveCleanupPolicy(options.cleanupOnSuccess, "keep");
     this.cleanupOnFailure = resolveCleanupPolicy(options.cleanupOnFailure, "keep");
+    this.worktreeRetentionMs = options.worktreeRetentionMs;
+    this.enrichPromptWithRepoContext = options.enrichPromptWithRepoContext ?? false;
+    this.enforcePathScope = options.enforcePathScope ?? false;
+    this.preflightCheckRemote = options.preflightCheckRemote ?? false;
+    this.reviewer = normalizeOptionalId(options.reviewer) ?? "reviewer";
     this.logger = options.logger ?? noopRuntimeLogger;
   }
 
@@ -191,21 +530,85 @@ export class TaskOrchestrator {
     await this.ensureInitialized();
   }
 
+  /** Applies new settings to future work; running tasks keep the settings they started with. */
+  reconfigure(options: ReconfigurableTaskOrchestratorOptions): void {
+    if (options.maxConcurrent !== undefined) {
+      this.maxConcurrent = normalizeMaxConcurrent(options.maxConcurrent);
+    }
+    if ("maxConcurrentPerProject" in options) {
+      this.maxConcurrentPerProject =
+        options.maxConcurrentPerProject === undefined ? undefined : normalizeMaxConcurrent(options.maxConcurrentPerProject);
+    }
+    if (options.retryMaxAttempts !== undefined) {
+      this.retryMaxAttempts = options.retryMaxAttempts;
+    }
+    if (options.retryBackoffMs !== undefined) {
+      this.retryBackoffMs = options.retryBackoffMs;
+    }
+    if ("runTimeoutMs" in options) {
+      this.runTimeoutMs = options.runTimeoutMs;
+    }
+    if ("stallTimeoutMs" in options) {
+      this.stallTimeoutMs = options.stallTimeoutMs;
+    }
+    if (options.cleanupOnSuccess !== undefined) {
+      this.cleanupOnSuccess = options.cleanupOnSuccess;
+    }
+    if (options.cleanupOnFailure !== undefined) {
+      this.cleanupOnFailure = options.cleanupOnFailure;
+    }
+    if ("worktreeRetentionMs" in options) {
+      this.worktreeRetentionMs = options.worktreeRetentionMs;
+    }
+    if (options.enrichPromptWithRepoContext !== undefined) {
+      this.enrichPromptWithRepoContext = options.enrichPromptWithRepoContext;
+    }
+    if (options.enforcePathScope !== undefined) {
+      this.enforcePathScope = options.enforcePathScope;
+    }
+    if (options.preflightCheckRemote !== undefined) {
+      this.preflightCheckRemote = options.preflightCheckRemote;
+    }
+    if (options.reviewer !== undefined) {
+      this.reviewer = normalizeOptionalId(options.reviewer) ?? this.reviewer;
+    }
+
+    // A raised limit should start queued tasks right away.
+    this.schedule();
+  }
+
   async runTask(input: RunTaskInput): Promise<RunTaskResult> {
     await this.ensureInitialized();
 
     const taskId = normalizeId(input.taskId, "Task id");
     const prompt = normalizePrompt(input.initialPrompt);
     const timestamp = normalizeTimestamp(input.timestamp ?? Date.now(), "Timestamp");
+    const projectId = normalizeOptionalId(input.projectId);
     const existingTask = this.tasksById.get(taskId);
 
     if (existingTask && (this.runningTaskIds.has(taskId) || existingTask.state !== "completed")) {
-      throw new Error(`Task ${taskId} is already queued or running.`);
+      throw new IkanbanError("conflict", `Task ${taskId} is already queued or running.`);
+    }
+
+    const blockedBy = [...new Set((input.blockedBy ?? []).map((blockerId) => normalizeId(blockerId, "Blocker id")))];
+    for (const blockerId of blockedBy) {
+      this.assertValidBlocker(taskId, projectId, blockerId);
     }
 
     const runtime: TaskRuntime = {
       taskId,
-      projectId: normalizeOptionalId(input.projectId) ?? "pending",
+      projectId: projectId ?? "pending",
+      // A re-run keeps its code; new tasks get one once the created hooks let them through.
+      shortCode: existingTask?.projectId === projectId ? existingTask?.shortCode : undefined,
+      title: input.title?.trim() || undefined,
+      prompt,
+      parentTaskId: normalizeOptionalId(input.parentTaskId),
+      blockedBy: blockedBy.length > 0 ? blockedBy : undefined,
+      priority: input.priority,
+      retryOf: normalizeOptionalId(input.retryOf),
+      retryAttempt: input.retryAttempt,
+      pathScope: normalizePathScope(input.pathScope),
+      timeoutMs: input.timeoutMs,
       state: "queued",
       model: input.model,
       createdAt: timestamp,
@@ -216,6 +619,24 @@ export class TaskOrchestrator {
     this.tasksById.set(taskId, runtime);
     this.persistTask(runtime);
 
+    try {
+      await this.runLifecycleHooks("task.created", runtime);
+    } catch (error) {
+      if (existingTask) {
+        this.tasksById.set(taskId, existingTask);
+        this.persistTask(existingTask);
+      } else {
+        this.tasksById.delete(taskId);
+        this.removePersistedTask(taskId);
+      }
+      throw error;
+    }
+
+    if (projectId && !runtime.shortCode) {
+      const shortCode = await this.nextTaskShortCode(projectId);
+      this.updateTask(taskId, (current) => ({ ...current, shortCode: current.shortCode ?? shortCode }));
+    }
+
     return new Promise<RunTaskResult>((resolve, reject) => {
       this.taskQueue.push({
         input: {
@@ -238,12 +659,52 @@ export class TaskOrchestrator {
     });
   }
 
+  /** Re-runs a failed task as a new task linked to the original through `retryOf`. */
+  async retryTask(taskId: string): Promise<RunTaskResult> {
+    await this.ensureInitialized();
+
+    const source = this.getTaskOrThrow(normalizeId(taskId, "Task id"));
+    if (source.state !== "failed") {
+      throw new IkanbanError(
+        "invalid_state",
+        `Only failed tasks can be retried (${source.shortCode ?? source.taskId} is ${source.state}).`,
+      );
+    }
+
+    const retryOf = source.retryOf ?? source.taskId;
+    const retryAttempt = (source.retryAttempt ?? 0) + 1;
+    const retryTaskId = this.tasksById.has(`${retryOf}-r${retryAttempt}`)
+      ? `${retryOf}-r${retryAttempt}-${Date.now().toString(36)}`
+      : `${retryOf}-r${retryAttempt}`;
+    // Retry the original objective, not the previous retry's prompt with its own preamble.
+    const objective = this.tasksById.get(retryOf)?.prompt ?? source.prompt;
+    const preamble = `Retry task ${source.shortCode ?? source.taskId}. Address the previous failure${source.error ? ` (${source.error})` : ""} and continue with the same objective.`;
+
+    return this.runTask({
+      taskId: retryTaskId,
+      projectId: source.projectId,
+      parentTaskId: source.parentTaskId,
+      priority: source.priority,
+      retryOf,
+      retryAttempt,
+      initialPrompt: objective ? `${preamble}\n\n${objective}` : preamble,
+      title: source.title,
+      pathScope: source.pathScope,
+      timeoutMs: source.timeoutMs,
+      model: source.model,
+    });
+  }
+
+  async runTasks(inputs: RunTaskInput[]): Promise<PromiseSettledResult<RunTaskResult>[]> {
+    return Promise.allSettled(inputs.map((input) => this.runTask(input)));
+  }
+
   async deleteTask(taskId: string): Promise<boolean> {
     await this.ensureInitialized();
 
     const normalizedTaskId = normalizeId(taskId, "Task id");
     if (this.runningTaskIds.has(normalizedTaskId)) {
-      throw new Error(`Task ${normalizedTaskId} is running and cannot be deleted.`);
+      throw new IkanbanError("conflict", `Task ${normalizedTaskId} is running and cannot be deleted.`);
     }
 
     const queueIndex = this.taskQueue.findIndex((entry) => entry.input.taskId === normalizedTaskId);
@@ -260,6 +721,7 @@ export class TaskOrchestrator {
     if (task.worktreeDirectory) {
       const project = await this.projectRegistry.getProject(task.projectId);
       if (project) {
+        await deleteWorktreeCheckpoints(project.rootDirectory, normalizedTaskId);
         await this.worktreeManager.cleanupTaskWorktree({
           taskId: normalizedTaskId,
           projectDirectory: project.rootDirectory,
@@ -269,11 +731,118 @@ export class TaskOrchestrator {
       }
     }
 
+    this.worktreeWatcher?.unwatch(normalizedTaskId);
+    this.checkpointChains.delete(normalizedTaskId);
+    this.lastCheckpointAt.delete(normalizedTaskId);
     this.tasksById.delete(normalizedTaskId);
     this.removePersistedTask(normalizedTaskId);
     return true;
   }
 
+  /**
+   * Moves a task to "cancelled", dropping it from the queue or aborting its
+   * running session. The worktree is kept so the work can still be inspected;
+   * deleting the task removes it.
+   */
+  async cancelTask(taskId: string, reason?: string): Promise<TaskRuntime> {
+    await this.ensureInitialized();
+
+    const normalizedTaskId = normalizeId(taskId, "Task id");
+    const task = this.getTaskOrThrow(normalizedTaskId);
+    const project = await this.projectRegistry.getProject(task.projectId);
+    assertTaskTransitionAllowed(task.state, "cancelled", project?.taskTransitions);
+
+    const cancelledTask = this.transitionTaskWithPatch(normalizedTaskId, "cancelled", {
+      cancelReason: normalizeOptionalId(reason),
+      cancelledAt: Date.now(),
+    });
+    this.emit({
+      type: "task.cancelled",
+      taskId: normalizedTaskId,
+      reason: cancelledTask.cancelReason,
+      task: cancelledTask,
+    });
+
+    const queueIndex = this.taskQueue.findIndex((entry) => entry.input.taskId === normalizedTaskId);
+    if (queueIndex >= 0) {
+      const [queued] = this.taskQueue.splice(queueIndex, 1);
+      queued?.reject(new TaskRunFailedError(`Task ${normalizedTaskId} was cancelled.`, { task: cancelledTask }));
+    }
+
+    this.stopWatchdog(normalizedTaskId);
+    if (task.state === "running" && task.sessionID) {
+      try {
+        await this.conversationManager.abortSession({
+          sessionID: task.sessionID,
+          worktreeDirectory: task.worktreeDirectory,
+        });
+      } catch (error) {
+        this.logger.log({
+          level: "warn",
+          source: "task-orchestrator.cancel",
+          message: "Failed to abort session for cancelled task.",
+          context: { taskId: normalizedTaskId, sessionID: task.sessionID },
+          error: toStructuredError(error),
+        });
+      }
+    }
+
+    return cancelledTask;
+  }
+
+  /**
+   * Restores a task worktree to one of its checkpoints. The current state is
+   * checkpointed first, so a rollback can itself be rolled back.
+   */
+  async rollbackToCheckpoint(taskId: string, commit: string): Promise<TaskRuntime> {
+    await this.ensureInitialized();
+
+    const normalizedTaskId = normalizeId(taskId, "Task id");
+    const task = this.getTaskOrThrow(normalizedTaskId);
+    if (this.runningTaskIds.has(normalizedTaskId) || task.state === "running") {
+      throw new IkanbanError("conflict", `Task ${normalizedTaskId} is running; cancel it or wait for review before rolling back.`);
+    }
+
+    if (!task.worktreeDirectory) {
+      throw new IkanbanError("invalid_state", `Task ${normalizedTaskId} is missing worktree directory.`);
+    }
+
+    const prefix = commit.trim().toLowerCase();
+    if (prefix.length < MIN_CHECKPOINT_PREFIX_LENGTH) {
+      throw new IkanbanError(
+        "validation",
+        `Checkpoint commit must be at least ${MIN_CHECKPOINT_PREFIX_LENGTH} characters.`,
+        { field: "commit" },
+      );
+    }
+
+    const matches = (task.checkpoints ?? []).filter((candidate) => candidate.commit.startsWith(prefix));
+    const checkpoint = matches[0];
+    if (!checkpoint) {
+      throw new IkanbanError("not_found", `Checkpoint not found for task ${normalizedTaskId}: ${commit}`);
+    }
+
+    if (matches.some((candidate) => candidate.commit !== checkpoint.commit)) {
+      throw new IkanbanError(
+        "conflict",
+        `Checkpoint ${prefix} is ambiguous for task ${normalizedTaskId}; use more of the commit hash.`,
+      );
+    }
+
+    await this.queueCheckpoint(normalizedTaskId, task.worktreeDirectory, `before rollback to ${checkpoint.commit.slice(0, 8)}`, true);
+    await restoreWorktreeCheckpoint(task.worktreeDirectory, checkpoint);
+
+    const restoredTask = this.getTaskOrThrow(normalizedTaskId);
+    this.emit({
+      type: "task.checkpoint.restored",
+      taskId: normalizedTaskId,
+      checkpoint,
+      task: restoredTask,
+    });
+
+    return restoredTask;
+  }
+
   async sendFollowUpPrompt(taskId: string, prompt: string): Promise<void> {
     await this.ensureInitialized();
 
@@ -282,28 +851,48 @@ export class TaskOrchestrator {
     const task = this.getTaskOrThrow(normalizedTaskId);
 
     if (task.state !== "review") {
-      throw new Error(`Task ${normalizedTaskId} must be in review state to send a follow-up prompt (current: ${task.state}).`);
+      throw new IkanbanError(
+        "invalid_state",
+        `Task ${normalizedTaskId} must be in review state to send a follow-up prompt (current: ${task.state}).`,
+      );
     }
 
     if (!task.sessionID || !task.worktreeDirectory) {
-      throw new Error(`Task ${normalizedTaskId} is missing session or worktree directory.`);
+      throw new IkanbanError("invalid_state", `Task ${normalizedTaskId} is missing session or worktree directory.`);
     }
 
-    const runtime = this.transitionTask(normalizedTaskId, "running");
+    const project = await this.resolveProject(task.projectId);
+    assertTaskTransitionAllowed(task.state, "running", project.taskTransitions);
+
+    const runtime = this.transitionTaskWithPatch(normalizedTaskId, "running", {
+      runStartedAt: Date.now(),
+      runFinishedAt: undefined,
+      planProgress: undefined,
+    });
     this.runningTaskIds.add(normalizedTaskId);
+    this.startWatchdog(normalizedTaskId);
 
     try {
       const promptExecution = await this.conversationManager.sendFollowUpPromptAndAwaitMessages({
         sessionID: task.sessionID,
         prompt: normalizedPrompt,
         worktreeDirectory: task.worktreeDirectory,
+        model: task.model,
         onMessage: (message) => {
           this.emit({
             type: "task.session.message.received",
             taskId: normalizedTaskId,
             sessionID: task.sessionID!,
-            sdkMessage: message,
+            sdkMessage: this.redactMessage(task.projectId, message),
           });
+          this.noteRunOutput(normalizedTaskId);
+          this.checkpointAfterTools(normalizedTaskId, task.worktreeDirectory!, message);
+        },
+        onPermission: (request) => {
+          this.addPendingPermission(normalizedTaskId, request);
+        },
+        onPlanProgress: (progress) => {
+          this.updatePlanProgress(normalizedTaskId, progress);
         },
       });
 
@@ -313,51 +902,104 @@ export class TaskOrchestrator {
         prompt: promptExecution.submission,
       });
 
-      const reviewRuntime = this.transitionTask(normalizedTaskId, "review");
+      this.assertNotCancelled(normalizedTaskId);
+      this.assertWatchdogNotFired(normalizedTaskId);
+      await this.queueCheckpoint(normalizedTaskId, task.worktreeDirectory, "follow-up finished", true);
+      await this.verifyPathScope(
+        normalizedTaskId,
+        (await this.resolveProject(task.projectId)).rootDirectory,
+        task.worktreeDirectory,
+      );
+      await this.runLifecycleHooks("session.finished", this.getTaskOrThrow(normalizedTaskId));
+
+      const reviewRuntime = this.transitionTaskWithPatch(normalizedTaskId, "review", {
+        review: this.createReviewRequest(),
+        runFinishedAt: Date.now(),
+      });
       this.emit({
         type: "task.review",
         taskId: normalizedTaskId,
         task: reviewRuntime,
       });
+      this.postRunSummary(normalizedTaskId, task.projectId, promptExecution.sdkMessages);
     } catch (error) {
-      const failureMessage = toErrorMessage(error);
+      if (this.getTaskOrThrow(normalizedTaskId).state === "cancelled") {
+        return;
+      }
+
+      const failure = this.stopWatchdog(normalizedTaskId) ?? error;
+      const failureMessage = toErrorMessage(failure);
       this.logger.log({
         level: "error",
         source: "task-orchestrator.follow-up",
         message: "Follow-up prompt failed.",
         context: { taskId: normalizedTaskId },
-        error: toStructuredError(error),
+        error: toStructuredError(failure),
       });
-      this.transitionTaskToFailed(normalizedTaskId, failureMessage);
+      this.transitionTaskToFailed(normalizedTaskId, failureMessage, getErrorCode(failure));
     } finally {
       this.runningTaskIds.delete(normalizedTaskId);
+      this.dropPendingPermissions(normalizedTaskId);
     }
   }
 
-  async mergeTask(taskId: string): Promise<MergeTaskWorktreeResult> {
+  /**
+   * Merges an approved task into the project's default branch and records how
+   * on the task. With the `remove` cleanup-on-success policy the worktree is
+   * removed afterwards.
+   */
+  async mergeTask(taskId: string, options: { strategy?: MergeStrategy } = {}): Promise<MergeTaskWorktreeResult> {
     await this.ensureInitialized();
 
     const normalizedTaskId = normalizeId(taskId, "Task id");
     const task = this.getTaskOrThrow(normalizedTaskId);
 
     if (task.state !== "review") {
-      throw new Error(`Task ${normalizedTaskId} must be in review state to merge (current: ${task.state}).`);
+      throw new IkanbanError(
+        "invalid_state",
+        `Task ${normalizedTaskId} must be in review state to merge (current: ${task.state}).`,
+      );
+    }
+
+    if (task.review?.decision !== "approved") {
+      throw new IkanbanError(
+        "invalid_state",
+        `Task ${normalizedTaskId} must be approved by ${task.review?.reviewer ?? this.reviewer} before merging.`,
+      );
     }
 
     if (!task.worktreeDirectory) {
-      throw new Error(`Task ${normalizedTaskId} is missing worktree directory.`);
+      throw new IkanbanError("invalid_state", `Task ${normalizedTaskId} is missing worktree directory.`);
     }
 
     const project = await this.resolveProject(task.projectId);
+    assertTaskTransitionAllowed(task.state, "completed", project.taskTransitions);
+
+    // A veto keeps the task in review rather than failing it.
+    await this.runLifecycleHooks("before.merge", task, {
+      projectDirectory: project.rootDirectory,
+    });
 
     try {
       const mergeResult = await this.worktreeManager.mergeTaskWorktree({
         projectDirectory: project.rootDirectory,
         taskId: normalizedTaskId,
+        shortCode: task.shortCode,
         worktreeDirectory: task.worktreeDirectory,
+        strategy: options.strategy,
       });
 
-      const completedRuntime = this.transitionTask(normalizedTaskId, "completed");
+      const completedRuntime = this.transitionTaskWithPatch(normalizedTaskId, "completed", {
+        merge: mergeResult.commit
+          ? {
+              strategy: mergeResult.strategy,
+              branch: mergeResult.branch,
+              targetBranch: mergeResult.targetBranch,
+              commit: mergeResult.commit,
+              mergedAt: Date.now(),
+            }
+          : undefined,
+      });
       this.emit({
         type: "task.merged",
         taskId: normalizedTaskId,
@@ -365,8 +1007,33 @@ export class TaskOrchestrator {
         task: completedRuntime,
       });
 
+      if (this.cleanupOnSuccess === "remove") {
+        await this.executeCleanup({
+          task: completedRuntime,
+          taskId: normalizedTaskId,
+          projectDirectory: project.rootDirectory,
+          policy: "remove",
+        });
+      }
+
       return mergeResult;
     } catch (error) {
+      // A rejected fast-forward or a conflicting merge leaves the task in
+      // review, where it can be rebased or merged with another strategy; so
+      // does a project checkout with local changes, which the user has to
+      // commit or stash first.
+      const code = getErrorCode(error);
+      if (code === "conflict" || code === "invalid_state") {
+        this.logger.log({
+          level: "warn",
+          source: "task-orchestrator.merge",
+          message: "Task merge was rejected; the task stays in review.",
+          context: { taskId: normalizedTaskId, strategy: options.strategy },
+          error: toStructuredError(error),
+        });
+        throw error;
+      }
+
       const failureMessage = toErrorMessage(error);
       this.logger.log({
         level: "error",
@@ -375,56 +1042,983 @@ export class TaskOrchestrator {
         context: { taskId: normalizedTaskId },
         error: toStructuredError(error),
       });
-      this.transitionTaskToFailed(normalizedTaskId, failureMessage);
+      this.transitionTaskToFailed(normalizedTaskId, failureMessage, getErrorCode(error));
       throw error;
     }
   }
 
-  getTask(taskId: string): TaskRuntime | undefined {
+  async pushTaskBranch(
+    taskId: string,
+    options: { remote?: string; forceWithLease?: boolean } = {},
+  ): Promise<PushTaskBranchResult> {
+    await this.ensureInitialized();
+
     const normalizedTaskId = normalizeId(taskId, "Task id");
-    return this.tasksById.get(normalizedTaskId);
-  }
+    const task = this.getTaskOrThrow(normalizedTaskId);
 
-  listTasks(): TaskRuntime[] {
-    return [...this.tasksById.values()].sort((left, right) => {
-      if (left.createdAt !== right.createdAt) {
-        return left.createdAt - right.createdAt;
-      }
+    if (task.state !== "review") {
+      throw new IkanbanError(
+        "invalid_state",
+        `Task ${normalizedTaskId} must be in review state to push its branch (current: ${task.state}).`,
+      );
+    }
 
-      return left.taskId.localeCompare(right.taskId);
+    if (!task.worktreeDirectory) {
+      throw new IkanbanError("invalid_state", `Task ${normalizedTaskId} is missing worktree directory.`);
+    }
+
+    const pushResult = await this.worktreeManager.pushTaskBranch({
+      taskId: normalizedTaskId,
+      shortCode: task.shortCode,
+      worktreeDirectory: task.worktreeDirectory,
+      remote: options.remote,
+      forceWithLease: options.forceWithLease,
     });
-  }
 
-  getQueueSize(): number {
-    return this.taskQueue.length;
-  }
+    const pushedTask = this.updateTask(normalizedTaskId, (current) => ({
+      ...current,
+      remoteRef: {
+        remote: pushResult.remote,
+        ref: pushResult.remoteRef,
+        pushedAt: Date.now(),
+      },
+    }));
+    this.emit({
+      type: "task.pushed",
+      taskId: normalizedTaskId,
+      push: pushResult,
+      task: pushedTask,
+    });
 
-  getRunningCount(): number {
-    return this.runningTaskIds.size;
+    return pushResult;
   }
 
-  subscribe(listener: (event: TaskOrchestratorEvent) => void): () => void {
-    this.listeners.add(listener);
+  /**
+   * Rebases a task in review onto the latest default branch. A conflicting
+   * rebase is aborted and reported in the result rather than thrown.
+   */
+  async rebaseTask(taskId: string): Promise<RebaseTaskWorktreeResult> {
+    await this.ensureInitialized();
+
+    const normalizedTaskId = normalizeId(taskId, "Task id");
+    const task = this.getTaskOrThrow(normalizedTaskId);
+
+    if (task.state !== "review") {
+      throw new IkanbanError(
+        "invalid_state",
+        `Task ${normalizedTaskId} must be in review state to rebase its branch (current: ${task.state}).`,
+      );
+    }
+
+    if (!task.worktreeDirectory) {
+      throw new IkanbanError("invalid_state", `Task ${normalizedTaskId} is missing worktree directory.`);
+    }
+
+    const project = await this.resolveProject(task.projectId);
+    const rebase = await this.worktreeManager.rebaseTaskWorktree({
+      projectDirectory: project.rootDirectory,
+      taskId: normalizedTaskId,
+      worktreeDirectory: task.worktreeDirectory,
+    });
+
+    this.emit({ type: "task.rebased", taskId: normalizedTaskId, rebase, task });
+
+    return rebase;
+  }
+
+  /**
+   * Pushes the task branch and opens a GitHub pull request for it against the
+   * branch checked out in the project.
+   */
+  async openPullRequest(taskId: string, options: { remote?: string } = {}): Promise<TaskPullRequest> {
+    await this.ensureInitialized();
+
+    const normalizedTaskId = normalizeId(taskId, "Task id");
+    const task = this.getTaskOrThrow(normalizedTaskId);
+    if (!this.github) {
+      throw new IkanbanError("invalid_state", "GitHub integration is not configured.");
+    }
+
+    if (task.pullRequest?.state === "open") {
+      throw new IkanbanError(
+        "conflict",
+        `Task ${normalizedTaskId} already has pull request #${task.pullRequest.number} open.`,
+      );
+    }
+
+    const project = await this.resolveProject(task.projectId);
+    const pushResult = await this.pushTaskBranch(normalizedTaskId, { remote: options.remote });
+    const { repository, baseBranch } = await resolveGitHubTarget(project.rootDirectory, pushResult.remote);
+    const title = task.title ?? task.prompt?.split("\n")[0] ?? normalizedTaskId;
+    const created = await this.github.createPullRequest(repository, {
+      head: pushResult.branch,
+      base: baseBranch,
+      title: task.shortCode ? `${task.shortCode} ${title}` : title,
+      body: task.prompt,
+    });
+
+    const now = Date.now();
+    const pullRequest: TaskPullRequest = {
+      ...created,
+      repository: `${repository.owner}/${repository.repo}`,
+      openedAt: now,
+      checkedAt: now,
+    };
+    const updatedTask = this.updateTask(normalizedTaskId, (current) => ({ ...current, pullRequest }));
+    this.emit({ type: "task.pr.changed", taskId: normalizedTaskId, pullRequest, task: updatedTask });
+    return pullRequest;
+  }
+
+  /** Re-reads the state of open task pull requests from GitHub. */
+  async refreshPullRequests(): Promise<void> {
+    await this.ensureInitialized();
+    if (!this.github) {
+      return;
+    }
+
+    for (const task of [...this.tasksById.values()]) {
+      const current = task.pullRequest;
+      if (current?.state !== "open") {
+        continue;
+      }
+
+      const [owner = "", repo = ""] = current.repository.split("/");
+      let fetched: GitHubPullRequest;
+      try {
+        fetched = await this.github.getPullRequest({ owner, repo }, current.number);
+      } catch (error) {
+        // One missing or rate-limited pull request must not stop the others from being followed.
+        this.logger.log({
+          level: "warn",
+          source: "task-orchestrator.pull-request",
+          message: `Failed to refresh pull request #${current.number} of ${current.repository}.`,
+          context: { taskId: task.taskId, repository: current.repository, number: current.number },
+          error: toStructuredError(error),
+        });
+        continue;
+      }
+
+      const pullRequest: TaskPullRequest = { ...current, state: fetched.state, checkedAt: Date.now() };
+      const updatedTask = this.updateTask(task.taskId, (latest) => ({ ...latest, pullRequest }));
+      if (fetched.state !== current.state) {
+        this.emit({ type: "task.pr.changed", taskId: task.taskId, pullRequest, task: updatedTask });
+      }
+    }
+  }
+
+  /**
+   * Removes a finished task's worktree while keeping the task and its branch
+   * (unless merged). A worktree with uncommitted changes is refused.
+   */
+  async removeTaskWorktree(taskId: string): Promise<TaskRuntime> {
+    await this.ensureInitialized();
+
+    const normalizedTaskId = normalizeId(taskId, "Task id");
+    const task = this.getTaskOrThrow(normalizedTaskId);
+    if (!FINISHED_TASK_STATES.includes(task.state)) {
+      throw new IkanbanError(
+        "invalid_state",
+        `Task ${normalizedTaskId} is ${task.state}; only finished tasks can have their worktree removed.`,
+      );
+    }
+
+    if (!task.worktreeDirectory || task.worktreeRemovedAt !== undefined) {
+      throw new IkanbanError("invalid_state", `Task ${normalizedTaskId} has no worktree to remove.`);
+    }
+
+    const project = await this.resolveProject(task.projectId);
+    const cleanup = await this.worktreeManager.cleanupTaskWorktree({
+      projectDirectory: project.rootDirectory,
+      taskId: normalizedTaskId,
+      policy: "remove",
+      worktreeDirectory: task.worktreeDirectory,
+    });
+
+    // Checkpoints stay usable for as long as the worktree they roll back does.
+    let updatedTask = task;
+    if (cleanup.removed) {
+      await deleteWorktreeCheckpoints(project.rootDirectory, normalizedTaskId);
+      updatedTask = this.updateTask(normalizedTaskId, (current) => ({
+        ...current,
+        checkpoints: undefined,
+        worktreeRemovedAt: Date.now(),
+      }));
+      this.worktreeWatcher?.unwatch(normalizedTaskId);
+      this.checkpointChains.delete(normalizedTaskId);
+      this.lastCheckpointAt.delete(normalizedTaskId);
+    }
+
+    this.emit({ type: "task.cleanup.completed", taskId: normalizedTaskId, cleanup, task: updatedTask });
+    return updatedTask;
+  }
+
+  /**
+   * Garbage-collects worktrees of tasks that finished (by their last update)
+   * more than the retention period ago. Worktrees with uncommitted changes are
+   * skipped; others that cannot be removed are logged and kept. Returns the
+   * task ids cleaned.
+   */
+  async collectFinishedWorktrees(now: number = Date.now()): Promise<string[]> {
+    await this.ensureInitialized();
+    if (this.worktreeRetentionMs === undefined) {
+      return [];
+    }
+
+    const collected: string[] = [];
+    for (const task of [...this.tasksById.values()]) {
+      if (
+        !FINISHED_TASK_STATES.includes(task.state) ||
+        !task.worktreeDirectory ||
+        task.worktreeRemovedAt !== undefined ||
+        now - task.updatedAt < this.worktreeRetentionMs
+      ) {
+        continue;
+      }
+
+      // Uncommitted work is kept for good, so it is not retried and warned about every pass.
+      if (await this.worktreeManager.hasLocalChanges(task.worktreeDirectory)) {
+        continue;
+      }
+
+      try {
+        await this.removeTaskWorktree(task.taskId);
+        collected.push(task.taskId);
+      } catch (error) {
+        this.logger.log({
+          level: "warn",
+          source: "task-orchestrator.worktree-gc",
+          message: "Kept an expired task worktree.",
+          context: { taskId: task.taskId, worktreeDirectory: task.worktreeDirectory },
+          error: toStructuredError(error),
+        });
+      }
+    }
+
+    return collected;
+  }
+
+  /**
+   * The project's worktrees that no task points at, e.g. left behind by a task
+   * deleted while its worktree could not be removed.
+   */
+  async listOrphanedWorktrees(projectId?: string): Promise<string[]> {
+    await this.ensureInitialized();
+
+    const project = await this.resolveProject(projectId);
+    const claimed = new Set(
+      [...this.tasksById.values()]
+        .filter((task) => task.worktreeDirectory && task.worktreeRemovedAt === undefined)
+        .map((task) => resolve(task.worktreeDirectory!)),
+    );
+    const directories = await this.worktreeManager.listWorktrees(project.rootDirectory);
+    return directories.filter((directory) => !claimed.has(directory));
+  }
+
+  /** Removes orphaned worktrees; ones with uncommitted changes are kept and reported. */
+  async pruneOrphanedWorktrees(projectId?: string): Promise<{ removed: string[]; kept: string[] }> {
+    const project = await this.resolveProject(projectId);
+    const removed: string[] = [];
+    const kept: string[] = [];
+
+    for (const directory of await this.listOrphanedWorktrees(project.id)) {
+      try {
+        if (await this.worktreeManager.removeWorktree(project.rootDirectory, directory)) {
+          removed.push(directory);
+        } else {
+          kept.push(directory);
+        }
+      } catch (error) {
+        this.logger.log({
+          level: "warn",
+          source: "task-orchestrator.worktree-gc",
+          message: "Kept an orphaned worktree.",
+          context: { projectId: project.id, worktreeDirectory: directory },
+          error: toStructuredError(error),
+        });
+        kept.push(directory);
+      }
+    }
+
+    return { removed, kept };
+  }
+
+  async decideTaskReview(
+    taskId: string,
+    decision: TaskReviewDecision,
+    note?: string,
+  ): Promise<TaskRuntime> {
+    await this.ensureInitialized();
+
+    const normalizedTaskId = normalizeId(taskId, "Task id");
+    const task = this.getTaskOrThrow(normalizedTaskId);
+
+    if (task.state !== "review") {
+      throw new IkanbanError(
+        "invalid_state",
+        `Task ${normalizedTaskId} must be in review state to record a review decision (current: ${task.state}).`,
+      );
+    }
+
+    const decidedTask = this.updateTask(normalizedTaskId, (current) => ({
+      ...current,
+      review: {
+        ...(current.review ?? this.createReviewRequest()),
+        decision,
+        decidedAt: Date.now(),
+        note: normalizeOptionalId(note),
+      },
+    }));
+    this.emit({
+      type: "task.review.decided",
+      taskId: normalizedTaskId,
+      decision,
+      task: decidedTask,
+    });
+
+    return decidedTask;
+  }
+
+  async renameTask(taskId: string, title: string): Promise<TaskRuntime> {
+    await this.ensureInitialized();
+
+    const normalizedTaskId = normalizeId(taskId, "Task id");
+    assertValid(validateTitle(title, "Task title"));
+    const normalizedTitle = title.trim();
+    this.getTaskOrThrow(normalizedTaskId);
+
+    const renamedTask = this.updateTask(normalizedTaskId, (current) => ({
+      ...current,
+      title: normalizedTitle,
+    }));
+    this.emit({
+      type: "task.renamed",
+      taskId: normalizedTaskId,
+      task: renamedTask,
+    });
+
+    return renamedTask;
+  }
+
+  async setTaskEstimate(taskId: string, estimate?: number): Promise<TaskRuntime> {
+    await this.ensureInitialized();
+
+    const normalizedTaskId = normalizeId(taskId, "Task id");
+    this.getTaskOrThrow(normalizedTaskId);
+
+    if (estimate !== undefined && (!Number.isFinite(estimate) || estimate < 0)) {
+      throw new IkanbanError("validation", "Task estimate must be a non-negative number.", { field: "estimate" });
+    }
+
+    const estimatedTask = this.updateTask(normalizedTaskId, (current) => ({
+      ...current,
+      estimate,
+    }));
+    this.emit({
+      type: "task.estimate.changed",
+      taskId: normalizedTaskId,
+      estimate,
+      task: estimatedTask,
+    });
+
+    return estimatedTask;
+  }
+
+  /** Sets the model later follow-ups to the task's session use; undefined falls back to the server default. */
+  async setTaskModel(taskId: string, model?: TaskRuntime["model"]): Promise<TaskRuntime> {
+    await this.ensureInitialized();
+
+    const normalizedTaskId = normalizeId(taskId, "Task id");
+    this.getTaskOrThrow(normalizedTaskId);
+
+    const updatedTask = this.updateTask(normalizedTaskId, (current) => ({
+      ...current,
+      model,
+    }));
+    this.emit({
+      type: "task.model.changed",
+      taskId: normalizedTaskId,
+      model,
+      task: updatedTask,
+    });
+
+    return updatedTask;
+  }
+
+  /** Sets the task's own run time limit; a running task's watchdog picks it up on its next check. */
+  async setTaskTimeout(taskId: string, timeoutMs?: number): Promise<TaskRuntime> {
+    await this.ensureInitialized();
+
+    const normalizedTaskId = normalizeId(taskId, "Task id");
+    this.getTaskOrThrow(normalizedTaskId);
+
+    if (timeoutMs !== undefined && (!Number.isFinite(timeoutMs) || timeoutMs <= 0)) {
+      throw new IkanbanError("validation", "Task timeout must be a positive number.", { field: "timeoutMs" });
+    }
+
+    const updatedTask = this.updateTask(normalizedTaskId, (current) => ({
+      ...current,
+      timeoutMs,
+    }));
+    this.emit({
+      type: "task.timeout.changed",
+      taskId: normalizedTaskId,
+      timeoutMs,
+      task: updatedTask,
+    });
+
+    return updatedTask;
+  }
+
+  /** Makes a queued task wait until `blockerId` is completed before it starts. */
+  async addTaskDependency(taskId: string, blockerId: string): Promise<TaskRuntime> {
+    await this.ensureInitialized();
+
+    const normalizedTaskId = normalizeId(taskId, "Task id");
+    const normalizedBlockerId = normalizeId(blockerId, "Blocker id");
+    const task = this.getTaskOrThrow(normalizedTaskId);
+
+    if (task.state !== "queued") {
+      throw new IkanbanError(
+        "invalid_state",
+        `Only queued tasks can wait on other tasks (${task.shortCode ?? normalizedTaskId} is ${task.state}).`,
+      );
+    }
+
+    if (task.blockedBy?.includes(normalizedBlockerId)) {
+      return task;
+    }
+
+    this.assertValidBlocker(normalizedTaskId, task.projectId, normalizedBlockerId);
+    return this.setTaskDependencies(normalizedTaskId, [...(task.blockedBy ?? []), normalizedBlockerId]);
+  }
+
+  async removeTaskDependency(taskId: string, blockerId: string): Promise<TaskRuntime> {
+    await this.ensureInitialized();
+
+    const normalizedTaskId = normalizeId(taskId, "Task id");
+    const normalizedBlockerId = normalizeId(blockerId, "Blocker id");
+    const task = this.getTaskOrThrow(normalizedTaskId);
+
+    if (!task.blockedBy?.includes(normalizedBlockerId)) {
+      return task;
+    }
+
+    const updatedTask = this.setTaskDependencies(
+      normalizedTaskId,
+      task.blockedBy.filter((id) => id !== normalizedBlockerId),
+    );
+    this.schedule();
+
+    return updatedTask;
+  }
+
+  async assignTaskMilestone(taskId: string, milestoneId?: string): Promise<TaskRuntime> {
+    await this.ensureInitialized();
+
+    const normalizedTaskId = normalizeId(taskId, "Task id");
+    const normalizedMilestoneId = normalizeOptionalId(milestoneId);
+    this.getTaskOrThrow(normalizedTaskId);
+
+    const assignedTask = this.updateTask(normalizedTaskId, (current) => ({
+      ...current,
+      milestoneId: normalizedMilestoneId,
+    }));
+    this.emit({
+      type: "task.milestone.changed",
+      taskId: normalizedTaskId,
+      milestoneId: normalizedMilestoneId,
+      task: assignedTask,
+    });
+
+    return assignedTask;
+  }
+
+  async setTaskPriority(taskId: string, priority?: TaskPriority): Promise<TaskRuntime> {
+    await this.ensureInitialized();
+
+    const normalizedTaskId = normalizeId(taskId, "Task id");
+    this.getTaskOrThrow(normalizedTaskId);
+
+    const updatedTask = this.updateTask(normalizedTaskId, (current) => ({
+      ...current,
+      priority,
+    }));
+    this.emit({
+      type: "task.priority.changed",
+      taskId: normalizedTaskId,
+      priority,
+      task: updatedTask,
+    });
+    // Urgent tasks skip working hours, so a queued one may be able to start now.
+    this.schedule();
+
+    return updatedTask;
+  }
+
+  /**
+   * Moves `taskId` to its place in `orderedTaskIds`, the new order of the
+   * cards it is sorted among. Only the moved task gets a new rank when the
+   * others are already ranked in that order; otherwise the whole group is
+   * ranked afresh.
+   */
+  async reorderTask(taskId: string, orderedTaskIds: string[]): Promise<TaskRuntime> {
+    await this.ensureInitialized();
+
+    const normalizedTaskId = normalizeId(taskId, "Task id");
+    const group = orderedTaskIds.map((id) => this.getTaskOrThrow(normalizeId(id, "Task id")));
+    const index = group.findIndex((task) => task.taskId === normalizedTaskId);
+    if (index < 0) {
+      throw new IkanbanError("validation", `Task ${normalizedTaskId} is not in the new order.`, {
+        field: "orderedTaskIds",
+      });
+    }
+
+    if (group.some((task) => task.projectId !== group[index]!.projectId)) {
+      throw new IkanbanError("validation", "Only tasks of one project can be ordered together.", {
+        field: "orderedTaskIds",
+      });
+    }
+
+    const others = group.filter((task) => task.taskId !== normalizedTaskId);
+    const othersRanked = others.every(
+      (task, position) => task.rank !== undefined && (position === 0 || others[position - 1]!.rank! < task.rank),
+    );
+    const ranks: [string, string][] = othersRanked
+      ? [[normalizedTaskId, rankBetween(group[index - 1]?.rank, group[index + 1]?.rank)]]
+      : rankSequence(group.length).map((rank, position) => [group[position]!.taskId, rank]);
+    for (const [rankedTaskId, rank] of ranks) {
+      const updatedTask = this.updateTask(rankedTaskId, (current) => ({ ...current, rank }));
+      this.emit({
+        type: "task.rank.changed",
+        taskId: rankedTaskId,
+        rank,
+        task: updatedTask,
+      });
+    }
+
+    return this.getTaskOrThrow(normalizedTaskId);
+  }
+
+  /** Replaces the task's labels; label ids are owned by the label registry and not checked here. */
+  async setTaskLabels(taskId: string, labelIds: string[]): Promise<TaskRuntime> {
+    await this.ensureInitialized();
+
+    const normalizedTaskId = normalizeId(taskId, "Task id");
+    this.getTaskOrThrow(normalizedTaskId);
+    const normalizedLabelIds = [...new Set(labelIds.map((labelId) => normalizeId(labelId, "Label id")))];
+
+    const updatedTask = this.updateTask(normalizedTaskId, (current) => ({
+      ...current,
+      labelIds: normalizedLabelIds.length > 0 ? normalizedLabelIds : undefined,
+    }));
+    this.emit({
+      type: "task.labels.changed",
+      taskId: normalizedTaskId,
+      labelIds: normalizedLabelIds,
+      task: updatedTask,
+    });
+
+    return updatedTask;
+  }
+
+  /**
+   * Queued tasks of the project only start inside these hours unless they are
+   * urgent; undefined lifts the limit.
+   */
+  async setProjectWorkingHours(projectId: string, workingHours: WorkingHours | undefined): Promise<ProjectRef> {
+    await this.ensureInitialized();
+
+    const project = await this.projectRegistry.setProjectWorkingHours(normalizeId(projectId, "Project id"), workingHours);
+    if (project.workingHours) {
+      this.workingHoursByProjectId.set(project.id, project.workingHours);
+    } else {
+      this.workingHoursByProjectId.delete(project.id);
+    }
+    this.schedule();
+
+    return project;
+  }
+
+  /**
+   * Caps the project's running tasks, overriding the global per-project limit;
+   * undefined falls back to it.
+   */
+  async setProjectMaxConcurrentTasks(projectId: string, maxConcurrentTasks: number | undefined): Promise<ProjectRef> {
+    await this.ensureInitialized();
+
+    const project = await this.projectRegistry.setProjectMaxConcurrentTasks(
+      normalizeId(projectId, "Project id"),
+      maxConcurrentTasks === undefined ? undefined : normalizeMaxConcurrent(maxConcurrentTasks),
+    );
+    if (project.maxConcurrentTasks !== undefined) {
+      this.maxConcurrentByProjectId.set(project.id, project.maxConcurrentTasks);
+    } else {
+      this.maxConcurrentByProjectId.delete(project.id);
+    }
+    // A raised limit should start queued tasks right away.
+    this.schedule();
+
+    return project;
+  }
+
+  /** Appends a comment to the task's thread; `author` defaults to the configured reviewer. */
+  async addTaskComment(taskId: string, input: { body: string; author?: string }): Promise<TaskComment> {
+    await this.ensureInitialized();
+
+    const normalizedTaskId = normalizeId(taskId, "Task id");
+    this.getTaskOrThrow(normalizedTaskId);
+    return this.appendComment(normalizedTaskId, {
+      kind: "user",
+      author: normalizeOptionalId(input.author) ?? this.reviewer,
+      body: normalizeCommentBody(input.body),
+    });
+  }
+
+  async editTaskComment(taskId: string, commentId: number, body: string): Promise<TaskComment> {
+    await this.ensureInitialized();
+
+    const normalizedTaskId = normalizeId(taskId, "Task id");
+    const existing = this.getCommentOrThrow(normalizedTaskId, commentId);
+    const comment: TaskComment = { ...existing, body: normalizeCommentBody(body), updatedAt: Date.now() };
+    const updatedTask = this.updateTask(normalizedTaskId, (current) => ({
+      ...current,
+      comments: (current.comments ?? []).map((candidate) => (candidate.id === commentId ? comment : candidate)),
+    }));
+    this.emit({
+      type: "task.comment.updated",
+      taskId: normalizedTaskId,
+      comment,
+      task: updatedTask,
+    });
+
+    return comment;
+  }
+
+  async removeTaskComment(taskId: string, commentId: number): Promise<TaskComment> {
+    await this.ensureInitialized();
+
+    const normalizedTaskId = normalizeId(taskId, "Task id");
+    const comment = this.getCommentOrThrow(normalizedTaskId, commentId);
+    const updatedTask = this.updateTask(normalizedTaskId, (current) => {
+      const comments = (current.comments ?? []).filter((candidate) => candidate.id !== commentId);
+      return { ...current, comments: comments.length > 0 ? comments : undefined };
+    });
+    this.emit({
+      type: "task.comment.deleted",
+      taskId: normalizedTaskId,
+      comment,
+      task: updatedTask,
+    });
+
+    return comment;
+  }
+
+  async listTaskMessages(taskId: string): Promise<ConversationSdkSessionMessage[]> {
+    await this.ensureInitialized();
+
+    const normalizedTaskId = normalizeId(taskId, "Task id");
+    const task = this.getTaskOrThrow(normalizedTaskId);
+    if (!task.sessionID) {
+      throw new IkanbanError("invalid_state", `Task ${normalizedTaskId} has no session yet.`);
+    }
+
+    // Merged or cleaned tasks may have lost their worktree; the session still lives on the server.
+    const directory = task.worktreeDirectory ?? (await this.resolveProject(task.projectId)).rootDirectory;
+    const messages = await this.conversationManager.listConversationMessages({
+      sessionID: task.sessionID,
+      worktreeDirectory: directory,
+    });
+    return this.redactor ? this.redactor.redact(messages, task.projectId) : messages;
+  }
+
+  /** Tool uses agents are blocked on, oldest first. */
+  listPendingPermissions(): PermissionRequest[] {
+    return [...this.pendingPermissions.values()].sort((left, right) => left.requestedAt - right.requestedAt);
+  }
+
+  async replyToPermission(requestId: string, reply: PermissionReply): Promise<void> {
+    const request = this.pendingPermissions.get(requestId);
+    if (!request?.taskId) {
+      throw new IkanbanError("not_found", `Permission request not found or already answered: ${requestId}`);
+    }
+
+    const task = this.getTaskOrThrow(request.taskId);
+    await this.conversationManager.replyToPermission({
+      sessionID: request.sessionID,
+      requestID: request.id,
+      reply,
+      worktreeDirectory: task.worktreeDirectory,
+    });
+
+    this.pendingPermissions.delete(requestId);
+    this.noteRunOutput(task.taskId);
+    this.logger.log({
+      level: "info",
+      source: "task-orchestrator.permission",
+      message: `${task.shortCode ?? task.taskId}: ${reply === "reject" ? "rejected" : `allowed (${reply})`} ${request.permission}.`,
+      context: { taskId: task.taskId, requestId, reply, patterns: request.patterns },
+    });
+    this.emit({
+      type: "task.permission.replied",
+      taskId: task.taskId,
+      request,
+      reply,
+      task,
+    });
+  }
+
+  getTask(taskId: string): TaskRuntime | undefined {
+    const normalizedTaskId = normalizeId(taskId, "Task id");
+    return this.tasksById.get(normalizedTaskId);
+  }
+
+  listTasks(): TaskRuntime[] {
+    return [...this.tasksById.values()].sort((left, right) => {
+      if (left.createdAt !== right.createdAt) {
+        return left.createdAt - right.createdAt;
+      }
+
+      return left.taskId.localeCompare(right.taskId);
+    });
+  }
+
+  getQueueSize(): number {
+    return this.taskQueue.length;
+  }
+
+  getRunningCount(): number {
+    return this.runningTaskIds.size;
+  }
+
+  /**
+   * Re-scores merge-conflict risk for open task branches, one project at a
+   * time: overlap with the other open branches and with upstream changes.
+   */
+  async refreshConflictRisk(): Promise<void> {
+    await this.ensureInitialized();
+
+    const openTasksByProject = new Map<string, TaskRuntime[]>();
+    for (const task of this.tasksById.values()) {
+      if ((task.state !== "running" && task.state !== "review") || !task.worktreeDirectory) {
+        continue;
+      }
+
+      openTasksByProject.set(task.projectId, [...(openTasksByProject.get(task.projectId) ?? []), task]);
+    }
+
+    for (const [projectId, tasks] of openTasksByProject) {
+      const project = await this.projectRegistry.getProject(projectId);
+      if (!project) {
+        continue;
+      }
+
+      const changeSets = new Map<string, BranchChangeSet>();
+      for (const task of tasks) {
+        try {
+          changeSets.set(
+            task.taskId,
+            await collectBranchChanges({
+              taskRef: task.shortCode ?? task.taskId,
+              projectDirectory: project.rootDirectory,
+              worktreeDirectory: task.worktreeDirectory!,
+            }),
+          );
+        } catch (error) {
+          this.logger.log({
+            level: "debug",
+            source: "task-orchestrator.conflict-risk",
+            message: "Skipped conflict risk for task.",
+            context: { taskId: task.taskId },
+            error: toStructuredError(error),
+          });
+        }
+      }
+
+      const checkedAt = Date.now();
+      for (const [taskId, changes] of changeSets) {
+        const others = [...changeSets]
+          .filter(([otherTaskId]) => otherTaskId !== taskId)
+          .map(([, otherChanges]) => otherChanges);
+        const risk = scoreConflictRisk(changes, others, checkedAt);
+        const current = this.tasksById.get(taskId);
+        if (!current || (current.state !== "running" && current.state !== "review")) {
+          continue;
+        }
+
+        if (isSameConflictRisk(current.conflictRisk, risk)) {
+          continue;
+        }
+
+        const updatedTask = this.updateTask(taskId, (task) => ({ ...task, conflictRisk: risk }));
+        this.emit({ type: "task.risk.changed", taskId, risk, task: updatedTask });
+      }
+    }
+  }
+
+  getQueueSnapshot(): QueueSnapshot {
+    return {
+      queued: this.taskQueue
+        .map((entry) => this.tasksById.get(entry.input.taskId))
+        .filter((task): task is TaskRuntime => task !== undefined),
+      running: [...this.runningTaskIds]
+        .map((taskId) => this.tasksById.get(taskId))
+        .filter((task): task is TaskRuntime => task !== undefined),
+      maxConcurrent: this.maxConcurrent,
+      maxConcurrentPerProject: this.maxConcurrentPerProject,
+      maxConcurrentByProjectId: Object.fromEntries(this.maxConcurrentByProjectId),
+    };
+  }
+
+  /** Moves a queued task by `offset` places (negative is earlier) and returns its new 0-based position. */
+  moveQueuedTask(taskId: string, offset: number): number {
+    const normalizedTaskId = normalizeId(taskId, "Task id");
+    const index = this.taskQueue.findIndex((entry) => entry.input.taskId === normalizedTaskId);
+    if (index < 0) {
+      throw new IkanbanError("invalid_state", `Task ${normalizedTaskId} is not queued.`);
+    }
+
+    const nextIndex = Math.min(Math.max(index + offset, 0), this.taskQueue.length - 1);
+    const [entry] = this.taskQueue.splice(index, 1);
+    if (entry) {
+      this.taskQueue.splice(nextIndex, 0, entry);
+    }
+
+    return nextIndex;
+  }
+
+  subscribe(listener: (event: TaskOrchestratorEvent) => void): () => void {
+    this.listeners.add(listener);
+
+    return () => {
+      this.listeners.delete(listener);
+    };
+  }
+
+  // Takes the first queued task whose project is under its cap, so one busy project does not block the others.
+  private schedule(): void {
+    while (this.runningTaskIds.size < this.maxConcurrent) {
+      const nextIndex = this.taskQueue.findIndex(
+        (entry) =>
+          this.hasProjectCapacity(entry) &&
+          !this.isTaskBlocked(entry.input.taskId) &&
+          !this.isWaitingForWorkingHours(entry.input.taskId),
+      );
+      const [nextEntry] = nextIndex >= 0 ? this.taskQueue.splice(nextIndex, 1) : [];
+      if (!nextEntry) {
+        this.scheduleWorkingHoursWakeup();
+        return;
+      }
+
+      const taskId = nextEntry.input.taskId;
+      this.runningTaskIds.add(taskId);
+      void this.executeTask(nextEntry).finally(() => {
+        this.runningTaskIds.delete(taskId);
+        this.dropPendingPermissions(taskId);
+        this.schedule();
+      });
+    }
+  }
+
+  private setTaskDependencies(taskId: string, blockedBy: string[]): TaskRuntime {
+    const updatedTask = this.updateTask(taskId, (current) => ({
+      ...current,
+      blockedBy: blockedBy.length > 0 ? blockedBy : undefined,
+    }));
+    this.emit({
+      type: "task.dependencies.changed",
+      taskId,
+      blockedBy,
+      task: updatedTask,
+    });
+
+    return updatedTask;
+  }
+
+  private isWaitingForWorkingHours(taskId: string): boolean {
+    const task = this.tasksById.get(taskId);
+    return task !== undefined && isDeferredByWorkingHours(task, this.workingHoursByProjectId.get(task.projectId));
+  }
+
+  // Tasks held back by working hours get another scheduling pass when the earliest window opens.
+  private scheduleWorkingHoursWakeup(): void {
+    const now = new Date();
+    const waiting = this.taskQueue
+      .map((entry) => this.tasksById.get(entry.input.taskId))
+      .filter((task): task is TaskRuntime => task !== undefined && this.isWaitingForWorkingHours(task.taskId));
+    const wakeAt = waiting
+      .map((task) => nextWorkingHoursStart(this.workingHoursByProjectId.get(task.projectId)!, now)?.getTime())
+      .filter((at): at is number => at !== undefined)
+      .reduce<number | undefined>((earliest, at) => (earliest === undefined ? at : Math.min(earliest, at)), undefined);
+    if (wakeAt === this.workingHoursWakeAt) {
+      return;
+    }
+
+    clearTimeout(this.workingHoursTimer);
+    this.workingHoursTimer = undefined;
+    this.workingHoursWakeAt = wakeAt;
+    if (wakeAt === undefined) {
+      return;
+    }
+
+    this.logger.log({
+      level: "info",
+      source: "task-orchestrator.working-hours",
+      message: `${waiting.length} queued task(s) wait for working hours until ${new Date(wakeAt).toLocaleString()}.`,
+      context: { taskIds: waiting.map((task) => task.taskId), wakeAt },
+    });
+    const timer = setTimeout(() => {
+      this.workingHoursTimer = undefined;
+      this.workingHoursWakeAt = undefined;
+      this.schedule();
+    }, wakeAt - now.getTime());
+    timer.unref();
+    this.workingHoursTimer = timer;
+  }
+
+  private isTaskBlocked(taskId: string): boolean {
+    const task = this.tasksById.get(taskId);
+    return task !== undefined && listOpenBlockers(task, [...this.tasksById.values()]).length > 0;
+  }
+
+  private assertValidBlocker(taskId: string, projectId: string | undefined, blockerId: string): void {
+    const blocker = this.getTaskOrThrow(blockerId);
+    if (blockerId === taskId) {
+      throw new IkanbanError("validation", `Task ${taskId} cannot block itself.`, { field: "blockedBy" });
+    }
+
+    if (projectId && blocker.projectId !== projectId) {
+      throw new IkanbanError(
+        "validation",
+        `Task ${blocker.shortCode ?? blockerId} belongs to another project and cannot block ${taskId}.`,
+        { field: "blockedBy" },
+      );
+    }
 
-    return () => {
-      this.listeners.delete(listener);
-    };
+    if (wouldCreateDependencyCycle(taskId, blockerId, (id) => this.tasksById.get(id))) {
+      throw new IkanbanError(
+        "conflict",
+        `Blocking ${taskId} on ${blocker.shortCode ?? blockerId} would make the two tasks wait on each other.`,
+        { field: "blockedBy" },
+      );
+    }
   }
 
-  private schedule(): void {
-    while (this.runningTaskIds.size < this.maxConcurrent && this.taskQueue.length > 0) {
-      const nextEntry = this.taskQueue.shift();
-      if (!nextEntry) {
-        return;
-      }
+  private hasProjectCapacity(entry: QueueEntry): boolean {
+    const projectId = this.tasksById.get(entry.input.taskId)?.projectId;
+    const limit =
+      (projectId === undefined ? undefined : this.maxConcurrentByProjectId.get(projectId)) ?? this.maxConcurrentPerProject;
+    if (limit === undefined) {
+      return true;
+    }
 
-      const taskId = nextEntry.input.taskId;
-      this.runningTaskIds.add(taskId);
-      void this.executeTask(nextEntry).finally(() => {
-        this.runningTaskIds.delete(taskId);
-        this.schedule();
-      });
+    let running = 0;
+    for (const taskId of this.runningTaskIds) {
+      if (this.tasksById.get(taskId)?.projectId === projectId) {
+        running += 1;
+      }
     }
+
+    return running < limit;
   }
 
   private async ensureInitialized(): Promise<void> {
@@ -433,12 +2027,12 @@ export class TaskOrchestrator {
     }
 
     if (!this.initializationPromise) {
-      this.initializationPromise = this.loadPersistedTasks()
+      this.initializationPromise = Promise.all([this.loadPersistedTasks(), this.loadProjectSchedules()])
         .catch((error) => {
           this.logger.log({
             level: "error",
             source: "task-orchestrator.load",
-            message: "Failed to load persisted tasks.",
+            message: "Failed to load persisted tasks or project working hours.",
             error: toStructuredError(error),
           });
         })
@@ -459,6 +2053,18 @@ export class TaskOrchestrator {
     const persistedTasks = await this.taskRegistry.listTasks();
     for (const task of persistedTasks) {
       this.tasksById.set(task.taskId, task);
+      this.syncWorktreeWatch(task);
+    }
+  }
+
+  private async loadProjectSchedules(): Promise<void> {
+    for (const project of await this.projectRegistry.listProjects()) {
+      if (project.workingHours) {
+        this.workingHoursByProjectId.set(project.id, project.workingHours);
+      }
+      if (project.maxConcurrentTasks !== undefined) {
+        this.maxConcurrentByProjectId.set(project.id, project.maxConcurrentTasks);
+      }
     }
   }
 
@@ -474,12 +2080,26 @@ export class TaskOrchestrator {
     try {
       const resolvedProject = await this.resolveProject(entry.input.projectId);
       project = resolvedProject;
+      const shortCode = runtime.shortCode ?? (await this.nextTaskShortCode(resolvedProject.id));
       runtime = this.updateTask(taskId, (current) => ({
         ...current,
         projectId: resolvedProject.id,
+        shortCode: current.shortCode ?? shortCode,
       }));
       runtime = this.transitionTask(taskId, "creating_worktree");
 
+      const preflight = await runRepositoryPreflight({
+        directory: resolvedProject.rootDirectory,
+        checkRemote: this.preflightCheckRemote,
+      });
+      runtime = this.updateTask(taskId, (current) => ({
+        ...current,
+        preflight: preflight.ok ? undefined : preflight.checks,
+      }));
+      if (!preflight.ok) {
+        throw new RepositoryPreflightError(preflight);
+      }
+
       const createdWorktree = await this.worktreeManager.createTaskWorktree({
         projectDirectory: resolvedProject.rootDirectory,
         taskId,
@@ -497,12 +2117,31 @@ export class TaskOrchestrator {
         worktree: createdWorktree,
       });
 
+      const instructions = await ensureWorktreeAgentInstructions({
+        projectDirectory: resolvedProject.rootDirectory,
+        worktreeDirectory: createdWorktree.directory,
+      });
+      runtime = this.updateTask(taskId, (current) => ({
+        ...current,
+        agentInstructions: instructions.fileName,
+      }));
+      this.logger.log({
+        level: instructions.status === "missing" ? "warn" : "info",
+        source: "task-orchestrator.agent-instructions",
+        message:
+          instructions.status === "missing"
+            ? "No AGENTS.md or CLAUDE.md in the project; the agent runs without project instructions."
+            : `Agent instructions: ${instructions.fileName}${instructions.status === "copied" ? " (copied uncommitted file into worktree)" : ""}.`,
+        context: { taskId, projectId: resolvedProject.id },
+      });
+
+      this.assertNotCancelled(taskId);
       const createdSession = await this.conversationManager.createTaskSession({
         projectId: resolvedProject.id,
         taskId,
         projectDirectory: resolvedProject.rootDirectory,
         worktreeDirectory: createdWorktree.directory,
-        title: entry.input.title,
+        title: entry.input.title ?? summarizeSessionName(entry.input.initialPrompt),
         timestamp: entry.input.timestamp,
       });
       session = createdSession;
@@ -514,12 +2153,22 @@ export class TaskOrchestrator {
 
       runtime = this.transitionTaskWithPatch(taskId, "running", {
         sessionID: createdSession.sessionID,
+        sessionName: createdSession.title,
         model: entry.input.model,
+        runStartedAt: Date.now(),
+        runFinishedAt: undefined,
       });
+      this.startWatchdog(taskId);
 
+      const initialPrompt = await this.buildInitialPrompt(
+        taskId,
+        resolvedProject.id,
+        entry.input.initialPrompt,
+        createdWorktree.directory,
+      );
       const promptExecution = await this.conversationManager.sendInitialPromptAndAwaitMessages({
         sessionID: createdSession.sessionID,
-        prompt: entry.input.initialPrompt,
+        prompt: initialPrompt,
         worktreeDirectory: createdWorktree.directory,
         model: entry.input.model,
         onMessage: (message) => {
@@ -527,8 +2176,16 @@ export class TaskOrchestrator {
             type: "task.session.message.received",
             taskId,
             sessionID: createdSession.sessionID,
-            sdkMessage: message,
+            sdkMessage: this.redactMessage(resolvedProject.id, message),
           });
+          this.noteRunOutput(taskId);
+          this.checkpointAfterTools(taskId, createdWorktree.directory, message);
+        },
+        onPermission: (request) => {
+          this.addPendingPermission(taskId, request);
+        },
+        onPlanProgress: (progress) => {
+          this.updatePlanProgress(taskId, progress);
         },
       });
       promptSubmission = promptExecution.submission;
@@ -538,14 +2195,39 @@ export class TaskOrchestrator {
         prompt: promptSubmission,
       });
 
-      runtime = this.transitionTask(taskId, "review");
+      this.assertNotCancelled(taskId);
+      this.assertWatchdogNotFired(taskId);
+      await this.queueCheckpoint(taskId, createdWorktree.directory, "run finished", true);
+      await this.verifyPathScope(taskId, resolvedProject.rootDirectory, createdWorktree.directory);
+      await this.runLifecycleHooks("session.finished", this.getTaskOrThrow(taskId), {
+        projectDirectory: resolvedProject.rootDirectory,
+      });
+
+      runtime = this.transitionTaskWithPatch(taskId, "review", {
+        review: this.createReviewRequest(),
+        runFinishedAt: Date.now(),
+      });
       this.emit({
         type: "task.review",
         taskId,
         task: runtime,
       });
+      this.postRunSummary(taskId, resolvedProject.id, promptExecution.sdkMessages);
     } catch (error) {
-      const failureMessage = toErrorMessage(error);
+      if (this.getTaskOrThrow(taskId).state === "cancelled") {
+        entry.reject(
+          new TaskRunFailedError(`Task ${taskId} was cancelled.`, {
+            task: this.getTaskOrThrow(taskId),
+            project,
+            worktree,
+            session,
+          }),
+        );
+        return;
+      }
+
+      const failure = this.stopWatchdog(taskId) ?? error;
+      const failureMessage = toErrorMessage(failure);
       this.logger.log({
         level: "error",
         source: "task-orchestrator.execute",
@@ -555,9 +2237,9 @@ export class TaskOrchestrator {
           projectId: project?.id,
           state: runtime.state,
         },
-        error: toStructuredError(error),
+        error: toStructuredError(failure),
       });
-      runtime = this.transitionTaskToFailed(taskId, failureMessage);
+      runtime = this.transitionTaskToFailed(taskId, failureMessage, getErrorCode(failure));
 
       const projectDirectory = project?.rootDirectory;
       if (projectDirectory) {
@@ -600,6 +2282,114 @@ export class TaskOrchestrator {
       task: runtime,
     });
     entry.reject(new TaskRunFailedError(runtime.error ?? "Task failed.", failedResult));
+    this.scheduleAutomaticRetry(runtime);
+  }
+
+  private scheduleAutomaticRetry(task: TaskRuntime): void {
+    const attempt = task.retryAttempt ?? 0;
+    const failureClass = classifyTaskFailure(task.error, task.errorCode);
+    if (attempt >= this.retryMaxAttempts || !RETRYABLE_FAILURE_CLASSES.includes(failureClass)) {
+      return;
+    }
+
+    const delayMs = this.retryBackoffMs * 2 ** attempt;
+    this.logger.log({
+      level: "info",
+      source: "task-orchestrator.retry",
+      message: `Retrying ${task.shortCode ?? task.taskId} in ${Math.round(delayMs / 1000)}s (attempt ${attempt + 1}/${this.retryMaxAttempts}).`,
+      context: { taskId: task.taskId, failureClass, delayMs },
+    });
+
+    const timer = setTimeout(() => {
+      // Skip if the task was cancelled, deleted or already retried by hand in the meantime.
+      const current = this.tasksById.get(task.taskId);
+      const rootId = task.retryOf ?? task.taskId;
+      const retriedSince = [...this.tasksById.values()].some(
+        (candidate) => candidate.retryOf === rootId && (candidate.retryAttempt ?? 0) > attempt,
+      );
+      if (current?.state !== "failed" || retriedSince) {
+        return;
+      }
+
+      this.retryTask(task.taskId).catch((error) => {
+        this.logger.log({
+          level: "warn",
+          source: "task-orchestrator.retry",
+          message: `Automatic retry of ${task.shortCode ?? task.taskId} did not succeed.`,
+          context: { taskId: task.taskId },
+          error: toStructuredError(error),
+        });
+      });
+    }, delayMs);
+    timer.unref();
+  }
+
+  private async buildInitialPrompt(
+    taskId: string,
+    projectId: string,
+    prompt: string,
+    worktreeDirectory: string,
+  ): Promise<string> {
+    const { pathScope } = this.getTaskOrThrow(taskId);
+    const promptWithPages = appendPathScope(
+      await this.appendProjectPages(taskId, projectId, prompt),
+      pathScope,
+    );
+    if (!this.enrichPromptWithRepoContext) {
+      return promptWithPages;
+    }
+
+    try {
+      const promptContext = await gatherPromptContext({
+        directory: worktreeDirectory,
+        prompt,
+        pathScope,
+      });
+      this.updateTask(taskId, (current) => ({
+        ...current,
+        promptContext,
+      }));
+
+      return appendPromptContext(promptWithPages, promptContext);
+    } catch (error) {
+      this.logger.log({
+        level: "warn",
+        source: "task-orchestrator.prompt-context",
+        message: "Failed to gather repository context; sending the prompt without it.",
+        context: { taskId, worktreeDirectory },
+        error: toStructuredError(error),
+      });
+
+      return promptWithPages;
+    }
+  }
+
+  private async appendProjectPages(
+    taskId: string,
+    projectId: string,
+    prompt: string,
+  ): Promise<string> {
+    if (!this.pageRegistry) {
+      return prompt;
+    }
+
+    try {
+      const pages = (await this.pageRegistry.listPages(projectId)).filter(
+        (page) => page.includeInPrompts && page.body.trim().length > 0,
+      );
+
+      return pages.length > 0 ? `${prompt}\n\n${formatPagesForPrompt(pages)}` : prompt;
+    } catch (error) {
+      this.logger.log({
+        level: "warn",
+        source: "task-orchestrator.pages",
+        message: "Failed to load project pages; sending the prompt without them.",
+        context: { taskId, projectId },
+        error: toStructuredError(error),
+      });
+
+      return prompt;
+    }
   }
 
   private async executeCleanup(input: {
@@ -624,9 +2414,12 @@ export class TaskOrchestrator {
       });
 
       const finalState = taskBeforeCleaning.error ? "failed" : "completed";
-      const finalizedTask = this.transitionTask(input.taskId, finalState, {
-        error: taskBeforeCleaning.error,
-      });
+      const finalizedTask = this.transitionTaskWithPatch(
+        input.taskId,
+        finalState,
+        cleanup.removed ? { worktreeRemovedAt: Date.now() } : {},
+        { error: taskBeforeCleaning.error },
+      );
 
       this.emit({
         type: "task.cleanup.completed",
@@ -703,10 +2496,13 @@ export class TaskOrchestrator {
     const nextTask = {
       ...baseTask,
       taskId,
+      errorCode: to === "failed" ? baseTask.errorCode : undefined,
+      worktreeChangedAt: undefined,
     };
     assertTaskRuntimeInvariants(nextTask);
     this.tasksById.set(taskId, nextTask);
     this.persistTask(nextTask);
+    this.syncWorktreeWatch(nextTask);
     this.emit({
       type: "task.state.changed",
       task: nextTask,
@@ -714,19 +2510,365 @@ export class TaskOrchestrator {
       to,
     });
 
+    // A finished or cancelled blocker may free queued tasks that wait on it.
+    if (to === "completed" || to === "cancelled") {
+      this.schedule();
+    }
+
     return nextTask;
   }
 
-  private transitionTaskToFailed(taskId: string, error: string): TaskRuntime {
+  // Only review worktrees are watched: while running, edits come from the agent itself.
+  private syncWorktreeWatch(task: TaskRuntime): void {
+    if (!this.worktreeWatcher) {
+      return;
+    }
+
+    if (task.state === "review" && task.worktreeDirectory) {
+      this.worktreeWatcher.watch(task.taskId, task.worktreeDirectory, (change) => {
+        this.handleWorktreeChanged(change);
+      });
+      return;
+    }
+
+    this.worktreeWatcher.unwatch(task.taskId);
+  }
+
+  private handleWorktreeChanged(change: WorktreeChange): void {
+    const task = this.tasksById.get(change.taskId);
+    if (!task || task.state !== "review") {
+      return;
+    }
+
+    const changedTask = this.updateTask(change.taskId, (current) => ({
+      ...current,
+      worktreeChangedAt: change.at,
+    }));
+    this.emit({
+      type: "task.worktree.changed",
+      taskId: change.taskId,
+      paths: change.paths,
+      task: changedTask,
+    });
+  }
+
+  /** Fails the run when scope enforcement is on and the agent changed files outside the task's path scope. */
+  private async verifyPathScope(taskId: string, projectDirectory: string, worktreeDirectory: string): Promise<void> {
+    const task = this.getTaskOrThrow(taskId);
+    if (!this.enforcePathScope || !task.pathScope) {
+      return;
+    }
+
+    const changes = await collectBranchChanges({
+      taskRef: task.shortCode ?? taskId,
+      projectDirectory,
+      worktreeDirectory,
+    });
+    const outsideFiles = changes.changedFiles.filter((file) => !isPathInScope(file, task.pathScope!));
+    if (outsideFiles.length === 0) {
+      return;
+    }
+
+    const listed = outsideFiles.slice(0, 5).join(", ");
+    const more = outsideFiles.length > 5 ? ` (+${outsideFiles.length - 5} more)` : "";
+    throw new IkanbanError(
+      "scope_violation",
+      `Task ${task.shortCode ?? taskId} changed files outside ${task.pathScope}/: ${listed}${more}.`,
+    );
+  }
+
+  // Cancelling only flips the state; in-flight work checks this at its next step and stops.
+  private assertNotCancelled(taskId: string): void {
+    if (this.getTaskOrThrow(taskId).state === "cancelled") {
+      throw new IkanbanError("invalid_state", `Task ${taskId} was cancelled.`);
+    }
+  }
+
+  private startWatchdog(taskId: string): void {
+    this.stopWatchdog(taskId);
+    const timer = setInterval(() => {
+      void this.checkWatchdog(taskId);
+    }, WATCHDOG_INTERVAL_MS);
+    timer.unref();
+    this.watchdogs.set(taskId, {
+      timer,
+      lastOutputAt: Date.now(),
+      runTimeoutMs: this.runTimeoutMs,
+      stallTimeoutMs: this.stallTimeoutMs,
+    });
+  }
+
+  private getCommentOrThrow(taskId: string, commentId: number): TaskComment {
+    const comment = this.getTaskOrThrow(taskId).comments?.find((candidate) => candidate.id === commentId);
+    if (!comment) {
+      throw new IkanbanError("not_found", `Task ${taskId} has no comment #${commentId}.`, { field: "commentId" });
+    }
+
+    return comment;
+  }
+
+  private appendComment(taskId: string, input: Omit<TaskComment, "id" | "createdAt">): TaskComment {
+    const comment: TaskComment = {
+      ...input,
+      id: nextTaskCommentId(this.getTaskOrThrow(taskId).comments),
+      createdAt: Date.now(),
+    };
+    const updatedTask = this.updateTask(taskId, (current) => ({
+      ...current,
+      comments: [...(current.comments ?? []), comment],
+    }));
+    this.emit({
+      type: "task.comment.created",
+      taskId,
+      comment,
+      task: updatedTask,
+    });
+
+    return comment;
+  }
+
+  // The agent's last message becomes a comment, so the thread records what each run did.
+  private postRunSummary(taskId: string, projectId: string, messages: ConversationSdkSessionMessage[]): void {
+    const summary = summarizeRunForComment(messages.map((message) => this.redactMessage(projectId, message)));
+    if (summary) {
+      this.appendComment(taskId, { kind: "agent", author: "agent", body: summary });
+    }
+  }
+
+  // Agent output is redacted before any listener sees it; checkpoints still read the raw message.
+  private redactMessage(
+    projectId: string,
+    message: ConversationSdkSessionMessage,
+  ): ConversationSdkSessionMessage {
+    return this.redactor ? this.redactor.redact(message, projectId) : message;
+  }
+
+  private updatePlanProgress(taskId: string, progress: TaskPlanProgress): void {
+    const current = this.tasksById.get(taskId)?.planProgress;
+    if (current?.completed === progress.completed && current.total === progress.total) {
+      return;
+    }
+
+    const updatedTask = this.updateTask(taskId, (task) => ({ ...task, planProgress: progress }));
+    this.emit({
+      type: "task.progress.changed",
+      taskId,
+      progress,
+      task: updatedTask,
+    });
+  }
+
+  private addPendingPermission(taskId: string, request: PermissionRequest): void {
+    const pending = { ...request, taskId };
+    this.pendingPermissions.set(request.id, pending);
+    this.emit({
+      type: "task.permission.requested",
+      taskId,
+      request: pending,
+      task: this.getTaskOrThrow(taskId),
+    });
+  }
+
+  // Requests die with the run that raised them; the session no longer waits on a reply.
+  private dropPendingPermissions(taskId: string): void {
+    for (const [requestId, request] of this.pendingPermissions) {
+      if (request.taskId === taskId) {
+        this.pendingPermissions.delete(requestId);
+      }
+    }
+  }
+
+  private hasPendingPermission(taskId: string): boolean {
+    return [...this.pendingPermissions.values()].some((request) => request.taskId === taskId);
+  }
+
+  private noteRunOutput(taskId: string): void {
+    const watchdog = this.watchdogs.get(taskId);
+    if (watchdog) {
+      watchdog.lastOutputAt = Date.now();
+    }
+  }
+
+  /** Stops the task's watchdog and returns the failure it recorded, if it fired. */
+  private stopWatchdog(taskId: string): IkanbanError | undefined {
+    const watchdog = this.watchdogs.get(taskId);
+    if (watchdog) {
+      clearInterval(watchdog.timer);
+      this.watchdogs.delete(taskId);
+    }
+
+    const failure = this.watchdogFailures.get(taskId);
+    this.watchdogFailures.delete(taskId);
+    return failure;
+  }
+
+  private assertWatchdogNotFired(taskId: string): void {
+    const failure = this.stopWatchdog(taskId);
+    if (failure) {
+      throw failure;
+    }
+  }
+
+  // Records the failure and aborts the session; the awaiting run then fails with the recorded error.
+  private async checkWatchdog(taskId: string): Promise<void> {
+    const watchdog = this.watchdogs.get(taskId);
+    const task = this.tasksById.get(taskId);
+    if (!watchdog || !task || task.state !== "running" || this.watchdogFailures.has(taskId)) {
+      return;
+    }
+
+    const verdict = checkTaskWatchdog({
+      runStartedAt: task.runStartedAt ?? watchdog.lastOutputAt,
+      lastOutputAt: watchdog.lastOutputAt,
+      timeoutMs: task.timeoutMs ?? watchdog.runTimeoutMs,
+      // Waiting on the user for a permission is not a stall.
+      stallTimeoutMs: this.hasPendingPermission(taskId) ? undefined : watchdog.stallTimeoutMs,
+      now: Date.now(),
+    });
+    if (!verdict) {
+      return;
+    }
+
+    clearInterval(watchdog.timer);
+    const message = describeWatchdogFailure(verdict);
+    this.watchdogFailures.set(taskId, new IkanbanError("timeout", message));
+    this.logger.log({
+      level: "warn",
+      source: "task-orchestrator.watchdog",
+      message: `${task.shortCode ?? taskId}: ${message} Aborting the session.`,
+      context: { taskId, reason: verdict.reason, limitMs: verdict.limitMs },
+    });
+    this.emit({
+      type: "task.watchdog.fired",
+      taskId,
+      reason: verdict.reason,
+      limitMs: verdict.limitMs,
+      task,
+    });
+
+    if (!task.sessionID) {
+      return;
+    }
+
+    try {
+      await this.conversationManager.abortSession({
+        sessionID: task.sessionID,
+        worktreeDirectory: task.worktreeDirectory,
+      });
+    } catch (error) {
+      this.logger.log({
+        level: "warn",
+        source: "task-orchestrator.watchdog",
+        message: "Failed to abort session for timed-out task.",
+        context: { taskId, sessionID: task.sessionID },
+        error: toStructuredError(error),
+      });
+    }
+  }
+
+  private transitionTaskToFailed(taskId: string, failure: string, errorCode?: IkanbanErrorCode): TaskRuntime {
     const currentTask = this.getTaskOrThrow(taskId);
+    // Session errors can quote agent output; the message is persisted with the task.
+    const error = this.redactor ? this.redactor.redact(failure, currentTask.projectId) : failure;
     if (currentTask.state === "failed") {
       return this.updateTask(taskId, (task) => ({
         ...task,
         error,
+        errorCode,
+      }));
+    }
+
+    return this.transitionTaskWithPatch(taskId, "failed", { errorCode }, { error });
+  }
+
+  private checkpointAfterTools(
+    taskId: string,
+    worktreeDirectory: string,
+    message: ConversationSdkSessionMessage,
+  ): void {
+    const finishedTools = message.parts.filter(
+      (part) => part.type === "tool" && (part.state.status === "completed" || part.state.status === "error"),
+    );
+    if (finishedTools.length > 0) {
+      void this.queueCheckpoint(taskId, worktreeDirectory, `after ${finishedTools.length} tool call(s)`);
+    }
+  }
+
+  // Checkpoints for one task run one at a time so each links to the previous one.
+  private queueCheckpoint(taskId: string, worktreeDirectory: string, label: string, force = false): Promise<void> {
+    const now = Date.now();
+    if (!force && now - (this.lastCheckpointAt.get(taskId) ?? 0) < CHECKPOINT_MIN_INTERVAL_MS) {
+      return this.checkpointChains.get(taskId) ?? Promise.resolve();
+    }
+
+    this.lastCheckpointAt.set(taskId, now);
+    const chain = (this.checkpointChains.get(taskId) ?? Promise.resolve()).then(async () => {
+      const task = this.tasksById.get(taskId);
+      if (!task) {
+        return;
+      }
+
+      try {
+        const checkpoint = await createWorktreeCheckpoint({
+          taskId,
+          worktreeDirectory,
+          label,
+          previous: task.checkpoints?.at(-1),
+        });
+        if (!checkpoint || !this.tasksById.has(taskId)) {
+          return;
+        }
+
+        const updatedTask = this.updateTask(taskId, (current) => ({
+          ...current,
+          checkpoints: [...(current.checkpoints ?? []), checkpoint].slice(-MAX_CHECKPOINTS),
+        }));
+        this.emit({ type: "task.checkpoint.created", taskId, checkpoint, task: updatedTask });
+      } catch (error) {
+        this.logger.log({
+          level: "warn",
+          source: "task-orchestrator.checkpoint",
+          message: "Failed to checkpoint worktree.",
+          context: { taskId, label },
+          error: toStructuredError(error),
+        });
+      }
+    });
+    this.checkpointChains.set(taskId, chain);
+
+    return chain;
+  }
+
+  private async runLifecycleHooks(
+    point: LifecycleHookPoint,
+    task: TaskRuntime,
+    extra: Record<string, unknown> = {},
+  ): Promise<void> {
+    if (!this.lifecycleHooks) {
+      return;
+    }
+
+    const outcome = await this.lifecycleHooks.run(point, { task, ...extra }, {
+      cwd: task.worktreeDirectory,
+    });
+
+    if (outcome.annotations.length > 0 && this.tasksById.has(task.taskId)) {
+      const at = Date.now();
+      const annotations: TaskHookAnnotation[] = outcome.annotations.map((annotation) => ({
+        point,
+        hook: annotation.hook,
+        message: annotation.message,
+        at,
+      }));
+      this.updateTask(task.taskId, (current) => ({
+        ...current,
+        hookAnnotations: [...(current.hookAnnotations ?? []), ...annotations].slice(-MAX_HOOK_ANNOTATIONS),
       }));
     }
 
-    return this.transitionTask(taskId, "failed", { error });
+    if (!outcome.allowed) {
+      throw new LifecycleHookVetoError(point, outcome.vetoedBy ?? "hook", outcome.reason);
+    }
   }
 
   private updateTask(taskId: string, updater: (current: TaskRuntime) => TaskRuntime): TaskRuntime {
@@ -781,10 +2923,37 @@ export class TaskOrchestrator {
     });
   }
 
+  private createReviewRequest(): TaskReviewRecord {
+    return {
+      reviewer: this.reviewer,
+      requestedAt: Date.now(),
+    };
+  }
+
+  // The project's counter only goes up, so deleting the newest task never frees its code.
+  // Codes already on tasks set a floor for projects saved before the counter existed.
+  private async nextTaskShortCode(projectId: string): Promise<string> {
+    let highestSequence = 0;
+
+    for (const task of this.tasksById.values()) {
+      if (task.projectId !== projectId || !task.shortCode) {
+        continue;
+      }
+
+      const parsed = parseTaskShortCode(task.shortCode);
+      if (parsed && parsed.sequence > highestSequence) {
+        highestSequence = parsed.sequence;
+      }
+    }
+
+    const sequence = await this.projectRegistry.allocateTaskSequence(projectId, highestSequence);
+    return formatTaskShortCode(buildTaskShortCodePrefix(projectId), sequence);
+  }
+
   private getTaskOrThrow(taskId: string): TaskRuntime {
     const task = this.tasksById.get(taskId);
     if (!task) {
-      throw new Error(`Task not found: ${taskId}`);
+      throw new IkanbanError("not_found", `Task not found: ${taskId}`);
     }
 
     return task;
@@ -794,7 +2963,7 @@ export class TaskOrchestrator {
     if (projectId) {
       const project = await this.projectRegistry.getProject(projectId);
       if (!project) {
-        throw new Error(`Unknown project id: ${projectId}`);
+        throw new IkanbanError("not_found", `Unknown project id: ${projectId}`);
       }
 
       return project;
@@ -802,7 +2971,7 @@ export class TaskOrchestrator {
 
     const activeProject = await this.projectRegistry.getActiveProject();
     if (!activeProject) {
-      throw new Error("No active project is selected.");
+      throw new IkanbanError("not_found", "No active project is selected.");
     }
 
     return activeProject;
@@ -830,7 +2999,7 @@ export class TaskOrchestrator {
 function normalizeId(value: string, label: string): string {
   const normalized = value.trim();
   if (!normalized) {
-    throw new Error(`${label} is required.`);
+    throw new IkanbanError("validation", `${label} is required.`, { field: label });
   }
 
   return normalized;
@@ -845,18 +3014,29 @@ function normalizeOptionalId(value: string | undefined): string | undefined {
   return normalized.length > 0 ? normalized : undefined;
 }
 
-function normalizePrompt(prompt: string): string {
-  const normalizedPrompt = prompt.trim();
-  if (!normalizedPrompt) {
-    throw new Error("Initial prompt is required.");
+function normalizeCommentBody(body: string): string {
+  const normalized = body.trim();
+  if (!normalized) {
+    throw new IkanbanError("validation", "Comment must not be empty.", { field: "body" });
+  }
+
+  if (normalized.length > TASK_COMMENT_MAX_LENGTH) {
+    throw new IkanbanError("validation", `Comment must be at most ${TASK_COMMENT_MAX_LENGTH} characters.`, {
+      field: "body",
+    });
   }
 
-  return normalizedPrompt;
+  return normalized;
+}
+
+function normalizePrompt(prompt: string): string {
+  assertValid(validatePrompt(prompt));
+  return prompt.trim();
 }
 
 function normalizeTimestamp(value: number, label: string): number {
   if (!Number.isFinite(value) || value <= 0) {
-    throw new Error(`${label} must be a positive finite number.`);
+    throw new IkanbanError("validation", `${label} must be a positive finite number.`, { field: label });
   }
 
   return Math.floor(value);
@@ -868,7 +3048,7 @@ function normalizeMaxConcurrent(value: number | undefined): number {
   }
 
   if (!Number.isInteger(value) || value <= 0) {
-    throw new Error("maxConcurrent must be a positive integer.");
+    throw new IkanbanError("validation", "maxConcurrent must be a positive integer.", { field: "maxConcurrent" });
   }
 
   return value;
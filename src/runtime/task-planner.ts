@@ -0,0 +1,122 @@
+import { lastAssistantText } from "../domain/conversation";
+import type { ProjectRef } from "../domain/project";
+import type { TaskRuntime } from "../domain/task";
+import type { ConversationManager, SendInitialPromptInput } from "./conversation-manager";
+import { noopRuntimeLogger, type RuntimeLogger } from "./runtime-logger";
+
+type ConversationManagerLike = Pick<
+  ConversationManager,
+  "createTaskSession" | "sendInitialPromptAndAwaitMessages"
+>;
+
+export type TaskPlannerOptions = {
+  agent?: string;
+  maxItems?: number;
+  logger?: RuntimeLogger;
+};
+
+export type PlanTaskInput = {
+  project: ProjectRef;
+  task: TaskRuntime;
+  objective: string;
+  model?: SendInitialPromptInput["model"];
+};
+
+export type TaskPlan = {
+  parentTaskId: string;
+  sessionID: string;
+  items: string[];
+};
+
+const DEFAULT_PLANNING_AGENT = "plan";
+const DEFAULT_MAX_PLAN_ITEMS = 20;
+const PLAN_ITEM_PATTERN = /^\s*(?:[-*+]|\d+[.)])\s+(?:\[[ xX]\]\s+)?(.+)$/;
+
+export class TaskPlanner {
+  private readonly conversationManager: ConversationManagerLike;
+  private readonly agent: string;
+  private readonly maxItems: number;
+  private readonly logger: RuntimeLogger;
+
+  constructor(conversationManager: ConversationManagerLike, options: TaskPlannerOptions = {}) {
+    this.conversationManager = conversationManager;
+    this.agent = options.agent ?? DEFAULT_PLANNING_AGENT;
+    this.maxItems = options.maxItems ?? DEFAULT_MAX_PLAN_ITEMS;
+    this.logger = options.logger ?? noopRuntimeLogger;
+  }
+
+  async planTask(input: PlanTaskInput): Promise<TaskPlan> {
+    const objective = input.objective.trim();
+    if (!objective) {
+      throw new Error(`Task ${input.task.taskId} has no description to plan from.`);
+    }
+
+    // Planning runs in the project root with a read-only agent, so it never needs a worktree.
+    const session = await this.conversationManager.createTaskSession({
+      projectId: input.project.id,
+      taskId: `${input.task.taskId}-plan`,
+      projectDirectory: input.project.rootDirectory,
+      worktreeDirectory: input.project.rootDirectory,
+      title: `Plan ${input.task.shortCode ?? input.task.taskId}`,
+    });
+
+    const execution = await this.conversationManager.sendInitialPromptAndAwaitMessages({
+      sessionID: session.sessionID,
+      prompt: buildPlanningPrompt(objective),
+      worktreeDirectory: input.project.rootDirectory,
+      agent: this.agent,
+      model: input.model,
+    });
+
+    const items = parsePlanItems(lastAssistantText(execution.sdkMessages)).slice(0, this.maxItems);
+
+    this.logger.log({
+      level: "info",
+      source: "task-planner.plan",
+      message: `Planned ${items.length} subtasks for task ${input.task.taskId}.`,
+      context: {
+        taskId: input.task.taskId,
+        sessionID: session.sessionID,
+      },
+    });
+
+    if (items.length === 0) {
+      throw new Error(`Planning for task ${input.task.taskId} did not return a list of subtasks.`);
+    }
+
+    return {
+      parentTaskId: input.task.taskId,
+      sessionID: session.sessionID,
+      items,
+    };
+  }
+}
+
+export function buildPlanningPrompt(objective: string): string {
+  return [
+    "Break the following task into small, independently implementable subtasks.",
+    "Do not modify any files.",
+    "Reply with only a Markdown bullet list, one subtask per line, without extra commentary.",
+    "",
+    "Task:",
+    objective,
+  ].join("\n");
+}
+
+export function parsePlanItems(text: string): string[] {
+  const seen = new Set<string>();
+  const items: string[] = [];
+
+  for (const line of text.split(/\r?\n/)) {
+    const match = PLAN_ITEM_PATTERN.exec(line);
+    const item = match?.[1]?.replace(/\*\*/g, "").trim();
+    if (!item || seen.has(item.toLowerCase())) {
+      continue;
+    }
+
+    seen.add(item.toLowerCase());
+    items.push(item);
+  }
+
+  return items;
+}
@@ -1,7 +1,25 @@
 import { mkdir } from "node:fs/promises";
 import { dirname } from "node:path";
 
-import { assertTaskRuntimeInvariants, type TaskRuntime } from "../domain/task";
+import type { TaskConflictRisk } from "../domain/conflict-risk";
+import { isIkanbanErrorCode } from "../domain/errors";
+import { isTaskCommentKind, type TaskComment } from "../domain/task-comment";
+import type { TaskPlanProgress } from "../domain/task-progress";
+import {
+  assertTaskRuntimeInvariants,
+  isTaskPriority,
+  MERGE_STRATEGIES,
+  PULL_REQUEST_STATES,
+  type MergeStrategy,
+  type TaskCheckpoint,
+  type TaskHookAnnotation,
+  type TaskMergeRecord,
+  type TaskPreflightCheck,
+  type TaskPullRequest,
+  type TaskRemoteRef,
+  type TaskReviewRecord,
+  type TaskRuntime,
+} from "../domain/task";
 
 const TASK_REGISTRY_STATE_VERSION = 1;
 
@@ -116,10 +134,47 @@ export class TaskRegistry {
         taskId: String(taskLike.taskId),
         projectId: String(taskLike.projectId),
         state: String(taskLike.state) as TaskRuntime["state"],
+        title: typeof taskLike.title === "string" ? taskLike.title : undefined,
+        shortCode: typeof taskLike.shortCode === "string" ? taskLike.shortCode : undefined,
+        prompt: typeof taskLike.prompt === "string" ? taskLike.prompt : undefined,
+        parentTaskId: typeof taskLike.parentTaskId === "string" ? taskLike.parentTaskId : undefined,
+        blockedBy: Array.isArray(taskLike.blockedBy) ? toStringArray(taskLike.blockedBy) : undefined,
+        retryOf: typeof taskLike.retryOf === "string" ? taskLike.retryOf : undefined,
+        retryAttempt: typeof taskLike.retryAttempt === "number" ? taskLike.retryAttempt : undefined,
+        milestoneId: typeof taskLike.milestoneId === "string" ? taskLike.milestoneId : undefined,
+        labelIds: Array.isArray(taskLike.labelIds) ? toStringArray(taskLike.labelIds) : undefined,
+        priority: isTaskPriority(taskLike.priority) ? taskLike.priority : undefined,
+        rank: typeof taskLike.rank === "string" ? taskLike.rank : undefined,
+        estimate: typeof taskLike.estimate === "number" ? taskLike.estimate : undefined,
+        timeoutMs: typeof taskLike.timeoutMs === "number" ? taskLike.timeoutMs : undefined,
+        pathScope: typeof taskLike.pathScope === "string" ? taskLike.pathScope : undefined,
+        promptContext: parsePromptContext(taskLike.promptContext),
         worktreeDirectory:
           typeof taskLike.worktreeDirectory === "string" ? taskLike.worktreeDirectory : undefined,
         sessionID: typeof taskLike.sessionID === "string" ? taskLike.sessionID : undefined,
+        sessionName: typeof taskLike.sessionName === "string" ? taskLike.sessionName : undefined,
+        agentInstructions:
+          typeof taskLike.agentInstructions === "string" ? taskLike.agentInstructions : undefined,
+        runStartedAt: typeof taskLike.runStartedAt === "number" ? taskLike.runStartedAt : undefined,
+        runFinishedAt: typeof taskLike.runFinishedAt === "number" ? taskLike.runFinishedAt : undefined,
+        review: parseReviewRecord(taskLike.review),
+        remoteRef: parseRemoteRef(taskLike.remoteRef),
+        merge: parseMergeRecord(taskLike.merge),
+        pullRequest: parsePullRequest(taskLike.pullRequest),
+        preflight: parsePreflightChecks(taskLike.preflight),
+        hookAnnotations: parseHookAnnotations(taskLike.hookAnnotations),
+        checkpoints: parseCheckpoints(taskLike.checkpoints),
+        comments: parseComments(taskLike.comments),
+        planProgress: parsePlanProgress(taskLike.planProgress),
+        worktreeChangedAt:
+          typeof taskLike.worktreeChangedAt === "number" ? taskLike.worktreeChangedAt : undefined,
+        worktreeRemovedAt:
+          typeof taskLike.worktreeRemovedAt === "number" ? taskLike.worktreeRemovedAt : undefined,
+        conflictRisk: parseConflictRisk(taskLike.conflictRisk),
         error: typeof taskLike.error === "string" ? taskLike.error : undefined,
+        errorCode: isIkanbanErrorCode(taskLike.errorCode) ? taskLike.errorCode : undefined,
+        cancelReason: typeof taskLike.cancelReason === "string" ? taskLike.cancelReason : undefined,
+        cancelledAt: typeof taskLike.cancelledAt === "number" ? taskLike.cancelledAt : undefined,
         model:
           typeof taskLike.model === "object" &&
           taskLike.model !== null &&
@@ -161,3 +216,256 @@ export class TaskRegistry {
     await Bun.write(this.options.stateFilePath, `${JSON.stringify(state, null, 2)}\n`);
   }
 }
+
+function parseReviewRecord(value: unknown): TaskReviewRecord | undefined {
+  if (!value || typeof value !== "object") {
+    return undefined;
+  }
+
+  const record = value as Record<string, unknown>;
+  if (typeof record.reviewer !== "string" || typeof record.requestedAt !== "number") {
+    return undefined;
+  }
+
+  return {
+    reviewer: record.reviewer,
+    requestedAt: record.requestedAt,
+    decision:
+      record.decision === "approved" || record.decision === "changes_requested"
+        ? record.decision
+        : undefined,
+    decidedAt: typeof record.decidedAt === "number" ? record.decidedAt : undefined,
+    note: typeof record.note === "string" ? record.note : undefined,
+  };
+}
+
+function parseMergeRecord(value: unknown): TaskMergeRecord | undefined {
+  if (!value || typeof value !== "object") {
+    return undefined;
+  }
+
+  const record = value as Record<string, unknown>;
+  if (
+    !MERGE_STRATEGIES.includes(record.strategy as MergeStrategy) ||
+    typeof record.branch !== "string" ||
+    typeof record.targetBranch !== "string" ||
+    typeof record.commit !== "string" ||
+    typeof record.mergedAt !== "number"
+  ) {
+    return undefined;
+  }
+
+  return {
+    strategy: record.strategy as MergeStrategy,
+    branch: record.branch,
+    targetBranch: record.targetBranch,
+    commit: record.commit,
+    mergedAt: record.mergedAt,
+  };
+}
+
+function parsePullRequest(value: unknown): TaskPullRequest | undefined {
+  if (!value || typeof value !== "object") {
+    return undefined;
+  }
+
+  const record = value as Record<string, unknown>;
+  if (
+    typeof record.repository !== "string" ||
+    typeof record.number !== "number" ||
+    typeof record.url !== "string" ||
+    !PULL_REQUEST_STATES.includes(record.state as TaskPullRequest["state"]) ||
+    typeof record.openedAt !== "number" ||
+    typeof record.checkedAt !== "number"
+  ) {
+    return undefined;
+  }
+
+  return {
+    repository: record.repository,
+    number: record.number,
+    url: record.url,
+    state: record.state as TaskPullRequest["state"],
+    openedAt: record.openedAt,
+    checkedAt: record.checkedAt,
+  };
+}
+
+function parseRemoteRef(value: unknown): TaskRemoteRef | undefined {
+  if (!value || typeof value !== "object") {
+    return undefined;
+  }
+
+  const record = value as Record<string, unknown>;
+  if (
+    typeof record.remote !== "string" ||
+    typeof record.ref !== "string" ||
+    typeof record.pushedAt !== "number"
+  ) {
+    return undefined;
+  }
+
+  return {
+    remote: record.remote,
+    ref: record.ref,
+    pushedAt: record.pushedAt,
+  };
+}
+
+function parseConflictRisk(value: unknown): TaskConflictRisk | undefined {
+  if (!value || typeof value !== "object") {
+    return undefined;
+  }
+
+  const record = value as Record<string, unknown>;
+  if (
+    typeof record.score !== "number" ||
+    (record.level !== "low" && record.level !== "medium" && record.level !== "high") ||
+    typeof record.checkedAt !== "number"
+  ) {
+    return undefined;
+  }
+
+  return {
+    score: record.score,
+    level: record.level,
+    changedFileCount: typeof record.changedFileCount === "number" ? record.changedFileCount : 0,
+    overlappingTasks: Array.isArray(record.overlappingTasks) ? record.overlappingTasks.map(String) : [],
+    upstreamFiles: Array.isArray(record.upstreamFiles) ? record.upstreamFiles.map(String) : [],
+    checkedAt: record.checkedAt,
+  };
+}
+
+function parsePreflightChecks(value: unknown): TaskPreflightCheck[] | undefined {
+  if (!Array.isArray(value)) {
+    return undefined;
+  }
+
+  return value.flatMap((entry): TaskPreflightCheck[] => {
+    if (!entry || typeof entry !== "object") {
+      return [];
+    }
+
+    const record = entry as Record<string, unknown>;
+    if (
+      typeof record.id !== "string" ||
+      typeof record.message !== "string" ||
+      (record.status !== "pass" && record.status !== "fail" && record.status !== "skip")
+    ) {
+      return [];
+    }
+
+    return [{ id: record.id, status: record.status, message: record.message }];
+  });
+}
+
+function parseHookAnnotations(value: unknown): TaskHookAnnotation[] | undefined {
+  if (!Array.isArray(value)) {
+    return undefined;
+  }
+
+  return value.flatMap((entry): TaskHookAnnotation[] => {
+    if (!entry || typeof entry !== "object") {
+      return [];
+    }
+
+    const record = entry as Record<string, unknown>;
+    if (
+      typeof record.point !== "string" ||
+      typeof record.hook !== "string" ||
+      typeof record.message !== "string" ||
+      typeof record.at !== "number"
+    ) {
+      return [];
+    }
+
+    return [{ point: record.point, hook: record.hook, message: record.message, at: record.at }];
+  });
+}
+
+function parsePlanProgress(value: unknown): TaskPlanProgress | undefined {
+  if (!value || typeof value !== "object") {
+    return undefined;
+  }
+
+  const record = value as Record<string, unknown>;
+  if (typeof record.completed !== "number" || typeof record.total !== "number") {
+    return undefined;
+  }
+
+  return { completed: record.completed, total: record.total };
+}
+
+function parseCheckpoints(value: unknown): TaskCheckpoint[] | undefined {
+  if (!Array.isArray(value)) {
+    return undefined;
+  }
+
+  return value.flatMap((entry): TaskCheckpoint[] => {
+    if (!entry || typeof entry !== "object") {
+      return [];
+    }
+
+    const record = entry as Record<string, unknown>;
+    if (
+      typeof record.commit !== "string" ||
+      typeof record.tree !== "string" ||
+      typeof record.label !== "string" ||
+      typeof record.createdAt !== "number"
+    ) {
+      return [];
+    }
+
+    return [{ commit: record.commit, tree: record.tree, label: record.label, createdAt: record.createdAt }];
+  });
+}
+
+function parseComments(value: unknown): TaskComment[] | undefined {
+  if (!Array.isArray(value)) {
+    return undefined;
+  }
+
+  return value.flatMap((entry): TaskComment[] => {
+    if (!entry || typeof entry !== "object") {
+      return [];
+    }
+
+    const record = entry as Record<string, unknown>;
+    if (
+      typeof record.id !== "number" ||
+      !isTaskCommentKind(record.kind) ||
+      typeof record.author !== "string" ||
+      typeof record.body !== "string" ||
+      typeof record.createdAt !== "number"
+    ) {
+      return [];
+    }
+
+    return [
+      {
+        id: record.id,
+        kind: record.kind,
+        author: record.author,
+        body: record.body,
+        createdAt: record.createdAt,
+        updatedAt: typeof record.updatedAt === "number" ? record.updatedAt : undefined,
+      },
+    ];
+  });
+}
+
+function parsePromptContext(value: unknown): TaskRuntime["promptContext"] {
+  if (!value || typeof value !== "object") {
+    return undefined;
+  }
+
+  const record = value as Record<string, unknown>;
+  return {
+    treeSummary: toStringArray(record.treeSummary),
+    relevantFiles: toStringArray(record.relevantFiles),
+  };
+}
+
+function toStringArray(value: unknown): string[] {
+  return Array.isArray(value) ? value.filter((entry): entry is string => typeof entry === "string") : [];
+}
@@ -0,0 +1,54 @@
+export const TERMINAL_NOTIFICATION_MODES = ["off", "bell", "osc9", "osc777"] as const;
+
+export type TerminalNotificationMode = (typeof TERMINAL_NOTIFICATION_MODES)[number];
+
+type NotificationStream = {
+  write(chunk: string): boolean;
+};
+
+export function isTerminalNotificationMode(value: string): value is TerminalNotificationMode {
+  return TERMINAL_NOTIFICATION_MODES.includes(value as TerminalNotificationMode);
+}
+
+export function formatTerminalNotification(
+  mode: TerminalNotificationMode,
+  title: string,
+  body: string,
+  env: Record<string, string | undefined> = process.env,
+): string {
+  switch (mode) {
+    case "off":
+      return "";
+    case "bell":
+      return "\x07";
+    case "osc9":
+      return wrapForTmux(`\x1b]9;${sanitize(`${title}: ${body}`)}\x07`, env);
+    case "osc777":
+      return wrapForTmux(`\x1b]777;notify;${sanitize(title)};${sanitize(body)}\x07`, env);
+  }
+}
+
+export function emitTerminalNotification(
+  mode: TerminalNotificationMode,
+  title: string,
+  body: string,
+  stream: NotificationStream = process.stdout,
+): void {
+  const sequence = formatTerminalNotification(mode, title, body);
+  if (sequence) {
+    stream.write(sequence);
+  }
+}
+
+// tmux swallows OSC sequences unless they are wrapped in a DCS passthrough.
+function wrapForTmux(sequence: string, env: Record<string, string | undefined>): string {
+  if (!env.TMUX) {
+    return sequence;
+  }
+
+  return `\x1bPtmux;${sequence.replace(/\x1b/g, "\x1b\x1b")}\x1b\\`;
+}
+
+function sanitize(value: string): string {
+  return value.replace(/[\x00-\x1f\x7f;]/g, " ").slice(0, 200);
+}
@@ -0,0 +1,242 @@
+import { mkdir, writeFile } from "node:fs/promises";
+import { join } from "node:path";
+
+import type { ConversationSdkPart, ConversationSdkSessionMessage } from "../domain/conversation";
+import type { TaskRuntime } from "../domain/task";
+
+export type TranscriptExportInput = {
+  task: TaskRuntime;
+  projectName?: string;
+  messages: ConversationSdkSessionMessage[];
+  exportedAt?: number;
+};
+
+export type TranscriptExportResult = {
+  filePath: string;
+  messageCount: number;
+};
+
+// Standard 8-colour palette plus the bright variants (SGR 30-37 / 90-97).
+const ANSI_COLORS = [
+  "#1e1e1e",
+  "#cd3131",
+  "#0dbc79",
+  "#e5e510",
+  "#2472c8",
+  "#bc3fbc",
+  "#11a8cd",
+  "#e5e5e5",
+] as const;
+const ANSI_BRIGHT_COLORS = [
+  "#666666",
+  "#f14c4c",
+  "#23d18b",
+  "#f5f543",
+  "#3b8eea",
+  "#d670d6",
+  "#29b8db",
+  "#ffffff",
+] as const;
+const ANSI_SEQUENCE_PATTERN = /\u001b\[([0-9;]*)([A-Za-z])/g;
+
+const TRANSCRIPT_STYLES = `
+body { font-family: ui-sans-serif, system-ui, sans-serif; background: #111418; color: #d8dee9; margin: 0 auto; max-width: 960px; padding: 24px; }
+header { border-bottom: 1px solid #2e3440; margin-bottom: 24px; }
+header h1 { font-size: 1.4em; margin: 0 0 8px; }
+header dl { display: grid; grid-template-columns: max-content 1fr; gap: 4px 16px; margin: 0 0 16px; }
+header dt { color: #81a1c1; }
+header dd { margin: 0; }
+.message { border-left: 3px solid #4c566a; margin: 0 0 20px; padding-left: 12px; }
+.message.user { border-color: #88c0d0; }
+.message.assistant { border-color: #a3be8c; }
+.role { color: #81a1c1; font-size: 0.85em; margin-bottom: 6px; }
+pre { background: #1b1f26; border-radius: 4px; margin: 6px 0; overflow-x: auto; padding: 8px; white-space: pre-wrap; word-break: break-word; }
+.reasoning pre { color: #9aa5b8; font-style: italic; }
+details.tool { background: #181c22; border: 1px solid #2e3440; border-radius: 4px; margin: 6px 0; padding: 4px 8px; }
+details.tool summary { cursor: pointer; }
+.status-completed { color: #a3be8c; }
+.status-error { color: #bf616a; }
+.status-running, .status-pending { color: #ebcb8b; }
+`;
+
+export async function exportTaskTranscriptHtml(
+  exportDirectory: string,
+  input: TranscriptExportInput,
+): Promise<TranscriptExportResult> {
+  const fileName = `${(input.task.shortCode ?? input.task.taskId).replace(/[^A-Za-z0-9_-]/g, "_")}.html`;
+  const filePath = join(exportDirectory, fileName);
+
+  await mkdir(exportDirectory, { recursive: true });
+  await writeFile(filePath, renderTranscriptHtml(input));
+
+  return { filePath, messageCount: input.messages.length };
+}
+
+/**
+ * Renders a standalone HTML page for a task session: a metadata header, one
+ * block per message, and tool calls folded into collapsible sections.
+ */
+export function renderTranscriptHtml(input: TranscriptExportInput): string {
+  const { task } = input;
+  const title = task.title ?? task.prompt?.split("\n")[0] ?? task.taskId;
+  const metadata: Array<[string, string | undefined]> = [
+    ["Task", task.shortCode ? `${task.shortCode} (${task.taskId})` : task.taskId],
+    ["Project", input.projectName ?? task.projectId],
+    ["State", task.state],
+    ["Model", task.model ? `${task.model.providerID}/${task.model.modelID}` : undefined],
+    ["Session", task.sessionName ? `${task.sessionName} (${task.sessionID ?? "-"})` : task.sessionID],
+    ["Created", formatTimestamp(task.createdAt)],
+    ["Updated", formatTimestamp(task.updatedAt)],
+    ["Exported", formatTimestamp(input.exportedAt ?? Date.now())],
+    ["Messages", String(input.messages.length)],
+  ];
+
+  const lines = [
+    "<!doctype html>",
+    '<html lang="en">',
+    "<head>",
+    '<meta charset="utf-8">',
+    `<title>${escapeHtml(title)}</title>`,
+    `<style>${TRANSCRIPT_STYLES}</style>`,
+    "</head>",
+    "<body>",
+    "<header>",
+    `<h1>${escapeHtml(title)}</h1>`,
+    "<dl>",
+    ...metadata
+      .filter((entry): entry is [string, string] => entry[1] !== undefined)
+      .map(([label, value]) => `<dt>${escapeHtml(label)}</dt><dd>${escapeHtml(value)}</dd>`),
+    "</dl>",
+    "</header>",
+    "<main>",
+    ...input.messages.map(renderMessage),
+    "</main>",
+    "</body>",
+    "</html>",
+    "",
+  ];
+
+  return lines.join("\n");
+}
+
+/**
+ * Converts SGR colour/bold sequences to inline-styled spans and drops any other
+ * escape sequences. The input is HTML-escaped along the way.
+ */
+export function ansiToHtml(text: string): string {
+  let html = "";
+  let openSpan = false;
+  let style: { color?: string; bold?: boolean; italic?: boolean; underline?: boolean } = {};
+  let lastIndex = 0;
+
+  const flushStyle = () => {
+    if (openSpan) {
+      html += "</span>";
+      openSpan = false;
+    }
+
+    const declarations = [
+      style.color ? `color:${style.color}` : undefined,
+      style.bold ? "font-weight:bold" : undefined,
+      style.italic ? "font-style:italic" : undefined,
+      style.underline ? "text-decoration:underline" : undefined,
+    ].filter(Boolean);
+    if (declarations.length > 0) {
+      html += `<span style="${declarations.join(";")}">`;
+      openSpan = true;
+    }
+  };
+
+  for (const match of text.matchAll(ANSI_SEQUENCE_PATTERN)) {
+    html += escapeHtml(text.slice(lastIndex, match.index));
+    lastIndex = match.index + match[0].length;
+
+    if (match[2] !== "m") {
+      continue;
+    }
+
+    const codes = (match[1] ?? "").split(";").map((code) => (code === "" ? 0 : Number(code)));
+    for (const code of codes) {
+      if (code === 0) {
+        style = {};
+      } else if (code === 1) {
+        style.bold = true;
+      } else if (code === 3) {
+        style.italic = true;
+      } else if (code === 4) {
+        style.underline = true;
+      } else if (code === 22) {
+        style.bold = false;
+      } else if (code === 23) {
+        style.italic = false;
+      } else if (code === 24) {
+        style.underline = false;
+      } else if (code >= 30 && code <= 37) {
+        style.color = ANSI_COLORS[code - 30];
+      } else if (code >= 90 && code <= 97) {
+        style.color = ANSI_BRIGHT_COLORS[code - 90];
+      } else if (code === 39) {
+        style.color = undefined;
+      }
+    }
+    flushStyle();
+  }
+
+  html += escapeHtml(text.slice(lastIndex));
+  if (openSpan) {
+    html += "</span>";
+  }
+
+  return html;
+}
+
+function renderMessage(message: ConversationSdkSessionMessage): string {
+  const role = message.info.role;
+  const parts = message.parts.map(renderPart).filter((part) => part.length > 0);
+
+  return [
+    `<section class="message ${escapeHtml(role)}">`,
+    `<div class="role">${escapeHtml(role)} · ${escapeHtml(message.info.id)}</div>`,
+    ...parts,
+    "</section>",
+  ].join("\n");
+}
+
+function renderPart(part: ConversationSdkPart): string {
+  switch (part.type) {
+    case "text":
+      return `<pre>${ansiToHtml(part.text)}</pre>`;
+    case "reasoning":
+      return `<div class="reasoning"><pre>${ansiToHtml(part.text)}</pre></div>`;
+    case "tool": {
+      const state = part.state;
+      const body = [`<pre>${escapeHtml(JSON.stringify(state.input ?? {}, null, 2))}</pre>`];
+      if (state.status === "completed") {
+        body.push(`<pre>${ansiToHtml(state.output)}</pre>`);
+      } else if (state.status === "error") {
+        body.push(`<pre class="status-error">${ansiToHtml(state.error)}</pre>`);
+      }
+
+      return [
+        '<details class="tool">',
+        `<summary>${escapeHtml(part.tool)} <span class="status-${state.status}">${state.status}</span></summary>`,
+        ...body,
+        "</details>",
+      ].join("\n");
+    }
+    default:
+      return "";
+  }
+}
+
+function formatTimestamp(value: number): string {
+  return new Date(value).toISOString();
+}
+
+function escapeHtml(value: string): string {
+  return value
+    .replace(/&/g, "&amp;")
+    .replace(/</g, "&lt;")
+    .replace(/>/g, "&gt;")
+    .replace(/"/g, "&quot;");
+}
@@ -0,0 +1,132 @@
+/**
+ * The web UI: one self-contained page, so the server ships no asset files.
+ * Everything it renders comes through `textContent`, never as HTML.
+ */
+export const WEB_BOARD_PAGE = `<!doctype html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>ikanban</title>
+<style>
+  :root { color-scheme: dark; font-family: ui-monospace, SFMono-Regular, Menlo, monospace; font-size: 13px; }
+  body { margin: 0; background: #111; color: #ddd; display: flex; flex-direction: column; height: 100vh; }
+  header { padding: 8px 12px; border-bottom: 1px solid #333; display: flex; gap: 12px; align-items: center; }
+  header h1 { font-size: 14px; margin: 0; color: #6cf; }
+  #status { color: #888; }
+  main { flex: 1; overflow: auto; padding: 8px 12px; }
+  section h2 { font-size: 13px; color: #6cf; margin: 12px 0 6px; }
+  .columns { display: flex; gap: 8px; align-items: flex-start; }
+  .column { flex: 1; min-width: 160px; background: #1a1a1a; border: 1px solid #333; padding: 6px; }
+  .column h3 { font-size: 12px; margin: 0 0 6px; color: #aaa; }
+  .column h3.over { color: #f66; }
+  .card { border: 1px solid #333; padding: 4px 6px; margin-bottom: 4px; cursor: pointer; }
+  .card:hover, .card.selected { border-color: #6cf; }
+  .card .meta { color: #888; }
+  .card .error { color: #f66; }
+  #logs { height: 35vh; overflow: auto; border-top: 1px solid #333; padding: 6px 12px; white-space: pre-wrap; }
+  #logs .warn { color: #fc6; }
+  #logs .error { color: #f66; }
+  #logs .debug { color: #777; }
+</style>
+</head>
+<body>
+<header><h1>ikanban</h1><span id="status">connecting…</span></header>
+<main id="board"></main>
+<div id="logs"></div>
+<script>
+  const token = new URLSearchParams(location.search).get("token");
+  const withToken = (path) => (token ? path + "?token=" + encodeURIComponent(token) : path);
+  const board = document.getElementById("board");
+  const logs = document.getElementById("logs");
+  const status = document.getElementById("status");
+  const entries = [];
+  let selectedTaskId;
+  let refreshTimer;
+
+  function element(tag, className, text) {
+    const node = document.createElement(tag);
+    if (className) node.className = className;
+    if (text !== undefined) node.textContent = text;
+    return node;
+  }
+
+  async function loadBoard() {
+    const response = await fetch(withToken("/api/board"));
+    if (!response.ok) {
+      status.textContent = "board unavailable (" + response.status + ")";
+      return;
+    }
+    renderBoard(await response.json());
+  }
+
+  function renderBoard(data) {
+    board.replaceChildren();
+    for (const project of data.projects) {
+      const section = element("section");
+      section.append(element("h2", "", project.name));
+      const columns = element("div", "columns");
+      for (const column of project.columns) {
+        const node = element("div", "column");
+        const over = column.wipLimit !== undefined && column.tasks.length > column.wipLimit;
+        const total = column.wipLimit !== undefined ? column.tasks.length + "/" + column.wipLimit : column.tasks.length;
+        node.append(element("h3", over ? "over" : "", column.name + " (" + total + ")"));
+        for (const task of column.tasks) {
+          const card = element("div", task.taskId === selectedTaskId ? "card selected" : "card");
+          card.append(element("div", "", (task.shortCode ? task.shortCode + " " : "") + (task.title || task.taskId)));
+          card.append(element("div", "meta", task.state + (task.priority ? " · " + task.priority : "")));
+          if (task.error) card.append(element("div", "error", task.error));
+          card.onclick = () => selectTask(task.taskId === selectedTaskId ? undefined : task.taskId);
+          node.append(card);
+        }
+        columns.append(node);
+      }
+      section.append(columns);
+      board.append(section);
+    }
+  }
+
+  function selectTask(taskId) {
+    selectedTaskId = taskId;
+    for (const card of board.querySelectorAll(".card")) card.classList.remove("selected");
+    scheduleRefresh();
+    renderLogs();
+  }
+
+  function logLine(entry) {
+    const time = new Date(entry.emittedAt).toTimeString().slice(0, 8);
+    return element("div", entry.level, time + " [" + entry.level + "] " + entry.message);
+  }
+
+  function renderLogs() {
+    const visible = entries.filter((entry) => !selectedTaskId || entry.taskId === selectedTaskId);
+    logs.replaceChildren(...visible.map(logLine));
+    logs.scrollTop = logs.scrollHeight;
+  }
+
+  function scheduleRefresh() {
+    clearTimeout(refreshTimer);
+    refreshTimer = setTimeout(loadBoard, 250);
+  }
+
+  const events = new EventSource(withToken("/api/events"));
+  events.onopen = () => { status.textContent = "live"; };
+  events.onerror = () => { status.textContent = "reconnecting…"; };
+  events.addEventListener("board", scheduleRefresh);
+  events.addEventListener("log", (message) => {
+    const entry = JSON.parse(message.data);
+    if (entries.some((known) => known.sequence === entry.sequence)) return;
+    entries.push(entry);
+    if (entries.length > 2000) entries.shift();
+    if (selectedTaskId && entry.taskId !== selectedTaskId) return;
+    const atBottom = logs.scrollTop + logs.clientHeight >= logs.scrollHeight - 4;
+    logs.append(logLine(entry));
+    if (logs.childElementCount > 2000) logs.firstChild.remove();
+    if (atBottom) logs.scrollTop = logs.scrollHeight;
+  });
+
+  loadBoard();
+</script>
+</body>
+</html>
+`;
@@ -0,0 +1,261 @@
+import { resolveBoardColumns } from "../domain/board-column";
+import type { ProjectRef } from "../domain/project";
+import type { TaskRuntime } from "../domain/task";
+import { compareBoardOrder } from "../domain/task-rank";
+import type { RuntimeEventBus, RuntimeLogEntry } from "./event-bus";
+import { noopRuntimeLogger, toStructuredError, type RuntimeLogger } from "./runtime-logger";
+import type { SecretRedactor } from "./secret-redactor";
+import type { TaskOrchestrator, TaskOrchestratorEvent } from "./task-orchestrator";
+import { WEB_BOARD_PAGE } from "./web-board-page";
+
+// Log entries kept for browsers that connect after they were written.
+const LOG_BACKLOG_SIZE = 500;
+const KEEPALIVE_MS = 20_000;
+// Events that move, add or relabel cards; the rest only reach the page as log lines.
+const BOARD_EVENT_TYPES: ReadonlySet<TaskOrchestratorEvent["type"]> = new Set([
+  "task.enqueued",
+  "task.state.changed",
+  "task.renamed",
+  "task.priority.changed",
+  "task.rank.changed",
+]);
+
+type WebServerDeps = {
+  orchestrator: Pick<TaskOrchestrator, "listTasks" | "subscribe">;
+  projectRegistry: { listProjects(): Promise<ProjectRef[]> };
+  eventBus: Pick<RuntimeEventBus, "subscribeToLogs">;
+  redactor: Pick<SecretRedactor, "redact" | "redactText">;
+};
+
+type WebServerOptions = {
+  hostname: string;
+  port: number;
+  /** When set, every request must carry it as `?token=` or a bearer token. */
+  token?: string;
+  logger?: RuntimeLogger;
+};
+
+/**
+ * Serves a read-only board and live execution logs to a browser. The page
+ * polls nothing: it loads `/api/board` once and refetches when `/api/events`
+ * says a task changed.
+ */
+export class WebBoardServer {
+  private readonly deps: WebServerDeps;
+  private readonly options: WebServerOptions;
+  private readonly logger: RuntimeLogger;
+  private readonly logBacklog: RuntimeLogEntry[] = [];
+  private readonly streams = new Set<ReadableStreamDefaultController<Uint8Array>>();
+  private readonly encoder = new TextEncoder();
+  private server?: ReturnType<typeof Bun.serve>;
+  private disposers: (() => void)[] = [];
+  private keepalive?: ReturnType<typeof setInterval>;
+
+  constructor(deps: WebServerDeps, options: WebServerOptions) {
+    this.deps = deps;
+    this.options = options;
+    this.logger = options.logger ?? noopRuntimeLogger;
+  }
+
+  get url(): string | undefined {
+    return this.server ? `http://${this.options.hostname}:${this.server.port}/` : undefined;
+  }
+
+  start(): void {
+    if (this.server) {
+      return;
+    }
+
+    try {
+      this.server = Bun.serve({
+        hostname: this.options.hostname,
+        port: this.options.port,
+        // Event streams stay open; the keepalive below is what proves them alive.
+        idleTimeout: 0,
+        fetch: (request) => this.handle(request),
+      });
+    } catch (error) {
+      this.logger.log({
+        level: "error",
+        source: "web-server",
+        message: `Web UI could not listen on ${this.options.hostname}:${this.options.port}.`,
+        error: toStructuredError(error),
+      });
+      return;
+    }
+
+    this.disposers = [
+      this.deps.orchestrator.subscribe((event) => {
+        if (BOARD_EVENT_TYPES.has(event.type)) {
+          this.broadcast("board", { type: event.type });
+        }
+      }),
+      this.deps.eventBus.subscribeToLogs((entry) => {
+        const safeEntry = this.toSafeLogEntry(entry);
+        this.logBacklog.push(safeEntry);
+        if (this.logBacklog.length > LOG_BACKLOG_SIZE) {
+          this.logBacklog.shift();
+        }
+        this.broadcast("log", safeEntry);
+      }),
+    ];
+    this.keepalive = setInterval(() => {
+      this.write(this.encoder.encode(": keepalive\n\n"));
+    }, KEEPALIVE_MS);
+    this.keepalive.unref();
+
+    this.logger.log({ level: "info", source: "web-server", message: `Web UI listening on ${this.url}` });
+  }
+
+  stop(): void {
+    for (const dispose of this.disposers) {
+      dispose();
+    }
+    this.disposers = [];
+    if (this.keepalive) {
+      clearInterval(this.keepalive);
+      this.keepalive = undefined;
+    }
+    for (const stream of this.streams) {
+      stream.close();
+    }
+    this.streams.clear();
+    void this.server?.stop(true);
+    this.server = undefined;
+  }
+
+  private async handle(request: Request): Promise<Response> {
+    const url = new URL(request.url);
+    if (!this.isAuthorized(request, url)) {
+      return new Response("Unauthorized", { status: 401 });
+    }
+
+    if (request.method !== "GET") {
+      return new Response("The web UI is read-only.", { status: 405, headers: { Allow: "GET" } });
+    }
+
+    switch (url.pathname) {
+      case "/":
+        return new Response(WEB_BOARD_PAGE, { headers: { "Content-Type": "text/html; charset=utf-8" } });
+      case "/api/board":
+        return Response.json(await this.buildBoard());
+      case "/api/events":
+        return this.openEventStream();
+      default:
+        return new Response("Not found", { status: 404 });
+    }
+  }
+
+  private isAuthorized(request: Request, url: URL): boolean {
+    const { token } = this.options;
+    if (!token) {
+      return true;
+    }
+
+    return url.searchParams.get("token") === token || request.headers.get("authorization") === `Bearer ${token}`;
+  }
+
+  // Same grouping as the terminal board: each project's own columns, cards in priority then manual order.
+  private async buildBoard(): Promise<unknown> {
+    const [projects, tasks] = await Promise.all([
+      this.deps.projectRegistry.listProjects(),
+      Promise.resolve(this.deps.orchestrator.listTasks()),
+    ]);
+
+    return {
+      projects: projects.map((project) => {
+        const projectTasks = tasks.filter((task) => task.projectId === project.id).sort(compareBoardOrder);
+        return this.deps.redactor.redact(
+          {
+            id: project.id,
+            name: project.name,
+            columns: resolveBoardColumns(project.columns).map((column) => ({
+              id: column.id,
+              name: column.name,
+              wipLimit: column.wipLimit,
+              tasks: projectTasks.filter((task) => column.states.includes(task.state)).map(toWebTask),
+            })),
+          },
+          project.id,
+        );
+      }),
+    };
+  }
+
+  // Every stream gets every log line; the page filters by the task it has open.
+  private openEventStream(): Response {
+    let streamController: ReadableStreamDefaultController<Uint8Array> | undefined;
+    const stream = new ReadableStream<Uint8Array>({
+      start: (controller) => {
+        streamController = controller;
+        this.streams.add(controller);
+        for (const entry of this.logBacklog) {
+          controller.enqueue(this.encodeEvent("log", entry));
+        }
+      },
+      cancel: () => {
+        if (streamController) {
+          this.streams.delete(streamController);
+        }
+      },
+    });
+
+    return new Response(stream, {
+      headers: {
+        "Content-Type": "text/event-stream",
+        "Cache-Control": "no-cache",
+        Connection: "keep-alive",
+      },
+    });
+  }
+
+  // Only the fields the page shows leave the process. `raw` carries the record's
+  // context and error (stack traces, command lines, whole agent messages) and is dropped.
+  private toSafeLogEntry(entry: RuntimeLogEntry): RuntimeLogEntry {
+    return {
+      sequence: entry.sequence,
+      emittedAt: entry.emittedAt,
+      level: entry.level,
+      message: this.deps.redactor.redactText(entry.message),
+      taskId: entry.taskId,
+      projectId: entry.projectId,
+      source: entry.source,
+      eventType: entry.eventType,
+    };
+  }
+
+  private broadcast(event: string, data: unknown): void {
+    if (this.streams.size === 0) {
+      return;
+    }
+
+    this.write(this.encodeEvent(event, data));
+  }
+
+  private write(chunk: Uint8Array): void {
+    for (const stream of this.streams) {
+      try {
+        stream.enqueue(chunk);
+      } catch {
+        this.streams.delete(stream);
+      }
+    }
+  }
+
+  private encodeEvent(event: string, data: unknown): Uint8Array {
+    return this.encoder.encode(`event: ${event}\ndata: ${JSON.stringify(data)}\n\n`);
+  }
+}
+
+function toWebTask(task: TaskRuntime) {
+  return {
+    taskId: task.taskId,
+    shortCode: task.shortCode,
+    title: task.title,
+    state: task.state,
+    priority: task.priority,
+    parentTaskId: task.parentTaskId,
+    updatedAt: task.updatedAt,
+    error: task.error,
+  };
+}
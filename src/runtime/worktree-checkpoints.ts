@@ -0,0 +1,62 @@
+import { rm } from "node:fs/promises";
+import { tmpdir } from "node:os";
+import { join } from "node:path";
+
+import type { TaskCheckpoint } from "../domain/task";
+
+const CHECKPOINT_REF_PREFIX = "refs/ikanban/checkpoints";
+
+export function checkpointRef(taskId: string): string {
+  return `${CHECKPOINT_REF_PREFIX}/${taskId}`;
+}
+
+/**
+ * Snapshots the full worktree (tracked, modified and untracked files) as a
+ * commit on a hidden per-task ref. The branch, index and working tree are not
+ * touched. Returns undefined when nothing changed since `previous`.
+ */
+export async function createWorktreeCheckpoint(input: {
+  taskId: string;
+  worktreeDirectory: string;
+  label: string;
+  previous?: TaskCheckpoint;
+}): Promise<TaskCheckpoint | undefined> {
+  const indexFile = join(tmpdir(), `ikanban-checkpoint-${input.taskId}-${Date.now()}.index`);
+  const env = { ...process.env, GIT_INDEX_FILE: indexFile };
+
+  try {
+    await Bun.$`git -C ${input.worktreeDirectory} add -A`.env(env).quiet();
+    const tree = (await Bun.$`git -C ${input.worktreeDirectory} write-tree`.env(env).quiet().text()).trim();
+    if (input.previous?.tree === tree) {
+      return undefined;
+    }
+
+    const parent = input.previous?.commit ?? (await Bun.$`git -C ${input.worktreeDirectory} rev-parse HEAD`.quiet().text()).trim();
+    const commit = (
+      await Bun.$`git -C ${input.worktreeDirectory} commit-tree ${tree} -p ${parent} -m ${`ikanban checkpoint: ${input.label}`}`
+        .quiet()
+        .text()
+    ).trim();
+    await Bun.$`git -C ${input.worktreeDirectory} update-ref ${checkpointRef(input.taskId)} ${commit}`.quiet();
+
+    return { commit, tree, label: input.label, createdAt: Date.now() };
+  } finally {
+    await rm(indexFile, { force: true });
+  }
+}
+
+/**
+ * Makes the working tree match a checkpoint, including removing files created
+ * after it. HEAD stays where it is, so the rollback shows up as uncommitted
+ * changes the agent (or a reviewer) can continue from.
+ */
+export async function restoreWorktreeCheckpoint(worktreeDirectory: string, checkpoint: TaskCheckpoint): Promise<void> {
+  // Track everything first so read-tree also removes files the checkpoint does not have.
+  await Bun.$`git -C ${worktreeDirectory} add -A`.quiet();
+  await Bun.$`git -C ${worktreeDirectory} read-tree -u --reset ${checkpoint.commit}`.quiet();
+  await Bun.$`git -C ${worktreeDirectory} reset -q`.quiet();
+}
+
+export async function deleteWorktreeCheckpoints(worktreeDirectory: string, taskId: string): Promise<void> {
+  await Bun.$`git -C ${worktreeDirectory} update-ref -d ${checkpointRef(taskId)}`.nothrow().quiet();
+}
@@ -0,0 +1,70 @@
+import { noopRuntimeLogger, toStructuredError, type RuntimeLogger } from "./runtime-logger";
+
+const DEFAULT_INTERVAL_MS = 60 * 60 * 1000;
+
+type FinishedWorktreeCollector = {
+  collectFinishedWorktrees(): Promise<string[]>;
+};
+
+/** Periodically removes worktrees of tasks finished longer ago than the retention period. */
+export class WorktreeGarbageCollector {
+  private readonly target: FinishedWorktreeCollector;
+  private readonly logger: RuntimeLogger;
+  private readonly intervalMs: number;
+  private timer?: ReturnType<typeof setInterval>;
+  private running = false;
+
+  constructor(target: FinishedWorktreeCollector, options: { intervalMs?: number; logger?: RuntimeLogger } = {}) {
+    this.target = target;
+    this.intervalMs = options.intervalMs ?? DEFAULT_INTERVAL_MS;
+    this.logger = options.logger ?? noopRuntimeLogger;
+  }
+
+  /** Collects once right away, so a long-unused board is tidied on launch, then on the interval. */
+  start(): void {
+    if (this.timer) {
+      return;
+    }
+
+    void this.collect();
+    this.timer = setInterval(() => {
+      void this.collect();
+    }, this.intervalMs);
+    this.timer.unref();
+  }
+
+  stop(): void {
+    if (this.timer) {
+      clearInterval(this.timer);
+      this.timer = undefined;
+    }
+  }
+
+  async collect(): Promise<void> {
+    if (this.running) {
+      return;
+    }
+
+    this.running = true;
+    try {
+      const collected = await this.target.collectFinishedWorktrees();
+      if (collected.length > 0) {
+        this.logger.log({
+          level: "info",
+          source: "worktree-gc",
+          message: `Removed ${collected.length} expired task worktree(s).`,
+          context: { taskIds: collected },
+        });
+      }
+    } catch (error) {
+      this.logger.log({
+        level: "warn",
+        source: "worktree-gc",
+        message: "Worktree garbage collection failed.",
+        error: toStructuredError(error),
+      });
+    } finally {
+      this.running = false;
+    }
+  }
+}
@@ -1,7 +1,10 @@
 import { resolve } from "node:path";
 
+import { parseUnifiedDiff, type DiffFile } from "../domain/diff";
+import { IkanbanError } from "../domain/errors";
+import type { MergeStrategy } from "../domain/task";
 import type { OpenCodeRuntime } from "./opencode-runtime";
-import { noopRuntimeLogger, type RuntimeLogger } from "./runtime-logger";
+import { noopRuntimeLogger, toStructuredError, type RuntimeLogger } from "./runtime-logger";
 
 type RuntimeClientProvider = Pick<OpenCodeRuntime, "getClient">;
 
@@ -48,19 +51,60 @@ export type CleanupTaskWorktreeResult = {
 export type MergeTaskWorktreeInput = {
   projectDirectory: string;
   taskId: string;
+  shortCode?: string;
   worktreeDirectory: string;
+  /** `squash` (default) lands one commit; `fast-forward` moves the default branch to the task branch. */
+  strategy?: MergeStrategy;
 };
 
 export type MergeTaskWorktreeResult = {
   taskId: string;
   branch: string;
+  targetBranch: string;
+  strategy: MergeStrategy;
   merged: boolean;
+  /** Tip of the default branch after the merge; unset when there was nothing to merge. */
+  commit?: string;
+};
+
+export type RebaseTaskWorktreeInput = {
+  projectDirectory: string;
+  taskId: string;
+  worktreeDirectory: string;
+};
+
+export type RebaseTaskWorktreeResult = {
+  taskId: string;
+  branch: string;
+  /** The default branch, or its upstream when that is ahead after fetching. */
+  onto: string;
+  status: "rebased" | "up_to_date" | "conflict";
+  /** Files that stopped the rebase; it is aborted, leaving the branch as it was. */
+  conflictedFiles: string[];
+};
+
+export type PushTaskBranchInput = {
+  taskId: string;
+  shortCode?: string;
+  worktreeDirectory: string;
+  remote?: string;
+  forceWithLease?: boolean;
+};
+
+export type PushTaskBranchResult = {
+  taskId: string;
+  branch: string;
+  remote: string;
+  remoteRef: string;
+  forced: boolean;
 };
 
 export type ReviewTaskWorktreeDiffInput = {
   projectDirectory: string;
   taskId: string;
   worktreeDirectory: string;
+  /** Limits the summary and diff to this repository-relative directory. */
+  pathScope?: string;
 };
 
 export type ReviewTaskWorktreeDiffResult = {
@@ -69,10 +113,32 @@ export type ReviewTaskWorktreeDiffResult = {
   defaultBranch: string;
   summary: string;
   diff: string;
+  /** `diff` split into files and hunks. */
+  files: DiffFile[];
   hasChanges: boolean;
 };
 
+export type TaskCommitsInput = {
+  projectDirectory: string;
+  worktreeDirectory: string;
+};
+
+/** A commit on the task branch that is not on the default branch. */
+export type TaskCommit = {
+  hash: string;
+  author: string;
+  committedAt: number;
+  subject: string;
+  filesChanged: number;
+  additions: number;
+  deletions: number;
+};
+
 const WORKTREE_TASK_ID_PATTERN = /^[A-Za-z0-9_-]+$/;
+// Separates commits and fields in `git log` output; neither occurs in commit metadata.
+const RECORD_SEPARATOR = "\x1e";
+const FIELD_SEPARATOR = "\x1f";
+const DEFAULT_PUSH_REMOTE = "origin";
 
 export class WorktreeManager {
   private readonly runtime: RuntimeClientProvider;
@@ -260,6 +326,18 @@ export class WorktreeManager {
     return wasRemoved;
   }
 
+  /** Whether the worktree has changes `removeWorktree` would refuse to discard. */
+  async hasLocalChanges(worktreeDirectory: string): Promise<boolean> {
+    const normalizedWorktreeDirectory = normalizeDirectory(
+      worktreeDirectory,
+      "Worktree directory",
+    );
+    const status =
+      await Bun.$`git -C ${normalizedWorktreeDirectory} status --porcelain`.quiet().nothrow();
+
+    return status.exitCode === 0 && status.stdout.toString().trim().length > 0;
+  }
+
   async cleanupTaskWorktree(
     input: CleanupTaskWorktreeInput,
   ): Promise<CleanupTaskWorktreeResult> {
@@ -330,6 +408,8 @@ export class WorktreeManager {
       "merge task worktree",
     );
     const logSource = "worktree-manager.merge";
+    const commitMessage = buildTaskCommitMessage(taskId, input.shortCode);
+    const strategy = input.strategy ?? "squash";
 
     this.logger.log({
       level: "info",
@@ -372,6 +452,17 @@ export class WorktreeManager {
       context: { taskId, defaultBranch, projectDirectory },
     });
 
+    // Local changes in the project checkout would be mixed into the merge, and
+    // backing a failed merge out would unstage them.
+    const checkoutStatus =
+      await Bun.$`git -C ${projectDirectory} status --porcelain --untracked-files=no`.quiet().text();
+    if (checkoutStatus.trim()) {
+      throw new IkanbanError(
+        "invalid_state",
+        `${projectDirectory} has uncommitted changes on ${defaultBranch}; commit or stash them before merging.`,
+      );
+    }
+
     // Commit any uncommitted changes in the worktree branch first
     const statusResult =
       await Bun.$`git -C ${worktreeDirectory} status --porcelain`.text();
@@ -385,7 +476,7 @@ export class WorktreeManager {
 
       try {
         await Bun.$`git -C ${worktreeDirectory} add -A`.text();
-        await Bun.$`git -C ${worktreeDirectory} commit -m ${taskId + " (ikanban)"}`.text();
+        await Bun.$`git -C ${worktreeDirectory} commit -m ${commitMessage}`.text();
       } catch (error) {
         throw new Error(
           `Failed to commit uncommitted changes in worktree ${branch}: ${formatUnknownError(error)}`,
@@ -407,6 +498,8 @@ export class WorktreeManager {
       return {
         taskId,
         branch,
+        targetBranch: defaultBranch,
+        strategy,
         merged: false,
       };
     }
@@ -414,31 +507,232 @@ export class WorktreeManager {
     this.logger.log({
       level: "info",
       source: logSource,
-      message: `Merging ${branch} into ${defaultBranch}.`,
-      context: { taskId, branch, defaultBranch },
+      message: `Merging ${branch} into ${defaultBranch} (${strategy}).`,
+      context: { taskId, branch, defaultBranch, strategy },
     });
 
-    // Squash merge the worktree branch into the default branch (single commit)
-    try {
-      await Bun.$`git -C ${projectDirectory} merge --squash ${branch}`.text();
-      await Bun.$`git -C ${projectDirectory} commit -m ${taskId + " (ikanban)"}`.text();
-    } catch (error) {
+    // A conflict is not a broken task: callers keep the task in review so it can be rebased.
+    if (strategy === "fast-forward") {
+      // Keeps the task's commits as they are; impossible once the default branch has moved on.
+      const ancestry =
+        await Bun.$`git -C ${projectDirectory} merge-base --is-ancestor ${defaultBranch} ${branch}`.quiet().nothrow();
+      if (ancestry.exitCode !== 0) {
+        throw new IkanbanError(
+          "conflict",
+          `Cannot fast-forward ${defaultBranch} to ${branch}; rebase the task or squash-merge instead.`,
+        );
+      }
+
+      await Bun.$`git -C ${projectDirectory} merge --ff-only ${branch}`.text();
+    } else {
+      // Squash merge the worktree branch into the default branch (single commit)
+      const squash = await Bun.$`git -C ${projectDirectory} merge --squash ${branch}`.quiet().nothrow();
+      if (squash.exitCode !== 0) {
+        const conflicted =
+          await Bun.$`git -C ${projectDirectory} diff --name-only --diff-filter=U`.nothrow().quiet().text();
+        const conflictedFiles = conflicted
+          .split("\n")
+          .map((line) => line.trim())
+          .filter((line) => line.length > 0);
+        if (conflictedFiles.length === 0) {
+          throw new Error(
+            `Failed to squash ${branch} into ${defaultBranch}: ${squash.stderr.toString().trim()}`,
+          );
+        }
+
+        // The checkout was clean, so this only backs out the half-applied squash.
+        await Bun.$`git -C ${projectDirectory} reset --merge`.quiet().nothrow();
+        throw new IkanbanError(
+          "conflict",
+          `Merging ${branch} into ${defaultBranch} conflicts in ${conflictedFiles.join(", ")}; rebase the task and merge again.`,
+        );
+      }
+
+      try {
+        await Bun.$`git -C ${projectDirectory} commit -m ${commitMessage}`.quiet();
+      } catch (error) {
+        // A commit hook or missing identity; unstage the squash so the checkout is as it was.
+        await Bun.$`git -C ${projectDirectory} reset --merge`.quiet().nothrow();
+        throw error;
+      }
+    }
+
+    const commit = (await Bun.$`git -C ${projectDirectory} rev-parse HEAD`.text()).trim();
+
+    this.logger.log({
+      level: "info",
+      source: logSource,
+      message: `${strategy === "fast-forward" ? "Fast-forwarded" : "Squash-merged"} ${branch} into ${defaultBranch} for task ${taskId}.`,
+      context: { taskId, branch, defaultBranch, strategy, commit },
+    });
+
+    return {
+      taskId,
+      branch,
+      targetBranch: defaultBranch,
+      strategy,
+      merged: true,
+      commit,
+    };
+  }
+
+  /**
+   * Replays the task branch on top of the latest default branch. The default
+   * branch's upstream is fetched first and used when it is ahead; uncommitted
+   * changes are stashed around the rebase. A conflicting rebase is aborted.
+   */
+  async rebaseTaskWorktree(
+    input: RebaseTaskWorktreeInput,
+  ): Promise<RebaseTaskWorktreeResult> {
+    const taskId = normalizeTaskId(input.taskId);
+    const projectDirectory = normalizeDirectory(
+      input.projectDirectory,
+      "Project directory",
+    );
+    const worktreeDirectory = normalizeDirectory(
+      input.worktreeDirectory,
+      "Worktree directory",
+    );
+    assertWorktreeDirectoryIsDistinct(
+      projectDirectory,
+      worktreeDirectory,
+      "rebase task worktree",
+    );
+    const logSource = "worktree-manager.rebase";
+
+    const branch = (
+      await Bun.$`git -C ${worktreeDirectory} rev-parse --abbrev-ref HEAD`.quiet().text()
+    ).trim();
+    const defaultBranch = (
+      await Bun.$`git -C ${projectDirectory} rev-parse --abbrev-ref HEAD`.quiet().text()
+    ).trim();
+    if (!branch || !defaultBranch) {
       throw new Error(
-        `Failed to merge ${branch} into ${defaultBranch}: ${formatUnknownError(error)}`,
+        `Failed to determine the branches of worktree ${worktreeDirectory} and project ${projectDirectory}.`,
       );
     }
 
+    let onto = defaultBranch;
+    const upstream = await resolveUpstreamBranch(projectDirectory, defaultBranch);
+    if (upstream) {
+      try {
+        await Bun.$`git -C ${projectDirectory} fetch ${upstream.remote}`.quiet();
+        const upstreamIsAhead = await Bun.$`git -C ${projectDirectory} merge-base --is-ancestor ${defaultBranch} ${upstream.ref}`
+          .nothrow()
+          .quiet();
+        if (upstreamIsAhead.exitCode === 0) {
+          onto = upstream.ref;
+        }
+      } catch (error) {
+        this.logger.log({
+          level: "warn",
+          source: logSource,
+          message: `Fetching ${upstream.remote} failed; rebasing onto local ${defaultBranch}.`,
+          context: { taskId, upstream: upstream.ref },
+          error: toStructuredError(error),
+        });
+      }
+    }
+
+    const upToDate = await Bun.$`git -C ${worktreeDirectory} merge-base --is-ancestor ${onto} HEAD`.nothrow().quiet();
+    if (upToDate.exitCode === 0) {
+      return { taskId, branch, onto, status: "up_to_date", conflictedFiles: [] };
+    }
+
+    const rebase = await Bun.$`git -C ${worktreeDirectory} rebase --autostash ${onto}`.nothrow().quiet();
+    if (rebase.exitCode === 0) {
+      this.logger.log({
+        level: "info",
+        source: logSource,
+        message: `Rebased ${branch} onto ${onto} for task ${taskId}.`,
+        context: { taskId, branch, onto },
+      });
+      return { taskId, branch, onto, status: "rebased", conflictedFiles: [] };
+    }
+
+    const conflicted = await Bun.$`git -C ${worktreeDirectory} diff --name-only --diff-filter=U`.nothrow().quiet().text();
+    await Bun.$`git -C ${worktreeDirectory} rebase --abort`.nothrow().quiet();
+    const conflictedFiles = conflicted
+      .split("\n")
+      .map((line) => line.trim())
+      .filter((line) => line.length > 0);
+
+    this.logger.log({
+      level: "warn",
+      source: logSource,
+      message: `Rebasing ${branch} onto ${onto} conflicted; the rebase was aborted.`,
+      context: { taskId, branch, onto, conflictedFiles, stderr: rebase.stderr.toString().trim() },
+    });
+    return { taskId, branch, onto, status: "conflict", conflictedFiles };
+  }
+
+  async pushTaskBranch(
+    input: PushTaskBranchInput,
+  ): Promise<PushTaskBranchResult> {
+    const taskId = normalizeTaskId(input.taskId);
+    const worktreeDirectory = normalizeDirectory(
+      input.worktreeDirectory,
+      "Worktree directory",
+    );
+    const remote = input.remote?.trim() || DEFAULT_PUSH_REMOTE;
+    const forced = input.forceWithLease ?? false;
+    const logSource = "worktree-manager.push";
+
+    const branchResult =
+      await Bun.$`git -C ${worktreeDirectory} rev-parse --abbrev-ref HEAD`.text();
+    const branch = branchResult.trim();
+    if (!branch || branch === "HEAD") {
+      throw new Error(
+        `Failed to determine branch for worktree at ${worktreeDirectory}.`,
+      );
+    }
+
+    try {
+      await Bun.$`git -C ${worktreeDirectory} remote get-url ${remote}`.quiet();
+    } catch {
+      throw new Error(`Remote ${remote} is not configured for ${worktreeDirectory}.`);
+    }
+
+    // Commit any uncommitted changes so the pushed branch matches the worktree
+    const statusResult =
+      await Bun.$`git -C ${worktreeDirectory} status --porcelain`.text();
+    if (statusResult.trim()) {
+      const commitMessage = buildTaskCommitMessage(taskId, input.shortCode);
+      try {
+        await Bun.$`git -C ${worktreeDirectory} add -A`.text();
+        await Bun.$`git -C ${worktreeDirectory} commit -m ${commitMessage}`.text();
+      } catch (error) {
+        throw new Error(
+          `Failed to commit uncommitted changes in worktree ${branch}: ${formatUnknownError(error)}`,
+        );
+      }
+    }
+
     this.logger.log({
       level: "info",
       source: logSource,
-      message: `Squash-merged ${branch} into ${defaultBranch} for task ${taskId}.`,
-      context: { taskId, branch, defaultBranch },
+      message: `Pushing ${branch} to ${remote}${forced ? " with --force-with-lease" : ""}.`,
+      context: { taskId, branch, remote, forced },
     });
 
+    try {
+      if (forced) {
+        await Bun.$`git -C ${worktreeDirectory} push --force-with-lease --set-upstream ${remote} ${branch}`.quiet();
+      } else {
+        await Bun.$`git -C ${worktreeDirectory} push --set-upstream ${remote} ${branch}`.quiet();
+      }
+    } catch (error) {
+      throw new Error(
+        `Failed to push ${branch} to ${remote}: ${formatUnknownError(error)}`,
+      );
+    }
+
     return {
       taskId,
       branch,
-      merged: true,
+      remote,
+      remoteRef: `refs/heads/${branch}`,
+      forced,
     };
   }
 
@@ -482,6 +776,7 @@ export class WorktreeManager {
     const statusResult =
       await Bun.$`git -C ${worktreeDirectory} status --porcelain`.text();
     const hasUncommittedChanges = statusResult.trim().length > 0;
+    const pathspec = input.pathScope ? ["--", input.pathScope] : [];
 
     let summary: string;
     let diff: string;
@@ -491,8 +786,8 @@ export class WorktreeManager {
       // Without this, `git diff` misses untracked files entirely.
       await Bun.$`git -C ${worktreeDirectory} add -A`.quiet();
       try {
-        const summaryCommand = Bun.$`git -C ${worktreeDirectory} diff --cached --no-color --stat ${defaultBranch}`;
-        const diffCommand = Bun.$`git -C ${worktreeDirectory} diff --cached --no-color ${defaultBranch}`;
+        const summaryCommand = Bun.$`git -C ${worktreeDirectory} diff --cached --no-color --stat ${defaultBranch} ${pathspec}`;
+        const diffCommand = Bun.$`git -C ${worktreeDirectory} diff --cached --no-color ${defaultBranch} ${pathspec}`;
         summary = (await summaryCommand.text()).trim();
         diff = (await diffCommand.text()).trim();
       } finally {
@@ -500,8 +795,8 @@ export class WorktreeManager {
         await Bun.$`git -C ${worktreeDirectory} reset`.quiet();
       }
     } else {
-      const summaryCommand = Bun.$`git -C ${projectDirectory} diff --no-color --stat ${defaultBranch}...${branch}`;
-      const diffCommand = Bun.$`git -C ${projectDirectory} diff --no-color ${defaultBranch}...${branch}`;
+      const summaryCommand = Bun.$`git -C ${projectDirectory} diff --no-color --stat ${defaultBranch}...${branch} ${pathspec}`;
+      const diffCommand = Bun.$`git -C ${projectDirectory} diff --no-color ${defaultBranch}...${branch} ${pathspec}`;
       summary = (await summaryCommand.text()).trim();
       diff = (await diffCommand.text()).trim();
     }
@@ -518,6 +813,7 @@ export class WorktreeManager {
         defaultBranch,
         hasUncommittedChanges,
         hasChanges,
+        pathScope: input.pathScope,
       },
     });
 
@@ -527,10 +823,64 @@ export class WorktreeManager {
       defaultBranch,
       summary,
       diff,
+      files: parseUnifiedDiff(diff),
       hasChanges,
     };
   }
 
+  /** Commits the task branch has on top of the default branch, newest first. */
+  async listTaskCommits(input: TaskCommitsInput): Promise<TaskCommit[]> {
+    const projectDirectory = normalizeDirectory(
+      input.projectDirectory,
+      "Project directory",
+    );
+    const worktreeDirectory = normalizeDirectory(
+      input.worktreeDirectory,
+      "Worktree directory",
+    );
+    const defaultBranch = (
+      await Bun.$`git -C ${projectDirectory} rev-parse --abbrev-ref HEAD`.quiet().text()
+    ).trim();
+    const output = await Bun.$`git -C ${worktreeDirectory} log --no-color --numstat --format=${`${RECORD_SEPARATOR}%H${FIELD_SEPARATOR}%an${FIELD_SEPARATOR}%ct${FIELD_SEPARATOR}%s`} ${`${defaultBranch}..HEAD`}`
+      .quiet()
+      .text();
+
+    return output
+      .split(RECORD_SEPARATOR)
+      .filter((record) => record.trim().length > 0)
+      .map((record) => {
+        const [header = "", ...statLines] = record.split("\n");
+        const [hash = "", author = "", committedAt = "0", subject = ""] = header.split(FIELD_SEPARATOR);
+        const stats = statLines
+          .map((line) => line.split("\t"))
+          .filter((fields) => fields.length >= 3);
+        return {
+          hash,
+          author,
+          committedAt: Number(committedAt) * 1000,
+          subject,
+          filesChanged: stats.length,
+          // Binary files report "-" for both counts.
+          additions: stats.reduce((total, [added]) => total + (Number(added) || 0), 0),
+          deletions: stats.reduce((total, [, deleted]) => total + (Number(deleted) || 0), 0),
+        };
+      });
+  }
+
+  /** One commit's changes, split into files and hunks. */
+  async getTaskCommitFiles(worktreeDirectory: string, hash: string): Promise<DiffFile[]> {
+    const normalizedWorktreeDirectory = normalizeDirectory(
+      worktreeDirectory,
+      "Worktree directory",
+    );
+    if (!/^[0-9a-f]{4,64}$/i.test(hash)) {
+      throw new Error(`Invalid commit hash: ${hash}`);
+    }
+
+    const patch = await Bun.$`git -C ${normalizedWorktreeDirectory} show --no-color --format= ${hash}`.quiet().text();
+    return parseUnifiedDiff(patch);
+  }
+
   getTaskWorktreeDirectory(taskId: string): string | undefined {
     return this.taskToWorktreeDirectory.get(normalizeTaskId(taskId));
   }
@@ -544,6 +894,10 @@ export function buildTaskWorktreeName(
   return normalizedTaskId;
 }
 
+export function buildTaskCommitMessage(taskId: string, shortCode?: string): string {
+  return shortCode ? `[${shortCode}] ${taskId} (ikanban)` : `${taskId} (ikanban)`;
+}
+
 export function shouldRemoveWorktree(policy: WorktreeCleanupPolicy): boolean {
   return policy === "remove";
 }
@@ -618,6 +972,21 @@ function formatUnknownError(error: unknown): string {
   return "Unknown SDK error";
 }
 
+/** `origin/main` style upstream of a local branch, if it tracks a remote branch. */
+async function resolveUpstreamBranch(
+  projectDirectory: string,
+  branch: string,
+): Promise<{ remote: string; ref: string } | undefined> {
+  const remote = (
+    await Bun.$`git -C ${projectDirectory} config --get ${`branch.${branch}.remote`}`.nothrow().quiet().text()
+  ).trim();
+  const ref = (
+    await Bun.$`git -C ${projectDirectory} rev-parse --abbrev-ref ${`${branch}@{upstream}`}`.nothrow().quiet().text()
+  ).trim();
+
+  return remote && remote !== "." && ref ? { remote, ref } : undefined;
+}
+
 function assertWorktreeDirectoryIsDistinct(
   projectDirectory: string,
   worktreeDirectory: string,
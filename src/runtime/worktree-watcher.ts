@@ -0,0 +1,120 @@
+import { watch, type FSWatcher } from "node:fs";
+
+import { noopRuntimeLogger, type RuntimeLogger } from "./runtime-logger";
+
+const DEFAULT_DEBOUNCE_MS = 500;
+
+export type WorktreeChange = {
+  taskId: string;
+  directory: string;
+  paths: string[];
+  at: number;
+};
+
+export type WorktreeChangeListener = (change: WorktreeChange) => void;
+
+type WatchedWorktree = {
+  directory: string;
+  watcher: FSWatcher;
+  pendingPaths: Set<string>;
+  timer?: ReturnType<typeof setTimeout>;
+};
+
+export class WorktreeWatcher {
+  private readonly debounceMs: number;
+  private readonly logger: RuntimeLogger;
+  private readonly watched = new Map<string, WatchedWorktree>();
+
+  constructor(options?: { debounceMs?: number; logger?: RuntimeLogger }) {
+    this.debounceMs = options?.debounceMs ?? DEFAULT_DEBOUNCE_MS;
+    this.logger = options?.logger ?? noopRuntimeLogger;
+  }
+
+  watch(taskId: string, directory: string, listener: WorktreeChangeListener): void {
+    const existing = this.watched.get(taskId);
+    if (existing?.directory === directory) {
+      return;
+    }
+
+    this.unwatch(taskId);
+
+    let watcher: FSWatcher;
+    try {
+      watcher = watch(directory, { recursive: true }, (_event, filename) => {
+        const path = filename?.toString();
+        // Git metadata churns on every status/diff call; only working tree edits matter.
+        if (!path || path === ".git" || path.startsWith(".git/")) {
+          return;
+        }
+
+        this.queueChange(taskId, path, listener);
+      });
+    } catch (error) {
+      this.logger.log({
+        level: "warn",
+        source: "worktree-watcher",
+        message: `Unable to watch worktree for task ${taskId}.`,
+        context: { taskId, directory, error: String(error) },
+      });
+      return;
+    }
+
+    // Watching must never keep the process alive after the TUI exits.
+    watcher.unref();
+    watcher.on("error", (error) => {
+      this.logger.log({
+        level: "warn",
+        source: "worktree-watcher",
+        message: `Stopped watching worktree for task ${taskId}.`,
+        context: { taskId, directory, error: String(error) },
+      });
+      this.unwatch(taskId);
+    });
+
+    this.watched.set(taskId, { directory, watcher, pendingPaths: new Set() });
+  }
+
+  unwatch(taskId: string): void {
+    const entry = this.watched.get(taskId);
+    if (!entry) {
+      return;
+    }
+
+    if (entry.timer) {
+      clearTimeout(entry.timer);
+    }
+
+    entry.watcher.close();
+    this.watched.delete(taskId);
+  }
+
+  close(): void {
+    for (const taskId of [...this.watched.keys()]) {
+      this.unwatch(taskId);
+    }
+  }
+
+  private queueChange(taskId: string, path: string, listener: WorktreeChangeListener): void {
+    const entry = this.watched.get(taskId);
+    if (!entry) {
+      return;
+    }
+
+    entry.pendingPaths.add(path);
+    if (entry.timer) {
+      return;
+    }
+
+    entry.timer = setTimeout(() => {
+      entry.timer = undefined;
+      const paths = [...entry.pendingPaths].sort((left, right) => left.localeCompare(right));
+      entry.pendingPaths.clear();
+      listener({
+        taskId,
+        directory: entry.directory,
+        paths,
+        at: Date.now(),
+      });
+    }, this.debounceMs);
+  }
+}